@@ -17,6 +17,7 @@ mod ccd3;
 mod compound3;
 mod convex_polyhedron3;
 mod heightfield3;
+mod heightfield_capsules3;
 mod joint_ball3;
 mod joint_fixed3;
 mod joint_prismatic3;
@@ -57,6 +58,7 @@ pub fn main() {
         ("Compound", compound3::init_world),
         ("Convex polyhedron", convex_polyhedron3::init_world),
         ("Heightfield", heightfield3::init_world),
+        ("Heightfield capsules", heightfield_capsules3::init_world),
         ("Stacks", stacks3::init_world),
         ("Pyramid", pyramid3::init_world),
         ("Trimesh", trimesh3::init_world),
@@ -115,6 +115,16 @@ impl Counters {
         self.cd.ncontact_pairs = n;
     }
 
+    /// Set the number of active dynamic rigid-bodies.
+    pub fn set_nactive_bodies(&mut self, n: usize) {
+        self.solver.nactive_bodies = n;
+    }
+
+    /// Set the number of islands of active rigid-bodies.
+    pub fn set_nislands(&mut self, n: usize) {
+        self.solver.nislands = n;
+    }
+
     /// Resets all the counters and timers.
     pub fn reset(&mut self) {
         if self.enabled {
@@ -226,6 +236,7 @@ impl Display for Counters {
         self.stages.fmt(f)?;
         self.cd.fmt(f)?;
         self.solver.fmt(f)?;
+        self.ccd.fmt(f)?;
         writeln!(f, "Custom timer: {}", self.custom)
     }
 }
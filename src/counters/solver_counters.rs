@@ -8,6 +8,10 @@ pub struct SolverCounters {
     pub nconstraints: usize,
     /// Number of contacts found.
     pub ncontacts: usize,
+    /// Number of active dynamic rigid-bodies.
+    pub nactive_bodies: usize,
+    /// Number of islands of active rigid-bodies.
+    pub nislands: usize,
     /// Time spent for the resolution of the constraints (force computation).
     pub velocity_resolution_time: Timer,
     /// Time spent for the assembly of all the velocity constraints.
@@ -26,6 +30,8 @@ impl SolverCounters {
         SolverCounters {
             nconstraints: 0,
             ncontacts: 0,
+            nactive_bodies: 0,
+            nislands: 0,
             velocity_assembly_time: Timer::new(),
             velocity_resolution_time: Timer::new(),
             velocity_update_time: Timer::new(),
@@ -38,6 +44,8 @@ impl SolverCounters {
     pub fn reset(&mut self) {
         self.nconstraints = 0;
         self.ncontacts = 0;
+        self.nactive_bodies = 0;
+        self.nislands = 0;
         self.velocity_resolution_time.reset();
         self.velocity_assembly_time.reset();
         self.velocity_update_time.reset();
@@ -50,6 +58,8 @@ impl Display for SolverCounters {
     fn fmt(&self, f: &mut Formatter) -> Result {
         writeln!(f, "Number of contacts: {}", self.ncontacts)?;
         writeln!(f, "Number of constraints: {}", self.nconstraints)?;
+        writeln!(f, "Number of active bodies: {}", self.nactive_bodies)?;
+        writeln!(f, "Number of islands: {}", self.nislands)?;
         writeln!(f, "Velocity assembly time: {}", self.velocity_assembly_time)?;
         writeln!(
             f,
@@ -0,0 +1,106 @@
+use crate::math::Real;
+use na::RealField;
+
+/// Drives the orientation of a 2D rigid-body toward a target angle.
+///
+/// The steering applies a torque to the rigid-body at each timestep so its angular
+/// acceleration never exceeds `max_angular_acceleration` and its angular velocity never
+/// exceeds `max_angular_velocity` (unless the rigid-body is pushed by other forces). Because
+/// this is a torque, the rigid-body remains fully dynamic: its collisions are still resolved
+/// by the constraints solver. This is useful for, e.g., turning cars or creatures in
+/// top-down games.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct AngularSteering {
+    /// The angle the rigid-body should turn toward.
+    pub target_angle: Real,
+    /// The maximum angular velocity reached by the rigid-body while turning.
+    pub max_angular_velocity: Real,
+    /// The maximum angular acceleration applied for turning the rigid-body.
+    pub max_angular_acceleration: Real,
+}
+
+impl AngularSteering {
+    /// Initializes a steering toward `target_angle` with the given maximum angular velocity and acceleration.
+    pub fn new(
+        target_angle: Real,
+        max_angular_velocity: Real,
+        max_angular_acceleration: Real,
+    ) -> Self {
+        Self {
+            target_angle,
+            max_angular_velocity,
+            max_angular_acceleration,
+        }
+    }
+
+    /// The angular acceleration to apply to a rigid-body with the given angle and angular velocity.
+    pub fn angular_acceleration(&self, angle: Real, angvel: Real, dt: Real) -> Real {
+        if dt == 0.0 {
+            return 0.0;
+        }
+
+        // The shortest signed angle to the target, in [-pi, pi).
+        let error =
+            (self.target_angle - angle + Real::pi()).rem_euclid(Real::two_pi()) - Real::pi();
+
+        // The highest angular velocity the rigid-body can have while still being able
+        // to stop at the target angle, without overshooting it during this timestep.
+        let max_angvel = (2.0 * self.max_angular_acceleration * error.abs())
+            .sqrt()
+            .min(self.max_angular_velocity)
+            .min(error.abs() / dt);
+        let desired_angvel = max_angvel.copysign(error);
+
+        let acc = (desired_angvel - angvel) / dt;
+        acc.max(-self.max_angular_acceleration)
+            .min(self.max_angular_acceleration)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AngularSteering;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Real, Vector};
+    use crate::pipeline::PhysicsWorld;
+    use na::RealField;
+
+    #[test]
+    fn shortest_turn_with_bounded_acceleration() {
+        let steering = AngularSteering::new(Real::pi() - 0.1, 2.0, 1.0);
+
+        // Turning from just below -pi to just below pi goes through -pi, not through zero.
+        assert!(steering.angular_acceleration(-Real::pi() + 0.1, 0.0, 0.01) < 0.0);
+        // The acceleration is bounded.
+        assert_eq!(steering.angular_acceleration(0.0, 0.0, 0.01), 1.0);
+        assert_eq!(steering.angular_acceleration(0.0, 10.0, 0.01), -1.0);
+        // No steering at the target.
+        assert_eq!(
+            steering.angular_acceleration(Real::pi() - 0.1, 0.0, 0.01),
+            0.0
+        );
+    }
+
+    #[test]
+    fn steered_body_reaches_target_angle() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let handle = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        world.colliders.insert(
+            ColliderBuilder::ball(0.5).build(),
+            handle,
+            &mut world.bodies,
+        );
+        let steering = AngularSteering::new(1.0, 2.0, 4.0);
+        world.bodies[handle].set_angular_steering(Some(steering), true);
+
+        for _ in 0..180 {
+            world.step(1.0 / 60.0);
+            assert!(world.bodies[handle].angvel().abs() <= 2.0 + 1.0e-3);
+        }
+
+        assert!((world.bodies[handle].position().rotation.angle() - 1.0).abs() < 1.0e-2);
+        assert!(world.bodies[handle].angvel().abs() < 1.0e-2);
+    }
+}
@@ -1,6 +1,6 @@
 use super::TOIEntry;
 use crate::dynamics::{RigidBodyHandle, RigidBodySet};
-use crate::geometry::{ColliderSet, IntersectionEvent, NarrowPhase};
+use crate::geometry::{ColliderHandle, ColliderSet, IntersectionEvent, NarrowPhase};
 use crate::math::Real;
 use crate::parry::utils::SortedPair;
 use crate::pipeline::{EventHandler, QueryPipeline, QueryPipelineMode};
@@ -8,6 +8,19 @@ use parry::query::{DefaultQueryDispatcher, QueryDispatcher};
 use parry::utils::hashmap::HashMap;
 use std::collections::BinaryHeap;
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// Event occurring when the motion of a rigid-body is clamped by the CCD solver.
+pub struct CcdEvent {
+    /// The rigid-body whose motion is clamped.
+    pub handle: RigidBodyHandle,
+    /// The time of impact, relative to the beginning of the timestep (or substep).
+    pub toi: Real,
+    /// The collider, attached to the clamped rigid-body, involved in the impact.
+    pub collider: ColliderHandle,
+    /// The collider hit by the clamped rigid-body.
+    pub other: ColliderHandle,
+}
+
 pub enum PredictedImpacts {
     Impacts(HashMap<RigidBodyHandle, Real>),
     ImpactsAfterEndTime(Real),
@@ -50,7 +63,7 @@ impl CCDSolver {
                 // println!("Num to clamp: {}", tois.len());
                 for (handle, toi) in tois {
                     if let Some(body) = bodies.get_mut_internal(*handle) {
-                        let min_toi = (body.ccd_thickness
+                        let min_toi = (body.ccd_thickness()
                             * 0.15
                             * crate::utils::inv(body.max_point_velocity()))
                         .min(dt);
@@ -84,6 +97,15 @@ impl CCDSolver {
         ccd_active
     }
 
+    /// Is there any CCD-active bullet?
+    ///
+    /// The CCD active flags must have been updated with `self.update_ccd_active_flags` first.
+    pub fn has_active_bullets(&self, bodies: &RigidBodySet) -> bool {
+        bodies
+            .iter_active_dynamic()
+            .any(|(_, body)| body.is_bullet() && body.is_ccd_active())
+    }
+
     /// Find the first time a CCD-enabled body has a non-sensor collider hitting another non-sensor collider.
     pub fn find_first_impact(
         &mut self,
@@ -91,6 +113,30 @@ impl CCDSolver {
         bodies: &RigidBodySet,
         colliders: &ColliderSet,
         narrow_phase: &NarrowPhase,
+    ) -> Option<Real> {
+        self.find_first_impact_with_filter(dt, bodies, colliders, narrow_phase, false)
+    }
+
+    /// Find the first time a CCD-active bullet has a non-sensor collider hitting another non-sensor collider.
+    ///
+    /// The impacts between bodies that are not bullets are ignored.
+    pub fn find_first_bullet_impact(
+        &mut self,
+        dt: Real,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+    ) -> Option<Real> {
+        self.find_first_impact_with_filter(dt, bodies, colliders, narrow_phase, true)
+    }
+
+    fn find_first_impact_with_filter(
+        &mut self,
+        dt: Real,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+        bullets_only: bool,
     ) -> Option<Real> {
         // Update the query pipeline.
         self.query_pipeline.update_with_mode(
@@ -103,7 +149,7 @@ impl CCDSolver {
         let mut min_toi = dt;
 
         for (_, rb1) in bodies.iter_active_dynamic() {
-            if rb1.is_ccd_active() {
+            if rb1.is_ccd_active() && (!bullets_only || rb1.is_bullet()) {
                 let predicted_body_pos1 = rb1.predict_position_using_velocity_and_forces(dt);
 
                 for ch1 in &rb1.colliders {
@@ -113,8 +159,7 @@ impl CCDSolver {
                         continue; // Ignore sensors.
                     }
 
-                    let aabb1 =
-                        co1.compute_swept_aabb(&(predicted_body_pos1 * co1.position_wrt_parent()));
+                    let aabb1 = co1.compute_swept_aabb_with_parent_position(&predicted_body_pos1);
 
                     self.query_pipeline
                         .colliders_with_aabb_intersecting_aabb(&aabb1, |ch2| {
@@ -210,7 +255,7 @@ impl CCDSolver {
         for (ch1, co1) in colliders.iter() {
             let rb1 = &bodies[co1.parent()];
             if rb1.is_ccd_active() {
-                let aabb = co1.compute_swept_aabb(&(rb1.next_position * co1.position_wrt_parent()));
+                let aabb = co1.compute_swept_aabb_with_parent_position(&rb1.next_position);
 
                 self.query_pipeline
                     .colliders_with_aabb_intersecting_aabb(&aabb, |ch2| {
@@ -320,11 +365,23 @@ impl CCDSolver {
             if should_freeze1 {
                 let _ = frozen.insert(toi.b1, toi.toi);
                 colliders_to_check.extend_from_slice(&body1.colliders);
+                events.handle_ccd_event(CcdEvent {
+                    handle: toi.b1,
+                    toi: toi.toi,
+                    collider: toi.c1,
+                    other: toi.c2,
+                });
             }
 
             if should_freeze2 {
                 let _ = frozen.insert(toi.b2, toi.toi);
                 colliders_to_check.extend_from_slice(&body2.colliders);
+                events.handle_ccd_event(CcdEvent {
+                    handle: toi.b2,
+                    toi: toi.toi,
+                    collider: toi.c2,
+                    other: toi.c1,
+                });
             }
 
             let start_time = toi.toi;
@@ -332,7 +389,7 @@ impl CCDSolver {
             for ch1 in &colliders_to_check {
                 let co1 = &colliders[*ch1];
                 let rb1 = &bodies[co1.parent];
-                let aabb = co1.compute_swept_aabb(&(rb1.next_position * co1.position_wrt_parent()));
+                let aabb = co1.compute_swept_aabb_with_parent_position(&rb1.next_position);
 
                 self.query_pipeline
                     .colliders_with_aabb_intersecting_aabb(&aabb, |ch2| {
@@ -1,4 +1,4 @@
-pub use self::ccd_solver::{CCDSolver, PredictedImpacts};
+pub use self::ccd_solver::{CCDSolver, CcdEvent, PredictedImpacts};
 pub use self::toi_entry::TOIEntry;
 
 mod ccd_solver;
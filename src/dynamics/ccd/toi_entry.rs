@@ -58,18 +58,23 @@ impl TOIEntry {
 
         #[cfg(feature = "dim2")]
         let vel12 = (linvel2 - linvel1).norm()
-            + angvel1.abs() * b1.ccd_max_dist
-            + angvel2.abs() * b2.ccd_max_dist;
+            + angvel1.abs() * b1.ccd_max_dist()
+            + angvel2.abs() * b2.ccd_max_dist();
         #[cfg(feature = "dim3")]
         let vel12 = (linvel2 - linvel1).norm()
-            + angvel1.norm() * b1.ccd_max_dist
-            + angvel2.norm() * b2.ccd_max_dist;
+            + angvel1.norm() * b1.ccd_max_dist()
+            + angvel2.norm() * b2.ccd_max_dist();
 
         // We may be slightly over-conservative by taking the `max(0.0)` here.
         // But removing the `max` doesn't really affect performances so let's
         // keep it since more conservatism is good at this stage.
-        let thickness = (c1.shape().ccd_thickness() + c2.shape().ccd_thickness())
-            + smallest_contact_dist.max(0.0);
+        let thickness1 = b1
+            .ccd_thickness_override()
+            .unwrap_or_else(|| c1.ccd_thickness());
+        let thickness2 = b2
+            .ccd_thickness_override()
+            .unwrap_or_else(|| c2.ccd_thickness());
+        let thickness = (thickness1 + thickness2) + smallest_contact_dist.max(0.0);
         let is_intersection_test = c1.is_sensor() || c2.is_sensor();
 
         if (end_time - start_time) * vel12 < thickness {
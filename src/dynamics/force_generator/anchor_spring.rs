@@ -0,0 +1,104 @@
+use crate::dynamics::{ForceGenerator, RigidBodyHandle, RigidBodySet};
+use crate::geometry::ColliderSet;
+use crate::math::{Point, Real, Vector};
+
+/// A spring attaching a point of a rigid-body to a fixed point of the world.
+///
+/// The spring is only applied while its rigid-body is active: the rigid-body must be woken up
+/// after moving the world anchor of the spring.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct AnchorSpring {
+    /// The rigid-body attached to the spring.
+    pub body: RigidBodyHandle,
+    /// The point attached to the spring, expressed in the local-space of the rigid-body.
+    pub local_anchor: Point<Real>,
+    /// The world-space point the other end of the spring is fixed to.
+    pub world_anchor: Point<Real>,
+    /// The length of the spring when it applies no force.
+    pub rest_length: Real,
+    /// The force applied by the spring per unit of stretch.
+    pub stiffness: Real,
+    /// The force opposing the stretching velocity of the spring, per unit of velocity.
+    pub damping: Real,
+}
+
+impl AnchorSpring {
+    /// Creates an undamped spring of length zero at rest, between a point of a rigid-body and
+    /// a world-space point.
+    pub fn new(
+        body: RigidBodyHandle,
+        local_anchor: Point<Real>,
+        world_anchor: Point<Real>,
+        stiffness: Real,
+    ) -> Self {
+        Self {
+            body,
+            local_anchor,
+            world_anchor,
+            rest_length: 0.0,
+            stiffness,
+            damping: 0.0,
+        }
+    }
+}
+
+impl ForceGenerator for AnchorSpring {
+    fn apply(&mut self, _: Real, bodies: &mut RigidBodySet, _: &ColliderSet) {
+        let rb = match bodies.get_mut_internal(self.body) {
+            Some(rb) if rb.is_dynamic() && !rb.is_sleeping() => rb,
+            _ => return,
+        };
+
+        let point = rb.position * self.local_anchor;
+        let delta = self.world_anchor - point;
+        let length = delta.norm();
+
+        if length <= Real::EPSILON {
+            return;
+        }
+
+        let dir = delta / length;
+        let stretch_velocity = -rb.velocity_at_point(&point).dot(&dir);
+        let magnitude =
+            self.stiffness * (length - self.rest_length) - self.damping * stretch_velocity;
+        rb.apply_force_at_point(dir * magnitude, point, false);
+    }
+
+    fn shift_origin(&mut self, shift: &Vector<Real>) {
+        self.world_anchor += *shift;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AnchorSpring;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Point, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn hanging_body_settles_at_spring_extension() {
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        let handle = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        world.colliders.insert(
+            ColliderBuilder::ball(0.5).build(),
+            handle,
+            &mut world.bodies,
+        );
+
+        // A critically-damped spring, stretched by a tenth of the gravity at rest.
+        let mass = world.bodies[handle].mass();
+        let stiffness = 10.0 * mass;
+        let mut spring = AnchorSpring::new(handle, Point::origin(), Point::origin(), stiffness);
+        spring.damping = 2.0 * (stiffness * mass).sqrt();
+        world.force_generators.insert(spring);
+
+        for _ in 0..600 {
+            world.step(1.0 / 60.0);
+        }
+
+        assert!((world.bodies[handle].position().translation.y + 0.981).abs() < 1.0e-2);
+    }
+}
@@ -0,0 +1,124 @@
+use crate::dynamics::{ForceGenerator, RigidBodySet};
+use crate::geometry::ColliderSet;
+use crate::math::{Point, Real, Vector};
+use na::Unit;
+
+/// The buoyancy of a fluid filling a half-space, like the sea.
+///
+/// Every active dynamic rigid-body with colliders partially or totally submerged is pushed
+/// out of the fluid by a force equal to the weight of the displaced fluid, applied at the
+/// center of the submerged volume. The submerged volume of each non-sensor collider is
+/// approximated from the part of its axis-aligned bounding box located below the surface of
+/// the fluid. Submerged rigid-bodies are also slowed down by the drag of the fluid.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct Buoyancy {
+    /// A point on the surface of the fluid.
+    pub surface: Point<Real>,
+    /// The normal of the surface of the fluid, pointing out of the fluid.
+    pub normal: Unit<Vector<Real>>,
+    /// The density of the fluid.
+    pub density: Real,
+    /// The gravity, the weight of the displaced fluid is computed from.
+    pub gravity: Vector<Real>,
+    /// The drag force proportional to the velocity of a fully-submerged collider.
+    pub linear_drag: Real,
+    /// The drag torque proportional to the angular velocity of a fully-submerged collider.
+    pub angular_drag: Real,
+}
+
+impl Buoyancy {
+    /// Creates the buoyancy of a fluid with the given surface and density, without any drag.
+    pub fn new(
+        surface: Point<Real>,
+        normal: Unit<Vector<Real>>,
+        density: Real,
+        gravity: Vector<Real>,
+    ) -> Self {
+        Self {
+            surface,
+            normal,
+            density,
+            gravity,
+            linear_drag: 0.0,
+            angular_drag: 0.0,
+        }
+    }
+}
+
+impl ForceGenerator for Buoyancy {
+    fn apply(&mut self, _: Real, bodies: &mut RigidBodySet, colliders: &ColliderSet) {
+        bodies.foreach_active_dynamic_body_mut_internal(|_, rb| {
+            for i in 0..rb.colliders().len() {
+                let collider = &colliders[rb.colliders()[i]];
+
+                if collider.is_sensor() {
+                    continue;
+                }
+
+                // The elevation of the collider AABB relative to the surface.
+                let aabb = collider.compute_aabb();
+                let center = aabb.center();
+                let radius = aabb.half_extents().dot(&self.normal.abs());
+                let height = (center - self.surface).dot(&self.normal);
+                let bottom = height - radius;
+                let top = (height + radius).min(0.0);
+
+                if bottom >= 0.0 || radius <= 0.0 {
+                    continue;
+                }
+
+                let submerged_fraction = ((top - bottom) / (2.0 * radius)).min(1.0);
+                let volume = collider.shape().mass_properties(1.0).mass() * submerged_fraction;
+                let submerged_center = center + *self.normal * ((top + bottom) / 2.0 - height);
+
+                let buoyancy = self.gravity * (-self.density * volume);
+                let drag = rb.velocity_at_point(&submerged_center)
+                    * (-self.linear_drag * submerged_fraction);
+                rb.apply_force_at_point(buoyancy + drag, submerged_center, false);
+                rb.torque -= rb.angvel * (self.angular_drag * submerged_fraction);
+            }
+        });
+    }
+
+    fn shift_origin(&mut self, shift: &Vector<Real>) {
+        self.surface += *shift;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Buoyancy;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Point, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn box_floats_at_equilibrium() {
+        let gravity = Vector::y() * -9.81;
+        let mut world = PhysicsWorld::new(gravity);
+
+        #[cfg(feature = "dim2")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 1.0);
+        #[cfg(feature = "dim3")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 1.0, 0.0);
+        let handle = world.bodies.insert(rb.build());
+        #[cfg(feature = "dim2")]
+        let collider = ColliderBuilder::cuboid(0.5, 0.5).density(1.0).build();
+        #[cfg(feature = "dim3")]
+        let collider = ColliderBuilder::cuboid(0.5, 0.5, 0.5).density(1.0).build();
+        world.colliders.insert(collider, handle, &mut world.bodies);
+
+        // A fluid twice as dense as the box, so half of the box is submerged at equilibrium.
+        let mut water = Buoyancy::new(Point::origin(), Vector::y_axis(), 2.0, gravity);
+        water.linear_drag = 5.0;
+        world.force_generators.insert(water);
+
+        for _ in 0..600 {
+            world.step(1.0 / 60.0);
+        }
+
+        assert!(world.bodies[handle].position().translation.y.abs() < 1.0e-2);
+    }
+}
@@ -0,0 +1,42 @@
+use crate::dynamics::{ForceGenerator, RigidBodySet};
+use crate::geometry::ColliderSet;
+use crate::math::Real;
+
+/// A drag slowing down every active dynamic rigid-body, like the resistance of the air.
+///
+/// Unlike the damping of a rigid-body, which is an acceleration, the drag is a force: heavy
+/// rigid-bodies are less affected than light ones. The drag is applied explicitly, so its
+/// coefficients must remain small compared to the mass of the rigid-bodies divided by the
+/// timestep length, or their velocities will oscillate instead of decreasing.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct Drag {
+    /// The drag force proportional to the linear velocity of a rigid-body.
+    pub linear: Real,
+    /// The drag force proportional to the square of the linear velocity of a rigid-body.
+    pub quadratic: Real,
+    /// The drag torque proportional to the angular velocity of a rigid-body.
+    pub angular: Real,
+}
+
+impl Drag {
+    /// Creates a drag with the given linear and angular coefficients, and no quadratic drag.
+    pub fn new(linear: Real, angular: Real) -> Self {
+        Self {
+            linear,
+            quadratic: 0.0,
+            angular,
+        }
+    }
+}
+
+impl ForceGenerator for Drag {
+    fn apply(&mut self, _: Real, bodies: &mut RigidBodySet, _: &ColliderSet) {
+        bodies.foreach_active_dynamic_body_mut_internal(|_, rb| {
+            let linvel = rb.linvel;
+            let coeff = self.linear + self.quadratic * linvel.norm();
+            rb.apply_force(linvel * -coeff, false);
+            rb.torque -= rb.angvel * self.angular;
+        });
+    }
+}
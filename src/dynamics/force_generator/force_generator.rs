@@ -0,0 +1,27 @@
+use crate::dynamics::RigidBodySet;
+use crate::geometry::ColliderSet;
+use crate::math::{Real, Vector};
+use downcast_rs::{impl_downcast, DowncastSync};
+
+/// Something applying forces to rigid-bodies at each timestep.
+///
+/// Forces applied with `RigidBody::apply_force` are reset after every timestep, so persistent
+/// forces like drag or buoyancy must be re-applied before each step. A force generator added
+/// to a `ForceGeneratorSet` does this automatically: the set applies all its generators right
+/// before each timestep, once the user modifications of the rigid-bodies are known.
+pub trait ForceGenerator: DowncastSync {
+    /// Applies the forces of this generator to the rigid-bodies, for the next timestep of
+    /// length `dt`.
+    ///
+    /// Forces should only be applied to the active dynamic rigid-bodies, without waking up the
+    /// sleeping ones, since the forces of sleeping rigid-bodies are only reset once they wake up.
+    fn apply(&mut self, dt: Real, bodies: &mut RigidBodySet, colliders: &ColliderSet);
+
+    /// Translates the world-space points of this generator by `shift`.
+    ///
+    /// This is called when the origin of the world is shifted with `PhysicsWorld::shift_origin`.
+    /// The default implementation does nothing, which suits generators without world-space points.
+    fn shift_origin(&mut self, _shift: &Vector<Real>) {}
+}
+
+impl_downcast!(sync ForceGenerator);
@@ -0,0 +1,159 @@
+use crate::data::arena::Arena;
+use crate::dynamics::{ForceGenerator, RigidBodySet};
+use crate::geometry::ColliderSet;
+use crate::math::{Real, Vector};
+
+/// The unique identifier of a force generator added to a force generator set.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[repr(transparent)]
+pub struct ForceGeneratorHandle(pub(crate) crate::data::arena::Index);
+
+impl ForceGeneratorHandle {
+    /// Converts this handle into its (index, generation) components.
+    pub fn into_raw_parts(self) -> (usize, u64) {
+        self.0.into_raw_parts()
+    }
+
+    /// Reconstructs an handle from its (index, generation) components.
+    pub fn from_raw_parts(id: usize, generation: u64) -> Self {
+        Self(crate::data::arena::Index::from_raw_parts(id, generation))
+    }
+
+    /// An always-invalid force generator handle.
+    pub fn invalid() -> Self {
+        Self(crate::data::arena::Index::from_raw_parts(
+            crate::INVALID_USIZE,
+            crate::INVALID_U64,
+        ))
+    }
+}
+
+/// A set of force generators, applied to the rigid-bodies before each timestep.
+///
+/// A `PhysicsWorld` applies its force generators automatically. When the `PhysicsPipeline` is
+/// used directly, `ForceGeneratorSet::apply` must be called right before `PhysicsPipeline::step`.
+#[derive(Default)]
+pub struct ForceGeneratorSet {
+    generators: Arena<Box<dyn ForceGenerator>>,
+}
+
+impl ForceGeneratorSet {
+    /// Creates an empty set of force generators.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of force generators in this set.
+    pub fn len(&self) -> usize {
+        self.generators.len()
+    }
+
+    /// `true` if there are no force generators in this set.
+    pub fn is_empty(&self) -> bool {
+        self.generators.is_empty()
+    }
+
+    /// Is the given force generator handle valid?
+    pub fn contains(&self, handle: ForceGeneratorHandle) -> bool {
+        self.generators.contains(handle.0)
+    }
+
+    /// Inserts a new force generator into this set.
+    pub fn insert(&mut self, generator: impl ForceGenerator) -> ForceGeneratorHandle {
+        ForceGeneratorHandle(self.generators.insert(Box::new(generator)))
+    }
+
+    /// Removes a force generator from this set.
+    pub fn remove(&mut self, handle: ForceGeneratorHandle) -> Option<Box<dyn ForceGenerator>> {
+        self.generators.remove(handle.0)
+    }
+
+    /// Gets the force generator with the given handle.
+    ///
+    /// Use `ForceGenerator::downcast_ref` to retrieve its concrete type.
+    pub fn get(&self, handle: ForceGeneratorHandle) -> Option<&dyn ForceGenerator> {
+        self.generators.get(handle.0).map(|g| &**g)
+    }
+
+    /// Gets a mutable reference to the force generator with the given handle.
+    ///
+    /// Use `ForceGenerator::downcast_mut` to retrieve its concrete type.
+    pub fn get_mut(&mut self, handle: ForceGeneratorHandle) -> Option<&mut dyn ForceGenerator> {
+        self.generators.get_mut(handle.0).map(|g| &mut **g)
+    }
+
+    /// Iterates through all the force generators of this set.
+    pub fn iter(&self) -> impl Iterator<Item = (ForceGeneratorHandle, &dyn ForceGenerator)> {
+        self.generators
+            .iter()
+            .map(|(h, g)| (ForceGeneratorHandle(h), &**g))
+    }
+
+    /// Applies the forces of all the generators of this set, for the next timestep of length `dt`.
+    ///
+    /// The generators are applied in the order of their handles.
+    pub fn apply(&mut self, dt: Real, bodies: &mut RigidBodySet, colliders: &ColliderSet) {
+        for (_, generator) in self.generators.iter_mut() {
+            generator.apply(dt, bodies, colliders);
+        }
+    }
+
+    /// Translates the world-space points of all the generators of this set by `shift`.
+    pub fn shift_origin(&mut self, shift: &Vector<Real>) {
+        for (_, generator) in self.generators.iter_mut() {
+            generator.shift_origin(shift);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ForceGeneratorSet;
+    use crate::dynamics::{ForceGenerator, RigidBodyBuilder, RigidBodySet, Wind};
+    use crate::geometry::{ColliderBuilder, ColliderSet};
+    use crate::math::{Real, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    struct ConstantForce(Vector<Real>);
+
+    impl ForceGenerator for ConstantForce {
+        fn apply(&mut self, _: Real, bodies: &mut RigidBodySet, _: &ColliderSet) {
+            bodies.foreach_active_dynamic_body_mut_internal(|_, rb| rb.apply_force(self.0, false));
+        }
+    }
+
+    #[test]
+    fn generators_are_applied_at_each_step() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let handle = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        world.colliders.insert(
+            ColliderBuilder::ball(0.5).build(),
+            handle,
+            &mut world.bodies,
+        );
+        let mass = world.bodies[handle].mass();
+        let generator = world
+            .force_generators
+            .insert(ConstantForce(Vector::x() * mass));
+
+        let dt = 1.0 / 60.0;
+        for _ in 0..10 {
+            world.step(dt);
+        }
+
+        // The force is re-applied at each step, so the velocity keeps increasing.
+        let linvel = *world.bodies[handle].linvel();
+        assert!((linvel - Vector::x() * (10.0 * dt)).norm() < 1.0e-4);
+
+        assert!(world
+            .force_generators
+            .get(generator)
+            .unwrap()
+            .downcast_ref::<Wind>()
+            .is_none());
+        assert!(world.force_generators.remove(generator).is_some());
+        world.step(dt);
+        assert!((world.bodies[handle].linvel() - linvel).norm() < 1.0e-6);
+    }
+}
@@ -0,0 +1,13 @@
+pub use self::anchor_spring::AnchorSpring;
+pub use self::buoyancy::Buoyancy;
+pub use self::drag::Drag;
+pub use self::force_generator::ForceGenerator;
+pub use self::force_generator_set::{ForceGeneratorHandle, ForceGeneratorSet};
+pub use self::wind::Wind;
+
+mod anchor_spring;
+mod buoyancy;
+mod drag;
+mod force_generator;
+mod force_generator_set;
+mod wind;
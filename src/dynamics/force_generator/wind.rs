@@ -0,0 +1,36 @@
+use crate::dynamics::{ForceGenerator, RigidBodySet};
+use crate::geometry::ColliderSet;
+use crate::math::{Real, Vector};
+
+/// A wind pushing every active dynamic rigid-body.
+///
+/// The force applied to a rigid-body is proportional to the velocity of the wind relative to
+/// the rigid-body, so the rigid-bodies carried by the wind tend toward its velocity. Sleeping
+/// rigid-bodies are not woken up by the wind.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct Wind {
+    /// The velocity of the wind.
+    pub velocity: Vector<Real>,
+    /// The force applied to a rigid-body per unit of velocity relative to the wind.
+    pub coefficient: Real,
+}
+
+impl Wind {
+    /// Creates a wind with the given velocity and coefficient.
+    pub fn new(velocity: Vector<Real>, coefficient: Real) -> Self {
+        Self {
+            velocity,
+            coefficient,
+        }
+    }
+}
+
+impl ForceGenerator for Wind {
+    fn apply(&mut self, _: Real, bodies: &mut RigidBodySet, _: &ColliderSet) {
+        bodies.foreach_active_dynamic_body_mut_internal(|_, rb| {
+            let force = (self.velocity - rb.linvel) * self.coefficient;
+            rb.apply_force(force, false);
+        });
+    }
+}
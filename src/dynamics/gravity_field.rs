@@ -0,0 +1,120 @@
+use crate::math::{Point, Real, Vector};
+
+/// A gravity field, giving the gravitational acceleration at any point of space.
+///
+/// The gravity given to `PhysicsPipeline::step` can be any gravity field: it is evaluated at
+/// the world-space center-of-mass of each active dynamic rigid-body, once per timestep. A
+/// `Vector` is a uniform gravity field. Planets or black holes can be modeled with a
+/// `PointGravity`, or with a user-defined gravity field.
+pub trait GravityField {
+    /// The gravitational acceleration at the given world-space point.
+    fn gravity_at(&self, point: &Point<Real>) -> Vector<Real>;
+}
+
+impl GravityField for Vector<Real> {
+    fn gravity_at(&self, _: &Point<Real>) -> Vector<Real> {
+        *self
+    }
+}
+
+/// A gravity field attracting everything toward a point, like the gravity of a planet.
+///
+/// The acceleration is inversely proportional to the squared distance to the attracting point.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct PointGravity {
+    /// The point everything is attracted to.
+    pub center: Point<Real>,
+    /// The magnitude of the acceleration at a distance of 1 from `center`.
+    ///
+    /// This is the standard gravitational parameter (the gravitational constant multiplied by
+    /// the mass) of the attracting body.
+    pub strength: Real,
+    /// The acceleration at points closer to `center` than this distance is computed as if
+    /// they were at this distance (default: `1.0`).
+    ///
+    /// This avoids arbitrarily large accelerations near the center.
+    pub min_distance: Real,
+}
+
+impl PointGravity {
+    /// Creates a gravity field attracting everything toward `center`, with an acceleration of
+    /// magnitude `strength` at a distance of 1.
+    pub fn new(center: Point<Real>, strength: Real) -> Self {
+        Self {
+            center,
+            strength,
+            min_distance: 1.0,
+        }
+    }
+}
+
+impl GravityField for PointGravity {
+    fn gravity_at(&self, point: &Point<Real>) -> Vector<Real> {
+        let dir = self.center - point;
+        let dist = dir.norm();
+
+        if dist <= Real::EPSILON {
+            return Vector::zeros();
+        }
+
+        let clamped_dist = dist.max(self.min_distance);
+        dir * (self.strength / (dist * clamped_dist * clamped_dist))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PointGravity;
+    use crate::dynamics::{CCDSolver, IntegrationParameters, JointSet};
+    use crate::dynamics::{RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::{BroadPhase, ColliderBuilder, ColliderSet, NarrowPhase};
+    use crate::math::{Isometry, Point, Real, Vector};
+    use crate::pipeline::PhysicsPipeline;
+
+    #[test]
+    fn bodies_fall_toward_point_gravity() {
+        let mut pipeline = PhysicsPipeline::new();
+        let mut broad_phase = BroadPhase::new();
+        let mut narrow_phase = NarrowPhase::new();
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut joints = JointSet::new();
+        let mut ccd = CCDSolver::new();
+
+        // An acceleration of magnitude 1 at a distance of 10 from the origin.
+        let planet = PointGravity::new(Point::origin(), 100.0);
+        let dirs = [Vector::x(), -Vector::y()];
+        let handles: Vec<_> = dirs
+            .iter()
+            .map(|dir| {
+                let rb = RigidBodyBuilder::new_dynamic()
+                    .position(Isometry::new(dir * 10.0, na::zero()))
+                    .build();
+                let handle = bodies.insert(rb);
+                colliders.insert(ColliderBuilder::ball(0.5).build(), handle, &mut bodies);
+                handle
+            })
+            .collect();
+
+        let dt: Real = 0.1;
+        pipeline.step(
+            &planet,
+            dt,
+            &IntegrationParameters::default(),
+            &mut broad_phase,
+            &mut narrow_phase,
+            &mut bodies,
+            &mut colliders,
+            &mut joints,
+            &mut ccd,
+            &(),
+            &(),
+        );
+
+        for (dir, handle) in dirs.iter().zip(handles.iter()) {
+            let expected_linvel = -dir * dt;
+            assert!((bodies[*handle].linvel() - expected_linvel).norm() < 1.0e-4);
+        }
+    }
+}
@@ -4,8 +4,10 @@ use crate::math::Real;
 #[derive(Copy, Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub struct IntegrationParameters {
-    /// The timestep length (default: `1.0 / 60.0`)
-    pub dt: Real,
+    /// The timestep length of the step being executed.
+    ///
+    /// This is set by `PhysicsPipeline::step` from its `dt` argument.
+    pub(crate) dt: Real,
     /// Minimum timestep size when using CCD with multiple substeps (default `1.0 / 60.0 / 100.0`)
     ///
     /// When CCD with multiple substeps is enabled, the timestep is subdivided
@@ -72,11 +74,74 @@ pub struct IntegrationParameters {
     pub min_island_size: usize,
     /// Maximum number of substeps performed by the  solver (default: `1`).
     pub max_ccd_substeps: usize,
+    /// Number of substeps each timestep is split into by the constraints solver (default: `1`).
+    ///
+    /// Each substep integrates the forces applied to the rigid-bodies, solves the constraints,
+    /// and moves the bodies by a fraction of the timestep, which improves the stability of stacks
+    /// and the stiffness of joints. The contacts are recomputed between substeps, but the
+    /// broad-phase is only updated once per timestep (or per CCD substep) so pairs of colliders
+    /// that come close during a timestep don't generate contacts before the next one. Setting
+    /// this to zero is the same as setting it to one.
+    pub num_substeps: usize,
+    /// Maximum number of substeps performed by the solver when a CCD-active bullet exists (default: `4`).
+    ///
+    /// This is only used if it is greater than `max_ccd_substeps`, and if `max_ccd_substeps`
+    /// is not zero. The substeps beyond `max_ccd_substeps` are only performed for the impacts
+    /// involving a bullet. See `RigidBody::set_bullet` for details.
+    pub max_bullet_ccd_substeps: usize,
+    /// Seed used for shuffling the order in which constraints are solved (default: `None`).
+    ///
+    /// If set, the contact and joint constraints of each island are shuffled, deterministically
+    /// from this seed, before being solved. This is useful for checking if a simulation behavior is
+    /// sensitive to the constraints resolution order. If `None`, the constraints are solved in the
+    /// order they are generated by the narrow-phase and the joint set.
+    pub solver_ordering_seed: Option<u64>,
+    /// Linear velocity below which a resting body may have its velocities snapped to zero (default: `0.0`).
+    ///
+    /// Once the linear and angular velocities of a dynamic body remained below
+    /// `snap_linear_velocity_threshold` and `snap_angular_velocity_threshold`, without any
+    /// contact starting or stopping on this body, for at least `snap_min_resting_steps`
+    /// consecutive steps, they are set to zero. This suppresses the micro-jitter of large piles
+    /// of bodies that are kept awake by their neighbors. Setting both thresholds to zero
+    /// disables velocity snapping.
+    pub snap_linear_velocity_threshold: Real,
+    /// Angular velocity below which a resting body may have its velocities snapped to zero (default: `0.0`).
+    ///
+    /// See `snap_linear_velocity_threshold` for details.
+    pub snap_angular_velocity_threshold: Real,
+    /// Number of consecutive steps a body must remain nearly at rest, with the same contacts,
+    /// before its velocities are snapped to zero (default: `10`).
+    ///
+    /// See `snap_linear_velocity_threshold` for details.
+    pub snap_min_resting_steps: u32,
+    /// How joints affect the sleeping state of the bodies they are attached to
+    /// (default: `JointIslandMergePolicy::Lazy`).
+    pub joint_island_merge_policy: JointIslandMergePolicy,
+}
+
+/// How joints affect the sleeping state of the bodies they are attached to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub enum JointIslandMergePolicy {
+    /// Awake bodies wake up the bodies they are jointed to, merging their islands.
+    ///
+    /// A set of jointed bodies may fall asleep even if one of its joints has a motor
+    /// targeting a non-zero velocity, e.g., if the motor is too weak to move the bodies,
+    /// or if the motor was configured after the bodies fell asleep.
+    Lazy,
+    /// Same as `Lazy`, but the bodies attached to a joint with a motor targeting a
+    /// non-zero velocity, or a target position, are always kept awake, together with the
+    /// whole island they are part of.
+    ///
+    /// This is more expensive than `Lazy` since all the joints are checked at each timestep.
+    Strict,
 }
 
 impl IntegrationParameters {
     /// Creates a set of integration parameters with the given values.
-    #[deprecated = "Use `IntegrationParameters { dt: 60.0, ..Default::default() }` instead"]
+    ///
+    /// The `dt` argument is ignored: the timestep length is given to `PhysicsPipeline::step`.
+    #[deprecated = "Use `IntegrationParameters { erp: 0.2, ..Default::default() }` instead, and give the timestep length to `PhysicsPipeline::step`"]
     pub fn new(
         dt: Real,
         erp: Real,
@@ -108,28 +173,19 @@ impl IntegrationParameters {
         }
     }
 
-    /// The current time-stepping length.
+    /// The length of the last timestep executed with these integration parameters.
     #[inline(always)]
-    #[deprecated = "You can just read the `IntegrationParams::dt` value directly"]
+    #[deprecated = "The timestep length is now given to `PhysicsPipeline::step`"]
     pub fn dt(&self) -> Real {
         self.dt
     }
 
-    /// The inverse of the time-stepping length, i.e. the steps per seconds (Hz).
-    ///
-    /// This is zero if `self.dt` is zero.
-    #[inline(always)]
-    pub fn inv_dt(&self) -> Real {
-        if self.dt == 0.0 {
-            0.0
-        } else {
-            1.0 / self.dt
-        }
-    }
-
     /// Sets the time-stepping length.
+    ///
+    /// This has no effect on the simulation: the timestep length is overwritten by the `dt`
+    /// argument of `PhysicsPipeline::step`.
     #[inline]
-    #[deprecated = "You can just set the `IntegrationParams::dt` value directly"]
+    #[deprecated = "Give the timestep length to `PhysicsPipeline::step` instead"]
     pub fn set_dt(&mut self, dt: Real) {
         assert!(dt >= 0.0, "The time-stepping length cannot be negative.");
         self.dt = dt;
@@ -137,8 +193,10 @@ impl IntegrationParameters {
 
     /// Sets the inverse time-stepping length (i.e. the frequency).
     ///
-    /// This automatically recompute `self.dt`.
+    /// This has no effect on the simulation: the timestep length is overwritten by the `dt`
+    /// argument of `PhysicsPipeline::step`.
     #[inline]
+    #[deprecated = "Give the timestep length to `PhysicsPipeline::step` instead"]
     pub fn set_inv_dt(&mut self, inv_dt: Real) {
         if inv_dt == 0.0 {
             self.dt = 0.0
@@ -147,10 +205,30 @@ impl IntegrationParameters {
         }
     }
 
+    /// The inverse of the length of the last timestep executed with these integration
+    /// parameters, i.e. the steps per seconds (Hz).
+    ///
+    /// This is zero if the timestep length is zero.
+    #[inline(always)]
+    #[deprecated = "The timestep length is now given to `PhysicsPipeline::step`"]
+    pub fn inv_dt(&self) -> Real {
+        self.step_inv_dt()
+    }
+
+    // The inverse of the length of the timestep being executed, or zero if it is zero.
+    #[inline(always)]
+    pub(crate) fn step_inv_dt(&self) -> Real {
+        if self.dt == 0.0 {
+            0.0
+        } else {
+            1.0 / self.dt
+        }
+    }
+
     /// Convenience: `velocity_based_erp / dt`
     #[inline]
     pub(crate) fn velocity_based_erp_inv_dt(&self) -> Real {
-        self.velocity_based_erp * self.inv_dt()
+        self.velocity_based_erp * self.step_inv_dt()
     }
 }
 
@@ -180,6 +258,13 @@ impl Default for IntegrationParameters {
             // tons of islands, reducing SIMD parallelism opportunities.
             min_island_size: 128,
             max_ccd_substeps: 1,
+            num_substeps: 1,
+            max_bullet_ccd_substeps: 4,
+            solver_ordering_seed: None,
+            snap_linear_velocity_threshold: 0.0,
+            snap_angular_velocity_threshold: 0.0,
+            snap_min_resting_steps: 10,
+            joint_island_merge_policy: JointIslandMergePolicy::Lazy,
         }
     }
 }
@@ -0,0 +1,199 @@
+use crate::dynamics::{JointSet, RigidBodyHandle, RigidBodySet};
+use crate::geometry::{ColliderSet, NarrowPhase};
+use crate::math::{AngVector, Isometry, Real, Vector};
+use std::collections::HashSet;
+
+/// The part of the state of a rigid-body that evolves during the simulation.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct RigidBodyState {
+    /// The world-space position of the rigid-body.
+    pub position: Isometry<Real>,
+    /// The linear velocity of the rigid-body.
+    pub linvel: Vector<Real>,
+    /// The angular velocity of the rigid-body.
+    pub angvel: AngVector<Real>,
+    /// Is the rigid-body sleeping?
+    pub sleeping: bool,
+}
+
+/// A snapshot of the state of all the rigid-bodies of one island.
+///
+/// Capturing a snapshot only copies the state of the rigid-bodies of the island, so the
+/// snapshots can be serialized in a background thread while the simulation keeps going, and
+/// a large world can be saved a few islands at a time.
+///
+/// However, the snapshots of distinct islands captured at different timesteps only form a
+/// consistent save if these islands didn't interact between the captures. Once a body of one
+/// island touches a body of another island, or gets attached to it by a joint, both islands
+/// merge and restoring their snapshots taken at different times may, e.g., make these bodies
+/// overlap. Call `IslandSnapshot::compute_islands` again after each timestep and capture the
+/// merged islands anew.
+///
+/// An island snapshot only contains the state evolving during the simulation (positions,
+/// velocities, and sleeping state). The colliders, joints, and other rigid-body properties
+/// are expected to be saved separately, e.g., by serializing the `RigidBodySet`,
+/// `ColliderSet`, and `JointSet` once.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct IslandSnapshot {
+    /// The states of the rigid-bodies of the island.
+    pub bodies: Vec<(RigidBodyHandle, RigidBodyState)>,
+}
+
+impl IslandSnapshot {
+    /// Captures the current state of the given rigid-bodies.
+    ///
+    /// The given rigid-bodies are typically one of the islands computed by
+    /// `IslandSnapshot::compute_islands`. Invalid handles are ignored.
+    pub fn capture(island: &[RigidBodyHandle], bodies: &RigidBodySet) -> Self {
+        let bodies = island
+            .iter()
+            .filter_map(|handle| {
+                let rb = bodies.get(*handle)?;
+                let state = RigidBodyState {
+                    position: rb.position,
+                    linvel: rb.linvel,
+                    angvel: rb.angvel,
+                    sleeping: rb.is_sleeping(),
+                };
+                Some((*handle, state))
+            })
+            .collect();
+
+        Self { bodies }
+    }
+
+    /// Sets the state of the rigid-bodies of this snapshot.
+    ///
+    /// Rigid-bodies that no longer exist are ignored.
+    pub fn restore(&self, bodies: &mut RigidBodySet) {
+        for (handle, state) in &self.bodies {
+            if let Some(rb) = bodies.get_mut(*handle) {
+                rb.set_position(state.position, false);
+                rb.set_linvel(state.linvel, false);
+                rb.set_angvel(state.angvel, false);
+
+                if state.sleeping {
+                    rb.sleep();
+                } else {
+                    rb.wake_up(true);
+                }
+            }
+        }
+    }
+
+    /// Groups all the dynamic rigid-bodies, sleeping or not, into islands.
+    ///
+    /// Two dynamic rigid-bodies are part of the same island if they are linked by a chain of
+    /// contacts or joints between dynamic rigid-bodies. Non-dynamic rigid-bodies don't belong
+    /// to any island since their state isn't affected by the simulation.
+    pub fn compute_islands(
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        narrow_phase: &NarrowPhase,
+        joints: &JointSet,
+    ) -> Vec<Vec<RigidBodyHandle>> {
+        let mut islands = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = Vec::new();
+
+        for (root, rb) in bodies.iter() {
+            if !rb.is_dynamic() || visited.contains(&root) {
+                continue;
+            }
+
+            let mut island = Vec::new();
+            stack.push(root);
+
+            while let Some(handle) = stack.pop() {
+                let rb = match bodies.get(handle) {
+                    Some(rb) if rb.is_dynamic() => rb,
+                    _ => continue,
+                };
+
+                if !visited.insert(handle) {
+                    continue;
+                }
+
+                island.push(handle);
+
+                for collider_handle in rb.colliders() {
+                    if let Some(contacts) = narrow_phase.contacts_with(*collider_handle) {
+                        for inter in contacts {
+                            if inter.2.manifolds.iter().any(|m| !m.points.is_empty()) {
+                                let other = crate::utils::select_other(
+                                    (inter.0, inter.1),
+                                    *collider_handle,
+                                );
+                                stack.push(colliders[other].parent());
+                            }
+                        }
+                    }
+                }
+
+                for inter in joints.joint_graph().interactions_with(rb.joint_graph_index) {
+                    stack.push(crate::utils::select_other((inter.0, inter.1), handle));
+                }
+            }
+
+            islands.push(island);
+        }
+
+        islands
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IslandSnapshot;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Isometry, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    fn islands(world: &PhysicsWorld) -> Vec<Vec<crate::dynamics::RigidBodyHandle>> {
+        IslandSnapshot::compute_islands(
+            &world.bodies,
+            &world.colliders,
+            &world.narrow_phase,
+            &world.joints,
+        )
+    }
+
+    #[test]
+    fn restore_island_snapshot() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let mut balls = vec![];
+
+        for x in &[-2.0, 2.0] {
+            let mut ball = RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(Vector::x() * *x, na::zero()))
+                .build();
+            ball.set_linvel(Vector::x() * -*x, true);
+            let ball = world.bodies.insert(ball);
+            world
+                .colliders
+                .insert(ColliderBuilder::ball(0.5).build(), ball, &mut world.bodies);
+            balls.push(ball);
+        }
+
+        world.step(1.0 / 60.0);
+        assert_eq!(islands(&world).len(), 2);
+
+        let snapshot = IslandSnapshot::capture(&[balls[0]], &world.bodies);
+        let position = *world.bodies[balls[0]].position();
+
+        // The balls move toward each other until they touch: their islands merge, so the
+        // snapshot of the first island can no longer be combined with a later snapshot of
+        // the second one.
+        for _ in 0..60 {
+            world.step(1.0 / 60.0);
+        }
+        assert_eq!(islands(&world).len(), 1);
+
+        snapshot.restore(&mut world.bodies);
+        assert_eq!(*world.bodies[balls[0]].position(), position);
+        assert_eq!(*world.bodies[balls[0]].linvel(), Vector::x() * 2.0);
+    }
+}
@@ -1,6 +1,9 @@
 #[cfg(feature = "dim3")]
 use crate::dynamics::RevoluteJoint;
-use crate::dynamics::{BallJoint, FixedJoint, JointHandle, PrismaticJoint, RigidBodyHandle};
+use crate::dynamics::{
+    BallJoint, FixedJoint, JointHandle, MouseJoint, PrismaticJoint, RigidBodyHandle,
+};
+use crate::math::Real;
 
 #[derive(Copy, Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -17,6 +20,8 @@ pub enum JointParams {
     /// A revolute joint that removes all degrees of degrees of freedom between the affected
     /// bodies except for the translation along one axis.
     RevoluteJoint(RevoluteJoint),
+    /// A soft joint that pulls a point of the dynamic body toward a world-space target.
+    MouseJoint(MouseJoint),
     // GenericJoint(GenericJoint),
 }
 
@@ -30,6 +35,19 @@ impl JointParams {
             // JointParams::GenericJoint(_) => 3,
             #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(_) => 4,
+            JointParams::MouseJoint(_) => 5,
+        }
+    }
+
+    /// The magnitude of the impulse applied by this joint during the last timestep.
+    pub(crate) fn impulse_magnitude(&self) -> Real {
+        match self {
+            JointParams::BallJoint(j) => j.impulse.norm(),
+            JointParams::FixedJoint(j) => j.impulse.norm(),
+            JointParams::PrismaticJoint(j) => j.impulse.norm(),
+            #[cfg(feature = "dim3")]
+            JointParams::RevoluteJoint(j) => j.impulse.norm(),
+            JointParams::MouseJoint(j) => j.impulse.norm(),
         }
     }
 
@@ -78,6 +96,26 @@ impl JointParams {
             None
         }
     }
+
+    /// Gets a reference to the underlying mouse joint, if `self` is one.
+    pub fn as_mouse_joint(&self) -> Option<&MouseJoint> {
+        if let JointParams::MouseJoint(j) = self {
+            Some(j)
+        } else {
+            None
+        }
+    }
+
+    /// Gets a mutable reference to the underlying mouse joint, if `self` is one.
+    ///
+    /// This can be used to update the target of the mouse joint at each frame.
+    pub fn as_mouse_joint_mut(&mut self) -> Option<&mut MouseJoint> {
+        if let JointParams::MouseJoint(j) = self {
+            Some(j)
+        } else {
+            None
+        }
+    }
 }
 
 impl From<BallJoint> for JointParams {
@@ -111,6 +149,31 @@ impl From<PrismaticJoint> for JointParams {
     }
 }
 
+impl From<MouseJoint> for JointParams {
+    fn from(j: MouseJoint) -> Self {
+        JointParams::MouseJoint(j)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// One of the two position limits of a joint.
+pub enum JointLimit {
+    /// The lower position limit.
+    Min,
+    /// The upper position limit.
+    Max,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// Event occurring when a joint starts being pressed against one of its position limits.
+pub struct JointLimitReached {
+    /// The joint that reached one of its limits.
+    pub joint: JointHandle,
+    /// The limit reached by the joint.
+    pub limit: JointLimit,
+}
+
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 /// A joint attached to two bodies.
@@ -130,6 +193,30 @@ pub struct Joint {
 }
 
 impl Joint {
+    /// Does this joint have a motor attempting to reach a non-zero relative velocity, or a
+    /// target position?
+    pub fn has_driving_motor(&self) -> bool {
+        match &self.params {
+            JointParams::BallJoint(j) => {
+                j.motor_max_impulse > 0.0
+                    && (j.motor_stiffness > 0.0
+                        || (j.motor_damping > 0.0 && j.motor_target_vel != na::zero()))
+            }
+            JointParams::PrismaticJoint(j) => {
+                j.motor_max_impulse > 0.0
+                    && (j.motor_stiffness > 0.0
+                        || (j.motor_damping > 0.0 && j.motor_target_vel != 0.0))
+            }
+            #[cfg(feature = "dim3")]
+            JointParams::RevoluteJoint(j) => {
+                j.motor_max_impulse > 0.0
+                    && (j.motor_stiffness > 0.0
+                        || (j.motor_damping > 0.0 && j.motor_target_vel != 0.0))
+            }
+            JointParams::FixedJoint(_) | JointParams::MouseJoint(_) => false,
+        }
+    }
+
     /// Can this joint use SIMD-accelerated constraint formulations?
     pub fn supports_simd_constraints(&self) -> bool {
         match &self.params {
@@ -138,6 +225,35 @@ impl Joint {
             JointParams::BallJoint(joint) => joint.supports_simd_constraints(),
             #[cfg(feature = "dim3")]
             JointParams::RevoluteJoint(joint) => joint.supports_simd_constraints(),
+            JointParams::MouseJoint(joint) => joint.supports_simd_constraints(),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::{BallJoint, FixedJoint, JointSet, RigidBodyBuilder, RigidBodySet};
+    use crate::math::{Isometry, Point, Rotation};
+
+    #[test]
+    fn driving_motors() {
+        let mut bodies = RigidBodySet::new();
+        let mut joints = JointSet::new();
+        let body1 = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let body2 = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+
+        let mut idle = BallJoint::new(Point::origin(), Point::origin());
+        idle.configure_motor_velocity(na::zero(), 1.0);
+        let mut position_motor = BallJoint::new(Point::origin(), Point::origin());
+        position_motor.configure_motor_position(Rotation::identity(), 10.0, 1.0);
+        let fixed = FixedJoint::new(Isometry::identity(), Isometry::identity());
+
+        let idle = joints.insert(&mut bodies, body1, body2, idle);
+        let position_motor = joints.insert(&mut bodies, body1, body2, position_motor);
+        let fixed = joints.insert(&mut bodies, body1, body2, fixed);
+
+        assert!(!joints.get(idle).unwrap().has_driving_motor());
+        assert!(joints.get(position_motor).unwrap().has_driving_motor());
+        assert!(!joints.get(fixed).unwrap().has_driving_motor());
+    }
+}
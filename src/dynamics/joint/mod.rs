@@ -1,9 +1,10 @@
 pub use self::ball_joint::BallJoint;
 pub use self::fixed_joint::FixedJoint;
 // pub use self::generic_joint::GenericJoint;
-pub use self::joint::{Joint, JointParams};
+pub use self::joint::{Joint, JointLimit, JointLimitReached, JointParams};
 pub(crate) use self::joint_set::{JointGraphEdge, JointIndex};
 pub use self::joint_set::{JointHandle, JointSet};
+pub use self::mouse_joint::MouseJoint;
 pub use self::prismatic_joint::PrismaticJoint;
 #[cfg(feature = "dim3")]
 pub use self::revolute_joint::RevoluteJoint;
@@ -14,6 +15,7 @@ mod fixed_joint;
 // mod generic_joint;
 mod joint;
 mod joint_set;
+mod mouse_joint;
 mod prismatic_joint;
 #[cfg(feature = "dim3")]
 mod revolute_joint;
@@ -0,0 +1,155 @@
+use crate::dynamics::SpringModel;
+use crate::math::{Point, Real, Vector};
+
+#[derive(Copy, Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A soft joint that pulls a point of a rigid-body toward a world-space target.
+///
+/// This is typically used for dragging rigid-bodies with the mouse in editors, or for
+/// telekinesis-like mechanics. Only the dynamic rigid-body attached to this joint is affected:
+/// the other rigid-body (usually a static ground body) is only needed for inserting the
+/// joint into the `JointSet`. If both rigid-bodies are dynamic, the second one is dragged.
+pub struct MouseJoint {
+    /// Where the joint is attached on the dragged body, expressed in its local frame.
+    pub local_anchor: Point<Real>,
+    /// The world-space point the anchor is pulled toward.
+    pub target: Point<Real>,
+    /// The joint's stiffness.
+    /// See the documentation of `SpringModel` for more information on this parameter.
+    pub stiffness: Real,
+    /// The joint's damping.
+    /// See the documentation of `SpringModel` for more information on this parameter.
+    pub damping: Real,
+    /// The maximal force the joint is able to apply to the dragged body.
+    pub max_force: Real,
+    /// The spring-like model used to pull the anchor toward the target.
+    pub model: SpringModel,
+    /// The impulse applied by this joint on the dragged body.
+    pub impulse: Vector<Real>,
+}
+
+impl MouseJoint {
+    /// Creates a new mouse joint pulling the given anchor (expressed in the dragged body local frame)
+    /// toward the given world-space target.
+    pub fn new(local_anchor: Point<Real>, target: Point<Real>) -> Self {
+        Self {
+            local_anchor,
+            target,
+            stiffness: 0.2,
+            damping: 1.0,
+            max_force: Real::MAX,
+            model: SpringModel::VelocityBased,
+            impulse: Vector::zeros(),
+        }
+    }
+
+    /// Can a SIMD constraint be used for resolving this joint?
+    pub fn supports_simd_constraints(&self) -> bool {
+        false
+    }
+
+    /// Sets the world-space point the anchor is pulled toward.
+    ///
+    /// Note that this will not wake up the dragged body if it is sleeping.
+    pub fn set_target(&mut self, target: Point<Real>) {
+        self.target = target;
+    }
+
+    /// Sets the stiffness and damping of this joint.
+    pub fn set_stiffness_and_damping(&mut self, stiffness: Real, damping: Real) {
+        self.stiffness = stiffness;
+        self.damping = damping;
+    }
+
+    /// Sets the maximal force this joint is able to apply to the dragged body.
+    pub fn set_max_force(&mut self, max_force: Real) {
+        self.max_force = max_force;
+    }
+
+    /// Set the spring-like model used to pull the anchor toward the target.
+    pub fn configure_model(&mut self, model: SpringModel) {
+        self.model = model;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MouseJoint;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Point, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn drag_toward_target() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let dragged = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        world.colliders.insert(
+            ColliderBuilder::ball(0.5).build(),
+            dragged,
+            &mut world.bodies,
+        );
+
+        let target = Point::from(Vector::x() * 3.0);
+        let joint = MouseJoint::new(Point::origin(), target);
+        let handle = world
+            .joints
+            .insert(&mut world.bodies, ground, dragged, joint);
+
+        for _ in 0..200 {
+            world.step(1.0 / 60.0);
+        }
+
+        assert!(
+            (world.bodies[dragged].position().translation.vector - target.coords).norm() < 0.05
+        );
+
+        // Move the target, as done each frame when dragging with the mouse.
+        let new_target = Point::from(Vector::y() * 2.0);
+        world
+            .joints
+            .get_mut(handle)
+            .unwrap()
+            .params
+            .as_mouse_joint_mut()
+            .unwrap()
+            .set_target(new_target);
+        world.bodies[dragged].wake_up(true);
+
+        for _ in 0..200 {
+            world.step(1.0 / 60.0);
+        }
+
+        assert!(
+            (world.bodies[dragged].position().translation.vector - new_target.coords).norm() < 0.05
+        );
+    }
+
+    #[test]
+    fn max_force() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let dragged = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        world.colliders.insert(
+            ColliderBuilder::ball(0.5).build(),
+            dragged,
+            &mut world.bodies,
+        );
+        let mass = world.bodies[dragged].mass();
+
+        let mut joint = MouseJoint::new(Point::origin(), Point::from(Vector::x() * 100.0));
+        joint.set_max_force(mass);
+        world
+            .joints
+            .insert(&mut world.bodies, ground, dragged, joint);
+
+        for _ in 0..60 {
+            world.step(1.0 / 60.0);
+        }
+
+        // The joint accelerates the body by at most 1m/s² during one second.
+        assert!(world.bodies[dragged].linvel().norm() < 1.01);
+        assert!(world.bodies[dragged].linvel().x > 0.5);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::dynamics::SpringModel;
+use crate::dynamics::{JointLimit, SpringModel};
 use crate::math::{Isometry, Point, Real, Vector, DIM};
 use crate::utils::WBasis;
 use na::Unit;
@@ -37,6 +37,7 @@ pub struct PrismaticJoint {
     ///
     /// The impulse applied to the second body is given by `-impulse`.
     pub limits_impulse: Real,
+    pub(crate) active_limit: Option<JointLimit>,
 
     /// The target relative angular velocity the motor will attempt to reach.
     pub motor_target_vel: Real,
@@ -77,6 +78,7 @@ impl PrismaticJoint {
             limits_enabled: false,
             limits: [-Real::MAX, Real::MAX],
             limits_impulse: 0.0,
+            active_limit: None,
             motor_target_vel: 0.0,
             motor_target_pos: 0.0,
             motor_stiffness: 0.0,
@@ -135,6 +137,7 @@ impl PrismaticJoint {
             limits_enabled: false,
             limits: [-Real::MAX, Real::MAX],
             limits_impulse: 0.0,
+            active_limit: None,
             motor_target_vel: 0.0,
             motor_target_pos: 0.0,
             motor_stiffness: 0.0,
@@ -155,6 +158,66 @@ impl PrismaticJoint {
         self.local_axis2
     }
 
+    /// The translation of the second body's anchor relative to the first body's anchor,
+    /// along this joint's axis, given the positions of the two attached bodies.
+    pub fn translation(&self, position1: &Isometry<Real>, position2: &Isometry<Real>) -> Real {
+        let anchor1 = position1 * self.local_anchor1;
+        let anchor2 = position2 * self.local_anchor2;
+        let axis1 = position1 * self.local_axis1;
+        axis1.dot(&(anchor2 - anchor1))
+    }
+
+    /// The limit this joint was pressed against at the end of the last timestep, if any.
+    ///
+    /// This is always `None` if the limits of this joint are disabled.
+    pub fn active_limit(&self) -> Option<JointLimit> {
+        self.active_limit
+    }
+
+    /// The magnitude of the force applied to enforce the limits of this joint
+    /// during the last timestep of length `dt`.
+    pub fn limits_force(&self, dt: Real) -> Real {
+        if dt == 0.0 {
+            0.0
+        } else {
+            self.limits_impulse.abs() / dt
+        }
+    }
+
+    /// Updates the limit this joint is pressed against.
+    ///
+    /// The joint is considered pressed against a limit if its translation is closer
+    /// to that limit than `tolerance`. Returns the limit if it was just reached.
+    pub(crate) fn update_active_limit(
+        &mut self,
+        position1: &Isometry<Real>,
+        position2: &Isometry<Real>,
+        tolerance: Real,
+    ) -> Option<JointLimit> {
+        let active_limit = if self.limits_enabled {
+            let translation = self.translation(position1, position2);
+
+            if translation <= self.limits[0] + tolerance {
+                Some(JointLimit::Min)
+            } else if translation >= self.limits[1] - tolerance {
+                Some(JointLimit::Max)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+
+        let reached = if active_limit != self.active_limit {
+            active_limit
+        } else {
+            None
+        };
+
+        self.active_limit = active_limit;
+        reached
+    }
+
     /// Can a SIMD constraint be used for resolving this joint?
     pub fn supports_simd_constraints(&self) -> bool {
         // SIMD revolute constraints don't support motors right now.
@@ -246,3 +309,69 @@ impl PrismaticJoint {
         self.motor_damping = damping;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::PrismaticJoint;
+    use crate::dynamics::{JointLimit, JointLimitReached, RigidBodyBuilder};
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Point, Vector};
+    use crate::pipeline::{ChannelEventCollector, PhysicsWorld};
+
+    #[test]
+    fn limit_events() {
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        let (intersection_send, _intersection_recv) = crossbeam::channel::unbounded();
+        let (contact_send, _contact_recv) = crossbeam::channel::unbounded();
+        let (limit_send, limit_recv) = crossbeam::channel::unbounded();
+        world.events = Box::new(
+            ChannelEventCollector::new(intersection_send, contact_send)
+                .with_joint_limit_event_sender(limit_send),
+        );
+
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let (piston, _) = world.insert_dynamic(Vector::zeros(), ColliderBuilder::ball(0.5));
+
+        #[cfg(feature = "dim2")]
+        let mut prismatic = PrismaticJoint::new(
+            Point::origin(),
+            Vector::y_axis(),
+            Point::origin(),
+            Vector::y_axis(),
+        );
+        #[cfg(feature = "dim3")]
+        let mut prismatic = PrismaticJoint::new(
+            Point::origin(),
+            Vector::y_axis(),
+            Vector::x(),
+            Point::origin(),
+            Vector::y_axis(),
+            Vector::x(),
+        );
+        prismatic.limits_enabled = true;
+        prismatic.limits = [-1.0, 1.0];
+        let joint = world
+            .joints
+            .insert(&mut world.bodies, ground, piston, prismatic);
+
+        world.run(120);
+
+        let prismatic = world
+            .joints
+            .get(joint)
+            .unwrap()
+            .params
+            .as_prismatic_joint()
+            .unwrap();
+        let weight = world.bodies[piston].mass() * 9.81;
+        assert_eq!(prismatic.active_limit(), Some(JointLimit::Min));
+        assert!((prismatic.limits_force(1.0 / 60.0) - weight).abs() < weight * 0.1);
+        assert_eq!(
+            limit_recv.try_iter().collect::<Vec<_>>(),
+            vec![JointLimitReached {
+                joint,
+                limit: JointLimit::Min
+            }]
+        );
+    }
+}
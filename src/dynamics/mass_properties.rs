@@ -0,0 +1,190 @@
+use crate::dynamics::MassProperties;
+#[cfg(feature = "dim3")]
+use crate::math::Point;
+use crate::math::{Real, Vector};
+#[cfg(feature = "dim3")]
+use parry::utils::hashmap::HashMap;
+#[cfg(feature = "dim3")]
+use std::fmt;
+
+/// Additional constructors for the mass properties of hollow objects.
+///
+/// Mass properties can already be composed using the `+` and `-` operators. The
+/// helpers of this trait build on top of these operators to compute the mass
+/// properties of common hollow shapes, without having to attach dummy colliders
+/// with negative densities.
+pub trait MassPropertiesExt: Sized {
+    /// Computes the mass properties of a hollow cuboid.
+    ///
+    /// The inner cuboid is centered at the origin of the outer cuboid and must be
+    /// fully contained by it.
+    fn cuboid_minus_cuboid(
+        density: Real,
+        outer_half_extents: Vector<Real>,
+        inner_half_extents: Vector<Real>,
+    ) -> Self;
+
+    /// Computes the mass properties of a hollow ball.
+    ///
+    /// The inner ball is centered at the origin of the outer ball and its radius
+    /// must not be greater than the outer radius.
+    fn ball_minus_ball(density: Real, outer_radius: Real, inner_radius: Real) -> Self;
+
+    /// Computes the mass properties of a hollow cylinder (e.g. a barrel) aligned with the `y` axis.
+    ///
+    /// The inner cylinder is centered at the origin of the outer cylinder and must be
+    /// fully contained by it.
+    #[cfg(feature = "dim3")]
+    fn cylinder_minus_cylinder(
+        density: Real,
+        outer_half_height: Real,
+        outer_radius: Real,
+        inner_half_height: Real,
+        inner_radius: Real,
+    ) -> Self;
+
+    /// Computes the mass properties of the volume enclosed by a closed triangle mesh.
+    ///
+    /// The mesh must be closed and its triangles consistently oriented counter-clockwise
+    /// when seen from the outside. An error is returned if the mesh does not satisfy
+    /// these conditions, or if it encloses a non-positive volume.
+    #[cfg(feature = "dim3")]
+    fn from_closed_trimesh(
+        density: Real,
+        vertices: &[Point<Real>],
+        indices: &[[u32; 3]],
+    ) -> Result<Self, TrimeshMassPropertiesError>;
+}
+
+impl MassPropertiesExt for MassProperties {
+    fn cuboid_minus_cuboid(
+        density: Real,
+        outer_half_extents: Vector<Real>,
+        inner_half_extents: Vector<Real>,
+    ) -> Self {
+        assert!(
+            inner_half_extents
+                .iter()
+                .zip(outer_half_extents.iter())
+                .all(|(inner, outer)| inner <= outer),
+            "The inner cuboid must be contained by the outer cuboid."
+        );
+        MassProperties::cuboid(density, outer_half_extents)
+            - MassProperties::cuboid(density, inner_half_extents)
+    }
+
+    fn ball_minus_ball(density: Real, outer_radius: Real, inner_radius: Real) -> Self {
+        assert!(
+            inner_radius <= outer_radius,
+            "The inner ball must be contained by the outer ball."
+        );
+        MassProperties::ball(density, outer_radius) - MassProperties::ball(density, inner_radius)
+    }
+
+    #[cfg(feature = "dim3")]
+    fn cylinder_minus_cylinder(
+        density: Real,
+        outer_half_height: Real,
+        outer_radius: Real,
+        inner_half_height: Real,
+        inner_radius: Real,
+    ) -> Self {
+        assert!(
+            inner_half_height <= outer_half_height && inner_radius <= outer_radius,
+            "The inner cylinder must be contained by the outer cylinder."
+        );
+        MassProperties::cylinder(density, outer_half_height, outer_radius)
+            - MassProperties::cylinder(density, inner_half_height, inner_radius)
+    }
+
+    #[cfg(feature = "dim3")]
+    fn from_closed_trimesh(
+        density: Real,
+        vertices: &[Point<Real>],
+        indices: &[[u32; 3]],
+    ) -> Result<Self, TrimeshMassPropertiesError> {
+        if indices.is_empty() {
+            return Err(TrimeshMassPropertiesError::EmptyMesh);
+        }
+
+        // Each directed edge must appear exactly once, and its opposite must
+        // appear exactly once too. This ensures the mesh is closed and
+        // consistently oriented.
+        let mut edges = HashMap::default();
+        let mut volume = 0.0;
+
+        for (tri_id, idx) in indices.iter().enumerate() {
+            if idx.iter().any(|i| *i as usize >= vertices.len()) {
+                return Err(TrimeshMassPropertiesError::IndexOutOfBounds(tri_id));
+            }
+
+            if idx[0] == idx[1] || idx[1] == idx[2] || idx[2] == idx[0] {
+                return Err(TrimeshMassPropertiesError::DegenerateTriangle(tri_id));
+            }
+
+            for k in 0..3 {
+                let edge = (idx[k], idx[(k + 1) % 3]);
+                if edges.insert(edge, tri_id).is_some() {
+                    return Err(TrimeshMassPropertiesError::InconsistentOrientation(tri_id));
+                }
+            }
+
+            let a = vertices[idx[0] as usize].coords;
+            let b = vertices[idx[1] as usize].coords;
+            let c = vertices[idx[2] as usize].coords;
+            volume += a.dot(&b.cross(&c)) / 6.0;
+        }
+
+        for (edge, tri_id) in &edges {
+            if !edges.contains_key(&(edge.1, edge.0)) {
+                return Err(TrimeshMassPropertiesError::NotClosed(*tri_id));
+            }
+        }
+
+        if volume <= 0.0 {
+            return Err(TrimeshMassPropertiesError::NonPositiveVolume);
+        }
+
+        Ok(MassProperties::from_trimesh(density, vertices, indices))
+    }
+}
+
+#[cfg(feature = "dim3")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// Error returned when the mass properties of a triangle mesh could not be computed.
+pub enum TrimeshMassPropertiesError {
+    /// The triangle mesh has no triangle.
+    EmptyMesh,
+    /// The triangle with the given index references a vertex that does not exist.
+    IndexOutOfBounds(usize),
+    /// The triangle with the given index references the same vertex more than once.
+    DegenerateTriangle(usize),
+    /// The triangle with the given index has an edge shared with a triangle with the same orientation.
+    InconsistentOrientation(usize),
+    /// The triangle with the given index has an edge not shared with any other triangle.
+    NotClosed(usize),
+    /// The triangle mesh encloses a zero or negative volume, e.g., because its triangles are
+    /// oriented clockwise.
+    NonPositiveVolume,
+}
+
+#[cfg(feature = "dim3")]
+impl fmt::Display for TrimeshMassPropertiesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyMesh => write!(f, "the triangle mesh is empty"),
+            Self::IndexOutOfBounds(i) => {
+                write!(f, "triangle {} references a non-existent vertex", i)
+            }
+            Self::DegenerateTriangle(i) => write!(f, "triangle {} is degenerate", i),
+            Self::InconsistentOrientation(i) => {
+                write!(f, "triangle {} is not consistently oriented", i)
+            }
+            Self::NotClosed(i) => write!(f, "triangle {} has a boundary edge", i),
+            Self::NonPositiveVolume => write!(f, "the triangle mesh has a non-positive volume"),
+        }
+    }
+}
+
+#[cfg(feature = "dim3")]
+impl std::error::Error for TrimeshMassPropertiesError {}
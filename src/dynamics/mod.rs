@@ -1,8 +1,15 @@
 //! Structures related to dynamics: bodies, joints, etc.
 
-pub use self::ccd::CCDSolver;
+#[cfg(feature = "dim2")]
+pub use self::angular_steering::AngularSteering;
+pub use self::ccd::{CCDSolver, CcdEvent};
 pub use self::coefficient_combine_rule::CoefficientCombineRule;
-pub use self::integration_parameters::IntegrationParameters;
+pub use self::force_generator::{
+    AnchorSpring, Buoyancy, Drag, ForceGenerator, ForceGeneratorHandle, ForceGeneratorSet, Wind,
+};
+pub use self::gravity_field::{GravityField, PointGravity};
+pub use self::integration_parameters::{IntegrationParameters, JointIslandMergePolicy};
+pub use self::island_snapshot::{IslandSnapshot, RigidBodyState};
 pub(crate) use self::joint::JointGraphEdge;
 pub(crate) use self::joint::JointIndex;
 #[cfg(feature = "dim3")]
@@ -12,13 +19,21 @@ pub use self::joint::{
     FixedJoint,
     Joint,
     JointHandle,
+    JointLimit,
+    JointLimitReached,
     JointParams,
     JointSet,
+    MouseJoint,
     PrismaticJoint,
     SpringModel, // GenericJoint
 };
-pub(crate) use self::rigid_body::RigidBodyChanges;
-pub use self::rigid_body::{ActivationStatus, BodyStatus, RigidBody, RigidBodyBuilder};
+pub use self::mass_properties::MassPropertiesExt;
+#[cfg(feature = "dim3")]
+pub use self::mass_properties::TrimeshMassPropertiesError;
+pub use self::plank_bridge::{PlankBridge, PlankBridgeBuilder, PlankBridgeWarning};
+pub use self::rigid_body::{
+    ActivationStatus, BodyStatus, RigidBody, RigidBodyBuilder, RigidBodyChanges, SolverImpulses,
+};
 pub use self::rigid_body_set::{BodyPair, RigidBodyHandle, RigidBodySet};
 #[cfg(not(feature = "parallel"))]
 pub(crate) use self::solver::IslandSolver;
@@ -26,10 +41,29 @@ pub(crate) use self::solver::IslandSolver;
 pub(crate) use self::solver::ParallelIslandSolver;
 pub use parry::mass_properties::MassProperties;
 
+#[derive(Copy, Clone, Hash, Debug, PartialEq, Eq)]
+/// Events occurring when a rigid-body falls asleep or wakes up.
+pub enum SleepEvent {
+    /// Event occurring when a dynamic rigid-body falls asleep.
+    FellAsleep(RigidBodyHandle),
+    /// Event occurring when a dynamic rigid-body wakes up.
+    ///
+    /// This event is generated whenever a rigid-body that was sleeping at the last timestep
+    /// becomes active again, either because of a contact, a joint, or a user action.
+    WokeUp(RigidBodyHandle),
+}
+
+#[cfg(feature = "dim2")]
+mod angular_steering;
 mod ccd;
 mod coefficient_combine_rule;
+mod force_generator;
+mod gravity_field;
 mod integration_parameters;
+mod island_snapshot;
 mod joint;
+mod mass_properties;
+mod plank_bridge;
 mod rigid_body;
 mod rigid_body_set;
 mod solver;
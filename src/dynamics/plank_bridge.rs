@@ -0,0 +1,339 @@
+#[cfg(feature = "dim2")]
+use crate::dynamics::BallJoint;
+#[cfg(feature = "dim3")]
+use crate::dynamics::RevoluteJoint;
+use crate::dynamics::{
+    IntegrationParameters, JointHandle, JointSet, RigidBodyBuilder, RigidBodyHandle, RigidBodySet,
+};
+use crate::geometry::{ColliderBuilder, ColliderSet};
+use crate::math::{Isometry, Point, Real, Rotation, Vector};
+use na::RealField;
+use std::fmt;
+
+/// The mass ratio between the load and a plank above which a bridge cannot be stable.
+const MAX_STABLE_MASS_RATIO: Real = 100.0;
+/// The minimum number of timesteps per oscillation period of a single plank.
+const MIN_STEPS_PER_PERIOD: Real = 10.0;
+
+/// A builder for a plank bridge (e.g. a rope bridge).
+///
+/// A plank bridge is a chain of dynamic planks linked by hinges, hanging between two
+/// static anchors. Long chains of joints are notoriously hard to simulate in a stable way,
+/// so this builder also provides helpers for tuning the integration parameters, and
+/// for detecting configurations that cannot be stable.
+#[derive(Copy, Clone, Debug)]
+pub struct PlankBridgeBuilder {
+    start: Point<Real>,
+    end: Point<Real>,
+    num_planks: usize,
+    plank_thickness: Real,
+    #[cfg(feature = "dim3")]
+    plank_width: Real,
+    plank_gap: Real,
+    plank_density: Real,
+    max_load_mass: Real,
+}
+
+/// The rigid-bodies and joints of a plank bridge built by a `PlankBridgeBuilder`.
+#[derive(Clone, Debug)]
+pub struct PlankBridge {
+    /// The static rigid-bodies the bridge hangs from.
+    pub anchors: [RigidBodyHandle; 2],
+    /// The dynamic rigid-bodies of the planks, from the first anchor to the second one.
+    pub planks: Vec<RigidBodyHandle>,
+    /// The hinges linking the planks together and to the anchors.
+    pub joints: Vec<JointHandle>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// A reason why a plank bridge may not be simulated in a stable way.
+pub enum PlankBridgeWarning {
+    /// The maximum load is too heavy compared to the mass of a plank.
+    ///
+    /// Increase the density of the planks or decrease the maximum load.
+    MassRatioTooLarge {
+        /// The ratio between the maximum load mass and the mass of one plank.
+        ratio: Real,
+    },
+    /// The velocity constraints solver does not perform enough iterations.
+    ///
+    /// See `PlankBridgeBuilder::tune_integration_parameters`.
+    NotEnoughIterations {
+        /// The recommended minimum number of velocity iterations.
+        recommended: usize,
+    },
+    /// The timestep is too large for resolving the swinging motion of the planks.
+    TimestepTooLarge {
+        /// The recommended maximum timestep length.
+        max_dt: Real,
+    },
+}
+
+impl fmt::Display for PlankBridgeWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MassRatioTooLarge { ratio } => write!(
+                f,
+                "the load to plank mass ratio ({}) is too large for a stable bridge",
+                ratio
+            ),
+            Self::NotEnoughIterations { recommended } => write!(
+                f,
+                "at least {} velocity iterations are recommended for this bridge",
+                recommended
+            ),
+            Self::TimestepTooLarge { max_dt } => write!(
+                f,
+                "a timestep smaller than {} is recommended for this bridge",
+                max_dt
+            ),
+        }
+    }
+}
+
+impl PlankBridgeBuilder {
+    /// Initializes the builder of a bridge spanning from `start` to `end` with the given number of planks.
+    pub fn new(start: Point<Real>, end: Point<Real>, num_planks: usize) -> Self {
+        assert!(num_planks > 0, "A plank bridge needs at least one plank.");
+        assert!(
+            start != end,
+            "The two ends of a plank bridge must be different."
+        );
+
+        Self {
+            start,
+            end,
+            num_planks,
+            plank_thickness: 0.1,
+            #[cfg(feature = "dim3")]
+            plank_width: 1.0,
+            plank_gap: 0.05,
+            plank_density: 1.0,
+            max_load_mass: 0.0,
+        }
+    }
+
+    /// Sets the thickness of the planks (default: `0.1`).
+    pub fn plank_thickness(mut self, thickness: Real) -> Self {
+        assert!(thickness > 0.0, "The plank thickness must be positive.");
+        self.plank_thickness = thickness;
+        self
+    }
+
+    /// Sets the width of the planks, i.e., their extent orthogonal to the bridge span (default: `1.0`).
+    #[cfg(feature = "dim3")]
+    pub fn plank_width(mut self, width: Real) -> Self {
+        assert!(width > 0.0, "The plank width must be positive.");
+        self.plank_width = width;
+        self
+    }
+
+    /// Sets the gap between two consecutive planks (default: `0.05`).
+    pub fn plank_gap(mut self, gap: Real) -> Self {
+        assert!(
+            gap >= 0.0 && gap < self.segment_length(),
+            "The plank gap must be non-negative and smaller than the plank spacing."
+        );
+        self.plank_gap = gap;
+        self
+    }
+
+    /// Sets the density of the planks (default: `1.0`).
+    pub fn plank_density(mut self, density: Real) -> Self {
+        assert!(density > 0.0, "The plank density must be positive.");
+        self.plank_density = density;
+        self
+    }
+
+    /// Sets the mass of the heaviest load expected to cross the bridge, e.g., a character (default: `0.0`).
+    pub fn max_load_mass(mut self, mass: Real) -> Self {
+        assert!(mass >= 0.0, "The maximum load mass must be non-negative.");
+        self.max_load_mass = mass;
+        self
+    }
+
+    fn segment_length(&self) -> Real {
+        (self.end - self.start).norm() / (self.num_planks as Real)
+    }
+
+    fn plank_length(&self) -> Real {
+        self.segment_length() - self.plank_gap
+    }
+
+    /// The mass of one plank of this bridge.
+    pub fn plank_mass(&self) -> Real {
+        #[cfg(feature = "dim2")]
+        return self.plank_density * self.plank_length() * self.plank_thickness;
+        #[cfg(feature = "dim3")]
+        return self.plank_density * self.plank_length() * self.plank_thickness * self.plank_width;
+    }
+
+    /// The number of velocity iterations recommended for simulating this bridge.
+    ///
+    /// The velocity solver propagates impulses by about one joint per iteration, so this
+    /// grows with the number of planks, as well as with the ratio between the maximum load
+    /// and the mass of a plank.
+    pub fn recommended_velocity_iterations(&self) -> usize {
+        let mass_ratio = self.max_load_mass / self.plank_mass();
+        let half_chain = (self.num_planks + 2) / 2;
+        let iterations = (half_chain as Real * (1.0 + mass_ratio).sqrt()).ceil() as usize;
+        iterations.max(4)
+    }
+
+    /// Adjusts the given integration parameters so this bridge can be simulated in a stable way.
+    ///
+    /// This increases the number of velocity and position iterations if needed, and reduces
+    /// the joint ERP for long chains of planks to avoid jittering. Note that these parameters
+    /// affect the whole simulation.
+    pub fn tune_integration_parameters(&self, params: &mut IntegrationParameters) {
+        params.max_velocity_iterations = params
+            .max_velocity_iterations
+            .max(self.recommended_velocity_iterations());
+        params.max_position_iterations = params.max_position_iterations.max(2);
+
+        let joint_erp = 0.2 * (8.0 / self.num_planks as Real).min(1.0).max(0.25);
+        params.joint_erp = params.joint_erp.min(joint_erp);
+    }
+
+    /// Checks if this bridge can be simulated in a stable way with the given timestep length,
+    /// integration parameters, and gravity.
+    ///
+    /// Returns an empty vector if no stability issue was detected.
+    pub fn stability_warnings(
+        &self,
+        dt: Real,
+        params: &IntegrationParameters,
+        gravity: &Vector<Real>,
+    ) -> Vec<PlankBridgeWarning> {
+        let mut warnings = Vec::new();
+        let ratio = self.max_load_mass / self.plank_mass();
+
+        if ratio > MAX_STABLE_MASS_RATIO {
+            warnings.push(PlankBridgeWarning::MassRatioTooLarge { ratio });
+        }
+
+        let recommended = self.recommended_velocity_iterations();
+        if params.max_velocity_iterations < recommended {
+            warnings.push(PlankBridgeWarning::NotEnoughIterations { recommended });
+        }
+
+        // The swinging period of a single plank, approximated as a pendulum.
+        let g = gravity.norm();
+        if g > 0.0 {
+            let period = 2.0 * Real::pi() * (self.segment_length() / g).sqrt();
+            let max_dt = period / MIN_STEPS_PER_PERIOD;
+
+            if dt > max_dt {
+                warnings.push(PlankBridgeWarning::TimestepTooLarge { max_dt });
+            }
+        }
+
+        warnings
+    }
+
+    /// Builds the bridge, inserting its rigid-bodies, colliders, and joints into the given sets.
+    pub fn build(
+        &self,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        joints: &mut JointSet,
+    ) -> PlankBridge {
+        let span = self.end - self.start;
+        let dir = span.normalize();
+        let segment_length = self.segment_length();
+        let half_segment = segment_length / 2.0;
+        let half_length = self.plank_length() / 2.0;
+        let half_thickness = self.plank_thickness / 2.0;
+
+        #[cfg(feature = "dim2")]
+        let rotation = Rotation::new(dir.y.atan2(dir.x));
+        #[cfg(feature = "dim3")]
+        let (rotation, lateral) = {
+            let mut lateral = dir.cross(&Vector::y());
+            if lateral.norm_squared() < 1.0e-6 {
+                // The bridge is vertical.
+                lateral = Vector::z();
+            }
+            let lateral = lateral.normalize();
+            let normal = lateral.cross(&dir);
+            let basis = na::Rotation3::from_basis_unchecked(&[dir, normal, lateral]);
+            (Rotation::from_rotation_matrix(&basis), lateral)
+        };
+
+        let anchor1 = bodies.insert(
+            RigidBodyBuilder::new_static()
+                .position(Isometry::new(self.start.coords, na::zero()))
+                .build(),
+        );
+        let anchor2 = bodies.insert(
+            RigidBodyBuilder::new_static()
+                .position(Isometry::new(self.end.coords, na::zero()))
+                .build(),
+        );
+
+        let mut planks = Vec::with_capacity(self.num_planks);
+        let mut plank_joints = Vec::with_capacity(self.num_planks + 1);
+        let mut prev = anchor1;
+
+        for i in 0..=self.num_planks {
+            let body = if i < self.num_planks {
+                let center = self.start + dir * (segment_length * (i as Real + 0.5));
+                let handle = bodies.insert(
+                    RigidBodyBuilder::new_dynamic()
+                        .position(Isometry::from_parts(center.coords.into(), rotation))
+                        .build(),
+                );
+
+                #[cfg(feature = "dim2")]
+                let collider = ColliderBuilder::cuboid(half_length, half_thickness);
+                #[cfg(feature = "dim3")]
+                let collider =
+                    ColliderBuilder::cuboid(half_length, half_thickness, self.plank_width / 2.0);
+                colliders.insert(collider.density(self.plank_density).build(), handle, bodies);
+                planks.push(handle);
+                handle
+            } else {
+                anchor2
+            };
+
+            // The first anchor and the last plank are attached at their origin, and
+            // at their right end, respectively.
+            let local_anchor1 = if prev == anchor1 {
+                Point::origin()
+            } else {
+                Point::from(Vector::x() * half_segment)
+            };
+            let local_anchor2 = if body == anchor2 {
+                Point::origin()
+            } else {
+                Point::from(-Vector::x() * half_segment)
+            };
+
+            #[cfg(feature = "dim2")]
+            let joint = BallJoint::new(local_anchor1, local_anchor2);
+            #[cfg(feature = "dim3")]
+            let joint = {
+                let axis1 = if prev == anchor1 {
+                    na::Unit::new_unchecked(lateral)
+                } else {
+                    Vector::z_axis()
+                };
+                let axis2 = if body == anchor2 {
+                    na::Unit::new_unchecked(lateral)
+                } else {
+                    Vector::z_axis()
+                };
+                RevoluteJoint::new(local_anchor1, axis1, local_anchor2, axis2)
+            };
+
+            plank_joints.push(joints.insert(bodies, prev, body, joint));
+            prev = body;
+        }
+
+        PlankBridge {
+            anchors: [anchor1, anchor2],
+            planks,
+            joints: plank_joints,
+        }
+    }
+}
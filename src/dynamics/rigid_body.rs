@@ -1,13 +1,21 @@
-use crate::dynamics::MassProperties;
+#[cfg(feature = "dim2")]
+use crate::dynamics::AngularSteering;
+use crate::dynamics::{IntegrationParameters, MassProperties, RigidBodyHandle};
 use crate::geometry::{
-    Collider, ColliderHandle, ColliderSet, InteractionGraph, RigidBodyGraphIndex,
+    Collider, ColliderChanges, ColliderHandle, ColliderSet, InteractionGraph, RigidBodyGraphIndex,
+    AABB,
 };
 use crate::math::{
     AngVector, AngularInertia, Isometry, Point, Real, Rotation, Translation, Vector,
 };
 use crate::utils::{self, WAngularInertia, WCross, WDot};
 use na::ComplexField;
+#[cfg(feature = "dim3")]
+use na::Unit;
 use num::Zero;
+use parry::bounding_volume::BoundingVolume;
+use std::any::Any;
+use std::sync::Arc;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -31,25 +39,54 @@ pub enum BodyStatus {
 bitflags::bitflags! {
     #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
     /// Flags affecting the behavior of the constraints solver for a given contact manifold.
-    pub(crate) struct RigidBodyFlags: u8 {
+    pub(crate) struct RigidBodyFlags: u16 {
         const TRANSLATION_LOCKED = 1 << 0;
         const ROTATION_LOCKED_X = 1 << 1;
         const ROTATION_LOCKED_Y = 1 << 2;
         const ROTATION_LOCKED_Z = 1 << 3;
         const CCD_ENABLED = 1 << 4;
         const CCD_ACTIVE = 1 << 5;
+        const GYROSCOPIC_FORCES_ENABLED = 1 << 6;
+        const TRANSLATION_LOCKED_X = 1 << 7;
+        const TRANSLATION_LOCKED_Y = 1 << 8;
+        const TRANSLATION_LOCKED_Z = 1 << 9;
+        const BULLET = 1 << 10;
     }
 }
 
+#[cfg(feature = "dim3")]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// An arbitrary axis the rotations of a rigid-body are locked about.
+enum RotationAxisLock {
+    /// The axis is expressed in world-space.
+    World(Unit<Vector<Real>>),
+    /// The axis is expressed in the local-space of the rigid-body.
+    Local(Unit<Vector<Real>>),
+}
+
 bitflags::bitflags! {
     #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-    /// Flags describing how the rigid-body has been modified by the user.
-    pub(crate) struct RigidBodyChanges: u32 {
+    /// Flags describing how a rigid-body has been modified.
+    ///
+    /// These flags are set as soon as the rigid-body is modified, and are accumulated until
+    /// `RigidBodySet::clear_modified_flags` is called. A rigid-body that was just inserted into
+    /// a `RigidBodySet` has all its flags set. See `RigidBody::changes` for details.
+    pub struct RigidBodyChanges: u32 {
+        /// The rigid-body was borrowed mutably from its `RigidBodySet`, or modified by the
+        /// physics pipeline.
         const MODIFIED    = 1 << 0;
+        /// The position of the rigid-body was set by the user, or the rigid-body was
+        /// moved by the physics pipeline.
         const POSITION    = 1 << 1;
+        /// The rigid-body was woken up or put to sleep by the user.
         const SLEEP       = 1 << 2;
+        /// A collider was attached to, or detached from, the rigid-body.
         const COLLIDERS   = 1 << 3;
+        /// The status (dynamic, static, kinematic) of the rigid-body changed.
         const BODY_STATUS = 1 << 4;
+        /// The dominance group of the rigid-body changed.
+        const DOMINANCE   = 1 << 5;
     }
 }
 
@@ -76,7 +113,13 @@ pub struct RigidBody {
     /// The world-space center of mass of the rigid-body.
     pub world_com: Point<Real>,
     /// The inverse mass taking into account translation locking.
+    ///
+    /// This is zero if all the translations are locked with `lock_translations`, but it
+    /// doesn't take into account the axes locked with `restrict_translations`.
+    #[deprecated = "Use `RigidBody::effective_inv_mass_along_axes` instead"]
     pub effective_inv_mass: Real,
+    /// The inverse mass along each coordinate axis, taking into account translation locking.
+    pub(crate) effective_inv_mass_along_axes: Vector<Real>,
     /// The square-root of the world-space inverse angular inertia tensor of the rigid-body,
     /// taking into account rotation locking.
     pub effective_world_inv_inertia_sqrt: AngularInertia<Real>,
@@ -94,6 +137,7 @@ pub struct RigidBody {
     pub(crate) torque: AngVector<Real>,
     pub(crate) colliders: Vec<ColliderHandle>,
     pub(crate) gravity_scale: Real,
+    pub(crate) constant_acceleration: Vector<Real>,
     /// Whether or not this rigid-body is sleeping.
     pub activation: ActivationStatus,
     pub(crate) joint_graph_index: RigidBodyGraphIndex,
@@ -102,18 +146,40 @@ pub struct RigidBody {
     pub(crate) active_set_offset: usize,
     pub(crate) active_set_timestamp: u32,
     flags: RigidBodyFlags,
+    #[cfg(feature = "dim3")]
+    rotation_axis_lock: Option<RotationAxisLock>,
+    #[cfg(feature = "dim2")]
+    angular_steering: Option<AngularSteering>,
     pub(crate) changes: RigidBodyChanges,
+    // The changes already handled by the physics pipeline, but not yet cleared by the user.
+    pub(crate) tracked_changes: RigidBodyChanges,
     /// The status of the body, governing how it is affected by external forces.
     body_status: BodyStatus,
     /// The dominance group this rigid-body is part of.
     dominance_group: i8,
     /// User-defined data associated to this rigid-body.
     pub user_data: u128,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    user_object: Option<Arc<dyn Any + Send + Sync>>,
+    pub(crate) name: Option<String>,
     pub(crate) ccd_thickness: Real,
     pub(crate) ccd_max_dist: Real,
+    ccd_thickness_override: Option<Real>,
+    ccd_max_dist_override: Option<Real>,
+    pub(crate) platform: Option<RigidBodyHandle>,
+    pub(crate) platform_linvel: Vector<Real>,
+    pub(crate) platform_angvel: AngVector<Real>,
+    pub(crate) resting_steps: u32,
+    linvel_at_step_start: Vector<Real>,
+    angvel_at_step_start: AngVector<Real>,
+    last_linacc: Vector<Real>,
+    last_angacc: AngVector<Real>,
+    last_gravity: Vector<Real>,
+    pub(crate) last_solver_impulses: SolverImpulses,
 }
 
 impl RigidBody {
+    #[allow(deprecated)]
     fn new() -> Self {
         Self {
             position: Isometry::identity(),
@@ -121,12 +187,14 @@ impl RigidBody {
             mass_properties: MassProperties::zero(),
             world_com: Point::origin(),
             effective_inv_mass: 0.0,
+            effective_inv_mass_along_axes: Vector::zeros(),
             effective_world_inv_inertia_sqrt: AngularInertia::zero(),
             linvel: Vector::zeros(),
             angvel: na::zero(),
             force: Vector::zeros(),
             torque: na::zero(),
             gravity_scale: 1.0,
+            constant_acceleration: Vector::zeros(),
             linear_damping: 0.0,
             angular_damping: 0.0,
             colliders: Vec::new(),
@@ -137,12 +205,31 @@ impl RigidBody {
             active_set_offset: 0,
             active_set_timestamp: 0,
             flags: RigidBodyFlags::empty(),
+            #[cfg(feature = "dim3")]
+            rotation_axis_lock: None,
+            #[cfg(feature = "dim2")]
+            angular_steering: None,
             changes: RigidBodyChanges::all(),
+            tracked_changes: RigidBodyChanges::empty(),
             body_status: BodyStatus::Dynamic,
             dominance_group: 0,
             user_data: 0,
+            user_object: None,
+            name: None,
             ccd_thickness: Real::MAX,
             ccd_max_dist: 0.0,
+            ccd_thickness_override: None,
+            ccd_max_dist_override: None,
+            platform: None,
+            platform_linvel: Vector::zeros(),
+            platform_angvel: na::zero(),
+            resting_steps: 0,
+            linvel_at_step_start: Vector::zeros(),
+            angvel_at_step_start: na::zero(),
+            last_linacc: Vector::zeros(),
+            last_angacc: na::zero(),
+            last_gravity: Vector::zeros(),
+            last_solver_impulses: SolverImpulses::default(),
         }
     }
 
@@ -153,22 +240,26 @@ impl RigidBody {
         self.active_set_id = 0;
         self.active_set_offset = 0;
         self.active_set_timestamp = 0;
+        self.platform = None;
+        self.platform_linvel = Vector::zeros();
+        self.platform_angvel = na::zero();
     }
 
     pub(crate) fn add_gravity(&mut self, gravity: Vector<Real>) {
-        if self.effective_inv_mass != 0.0 {
-            self.force += gravity * self.gravity_scale * self.mass();
+        self.last_gravity = gravity * self.gravity_scale;
+
+        if !self.effective_inv_mass_along_axes.is_zero() {
+            self.force += (gravity * self.gravity_scale + self.constant_acceleration) * self.mass();
         }
     }
 
-    #[cfg(not(feature = "parallel"))] // in parallel solver this is not needed
-    pub(crate) fn integrate_accelerations(&mut self, dt: Real) {
-        let linear_acc = self.force * self.effective_inv_mass;
-        let angular_acc = self.effective_world_inv_inertia_sqrt
-            * (self.effective_world_inv_inertia_sqrt * self.torque);
-
-        self.linvel += linear_acc * dt;
-        self.angvel += angular_acc * dt;
+    #[cfg(feature = "dim2")]
+    pub(crate) fn add_angular_steering_torque(&mut self, dt: Real) {
+        if let Some(steering) = &self.angular_steering {
+            let acc =
+                steering.angular_acceleration(self.position.rotation.angle(), self.angvel, dt);
+            self.torque += self.effective_angular_inertia() * acc;
+        }
     }
 
     /// The status of this rigid-body.
@@ -190,7 +281,57 @@ impl RigidBody {
         &self.mass_properties
     }
 
+    /// The name of this rigid-body, if it has one.
+    ///
+    /// Use `RigidBodySet::set_name` to change the name of a rigid-body already
+    /// inserted into a set.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The user-defined object attached to this rigid-body, if it is of type `T`.
+    pub fn user_object<T: Any>(&self) -> Option<&T> {
+        self.user_object.as_ref()?.downcast_ref()
+    }
+
+    /// A mutable reference to the user-defined object attached to this rigid-body, if it is of type `T`.
+    ///
+    /// Returns `None` if the object is shared with a clone of this rigid-body.
+    pub fn user_object_mut<T: Any>(&mut self) -> Option<&mut T> {
+        Arc::get_mut(self.user_object.as_mut()?)?.downcast_mut()
+    }
+
+    /// Attaches an arbitrary user-defined object to this rigid-body, replacing the previous one.
+    ///
+    /// Unlike `user_data`, this object is not serialized. Cloning this rigid-body
+    /// will share the object between the clones.
+    pub fn set_user_object<T: Any + Send + Sync>(&mut self, object: T) {
+        self.user_object = Some(Arc::new(object));
+    }
+
+    /// Detaches the user-defined object attached to this rigid-body, if any.
+    pub fn remove_user_object(&mut self) -> Option<Arc<dyn Any + Send + Sync>> {
+        self.user_object.take()
+    }
+
     /// The dominance group of this rigid-body.
+    #[inline]
+    pub fn dominance_group(&self) -> i8 {
+        self.dominance_group
+    }
+
+    /// Sets the dominance group of this rigid-body.
+    ///
+    /// The contacts involving this rigid-body will take the new dominance
+    /// group into account at the next timestep.
+    pub fn set_dominance_group(&mut self, group: i8) {
+        if group != self.dominance_group {
+            self.changes.insert(RigidBodyChanges::DOMINANCE);
+            self.dominance_group = group;
+        }
+    }
+
+    /// The effective dominance group of this rigid-body.
     ///
     /// This method always returns `i8::MAX + 1` for non-dynamic
     /// rigid-bodies.
@@ -208,6 +349,52 @@ impl RigidBody {
         self.flags.contains(RigidBodyFlags::TRANSLATION_LOCKED)
     }
 
+    /// Returns `true` for each translational degrees of freedom locked on this rigid-body.
+    #[cfg(feature = "dim2")]
+    pub fn is_translation_locked_along_axes(&self) -> [bool; 2] {
+        let locked = self.is_translation_locked();
+        [
+            locked || self.flags.contains(RigidBodyFlags::TRANSLATION_LOCKED_X),
+            locked || self.flags.contains(RigidBodyFlags::TRANSLATION_LOCKED_Y),
+        ]
+    }
+
+    /// Returns `true` for each translational degrees of freedom locked on this rigid-body.
+    #[cfg(feature = "dim3")]
+    pub fn is_translation_locked_along_axes(&self) -> [bool; 3] {
+        let locked = self.is_translation_locked();
+        [
+            locked || self.flags.contains(RigidBodyFlags::TRANSLATION_LOCKED_X),
+            locked || self.flags.contains(RigidBodyFlags::TRANSLATION_LOCKED_Y),
+            locked || self.flags.contains(RigidBodyFlags::TRANSLATION_LOCKED_Z),
+        ]
+    }
+
+    // Removes the components of `v` along the coordinate axes the translations are locked along.
+    //
+    // NOTE: the effective inverse mass is already zero along the locked axes so the solver
+    //       never moves the body along them. This only cancels the velocities set by the user.
+    fn remove_locked_translations(&self, v: &mut Vector<Real>) {
+        if self.flags.contains(RigidBodyFlags::TRANSLATION_LOCKED_X) {
+            v.x = 0.0;
+        }
+        if self.flags.contains(RigidBodyFlags::TRANSLATION_LOCKED_Y) {
+            v.y = 0.0;
+        }
+        #[cfg(feature = "dim3")]
+        if self.flags.contains(RigidBodyFlags::TRANSLATION_LOCKED_Z) {
+            v.z = 0.0;
+        }
+    }
+
+    fn has_partially_locked_translations(&self) -> bool {
+        self.flags.intersects(
+            RigidBodyFlags::TRANSLATION_LOCKED_X
+                | RigidBodyFlags::TRANSLATION_LOCKED_Y
+                | RigidBodyFlags::TRANSLATION_LOCKED_Z,
+        )
+    }
+
     /// Are the rotations of this rigid-body locked?
     #[cfg(feature = "dim2")]
     pub fn is_rotation_locked(&self) -> bool {
@@ -224,6 +411,43 @@ impl RigidBody {
         ]
     }
 
+    /// Locks the rotations of this rigid-body about the given world-space axis.
+    ///
+    /// This is in addition to the locks about the principal coordinate axes. Only one
+    /// arbitrary axis can be locked at a time: this replaces any previously locked arbitrary axis.
+    #[cfg(feature = "dim3")]
+    pub fn lock_rotation_about_world_axis(&mut self, axis: Unit<Vector<Real>>) {
+        self.rotation_axis_lock = Some(RotationAxisLock::World(axis));
+        self.update_world_mass_properties();
+    }
+
+    /// Locks the rotations of this rigid-body about the given axis expressed in its local-space.
+    ///
+    /// The locked axis follows the rotations of the rigid-body. This is in addition to the locks
+    /// about the principal coordinate axes. Only one arbitrary axis can be locked at a time: this
+    /// replaces any previously locked arbitrary axis.
+    #[cfg(feature = "dim3")]
+    pub fn lock_rotation_about_local_axis(&mut self, axis: Unit<Vector<Real>>) {
+        self.rotation_axis_lock = Some(RotationAxisLock::Local(axis));
+        self.update_world_mass_properties();
+    }
+
+    /// Removes the lock set by `self.lock_rotation_about_world_axis` or `self.lock_rotation_about_local_axis`.
+    #[cfg(feature = "dim3")]
+    pub fn unlock_rotation_axis(&mut self) {
+        self.rotation_axis_lock = None;
+        self.update_world_mass_properties();
+    }
+
+    /// The world-space arbitrary axis the rotations of this rigid-body are locked about, if any.
+    #[cfg(feature = "dim3")]
+    pub fn locked_rotation_axis(&self) -> Option<Unit<Vector<Real>>> {
+        self.rotation_axis_lock.map(|lock| match lock {
+            RotationAxisLock::World(axis) => axis,
+            RotationAxisLock::Local(axis) => self.position.rotation * axis,
+        })
+    }
+
     /// Enables of disable CCD (continuous collision-detection) for this rigid-body.
     pub fn enable_ccd(&mut self, enabled: bool) {
         self.flags.set(RigidBodyFlags::CCD_ENABLED, enabled)
@@ -234,6 +458,84 @@ impl RigidBody {
         self.flags.contains(RigidBodyFlags::CCD_ENABLED)
     }
 
+    /// Enables or disables the bullet mode of this rigid-body.
+    ///
+    /// When a bullet is moving fast enough for its CCD to be active, the timestep is split
+    /// into up to `IntegrationParameters::max_bullet_ccd_substeps` substeps instead of
+    /// `IntegrationParameters::max_ccd_substeps`. This lets the bullet keep moving after its
+    /// first impact within the same timestep, without tunneling through the geometry it
+    /// hits next (e.g. corners). The extra substeps are only performed for the impacts
+    /// involving a bullet: the impacts between other bodies don't use them. Enabling the
+    /// bullet mode also enables CCD.
+    pub fn set_bullet(&mut self, enabled: bool) {
+        self.flags.set(RigidBodyFlags::BULLET, enabled);
+
+        if enabled {
+            self.enable_ccd(true);
+        }
+    }
+
+    /// Is this rigid-body a bullet?
+    ///
+    /// See `set_bullet` for details.
+    pub fn is_bullet(&self) -> bool {
+        self.flags.contains(RigidBodyFlags::BULLET)
+    }
+
+    /// The thickness used by the CCD to detect if this rigid-body is moving fast enough
+    /// to potentially tunnel through other objects.
+    ///
+    /// Unless overridden with `self.set_ccd_thickness`, this is automatically computed
+    /// from the shapes of the colliders attached to this rigid-body.
+    pub fn ccd_thickness(&self) -> Real {
+        self.ccd_thickness_override.unwrap_or(self.ccd_thickness)
+    }
+
+    /// Overrides the thickness used by the CCD for this rigid-body.
+    ///
+    /// Smaller thicknesses make the CCD more conservative, at the cost of more
+    /// frequent CCD runs. Set to `None` to use the automatically-computed thickness.
+    pub fn set_ccd_thickness(&mut self, thickness: Option<Real>) {
+        self.ccd_thickness_override = thickness;
+    }
+
+    pub(crate) fn ccd_thickness_override(&self) -> Option<Real> {
+        self.ccd_thickness_override
+    }
+
+    /// The maximum distance between the center-of-mass of this rigid-body and
+    /// the points of its colliders, used by the CCD to bound the motion due to
+    /// its angular velocity.
+    ///
+    /// Unless overridden with `self.set_ccd_max_dist`, this is automatically computed
+    /// from the shapes of the colliders attached to this rigid-body.
+    pub fn ccd_max_dist(&self) -> Real {
+        self.ccd_max_dist_override.unwrap_or(self.ccd_max_dist)
+    }
+
+    /// Overrides the maximum distance used by the CCD for bounding the angular motion of
+    /// this rigid-body.
+    ///
+    /// Larger distances make the CCD more conservative. Set to `None` to use the
+    /// automatically-computed distance.
+    pub fn set_ccd_max_dist(&mut self, max_dist: Option<Real>) {
+        self.ccd_max_dist_override = max_dist;
+    }
+
+    /// Enables or disables the integration of gyroscopic forces for this rigid-body.
+    #[cfg(feature = "dim3")]
+    pub fn enable_gyroscopic_forces(&mut self, enabled: bool) {
+        self.flags
+            .set(RigidBodyFlags::GYROSCOPIC_FORCES_ENABLED, enabled)
+    }
+
+    /// Are gyroscopic forces integrated for this rigid-body?
+    #[cfg(feature = "dim3")]
+    pub fn is_gyroscopic_forces_enabled(&self) -> bool {
+        self.flags
+            .contains(RigidBodyFlags::GYROSCOPIC_FORCES_ENABLED)
+    }
+
     // This is different from `is_ccd_enabled`. This checks that CCD
     // is active for this rigid-body, i.e., if it was seen to move fast
     // enough to justify a CCD run.
@@ -264,14 +566,14 @@ impl RigidBody {
             //       the narrow-phase, which can be pretty expensive. So we use the CCD thickness
             //       divided by 10 right now. We will see in practice if this value is OK or if we
             //       should use a smaller (to be less conservative) or larger divisor (to be more conservative).
-            let threshold = self.ccd_thickness / 10.0;
+            let threshold = self.ccd_thickness() / 10.0;
 
             if include_forces {
                 let linear_part = (self.linvel + self.force * dt).norm();
                 #[cfg(feature = "dim2")]
-                let angular_part = (self.angvel + self.torque * dt).abs() * self.ccd_max_dist;
+                let angular_part = (self.angvel + self.torque * dt).abs() * self.ccd_max_dist();
                 #[cfg(feature = "dim3")]
-                let angular_part = (self.angvel + self.torque * dt).norm() * self.ccd_max_dist;
+                let angular_part = (self.angvel + self.torque * dt).norm() * self.ccd_max_dist();
                 let vel_with_forces = linear_part + angular_part;
                 vel_with_forces > threshold
             } else {
@@ -284,9 +586,9 @@ impl RigidBody {
 
     pub(crate) fn max_point_velocity(&self) -> Real {
         #[cfg(feature = "dim2")]
-        return self.linvel.norm() + self.angvel.abs() * self.ccd_max_dist;
+        return self.linvel.norm() + self.angvel.abs() * self.ccd_max_dist();
         #[cfg(feature = "dim3")]
-        return self.linvel.norm() + self.angvel.norm() * self.ccd_max_dist;
+        return self.linvel.norm() + self.angvel.norm() * self.ccd_max_dist();
     }
 
     /// Sets the rigid-body's initial mass properties.
@@ -308,6 +610,44 @@ impl RigidBody {
         &self.colliders[..]
     }
 
+    /// Computes the world-space AABB enclosing all the colliders attached to this rigid-body.
+    ///
+    /// The AABB is computed from the current position of this rigid-body. If this rigid-body
+    /// has no collider, the returned AABB is invalid (its `mins` are greater than its `maxs`).
+    pub fn compute_aabb(&self, colliders: &ColliderSet) -> AABB {
+        let mut aabb = AABB::new_invalid();
+
+        for handle in &self.colliders {
+            if let Some(collider) = colliders.get(*handle) {
+                let pos = self.position * collider.position_wrt_parent();
+                aabb.merge(&collider.shape().compute_aabb(&pos));
+            }
+        }
+
+        aabb
+    }
+
+    /// Computes the world-space AABB enclosing all the colliders attached to this rigid-body
+    /// while it moves from its current position to its next position.
+    ///
+    /// If this rigid-body has no collider, the returned AABB is invalid (its `mins` are greater
+    /// than its `maxs`).
+    pub fn compute_swept_aabb(&self, colliders: &ColliderSet) -> AABB {
+        let mut aabb = AABB::new_invalid();
+
+        for handle in &self.colliders {
+            if let Some(collider) = colliders.get(*handle) {
+                let shape = collider.shape();
+                let pos1 = self.position * collider.position_wrt_parent();
+                let pos2 = self.next_position * collider.position_wrt_parent();
+                aabb.merge(&shape.compute_aabb(&pos1));
+                aabb.merge(&shape.compute_aabb(&pos2));
+            }
+        }
+
+        aabb
+    }
+
     /// Is this rigid body dynamic?
     ///
     /// A dynamic body can move freely and is affected by forces.
@@ -336,6 +676,81 @@ impl RigidBody {
         utils::inv(self.mass_properties.inv_mass)
     }
 
+    /// The inverse mass of this rigid body along each coordinate axis.
+    ///
+    /// This is zero along the axes the translations are locked along, and along every axis if
+    /// this rigid body has an infinite mass.
+    pub fn effective_inv_mass_along_axes(&self) -> Vector<Real> {
+        self.effective_inv_mass_along_axes
+    }
+
+    /// The world-space angular inertia tensor of this rigid-body, taking rotation locking into account.
+    ///
+    /// This is the (non-inverse) inertia tensor expressed in world-space. The components
+    /// associated to locked rotation axes, as well as any infinite angular inertia, are set to zero.
+    pub fn effective_angular_inertia(&self) -> AngularInertia<Real> {
+        #[cfg(feature = "dim2")]
+        {
+            utils::inv(self.effective_world_inv_inertia_sqrt.squared())
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            // NOTE: the inverse inertia has a zero principal component along the locked axes,
+            //       and along the axes with an infinite angular inertia, e.g. if the body is
+            //       static or kinematic. Only its other principal components are inverted, so
+            //       the inertia is zero along these axes, like in 2D.
+            let inv_inertia = self
+                .effective_world_inv_inertia_sqrt
+                .squared()
+                .into_matrix();
+            let eigen = inv_inertia.symmetric_eigen();
+            let threshold = eigen.eigenvalues.amax() * Real::EPSILON * 100.0;
+            let eigenvalues = eigen
+                .eigenvalues
+                .map(|e| if e > threshold { 1.0 / e } else { 0.0 });
+            let inertia = eigen.eigenvectors
+                * na::Matrix3::from_diagonal(&eigenvalues)
+                * eigen.eigenvectors.transpose();
+
+            AngularInertia::new(
+                inertia.m11,
+                inertia.m12,
+                inertia.m13,
+                inertia.m22,
+                inertia.m23,
+                inertia.m33,
+            )
+        }
+    }
+
+    /// The world-space inverse angular inertia tensor of this rigid-body, taking rotation locking into account.
+    ///
+    /// The components associated to locked rotation axes are zero. See `effective_world_inertia`
+    /// for the (non-inverse) world-space angular inertia tensor.
+    pub fn effective_world_inv_inertia(&self) -> AngularInertia<Real> {
+        self.effective_world_inv_inertia_sqrt.squared()
+    }
+
+    /// The world-space angular inertia tensor of this rigid-body, taking rotation locking into account.
+    ///
+    /// This is the inverse of `effective_world_inv_inertia`, where the components associated to
+    /// locked rotation axes, or to an infinite angular inertia, are zero. It is the same as
+    /// `effective_angular_inertia`.
+    pub fn effective_world_inertia(&self) -> AngularInertia<Real> {
+        self.effective_angular_inertia()
+    }
+
+    /// The effective angular inertia of this rigid-body around the given world-space axis.
+    ///
+    /// This is the scalar moment of inertia `axisᵀ * I * axis` where `I` is the tensor
+    /// returned by `effective_angular_inertia`. It is zero if the rotations around `axis` are locked.
+    #[cfg(feature = "dim3")]
+    pub fn effective_angular_inertia_around(&self, axis: &Unit<Vector<Real>>) -> Real {
+        let axis = axis.into_inner();
+        axis.dot(&self.effective_angular_inertia().transform_vector(axis))
+    }
+
     /// The predicted position of this rigid-body.
     ///
     /// If this rigid-body is kinematic this value is set by the `set_next_kinematic_position`
@@ -360,6 +775,44 @@ impl RigidBody {
         self.gravity_scale = scale;
     }
 
+    /// The constant linear acceleration applied to this rigid-body at each timestep, in addition to gravity.
+    pub fn constant_acceleration(&self) -> &Vector<Real> {
+        &self.constant_acceleration
+    }
+
+    /// Sets the constant linear acceleration applied to this rigid-body at each timestep.
+    ///
+    /// Unlike forces applied with `self.apply_force`, this acceleration is not reset
+    /// after each timestep: it keeps being applied until it is set to zero.
+    pub fn set_constant_acceleration(&mut self, acceleration: Vector<Real>, wake_up: bool) {
+        if wake_up && self.activation.sleeping {
+            self.changes.insert(RigidBodyChanges::SLEEP);
+            self.activation.sleeping = false;
+        }
+
+        self.constant_acceleration = acceleration;
+    }
+
+    /// The steering driving the orientation of this rigid-body toward a target angle, if any.
+    #[cfg(feature = "dim2")]
+    pub fn angular_steering(&self) -> Option<&AngularSteering> {
+        self.angular_steering.as_ref()
+    }
+
+    /// Sets the steering driving the orientation of this rigid-body toward a target angle.
+    ///
+    /// Set this to `None` to stop steering the rigid-body. This has no effect on
+    /// non-dynamic rigid-bodies and on rigid-bodies with locked rotations.
+    #[cfg(feature = "dim2")]
+    pub fn set_angular_steering(&mut self, steering: Option<AngularSteering>, wake_up: bool) {
+        if wake_up && self.activation.sleeping {
+            self.changes.insert(RigidBodyChanges::SLEEP);
+            self.activation.sleeping = false;
+        }
+
+        self.angular_steering = steering;
+    }
+
     /// Adds a collider to this rigid-body.
     pub(crate) fn add_collider(&mut self, handle: ColliderHandle, coll: &Collider) {
         self.changes.set(
@@ -367,7 +820,18 @@ impl RigidBody {
             true,
         );
 
-        self.ccd_thickness = self.ccd_thickness.min(coll.shape().ccd_thickness());
+        self.merge_collider_ccd_data(coll);
+
+        let mass_properties = coll
+            .mass_properties()
+            .transform_by(coll.position_wrt_parent());
+        self.colliders.push(handle);
+        self.mass_properties += mass_properties;
+        self.update_world_mass_properties();
+    }
+
+    fn merge_collider_ccd_data(&mut self, coll: &Collider) {
+        self.ccd_thickness = self.ccd_thickness.min(coll.ccd_thickness());
 
         let shape_bsphere = coll
             .shape()
@@ -375,12 +839,30 @@ impl RigidBody {
         self.ccd_max_dist = self
             .ccd_max_dist
             .max(shape_bsphere.center.coords.norm() + shape_bsphere.radius);
+    }
 
-        let mass_properties = coll
+    /// Recomputes the CCD thickness and max distance from all the colliders attached to this
+    /// rigid-body, e.g., after the shape of one of them changed.
+    pub(crate) fn update_colliders_ccd_data(&mut self, colliders: &ColliderSet) {
+        self.ccd_thickness = Real::MAX;
+        self.ccd_max_dist = 0.0;
+
+        for i in 0..self.colliders.len() {
+            if let Some(coll) = colliders.get(self.colliders[i]) {
+                self.merge_collider_ccd_data(coll);
+            }
+        }
+    }
+
+    pub(crate) fn update_collider_mass_properties(
+        &mut self,
+        coll: &Collider,
+        prev_mass_properties: &MassProperties,
+    ) {
+        self.mass_properties -= *prev_mass_properties;
+        self.mass_properties += coll
             .mass_properties()
             .transform_by(coll.position_wrt_parent());
-        self.colliders.push(handle);
-        self.mass_properties += mass_properties;
         self.update_world_mass_properties();
     }
 
@@ -396,14 +878,39 @@ impl RigidBody {
         }
     }
 
+    pub(crate) fn update_colliders_status(&mut self, colliders: &mut ColliderSet) {
+        for handle in &self.colliders {
+            let collider = colliders
+                .get_mut_internal_with_modification_tracking(*handle)
+                .unwrap();
+            collider.changes.insert(ColliderChanges::PARENT_STATUS);
+        }
+    }
+
+    pub(crate) fn update_colliders_dominance(&mut self, colliders: &mut ColliderSet) {
+        for handle in &self.colliders {
+            let collider = colliders
+                .get_mut_internal_with_modification_tracking(*handle)
+                .unwrap();
+            collider.changes.insert(ColliderChanges::PARENT_DOMINANCE);
+        }
+    }
+
     /// Removes a collider from this rigid-body.
     pub(crate) fn remove_collider_internal(&mut self, handle: ColliderHandle, coll: &Collider) {
         if let Some(i) = self.colliders.iter().position(|e| *e == handle) {
             self.changes.set(RigidBodyChanges::COLLIDERS, true);
             self.colliders.swap_remove(i);
+            // NOTE: if the collider mass properties were modified since the last
+            //       timestep, then we need to remove the ones we currently account for.
             let mass_properties = coll
-                .mass_properties()
-                .transform_by(coll.position_wrt_parent());
+                .prev_mass_properties
+                .as_deref()
+                .copied()
+                .unwrap_or_else(|| {
+                    coll.mass_properties()
+                        .transform_by(coll.position_wrt_parent())
+                });
             self.mass_properties -= mass_properties;
             self.update_world_mass_properties();
         }
@@ -419,6 +926,11 @@ impl RigidBody {
         self.activation.sleeping = true;
         self.linvel = na::zero();
         self.angvel = na::zero();
+        self.linvel_at_step_start = na::zero();
+        self.angvel_at_step_start = na::zero();
+        self.last_linacc = na::zero();
+        self.last_angacc = na::zero();
+        self.last_solver_impulses = SolverImpulses::default();
     }
 
     /// Wakes up this rigid body if it is sleeping.
@@ -433,14 +945,37 @@ impl RigidBody {
 
         if (strong || self.activation.energy == 0.0) && self.is_dynamic() {
             self.activation.energy = self.activation.threshold.abs() * 2.0;
+            self.activation.time_since_can_sleep = 0.0;
         }
     }
 
-    pub(crate) fn update_energy(&mut self) {
+    pub(crate) fn update_energy(&mut self, dt: Real) {
+        let linvel_sq = self.linvel.norm_squared();
+        let angvel_sq = self.angvel.gdot(self.angvel);
+
         let mix_factor = 0.01;
-        let new_energy = (1.0 - mix_factor) * self.activation.energy
-            + mix_factor * (self.linvel.norm_squared() + self.angvel.gdot(self.angvel));
+        let new_energy =
+            (1.0 - mix_factor) * self.activation.energy + mix_factor * (linvel_sq + angvel_sq);
         self.activation.energy = new_energy.min(self.activation.threshold.abs() * 4.0);
+
+        if linvel_sq <= self.activation.linear_threshold * self.activation.linear_threshold
+            && angvel_sq <= self.activation.angular_threshold * self.activation.angular_threshold
+        {
+            self.activation.time_since_can_sleep += dt;
+        } else {
+            self.activation.time_since_can_sleep = 0.0;
+        }
+    }
+
+    /// The modifications made to this rigid-body since the last call to
+    /// `RigidBodySet::clear_modified_flags`.
+    ///
+    /// This includes the modifications made by the user (through `RigidBodySet::get_mut`
+    /// and the setters of this rigid-body), as well as the motion of this rigid-body during
+    /// the timesteps of the physics pipeline. This can be used by engine integrations to
+    /// synchronize only the rigid-bodies that actually changed.
+    pub fn changes(&self) -> RigidBodyChanges {
+        self.changes | self.tracked_changes
     }
 
     /// Is this rigid body sleeping?
@@ -460,7 +995,10 @@ impl RigidBody {
     /// Computes the predict position of this rigid-body after `dt` seconds, taking
     /// into account its velocities and external forces applied to it.
     pub fn predict_position_using_velocity_and_forces(&self, dt: Real) -> Isometry<Real> {
-        let dlinvel = self.force * (self.effective_inv_mass * dt);
+        let dlinvel = self
+            .force
+            .component_mul(&self.effective_inv_mass_along_axes)
+            * dt;
         let dangvel = self
             .effective_world_inv_inertia_sqrt
             .transform_vector(self.torque * dt);
@@ -478,12 +1016,163 @@ impl RigidBody {
         shift * Isometry::new(self.linvel * dt, self.angvel * dt) * shift.inverse()
     }
 
+    /// Integrates the gyroscopic torque `-w x (I w)` into the angular velocity.
+    ///
+    /// This uses one step of Newton's method for solving the implicit Euler
+    /// integration of the gyroscopic term, in the principal inertia frame. Being
+    /// implicit, this remains stable even for bodies with very anisotropic inertia.
+    #[cfg(feature = "dim3")]
+    pub(crate) fn integrate_gyroscopic_forces(&mut self, dt: Real) {
+        if !self.is_gyroscopic_forces_enabled()
+            || self.is_rotation_locked().iter().any(|locked| *locked)
+        {
+            return;
+        }
+
+        let inv_inertia_sqrt = self.mass_properties.inv_principal_inertia_sqrt;
+
+        if inv_inertia_sqrt.iter().any(|e| *e == 0.0) {
+            // Infinite principal inertia: the gyroscopic term is not well-defined.
+            return;
+        }
+
+        let inertia = na::Matrix3::from_diagonal(&inv_inertia_sqrt.map(|e| 1.0 / (e * e)));
+        let frame = self.position.rotation * self.mass_properties.principal_inertia_local_frame;
+        let angvel = frame.inverse_transform_vector(&self.angvel);
+        let ang_momentum = inertia * angvel;
+
+        // Residual and jacobian of `I (w2 - w1) + dt * w2 x (I w2) = 0`, evaluated at `w2 = w1`.
+        let residual = angvel.cross(&ang_momentum) * dt;
+        let jacobian =
+            inertia + (angvel.cross_matrix() * inertia - ang_momentum.cross_matrix()) * dt;
+
+        if let Some(inv_jacobian) = jacobian.try_inverse() {
+            self.angvel = frame * (angvel - inv_jacobian * residual);
+        }
+    }
+
     pub(crate) fn apply_damping(&mut self, dt: Real) {
         self.linvel *= 1.0 / (1.0 + dt * self.linear_damping);
         self.angvel *= 1.0 / (1.0 + dt * self.angular_damping);
     }
 
+    /// Sets the velocities of this rigid-body to zero if they remained tiny, and its contacts
+    /// didn't change, during enough consecutive steps.
+    ///
+    /// See `IntegrationParameters::snap_linear_velocity_threshold` for details.
+    pub(crate) fn snap_velocities(&mut self, params: &IntegrationParameters) {
+        let linear_threshold = params.snap_linear_velocity_threshold;
+        let angular_threshold = params.snap_angular_velocity_threshold;
+
+        if linear_threshold == 0.0 && angular_threshold == 0.0 {
+            return;
+        }
+
+        if self.linvel.norm_squared() <= linear_threshold * linear_threshold
+            && self.angvel.gdot(self.angvel) <= angular_threshold * angular_threshold
+        {
+            self.resting_steps = self.resting_steps.saturating_add(1);
+
+            if self.resting_steps >= params.snap_min_resting_steps {
+                self.linvel = na::zero();
+                self.angvel = na::zero();
+            }
+        } else {
+            self.resting_steps = 0;
+        }
+    }
+
+    pub(crate) fn save_velocities_at_step_start(&mut self) {
+        self.linvel_at_step_start = self.linvel;
+        self.angvel_at_step_start = self.angvel;
+        self.last_solver_impulses = SolverImpulses::default();
+    }
+
+    pub(crate) fn update_last_accelerations(&mut self, inv_dt: Real) {
+        self.last_linacc = (self.linvel - self.linvel_at_step_start) * inv_dt;
+        self.last_angacc = (self.angvel - self.angvel_at_step_start) * inv_dt;
+    }
+
+    /// The average linear acceleration of this rigid-body during the last timestep.
+    ///
+    /// This is the velocity change caused by all the forces and constraints (contacts, joints, etc.)
+    /// applied during the last call to `PhysicsPipeline::step`, divided by the timestep length.
+    /// Velocity changes made by the user between two timesteps are not taken into account.
+    /// This is zero for sleeping, static, and kinematic rigid-bodies.
+    pub fn last_linear_acceleration(&self) -> Vector<Real> {
+        self.last_linacc
+    }
+
+    /// The average angular acceleration of this rigid-body during the last timestep.
+    ///
+    /// See `last_linear_acceleration` for details.
+    pub fn last_angular_acceleration(&self) -> AngVector<Real> {
+        self.last_angacc
+    }
+
+    /// The average linear acceleration of this rigid-body during the last timestep,
+    /// expressed in its local-space.
+    ///
+    /// See `last_linear_acceleration` for details.
+    pub fn local_linear_acceleration(&self) -> Vector<Real> {
+        self.position
+            .rotation
+            .inverse_transform_vector(&self.last_linacc)
+    }
+
+    /// The average angular acceleration of this rigid-body during the last timestep,
+    /// expressed in its local-space.
+    ///
+    /// See `last_linear_acceleration` for details.
+    pub fn local_angular_acceleration(&self) -> AngVector<Real> {
+        #[cfg(feature = "dim2")]
+        return self.last_angacc;
+        #[cfg(feature = "dim3")]
+        return self
+            .position
+            .rotation
+            .inverse_transform_vector(&self.last_angacc);
+    }
+
+    /// The linear acceleration an accelerometer attached to the center-of-mass of this
+    /// rigid-body would have measured during the last timestep, expressed in its local-space.
+    ///
+    /// Unlike `local_linear_acceleration`, this excludes the acceleration due to gravity:
+    /// a rigid-body in free fall reads zero, while a rigid-body resting on the ground
+    /// reads the opposite of the gravity.
+    pub fn accelerometer_reading(&self) -> Vector<Real> {
+        self.position
+            .rotation
+            .inverse_transform_vector(&(self.last_linacc - self.last_gravity))
+    }
+
+    /// The angular velocity a gyroscope attached to this rigid-body would measure,
+    /// expressed in its local-space.
+    pub fn gyroscope_reading(&self) -> AngVector<Real> {
+        #[cfg(feature = "dim2")]
+        return self.angvel;
+        #[cfg(feature = "dim3")]
+        return self
+            .position
+            .rotation
+            .inverse_transform_vector(&self.angvel);
+    }
+
+    /// The total magnitudes of the contact and joint impulses applied to this rigid-body
+    /// by the constraints solver during the last timestep.
+    ///
+    /// This is zero for sleeping, static, and kinematic rigid-bodies.
+    pub fn last_solver_impulses(&self) -> SolverImpulses {
+        self.last_solver_impulses
+    }
+
     pub(crate) fn integrate_next_position(&mut self, dt: Real) {
+        if self.has_partially_locked_translations() {
+            let mut linvel = self.linvel;
+            self.remove_locked_translations(&mut linvel);
+            self.linvel = linvel;
+        }
+
         self.next_position = self.integrate_velocity(dt) * self.position;
         let _ = self.next_position.rotation.renormalize_fast();
     }
@@ -569,7 +1258,17 @@ impl RigidBody {
     }
 
     pub(crate) fn set_next_position(&mut self, pos: Isometry<Real>) {
-        self.next_position = pos;
+        if self.has_partially_locked_translations() {
+            // Cancel the displacement of the center-of-mass along the locked axes.
+            let local_com = self.mass_properties.local_com;
+            let dcom = pos * local_com - self.next_position * local_com;
+            let mut allowed_dcom = dcom;
+            self.remove_locked_translations(&mut allowed_dcom);
+            self.next_position = pos;
+            self.next_position.translation.vector -= dcom - allowed_dcom;
+        } else {
+            self.next_position = pos;
+        }
     }
 
     /// If this rigid body is kinematic, sets its future position after the next timestep integration.
@@ -579,6 +1278,49 @@ impl RigidBody {
         }
     }
 
+    /// The moving platform this rigid-body is attached to, if any.
+    ///
+    /// See `RigidBodySet::attach_to_platform` for details.
+    pub fn platform(&self) -> Option<RigidBodyHandle> {
+        self.platform
+    }
+
+    /// Adds the velocity of the platform this rigid-body is attached to.
+    ///
+    /// For dynamic bodies, only the variation of the platform velocity since
+    /// the last call is added. For kinematic bodies, the motion of the platform
+    /// during `dt` is applied to their next position.
+    pub(crate) fn apply_platform_velocity(
+        &mut self,
+        linvel: Vector<Real>,
+        angvel: AngVector<Real>,
+        dt: Real,
+    ) {
+        if self.is_dynamic() {
+            self.linvel += linvel - self.platform_linvel;
+            self.angvel += angvel - self.platform_angvel;
+            self.platform_linvel = linvel;
+            self.platform_angvel = angvel;
+        } else if self.is_kinematic() {
+            let com = self.position * self.mass_properties.local_com;
+            let shift = Translation::from(com.coords);
+            let motion = shift * Isometry::new(linvel * dt, angvel * dt) * shift.inverse();
+            self.next_position = motion * self.next_position;
+            self.compute_velocity_from_next_position(utils::inv(dt));
+        }
+    }
+
+    /// Removes the platform velocity previously added by `self.apply_platform_velocity`.
+    pub(crate) fn remove_platform_velocity(&mut self) {
+        if self.is_dynamic() {
+            self.linvel -= self.platform_linvel;
+            self.angvel -= self.platform_angvel;
+        }
+
+        self.platform_linvel = Vector::zeros();
+        self.platform_angvel = na::zero();
+    }
+
     pub(crate) fn compute_velocity_from_next_position(&mut self, inv_dt: Real) {
         let dpos = self.next_position * self.position.inverse();
         #[cfg(feature = "dim2")]
@@ -592,18 +1334,27 @@ impl RigidBody {
         self.linvel = dpos.translation.vector * inv_dt;
     }
 
+    #[allow(deprecated)] // Keeps `effective_inv_mass` up to date.
     pub(crate) fn update_world_mass_properties(&mut self) {
         self.world_com = self.mass_properties.world_com(&self.position);
-        self.effective_inv_mass = self.mass_properties.inv_mass;
+        self.effective_inv_mass_along_axes = Vector::repeat(self.mass_properties.inv_mass);
         self.effective_world_inv_inertia_sqrt = self
             .mass_properties
             .world_inv_inertia_sqrt(&self.position.rotation);
 
         // Take into account translation/rotation locking.
-        if self.flags.contains(RigidBodyFlags::TRANSLATION_LOCKED) {
-            self.effective_inv_mass = 0.0;
+        for (i, locked) in self.is_translation_locked_along_axes().iter().enumerate() {
+            if *locked {
+                self.effective_inv_mass_along_axes[i] = 0.0;
+            }
         }
 
+        self.effective_inv_mass = if self.is_translation_locked() {
+            0.0
+        } else {
+            self.mass_properties.inv_mass
+        };
+
         #[cfg(feature = "dim2")]
         {
             if self.flags.contains(RigidBodyFlags::ROTATION_LOCKED_Z) {
@@ -628,6 +1379,13 @@ impl RigidBody {
                 self.effective_world_inv_inertia_sqrt.m13 = 0.0;
                 self.effective_world_inv_inertia_sqrt.m23 = 0.0;
             }
+
+            // Project onto the subspace orthogonal to the arbitrary locked axis.
+            if let Some(axis) = self.locked_rotation_axis() {
+                let projector = na::Matrix3::identity() - axis.as_ref() * axis.transpose();
+                self.effective_world_inv_inertia_sqrt =
+                    self.effective_world_inv_inertia_sqrt.quadform(&projector);
+            }
         }
     }
 }
@@ -688,6 +1446,53 @@ impl RigidBody {
             }
         }
     }
+
+    /// Applies the torque resulting from a force applied at the given world-space point of this rigid-body.
+    ///
+    /// Unlike `self.apply_force_at_point`, this only applies the angular part of the force, so
+    /// the center-of-mass of this rigid-body is not accelerated.
+    /// The torque will be applied in the next simulation step.
+    /// This does nothing on non-dynamic bodies.
+    pub fn apply_torque_at_point(
+        &mut self,
+        force: Vector<Real>,
+        point: Point<Real>,
+        wake_up: bool,
+    ) {
+        let torque = self.torque_from_force_at_point(&force, &point);
+        self.apply_torque(torque, wake_up);
+    }
+
+    /// Applies a linear acceleration at the center-of-mass of this rigid-body, independently
+    /// from its mass.
+    /// The acceleration will be applied in the next simulation step.
+    /// This does nothing on non-dynamic bodies, and on locked translations.
+    pub fn apply_acceleration(&mut self, acceleration: Vector<Real>, wake_up: bool) {
+        let force = acceleration.component_mul(&self.effective_inv_mass_along_axes.map(utils::inv));
+        self.apply_force(force, wake_up);
+    }
+
+    /// Applies an angular acceleration to this rigid-body, independently from its
+    /// angular inertia.
+    /// The acceleration will be applied in the next simulation step.
+    /// This does nothing on non-dynamic bodies, and on locked rotations.
+    #[cfg(feature = "dim2")]
+    pub fn apply_angular_acceleration(&mut self, acceleration: Real, wake_up: bool) {
+        let torque = self.effective_angular_inertia() * acceleration;
+        self.apply_torque(torque, wake_up);
+    }
+
+    /// Applies an angular acceleration to this rigid-body, independently from its
+    /// angular inertia.
+    /// The acceleration will be applied in the next simulation step.
+    /// This does nothing on non-dynamic bodies, and on locked rotations.
+    #[cfg(feature = "dim3")]
+    pub fn apply_angular_acceleration(&mut self, acceleration: Vector<Real>, wake_up: bool) {
+        let torque = self
+            .effective_angular_inertia()
+            .transform_vector(acceleration);
+        self.apply_torque(torque, wake_up);
+    }
 }
 
 /// ## Applying impulses and angular impulses
@@ -697,7 +1502,7 @@ impl RigidBody {
     /// This does nothing on non-dynamic bodies.
     pub fn apply_impulse(&mut self, impulse: Vector<Real>, wake_up: bool) {
         if self.body_status == BodyStatus::Dynamic {
-            self.linvel += impulse * self.effective_inv_mass;
+            self.linvel += impulse.component_mul(&self.effective_inv_mass_along_axes);
 
             if wake_up {
                 self.wake_up(true);
@@ -748,9 +1553,48 @@ impl RigidBody {
         self.apply_impulse(impulse, wake_up);
         self.apply_torque_impulse(torque_impulse, wake_up);
     }
+
+    /// Applies the angular impulse resulting from an impulse applied at the given world-space point
+    /// of this rigid-body.
+    ///
+    /// Unlike `self.apply_impulse_at_point`, this only applies the angular part of the impulse, so
+    /// the linear velocity of this rigid-body is not modified.
+    /// The impulse is applied right away, changing the angular velocity.
+    /// This does nothing on non-dynamic bodies.
+    pub fn apply_torque_impulse_at_point(
+        &mut self,
+        impulse: Vector<Real>,
+        point: Point<Real>,
+        wake_up: bool,
+    ) {
+        let torque_impulse = self.torque_from_force_at_point(&impulse, &point);
+        self.apply_torque_impulse(torque_impulse, wake_up);
+    }
 }
 
 impl RigidBody {
+    /// The torque, relative to the center-of-mass of this rigid-body, resulting from the given
+    /// world-space force applied at the given world-space point.
+    pub fn torque_from_force_at_point(
+        &self,
+        force: &Vector<Real>,
+        point: &Point<Real>,
+    ) -> AngVector<Real> {
+        (point - self.world_com).gcross(*force)
+    }
+
+    /// The world-space torque, relative to the center-of-mass of this rigid-body, resulting from
+    /// the given force applied at the given point, both expressed in the local-space of this rigid-body.
+    pub fn torque_from_local_force_at_local_point(
+        &self,
+        local_force: &Vector<Real>,
+        local_point: &Point<Real>,
+    ) -> AngVector<Real> {
+        let force = self.position * local_force;
+        let point = self.position * local_point;
+        self.torque_from_force_at_point(&force, &point)
+    }
+
     /// The velocity of the given world-space point on this rigid-body.
     pub fn velocity_at_point(&self, point: &Point<Real>) -> Vector<Real> {
         let dpt = point - self.world_com;
@@ -776,6 +1620,22 @@ impl RigidBody {
         energy
     }
 
+    /// The linear momentum of this body.
+    pub fn linear_momentum(&self) -> Vector<Real> {
+        self.linvel * self.mass()
+    }
+
+    /// The angular momentum of this body, computed relative to its center of mass.
+    pub fn angular_momentum(&self) -> AngVector<Real> {
+        self.effective_angular_inertia()
+            .transform_vector(self.angvel)
+    }
+
+    /// The angular momentum of this body, computed relative to the given world-space point.
+    pub fn angular_momentum_about_point(&self, point: &Point<Real>) -> AngVector<Real> {
+        self.angular_momentum() + (self.world_com - point).gcross(self.linear_momentum())
+    }
+
     /// The potential energy of this body in a gravity field.
     pub fn gravitational_potential_energy(&self, dt: Real, gravity: Vector<Real>) -> Real {
         let world_com = self.mass_properties().world_com(&self.position).coords;
@@ -794,16 +1654,28 @@ pub struct RigidBodyBuilder {
     linvel: Vector<Real>,
     angvel: AngVector<Real>,
     gravity_scale: Real,
+    constant_acceleration: Vector<Real>,
     linear_damping: Real,
     angular_damping: Real,
     body_status: BodyStatus,
     flags: RigidBodyFlags,
+    #[cfg(feature = "dim3")]
+    rotation_axis_lock: Option<RotationAxisLock>,
     mass_properties: MassProperties,
     can_sleep: bool,
     sleeping: bool,
+    #[cfg(feature = "dim3")]
+    gyroscopic_forces_enabled: bool,
+    linear_sleep_threshold: Real,
+    angular_sleep_threshold: Real,
+    time_until_sleep: Real,
     ccd_enabled: bool,
+    ccd_thickness: Option<Real>,
+    ccd_max_dist: Option<Real>,
     dominance_group: i8,
     user_data: u128,
+    user_object: Option<Arc<dyn Any + Send + Sync>>,
+    name: Option<String>,
 }
 
 impl RigidBodyBuilder {
@@ -814,16 +1686,28 @@ impl RigidBodyBuilder {
             linvel: Vector::zeros(),
             angvel: na::zero(),
             gravity_scale: 1.0,
+            constant_acceleration: Vector::zeros(),
             linear_damping: 0.0,
             angular_damping: 0.0,
             body_status,
             flags: RigidBodyFlags::empty(),
+            #[cfg(feature = "dim3")]
+            rotation_axis_lock: None,
             mass_properties: MassProperties::zero(),
             can_sleep: true,
             sleeping: false,
+            #[cfg(feature = "dim3")]
+            gyroscopic_forces_enabled: false,
+            linear_sleep_threshold: ActivationStatus::default_linear_threshold(),
+            angular_sleep_threshold: ActivationStatus::default_angular_threshold(),
+            time_until_sleep: 0.0,
             ccd_enabled: false,
+            ccd_thickness: None,
+            ccd_max_dist: None,
             dominance_group: 0,
             user_data: 0,
+            user_object: None,
+            name: None,
         }
     }
 
@@ -848,6 +1732,12 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Sets the constant linear acceleration applied to the rigid-body to be created at each timestep.
+    pub fn constant_acceleration(mut self, acceleration: Vector<Real>) -> Self {
+        self.constant_acceleration = acceleration;
+        self
+    }
+
     /// Sets the dominance group of this rigid-body.
     pub fn dominance_group(mut self, group: i8) -> Self {
         self.dominance_group = group;
@@ -889,6 +1779,22 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// An arbitrary user-defined object attached to the rigid-bodies built by this builder.
+    ///
+    /// All the rigid-bodies built by this builder will share the same object.
+    pub fn user_object<T: Any + Send + Sync>(mut self, object: T) -> Self {
+        self.user_object = Some(Arc::new(object));
+        self
+    }
+
+    /// Sets the name of the rigid-bodies built by this builder.
+    ///
+    /// Named rigid-bodies can be retrieved with `RigidBodySet::find_by_name`.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
     /// Sets the additional mass properties of the rigid-body being built.
     ///
     /// Note that "additional" means that the final mass properties of the rigid-bodies depends
@@ -910,6 +1816,51 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Only allow translations of this rigid-body along specific coordinate axes.
+    ///
+    /// If only some of the translations are locked, the locked components of the velocity
+    /// and of the position corrections are removed after the constraints resolution.
+    #[cfg(feature = "dim2")]
+    pub fn restrict_translations(
+        mut self,
+        allow_translation_x: bool,
+        allow_translation_y: bool,
+    ) -> Self {
+        self.flags.set(
+            RigidBodyFlags::TRANSLATION_LOCKED,
+            !allow_translation_x && !allow_translation_y,
+        );
+        self.flags
+            .set(RigidBodyFlags::TRANSLATION_LOCKED_X, !allow_translation_x);
+        self.flags
+            .set(RigidBodyFlags::TRANSLATION_LOCKED_Y, !allow_translation_y);
+        self
+    }
+
+    /// Only allow translations of this rigid-body along specific coordinate axes.
+    ///
+    /// If only some of the translations are locked, the locked components of the velocity
+    /// and of the position corrections are removed after the constraints resolution.
+    #[cfg(feature = "dim3")]
+    pub fn restrict_translations(
+        mut self,
+        allow_translation_x: bool,
+        allow_translation_y: bool,
+        allow_translation_z: bool,
+    ) -> Self {
+        self.flags.set(
+            RigidBodyFlags::TRANSLATION_LOCKED,
+            !allow_translation_x && !allow_translation_y && !allow_translation_z,
+        );
+        self.flags
+            .set(RigidBodyFlags::TRANSLATION_LOCKED_X, !allow_translation_x);
+        self.flags
+            .set(RigidBodyFlags::TRANSLATION_LOCKED_Y, !allow_translation_y);
+        self.flags
+            .set(RigidBodyFlags::TRANSLATION_LOCKED_Z, !allow_translation_z);
+        self
+    }
+
     /// Prevents this rigid-body from rotating because of forces.
     pub fn lock_rotations(mut self) -> Self {
         self.flags.set(RigidBodyFlags::ROTATION_LOCKED_X, true);
@@ -935,6 +1886,20 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Prevents this rigid-body from rotating about the given world-space axis.
+    #[cfg(feature = "dim3")]
+    pub fn lock_rotation_about_world_axis(mut self, axis: Unit<Vector<Real>>) -> Self {
+        self.rotation_axis_lock = Some(RotationAxisLock::World(axis));
+        self
+    }
+
+    /// Prevents this rigid-body from rotating about the given axis expressed in its local-space.
+    #[cfg(feature = "dim3")]
+    pub fn lock_rotation_about_local_axis(mut self, axis: Unit<Vector<Real>>) -> Self {
+        self.rotation_axis_lock = Some(RotationAxisLock::Local(axis));
+        self
+    }
+
     /// Sets the additional mass of the rigid-body being built.
     ///
     /// This is only the "additional" mass because the total mass of the  rigid-body is
@@ -1057,12 +2022,70 @@ impl RigidBodyBuilder {
         self
     }
 
+    /// Enables the bullet mode for this rigid-body.
+    ///
+    /// This also enables CCD. See `RigidBody::set_bullet` for details.
+    pub fn bullet(mut self, enabled: bool) -> Self {
+        self.flags.set(RigidBodyFlags::BULLET, enabled);
+        self.ccd_enabled = self.ccd_enabled || enabled;
+        self
+    }
+
+    /// Overrides the thickness used by the CCD for this rigid-body.
+    ///
+    /// By default, it is computed automatically from the shapes of the attached colliders.
+    /// Smaller thicknesses make the CCD more conservative.
+    pub fn ccd_thickness(mut self, thickness: Real) -> Self {
+        self.ccd_thickness = Some(thickness);
+        self
+    }
+
+    /// Overrides the maximum distance used by the CCD for bounding the angular motion of this
+    /// rigid-body.
+    ///
+    /// By default, it is computed automatically from the shapes of the attached colliders.
+    /// Larger distances make the CCD more conservative.
+    pub fn ccd_max_dist(mut self, max_dist: Real) -> Self {
+        self.ccd_max_dist = Some(max_dist);
+        self
+    }
+
+    /// Enables the integration of gyroscopic forces for this rigid-body.
+    ///
+    /// Gyroscopic forces are responsible for the precession of spinning bodies
+    /// with non-uniform angular inertia (spinning tops, gyroscopes, flywheels, etc.)
+    #[cfg(feature = "dim3")]
+    pub fn enable_gyroscopic_forces(mut self, enabled: bool) -> Self {
+        self.gyroscopic_forces_enabled = enabled;
+        self
+    }
+
     /// Sets whether or not the rigid-body is to be created asleep.
     pub fn sleeping(mut self, sleeping: bool) -> Self {
         self.sleeping = sleeping;
         self
     }
 
+    /// Sets the linear and angular velocity thresholds below which the rigid-body to be
+    /// created is considered at rest by the time-based sleeping criterion.
+    ///
+    /// This only has an effect if [`Self::time_until_sleep`] is set to a positive value.
+    pub fn sleep_thresholds(mut self, linear_threshold: Real, angular_threshold: Real) -> Self {
+        self.linear_sleep_threshold = linear_threshold;
+        self.angular_sleep_threshold = angular_threshold;
+        self
+    }
+
+    /// Sets the amount of time the rigid-body to be created must remain below its
+    /// sleep velocity thresholds before it is put to sleep.
+    ///
+    /// Setting this to a positive value enables the time-based sleeping criterion instead of
+    /// the default pseudo-kinetic energy criterion. See [`ActivationStatus::time_until_sleep`].
+    pub fn time_until_sleep(mut self, time: Real) -> Self {
+        self.time_until_sleep = time;
+        self
+    }
+
     /// Build a new rigid-body with the parameters configured with this builder.
     pub fn build(&self) -> RigidBody {
         let mut rb = RigidBody::new();
@@ -1072,13 +2095,27 @@ impl RigidBodyBuilder {
         rb.angvel = self.angvel;
         rb.body_status = self.body_status;
         rb.user_data = self.user_data;
+        rb.user_object = self.user_object.clone();
+        rb.name = self.name.clone();
         rb.mass_properties = self.mass_properties;
         rb.linear_damping = self.linear_damping;
         rb.angular_damping = self.angular_damping;
         rb.gravity_scale = self.gravity_scale;
+        rb.constant_acceleration = self.constant_acceleration;
         rb.flags = self.flags;
+        #[cfg(feature = "dim3")]
+        {
+            rb.rotation_axis_lock = self.rotation_axis_lock;
+        }
         rb.dominance_group = self.dominance_group;
         rb.enable_ccd(self.ccd_enabled);
+        rb.set_ccd_thickness(self.ccd_thickness);
+        rb.set_ccd_max_dist(self.ccd_max_dist);
+        #[cfg(feature = "dim3")]
+        rb.enable_gyroscopic_forces(self.gyroscopic_forces_enabled);
+        rb.activation.linear_threshold = self.linear_sleep_threshold;
+        rb.activation.angular_threshold = self.angular_sleep_threshold;
+        rb.activation.time_until_sleep = self.time_until_sleep;
 
         if self.can_sleep && self.sleeping {
             rb.sleep();
@@ -1096,6 +2133,13 @@ impl RigidBodyBuilder {
 ///
 /// This controls whether a body is sleeping or not.
 /// If the threshold is negative, the body never sleeps.
+///
+/// Two criteria are available for deciding when a body can fall asleep:
+/// - By default, a body falls asleep when its pseudo-kinetic energy, blended over
+///   multiple timesteps, drops below `threshold`.
+/// - If `time_until_sleep` is positive, a body falls asleep once its linear and angular
+///   velocities remained below `linear_threshold` and `angular_threshold` for at least
+///   `time_until_sleep` seconds.
 #[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 pub struct ActivationStatus {
@@ -1103,6 +2147,16 @@ pub struct ActivationStatus {
     pub threshold: Real,
     /// The current pseudo-kinetic energy of the body.
     pub energy: Real,
+    /// The linear velocity below which the body is considered at rest by the time-based criterion.
+    pub linear_threshold: Real,
+    /// The angular velocity below which the body is considered at rest by the time-based criterion.
+    pub angular_threshold: Real,
+    /// The amount of time the body must remain at rest before falling asleep.
+    ///
+    /// If this is zero or negative, the pseudo-kinetic energy criterion is used instead.
+    pub time_until_sleep: Real,
+    /// The elapsed time since the body velocities dropped below the linear and angular thresholds.
+    pub time_since_can_sleep: Real,
     /// Is this body already sleeping?
     pub sleeping: bool,
 }
@@ -1113,11 +2167,25 @@ impl ActivationStatus {
         0.01
     }
 
+    /// The default linear velocity below which a body is considered at rest by the time-based criterion.
+    pub fn default_linear_threshold() -> Real {
+        0.1
+    }
+
+    /// The default angular velocity below which a body is considered at rest by the time-based criterion.
+    pub fn default_angular_threshold() -> Real {
+        0.1
+    }
+
     /// Create a new activation status initialised with the default activation threshold and is active.
     pub fn new_active() -> Self {
         ActivationStatus {
             threshold: Self::default_threshold(),
             energy: Self::default_threshold() * 4.0,
+            linear_threshold: Self::default_linear_threshold(),
+            angular_threshold: Self::default_angular_threshold(),
+            time_until_sleep: 0.0,
+            time_since_can_sleep: 0.0,
             sleeping: false,
         }
     }
@@ -1127,6 +2195,10 @@ impl ActivationStatus {
         ActivationStatus {
             threshold: Self::default_threshold(),
             energy: 0.0,
+            linear_threshold: Self::default_linear_threshold(),
+            angular_threshold: Self::default_angular_threshold(),
+            time_until_sleep: 0.0,
+            time_since_can_sleep: 0.0,
             sleeping: true,
         }
     }
@@ -1136,4 +2208,273 @@ impl ActivationStatus {
     pub fn is_active(&self) -> bool {
         self.energy != 0.0
     }
+
+    /// Returns `true` if the body satisfies the criterion for falling asleep.
+    #[inline]
+    pub fn can_fall_asleep(&self) -> bool {
+        if self.time_until_sleep > 0.0 {
+            self.threshold >= 0.0 && self.time_since_can_sleep >= self.time_until_sleep
+        } else {
+            self.energy <= self.threshold
+        }
+    }
+}
+
+/// The total impulses applied to a rigid-body by the constraints solver during one timestep.
+///
+/// This is a cheap measure of how strongly a rigid-body has been pushed by contacts and
+/// joints, e.g., for triggering rumble or damage.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct SolverImpulses {
+    /// The sum of the normal impulses of all the contacts involving the rigid-body.
+    pub contact_impulse: Real,
+    /// The sum of the magnitudes of the impulses of all the joints attached to the rigid-body.
+    pub joint_impulse: Real,
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::RigidBodyBuilder;
+
+    #[test]
+    #[cfg(feature = "dim3")]
+    fn effective_angular_inertia_of_non_dynamic_bodies_is_zero() {
+        use crate::math::Vector;
+
+        let bodies = [
+            RigidBodyBuilder::new_static().build(),
+            RigidBodyBuilder::new_kinematic().build(),
+            RigidBodyBuilder::new_dynamic().build(),
+        ];
+
+        for rb in &bodies {
+            let inertia = rb.effective_angular_inertia().into_matrix();
+            assert!(inertia.iter().all(|e| *e == 0.0));
+            assert_eq!(rb.effective_angular_inertia_around(&Vector::x_axis()), 0.0);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "dim3")]
+    fn effective_angular_inertia_is_zero_only_along_infinite_axes() {
+        use crate::math::{Real, Vector};
+
+        let mut rb = RigidBodyBuilder::new_dynamic()
+            .additional_mass(1.0)
+            .additional_principal_angular_inertia(Vector::new(Real::INFINITY, 2.0, 4.0))
+            .rotation(Vector::new(0.3, 0.4, 0.5))
+            .build();
+        rb.update_world_mass_properties();
+
+        let rot = rb.position().rotation;
+        let inertia_x = rb.effective_angular_inertia_around(&(rot * Vector::x_axis()));
+        let inertia_y = rb.effective_angular_inertia_around(&(rot * Vector::y_axis()));
+        let inertia_z = rb.effective_angular_inertia_around(&(rot * Vector::z_axis()));
+        assert!(inertia_x.abs() < 1.0e-5);
+        assert!((inertia_y - 2.0).abs() < 1.0e-5);
+        assert!((inertia_z - 4.0).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn effective_world_inertia_is_the_inverse_of_the_inverse_inertia() {
+        #[cfg(feature = "dim2")]
+        let inertia = 2.0;
+        #[cfg(feature = "dim3")]
+        let inertia = crate::math::Vector::new(1.0, 2.0, 3.0);
+
+        let mut rb = RigidBodyBuilder::new_dynamic()
+            .additional_mass(1.0)
+            .principal_angular_inertia(inertia)
+            .build();
+        rb.update_world_mass_properties();
+
+        #[cfg(feature = "dim2")]
+        assert!(
+            (rb.effective_world_inertia() * rb.effective_world_inv_inertia() - 1.0).abs() < 1.0e-5
+        );
+        #[cfg(feature = "dim3")]
+        {
+            let product = rb.effective_world_inertia().into_matrix()
+                * rb.effective_world_inv_inertia().into_matrix();
+            assert!((product - na::Matrix3::identity()).norm() < 1.0e-5);
+        }
+    }
+
+    #[test]
+    fn restricted_translations_have_a_zero_inverse_mass() {
+        use crate::math::Vector;
+
+        #[cfg(feature = "dim2")]
+        let builder = RigidBodyBuilder::new_dynamic().restrict_translations(true, false);
+        #[cfg(feature = "dim3")]
+        let builder = RigidBodyBuilder::new_dynamic().restrict_translations(true, false, true);
+        let mut rb = builder.additional_mass(2.0).build();
+        rb.update_world_mass_properties();
+
+        assert_eq!(rb.effective_inv_mass_along_axes().x, 0.5);
+        assert_eq!(rb.effective_inv_mass_along_axes().y, 0.0);
+
+        // The impulse must not leak into the locked axis, even before the next step.
+        rb.apply_impulse(Vector::repeat(1.0), true);
+        assert_eq!(rb.linvel().x, 0.5);
+        assert_eq!(rb.linvel().y, 0.0);
+    }
+
+    #[test]
+    fn contact_changes_prevent_velocity_snapping() {
+        use crate::geometry::ColliderBuilder;
+        use crate::math::{Isometry, Vector};
+        use crate::pipeline::PhysicsWorld;
+
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        world.integration_parameters.snap_linear_velocity_threshold = 10.0;
+        world.integration_parameters.snap_angular_velocity_threshold = 10.0;
+        world.integration_parameters.snap_min_resting_steps = 1000;
+
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        world.colliders.insert(
+            ColliderBuilder::halfspace(Vector::y_axis()).build(),
+            ground,
+            &mut world.bodies,
+        );
+
+        let mut balls = vec![];
+        for x in &[-5.0, 5.0] {
+            let ball = world.bodies.insert(
+                RigidBodyBuilder::new_dynamic()
+                    .position(Isometry::new(
+                        Vector::x() * *x + Vector::y() * 5.0,
+                        na::zero(),
+                    ))
+                    .build(),
+            );
+            world
+                .colliders
+                .insert(ColliderBuilder::ball(0.5).build(), ball, &mut world.bodies);
+            balls.push(ball);
+        }
+
+        for _ in 0..3 {
+            world.step(1.0 / 60.0);
+        }
+
+        // Teleport the second ball onto the ground: its resting count restarts.
+        let pos = Isometry::new(Vector::x() * 5.0 + Vector::y() * 0.49, na::zero());
+        world.bodies[balls[1]].set_position(pos, true);
+        world.step(1.0 / 60.0);
+
+        assert_eq!(world.bodies[balls[0]].resting_steps, 4);
+        assert_eq!(world.bodies[balls[1]].resting_steps, 1);
+    }
+
+    #[test]
+    fn free_fall_accelerometer() {
+        use crate::geometry::ColliderBuilder;
+        use crate::math::Vector;
+        use crate::pipeline::PhysicsWorld;
+
+        let gravity = Vector::y() * -9.81;
+        let mut world = PhysicsWorld::new(gravity);
+        let (h, _) = world.insert_dynamic(Vector::zeros(), ColliderBuilder::ball(0.5));
+        world.run(1);
+
+        // A body in free fall doesn't feel any acceleration.
+        assert!((world.bodies[h].local_linear_acceleration() - gravity).norm() < 1.0e-4);
+        assert!(world.bodies[h].accelerometer_reading().norm() < 1.0e-4);
+    }
+
+    #[test]
+    fn last_acceleration_of_falling_body() {
+        use crate::geometry::ColliderBuilder;
+        use crate::math::Vector;
+        use crate::pipeline::PhysicsWorld;
+
+        let gravity = Vector::y() * -9.81;
+        let mut world = PhysicsWorld::new(gravity);
+        let (h, _) = world.insert_dynamic(Vector::zeros(), ColliderBuilder::ball(0.5));
+        world.bodies[h].set_linvel(Vector::x() * 3.0, true);
+        world.run(1);
+
+        // The initial velocity must not be mistaken for an acceleration.
+        let acc = world.bodies[h].last_linear_acceleration();
+        assert!((acc - gravity).norm() < 1.0e-3);
+    }
+
+    #[test]
+    #[cfg(feature = "dim3")]
+    fn gyroscopic_forces_preserve_angular_momentum() {
+        use crate::dynamics::RigidBodyHandle;
+        use crate::geometry::ColliderBuilder;
+        use crate::math::{Real, Vector};
+        use crate::pipeline::PhysicsWorld;
+
+        // Two identical bodies spinning around an axis which is not a principal axis
+        // of inertia, so their angular velocity has to precess.
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let spin = Vector::new(0.5, 5.0, 0.0);
+        let handles: Vec<_> = [true, false]
+            .iter()
+            .enumerate()
+            .map(|(i, enabled)| {
+                let rb = RigidBodyBuilder::new_dynamic()
+                    .translation(i as Real * 10.0, 0.0, 0.0)
+                    .angvel(spin)
+                    .enable_gyroscopic_forces(*enabled)
+                    .build();
+                let handle = world.bodies.insert(rb);
+                world.colliders.insert(
+                    ColliderBuilder::cuboid(1.0, 0.2, 0.5).build(),
+                    handle,
+                    &mut world.bodies,
+                );
+                handle
+            })
+            .collect();
+
+        let angular_momentum = |world: &mut PhysicsWorld, handle: RigidBodyHandle| {
+            let rb = &mut world.bodies[handle];
+            rb.update_world_mass_properties();
+            rb.effective_angular_inertia()
+                .transform_vector(*rb.angvel())
+        };
+        let initial_momenta: Vec<_> = handles
+            .iter()
+            .map(|h| angular_momentum(&mut world, *h))
+            .collect();
+        world.run(120);
+
+        let drift = |world: &mut PhysicsWorld, i: usize| {
+            let momentum = angular_momentum(world, handles[i]);
+            (momentum - initial_momenta[i]).norm() / initial_momenta[i].norm()
+        };
+        assert!(drift(&mut world, 0) < 0.02);
+        // Without gyroscopic forces, the angular velocity is constant instead.
+        assert!((world.bodies[handles[1]].angvel() - spin).norm() < 1.0e-5);
+        assert!(drift(&mut world, 1) > 0.1);
+    }
+
+    #[test]
+    fn time_based_sleeping_criterion() {
+        use crate::math::Vector;
+
+        let mut rb = RigidBodyBuilder::new_dynamic()
+            .sleep_thresholds(0.1, 0.1)
+            .time_until_sleep(0.5)
+            .build();
+        rb.set_linvel(Vector::x() * 0.05, true);
+
+        // The pseudo-kinetic energy is still high, but the velocity is below the thresholds.
+        rb.update_energy(0.25);
+        assert!(!rb.activation.can_fall_asleep());
+        rb.update_energy(0.25);
+        assert!(rb.activation.can_fall_asleep());
+        assert!(rb.activation.energy > rb.activation.threshold);
+
+        // Moving faster than the thresholds resets the timer.
+        rb.set_linvel(Vector::x() * 0.2, false);
+        rb.update_energy(0.25);
+        assert_eq!(rb.activation.time_since_can_sleep, 0.0);
+        assert!(!rb.activation.can_fall_asleep());
+    }
 }
@@ -2,9 +2,14 @@
 use rayon::prelude::*;
 
 use crate::data::arena::Arena;
-use crate::dynamics::{BodyStatus, Joint, JointSet, RigidBody, RigidBodyChanges};
+use crate::dynamics::{
+    BodyStatus, Joint, JointIslandMergePolicy, JointSet, RigidBody, RigidBodyChanges, SleepEvent,
+};
 use crate::geometry::{ColliderSet, InteractionGraph, NarrowPhase};
+use crate::math::{Isometry, Real, Vector};
+use crate::pipeline::EventHandler;
 use parry::partitioning::IndexedData;
+use std::collections::HashMap;
 use std::ops::{Index, IndexMut};
 
 /// The unique handle of a rigid body added to a `RigidBodySet`.
@@ -78,6 +83,10 @@ pub struct RigidBodySet {
     active_set_timestamp: u32,
     pub(crate) modified_bodies: Vec<RigidBodyHandle>,
     pub(crate) modified_all_bodies: bool,
+    // The bodies with changes already handled by the physics pipeline, but not yet cleared.
+    tracked_bodies: Vec<RigidBodyHandle>,
+    carried_bodies: Vec<RigidBodyHandle>,
+    names: HashMap<String, Vec<RigidBodyHandle>>,
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
     can_sleep: Vec<RigidBodyHandle>, // Workspace.
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
@@ -96,6 +105,9 @@ impl RigidBodySet {
             active_set_timestamp: 0,
             modified_bodies: Vec::new(),
             modified_all_bodies: false,
+            tracked_bodies: Vec::new(),
+            carried_bodies: Vec::new(),
+            names: HashMap::new(),
             can_sleep: Vec::new(),
             stack: Vec::new(),
         }
@@ -123,11 +135,23 @@ impl RigidBodySet {
         rb.reset_internal_references();
         rb.changes.set(RigidBodyChanges::all(), true);
 
+        // Pretend an awake body was already active during the last island
+        // computation, so that no wake-up event is generated for it.
+        rb.active_set_timestamp = if rb.is_sleeping() {
+            self.active_set_timestamp.wrapping_sub(1)
+        } else {
+            self.active_set_timestamp
+        };
+
         let handle = RigidBodyHandle(self.bodies.insert(rb));
         self.modified_bodies.push(handle);
 
         let rb = &mut self.bodies[handle.0];
 
+        if let Some(name) = &rb.name {
+            self.names.entry(name.clone()).or_default().push(handle);
+        }
+
         if rb.is_kinematic() {
             rb.active_set_id = self.active_kinematic_set.len();
             self.active_kinematic_set.push(handle);
@@ -144,6 +168,11 @@ impl RigidBodySet {
         joints: &mut JointSet,
     ) -> Option<RigidBody> {
         let rb = self.bodies.remove(handle.0)?;
+
+        if let Some(name) = &rb.name {
+            self.remove_name_entry(name, handle);
+        }
+
         /*
          * Update active sets.
          */
@@ -174,10 +203,65 @@ impl RigidBodySet {
         Some(rb)
     }
 
+    /// Sets the name of the given rigid-body, replacing its previous name.
+    ///
+    /// Does nothing if the handle is invalid.
+    pub fn set_name(&mut self, handle: RigidBodyHandle, name: Option<String>) {
+        let rb = match self.bodies.get_mut(handle.0) {
+            Some(rb) => rb,
+            None => return,
+        };
+        let old_name = std::mem::replace(&mut rb.name, name.clone());
+
+        if let Some(old_name) = old_name {
+            self.remove_name_entry(&old_name, handle);
+        }
+
+        if let Some(name) = name {
+            self.names.entry(name).or_default().push(handle);
+        }
+    }
+
+    /// Finds a rigid-body with the given name.
+    ///
+    /// If several rigid-bodies have this name, the one that was named first is returned.
+    /// See `find_all_by_name` for retrieving all of them.
+    pub fn find_by_name(&self, name: &str) -> Option<RigidBodyHandle> {
+        self.find_all_by_name(name).first().copied()
+    }
+
+    /// Finds all the rigid-bodies with the given name.
+    pub fn find_all_by_name(&self, name: &str) -> &[RigidBodyHandle] {
+        self.names
+            .get(name)
+            .map(|handles| &handles[..])
+            .unwrap_or(&[])
+    }
+
+    fn remove_name_entry(&mut self, name: &str, handle: RigidBodyHandle) {
+        if let Some(handles) = self.names.get_mut(name) {
+            handles.retain(|h| *h != handle);
+
+            if handles.is_empty() {
+                let _ = self.names.remove(name);
+            }
+        }
+    }
+
     pub(crate) fn num_islands(&self) -> usize {
         self.active_islands.len() - 1
     }
 
+    /// Restarts the count of the consecutive steps the specified rigid-body spent at rest,
+    /// because the set of colliders it touches changed.
+    ///
+    /// See `IntegrationParameters::snap_linear_velocity_threshold` for details.
+    pub(crate) fn reset_resting_steps(&mut self, handle: RigidBodyHandle) {
+        if let Some(rb) = self.bodies.get_mut(handle.0) {
+            rb.resting_steps = 0;
+        }
+    }
+
     /// Forces the specified rigid-body to wake up if it is dynamic.
     ///
     /// If `strong` is `true` then it is assured that the rigid-body will
@@ -196,6 +280,85 @@ impl RigidBodySet {
         }
     }
 
+    /// Attaches a rigid-body to a moving platform, e.g., an elevator, a train, or a boat.
+    ///
+    /// While attached, the velocity of the platform at the center-of-mass of the rigid-body,
+    /// as well as its angular velocity, are added to the velocity of the rigid-body at each
+    /// timestep. In other words, the velocity of the rigid-body becomes relative to the platform.
+    /// The platform velocity is removed from the rigid-body velocity when it is detached.
+    ///
+    /// Returns `false` if any of the handles is invalid, or if both handles are equal.
+    pub fn attach_to_platform(&mut self, body: RigidBodyHandle, platform: RigidBodyHandle) -> bool {
+        if body == platform || !self.bodies.contains(platform.0) {
+            return false;
+        }
+
+        if let Some(rb) = self.bodies.get_mut(body.0) {
+            if rb.platform.is_none() {
+                self.carried_bodies.push(body);
+            }
+
+            rb.platform = Some(platform);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Detaches a rigid-body from the moving platform it is attached to.
+    ///
+    /// The velocity of the platform is removed from the velocity of the rigid-body.
+    pub fn detach_from_platform(&mut self, body: RigidBodyHandle) {
+        if let Some(rb) = self.bodies.get_mut(body.0) {
+            if rb.platform.take().is_some() {
+                rb.remove_platform_velocity();
+                self.carried_bodies.retain(|h| *h != body);
+            }
+        }
+    }
+
+    /// Adds the velocities of the moving platforms to the rigid-bodies attached to them.
+    pub(crate) fn apply_platform_velocities(&mut self, dt: Real) {
+        let mut i = 0;
+
+        while i < self.carried_bodies.len() {
+            let handle = self.carried_bodies[i];
+            let (platform, com) = match self.bodies.get(handle.0) {
+                Some(rb) if rb.platform.is_some() => (
+                    rb.platform.unwrap(),
+                    rb.position * rb.mass_properties().local_com,
+                ),
+                _ => {
+                    // The body was removed.
+                    let _ = self.carried_bodies.swap_remove(i);
+                    continue;
+                }
+            };
+
+            let platform_vels = self
+                .bodies
+                .get(platform.0)
+                .map(|platform| (platform.velocity_at_point(&com), platform.angvel));
+            let rb = &mut self.bodies[handle.0];
+
+            if let Some((linvel, angvel)) = platform_vels {
+                let moved = linvel != rb.platform_linvel || angvel != rb.platform_angvel;
+                rb.apply_platform_velocity(linvel, angvel, dt);
+
+                if moved && rb.is_sleeping() {
+                    self.wake_up(handle, false);
+                }
+
+                i += 1;
+            } else {
+                // The platform was removed.
+                rb.platform = None;
+                rb.remove_platform_velocity();
+                let _ = self.carried_bodies.swap_remove(i);
+            }
+        }
+    }
+
     /// Gets the rigid-body with the given handle without a known generation.
     ///
     /// This is useful when you know you want the rigid-body at position `i` but
@@ -263,6 +426,96 @@ impl RigidBodySet {
         Some(result)
     }
 
+    /// Iterates through all the rigid-bodies modified since the last call to
+    /// `Self::clear_modified_flags`.
+    ///
+    /// See `RigidBody::changes` for details on the modifications being tracked.
+    pub fn modified_bodies(&self) -> impl Iterator<Item = (RigidBodyHandle, &RigidBody)> {
+        let pending: Box<dyn Iterator<Item = RigidBodyHandle> + '_> = if self.modified_all_bodies {
+            Box::new(self.bodies.iter().map(|(h, _)| RigidBodyHandle(h)))
+        } else {
+            Box::new(self.modified_bodies.iter().copied())
+        };
+
+        // Pending bodies with tracked changes are already part of `self.tracked_bodies`.
+        let pending = pending.filter_map(move |handle| {
+            let rb = self.bodies.get(handle.0)?;
+            if rb.tracked_changes.is_empty() {
+                Some((handle, rb))
+            } else {
+                None
+            }
+        });
+
+        self.tracked_bodies
+            .iter()
+            .filter_map(move |handle| Some((*handle, self.bodies.get(handle.0)?)))
+            .chain(pending)
+    }
+
+    /// Clears the modification flags of all the rigid-bodies.
+    ///
+    /// This only clears the modifications already taken into account by a timestep of the
+    /// physics pipeline. The modifications made since the last timestep will still be
+    /// reported until the next timestep is executed, and this method is called again.
+    pub fn clear_modified_flags(&mut self) {
+        for handle in self.tracked_bodies.drain(..) {
+            if let Some(rb) = self.bodies.get_mut(handle.0) {
+                rb.tracked_changes = RigidBodyChanges::empty();
+            }
+        }
+    }
+
+    // Flags the active bodies that will be moved to their next position as modified.
+    pub(crate) fn track_active_bodies_motion(&mut self) {
+        for handle in self
+            .active_dynamic_set
+            .iter()
+            .chain(self.active_kinematic_set.iter())
+        {
+            if let Some(rb) = self.bodies.get_mut(handle.0) {
+                if rb.position != rb.next_position {
+                    if rb.tracked_changes.is_empty() {
+                        self.tracked_bodies.push(*handle);
+                    }
+
+                    rb.tracked_changes |= RigidBodyChanges::MODIFIED | RigidBodyChanges::POSITION;
+                }
+            }
+        }
+    }
+
+    /// Sets the next kinematic positions of several rigid-bodies at once.
+    ///
+    /// This is equivalent to calling `RigidBody::set_next_kinematic_position` on each
+    /// rigid-body, but the change tracking is performed in a single pass, which is
+    /// faster when a large number of kinematic bodies are animated (e.g. skeletal bones).
+    /// Invalid handles, as well as handles of non-kinematic bodies, are ignored.
+    pub fn set_next_kinematic_positions(
+        &mut self,
+        positions: impl IntoIterator<Item = (RigidBodyHandle, Isometry<Real>)>,
+    ) {
+        let positions = positions.into_iter();
+
+        if !self.modified_all_bodies {
+            self.modified_bodies.reserve(positions.size_hint().0);
+        }
+
+        for (handle, position) in positions {
+            if let Some(rb) = self.bodies.get_mut(handle.0) {
+                if rb.is_kinematic() {
+                    Self::mark_as_modified(
+                        handle,
+                        rb,
+                        &mut self.modified_bodies,
+                        self.modified_all_bodies,
+                    );
+                    rb.set_next_kinematic_position(position);
+                }
+            }
+        }
+    }
+
     pub(crate) fn get_mut_internal(&mut self, handle: RigidBodyHandle) -> Option<&mut RigidBody> {
         self.bodies.get_mut(handle.0)
     }
@@ -291,6 +544,15 @@ impl RigidBodySet {
         self.bodies.get2_mut(h1.0, h2.0)
     }
 
+    /// Translates all the rigid-bodies of this set by `shift`, without marking them as modified.
+    pub(crate) fn shift_origin(&mut self, shift: &Vector<Real>) {
+        for (_, rb) in self.bodies.iter_mut() {
+            rb.position.translation.vector += shift;
+            rb.next_position.translation.vector += shift;
+            rb.world_com += *shift;
+        }
+    }
+
     /// Iterates through all the rigid-bodies on this set.
     pub fn iter(&self) -> impl Iterator<Item = (RigidBodyHandle, &RigidBody)> {
         self.bodies.iter().map(|(h, b)| (RigidBodyHandle(h), b))
@@ -459,6 +721,7 @@ impl RigidBodySet {
         modified_inactive_set: &mut Vec<RigidBodyHandle>,
         active_kinematic_set: &mut Vec<RigidBodyHandle>,
         active_dynamic_set: &mut Vec<RigidBodyHandle>,
+        tracked_bodies: &mut Vec<RigidBodyHandle>,
     ) {
         enum FinalAction {
             UpdateActiveKinematicSetId,
@@ -504,6 +767,9 @@ impl RigidBodySet {
                     }
                     BodyStatus::Static => {}
                 }
+
+                // Make sure the broad-phase knows about the new status of the colliders.
+                rb.update_colliders_status(colliders);
             }
 
             // Update the positions of the colliders.
@@ -523,6 +789,12 @@ impl RigidBodySet {
                 }
             }
 
+            // Make sure the narrow-phase recomputes the relative dominance
+            // of all the contacts involving this rigid-body.
+            if rb.changes.contains(RigidBodyChanges::DOMINANCE) {
+                rb.update_colliders_dominance(colliders);
+            }
+
             // Push the body to the active set if it is not
             // sleeping and if it is not already inside of the active set.
             if rb.changes.contains(RigidBodyChanges::SLEEP)
@@ -534,6 +806,11 @@ impl RigidBodySet {
                 active_dynamic_set.push(handle);
             }
 
+            // Keep track of the changes until the user clears them.
+            if rb.tracked_changes.is_empty() {
+                tracked_bodies.push(handle);
+            }
+            rb.tracked_changes |= rb.changes | RigidBodyChanges::MODIFIED;
             rb.changes = RigidBodyChanges::empty();
 
             // Adjust some ids, if needed.
@@ -574,6 +851,7 @@ impl RigidBodySet {
                 &mut self.modified_inactive_set,
                 &mut self.active_kinematic_set,
                 &mut self.active_dynamic_set,
+                &mut self.tracked_bodies,
             )
         }
 
@@ -589,6 +867,9 @@ impl RigidBodySet {
         narrow_phase: &NarrowPhase,
         joint_graph: &InteractionGraph<RigidBodyHandle, Joint>,
         min_island_size: usize,
+        joint_island_merge_policy: JointIslandMergePolicy,
+        dt: Real,
+        events: &dyn EventHandler,
     ) {
         assert!(
             min_island_size > 0,
@@ -598,7 +879,8 @@ impl RigidBodySet {
         // Update the energy of every rigid body and
         // keep only those that may not sleep.
         //        let t = instant::now();
-        self.active_set_timestamp += 1;
+        let prev_active_set_timestamp = self.active_set_timestamp;
+        self.active_set_timestamp = self.active_set_timestamp.wrapping_add(1);
         self.stack.clear();
         self.can_sleep.clear();
 
@@ -608,8 +890,8 @@ impl RigidBodySet {
         // debugging slightly nicer so we keep this rev.
         for h in self.active_dynamic_set.drain(..).rev() {
             let rb = &mut self.bodies[h.0];
-            rb.update_energy();
-            if rb.activation.energy <= rb.activation.threshold {
+            rb.update_energy(dt);
+            if rb.activation.can_fall_asleep() {
                 // Mark them as sleeping for now. This will
                 // be set to false during the graph traversal
                 // if it should not be put to sleep.
@@ -661,6 +943,17 @@ impl RigidBodySet {
             push_contacting_bodies(rb, colliders, narrow_phase, &mut self.stack);
         }
 
+        // With the strict policy, the bodies attached to a driving motor must stay awake,
+        // even if they were sleeping already.
+        if joint_island_merge_policy == JointIslandMergePolicy::Strict {
+            for (body1, body2, joint) in joint_graph.interactions_with_endpoints() {
+                if joint.has_driving_motor() {
+                    self.stack.push(body1);
+                    self.stack.push(body2);
+                }
+            }
+        }
+
         //        println!("Selection: {}", instant::now() - t);
 
         //        let t = instant::now();
@@ -692,6 +985,11 @@ impl RigidBodySet {
                 island_marker = self.stack.len();
             }
 
+            if rb.active_set_timestamp != prev_active_set_timestamp {
+                // This body was not active during the last island computation.
+                events.handle_sleep_event(SleepEvent::WokeUp(handle));
+            }
+
             rb.wake_up(false);
             rb.active_island_id = self.active_islands.len() - 1;
             rb.active_set_id = self.active_dynamic_set.len();
@@ -722,6 +1020,7 @@ impl RigidBodySet {
             let b = &mut self.bodies[h.0];
             if b.activation.sleeping {
                 b.sleep();
+                events.handle_sleep_event(SleepEvent::FellAsleep(*h));
             }
         }
         //        println!("Activation: {}", instant::now() - t);
@@ -749,3 +1048,184 @@ impl IndexMut<RigidBodyHandle> for RigidBodySet {
         rb
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Isometry, Real, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn set_next_kinematic_positions() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let kinematic: Vec<_> = (0..3)
+            .map(|_| {
+                let handle = world
+                    .bodies
+                    .insert(RigidBodyBuilder::new_kinematic().build());
+                world.colliders.insert(
+                    ColliderBuilder::ball(0.1).build(),
+                    handle,
+                    &mut world.bodies,
+                );
+                handle
+            })
+            .collect();
+        let dynamic = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let removed = world
+            .bodies
+            .insert(RigidBodyBuilder::new_kinematic().build());
+        world
+            .bodies
+            .remove(removed, &mut world.colliders, &mut world.joints);
+
+        // Invalid handles and non-kinematic bodies are ignored.
+        let target = |i: usize| Isometry::new(Vector::x() * (i as Real + 1.0), na::zero());
+        world.bodies.set_next_kinematic_positions(
+            kinematic
+                .iter()
+                .enumerate()
+                .map(|(i, handle)| (*handle, target(i)))
+                .chain(vec![(dynamic, target(10)), (removed, target(10))]),
+        );
+        world.step(1.0 / 60.0);
+
+        for (i, handle) in kinematic.iter().enumerate() {
+            let position = *world.bodies[*handle].position();
+            assert!((position.translation.vector - target(i).translation.vector).norm() < 1.0e-4);
+            // The colliders followed their parent.
+            let collider = world.bodies[*handle].colliders()[0];
+            assert_eq!(*world.colliders[collider].position(), position);
+        }
+
+        assert_eq!(*world.bodies[dynamic].position(), Isometry::identity());
+    }
+
+    #[test]
+    fn find_by_name() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let player = world
+            .bodies
+            .insert(RigidBodyBuilder::new_dynamic().name("player").build());
+        let crate1 = world
+            .bodies
+            .insert(RigidBodyBuilder::new_dynamic().name("crate").build());
+        let crate2 = world
+            .bodies
+            .insert(RigidBodyBuilder::new_dynamic().name("crate").build());
+        let unnamed = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+
+        assert_eq!(world.bodies.find_by_name("player"), Some(player));
+        assert_eq!(world.bodies.find_by_name("crate"), Some(crate1));
+        assert_eq!(
+            world.bodies.find_all_by_name("crate"),
+            &[crate1, crate2][..]
+        );
+        assert_eq!(world.bodies.find_by_name("enemy"), None);
+
+        // Renaming and removing bodies keeps the index in sync.
+        world.bodies.set_name(unnamed, Some("enemy".to_string()));
+        world.bodies.set_name(player, None);
+        world
+            .bodies
+            .remove(crate1, &mut world.colliders, &mut world.joints);
+
+        assert_eq!(world.bodies.find_by_name("enemy"), Some(unnamed));
+        assert_eq!(world.bodies[unnamed].name(), Some("enemy"));
+        assert_eq!(world.bodies.find_by_name("player"), None);
+        assert_eq!(world.bodies[player].name(), None);
+        assert_eq!(world.bodies.find_all_by_name("crate"), &[crate2][..]);
+    }
+
+    #[test]
+    fn sleep_and_wake_up_events() {
+        use crate::dynamics::SleepEvent;
+        use crate::pipeline::ChannelEventCollector;
+
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let (intersection_send, _intersection_recv) = crossbeam::channel::unbounded();
+        let (contact_send, _contact_recv) = crossbeam::channel::unbounded();
+        let (sleep_send, sleep_recv) = crossbeam::channel::unbounded();
+        world.events = Box::new(
+            ChannelEventCollector::new(intersection_send, contact_send)
+                .with_sleep_event_sender(sleep_send),
+        );
+        let (h, _) = world.insert_dynamic(Vector::zeros(), ColliderBuilder::ball(0.5));
+
+        world.run(300);
+        assert!(world.bodies[h].is_sleeping());
+        assert_eq!(
+            sleep_recv.try_iter().collect::<Vec<_>>(),
+            vec![SleepEvent::FellAsleep(h)]
+        );
+
+        world.bodies.wake_up(h, true);
+        world.run(1);
+        assert!(!world.bodies[h].is_sleeping());
+        assert_eq!(
+            sleep_recv.try_iter().collect::<Vec<_>>(),
+            vec![SleepEvent::WokeUp(h)]
+        );
+    }
+
+    #[test]
+    fn change_tracking() {
+        use crate::dynamics::RigidBodyChanges;
+        use crate::geometry::ColliderChanges;
+
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let rb = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let co = world
+            .colliders
+            .insert(ColliderBuilder::ball(0.5).build(), rb, &mut world.bodies);
+
+        // The insertions are still reported after the timestep.
+        world.run(1);
+        assert_eq!(world.bodies.modified_bodies().count(), 1);
+        assert_eq!(world.colliders.modified_colliders().count(), 1);
+        assert!(world.bodies[rb]
+            .changes()
+            .contains(RigidBodyChanges::COLLIDERS));
+        assert!(world.colliders[co]
+            .changes()
+            .contains(ColliderChanges::SHAPE));
+
+        // Nothing is modified after clearing the flags.
+        world.bodies.clear_modified_flags();
+        world.colliders.clear_modified_flags();
+        world.run(1);
+        assert_eq!(world.bodies.modified_bodies().count(), 0);
+        assert_eq!(world.colliders.modified_colliders().count(), 0);
+
+        // User modifications are reported immediately, and until cleared after a timestep.
+        let pos = Isometry::new(Vector::x(), na::zero());
+        world.bodies[rb].set_position(pos, true);
+        assert!(world.bodies[rb]
+            .changes()
+            .contains(RigidBodyChanges::POSITION));
+        world.bodies.clear_modified_flags();
+        assert!(world.bodies[rb]
+            .changes()
+            .contains(RigidBodyChanges::POSITION));
+        world.run(1);
+        assert!(world.bodies[rb]
+            .changes()
+            .contains(RigidBodyChanges::POSITION));
+        assert!(world.colliders[co]
+            .changes()
+            .contains(ColliderChanges::POSITION));
+        world.bodies.clear_modified_flags();
+        assert!(world.bodies[rb].changes().is_empty());
+
+        // The motion of the simulated bodies is reported too.
+        let rb = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        world.bodies[rb].set_linvel(Vector::x(), true);
+        world.run(1);
+        world.bodies.clear_modified_flags();
+        world.run(1);
+        assert!(world.bodies[rb]
+            .changes()
+            .contains(RigidBodyChanges::POSITION));
+    }
+}
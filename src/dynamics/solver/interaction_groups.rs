@@ -65,7 +65,9 @@ impl ParallelInteractionGroups {
         self.sorted_interactions.clear();
         self.interaction_colors.clear();
 
-        let mut color_len = [0; 128];
+        // The last color is for the interactions that can't be given any of the 128 colors
+        // available. Each one of them is then put into its own group.
+        let mut color_len = [0; 129];
         self.bodies_color.resize(num_island_bodies, 0u128);
         self.interaction_indices
             .extend_from_slice(interaction_indices);
@@ -81,32 +83,29 @@ impl ParallelInteractionGroups {
             let rb1 = &bodies[body_pair.body1];
             let rb2 = &bodies[body_pair.body2];
 
-            match (rb1.is_static(), rb2.is_static()) {
-                (false, false) => {
-                    let color_mask =
-                        bcolors[rb1.active_set_offset] | bcolors[rb2.active_set_offset];
-                    *color = (!color_mask).trailing_zeros() as usize;
-                    color_len[*color] += 1;
+            // NOTE: only the dynamic bodies are part of the island, and only their
+            //       velocities are modified by the solver.
+            let color_mask = match (rb1.is_dynamic(), rb2.is_dynamic()) {
+                (true, true) => bcolors[rb1.active_set_offset] | bcolors[rb2.active_set_offset],
+                (false, true) => bcolors[rb2.active_set_offset],
+                (true, false) => bcolors[rb1.active_set_offset],
+                (false, false) => unreachable!(),
+            };
+
+            *color = (!color_mask).trailing_zeros() as usize;
+            color_len[*color] += 1;
+
+            if *color < 128 {
+                if rb1.is_dynamic() {
                     bcolors[rb1.active_set_offset] |= 1 << *color;
-                    bcolors[rb2.active_set_offset] |= 1 << *color;
                 }
-                (true, false) => {
-                    let color_mask = bcolors[rb2.active_set_offset];
-                    *color = (!color_mask).trailing_zeros() as usize;
-                    color_len[*color] += 1;
+                if rb2.is_dynamic() {
                     bcolors[rb2.active_set_offset] |= 1 << *color;
                 }
-                (false, true) => {
-                    let color_mask = bcolors[rb1.active_set_offset];
-                    *color = (!color_mask).trailing_zeros() as usize;
-                    color_len[*color] += 1;
-                    bcolors[rb1.active_set_offset] |= 1 << *color;
-                }
-                (true, true) => unreachable!(),
             }
         }
 
-        let mut sort_offsets = [0; 128];
+        let mut sort_offsets = [0; 129];
         let mut last_offset = 0;
 
         for i in 0..128 {
@@ -119,6 +118,11 @@ impl ParallelInteractionGroups {
             last_offset += color_len[i];
         }
 
+        sort_offsets[128] = last_offset;
+        for i in 0..color_len[128] {
+            self.groups.push(last_offset + i);
+        }
+
         self.sorted_interactions
             .resize(interaction_indices.len(), 0);
 
@@ -157,17 +161,6 @@ impl InteractionGroups {
         }
     }
 
-    #[cfg(not(feature = "parallel"))]
-    pub fn clear(&mut self) {
-        #[cfg(feature = "simd-is-enabled")]
-        {
-            self.buckets.clear();
-            self.body_masks.clear();
-            self.grouped_interactions.clear();
-        }
-        self.nongrouped_interactions.clear();
-    }
-
     // FIXME: there is a lot of duplicated code with group_manifolds here.
     // But we don't refactor just now because we may end up with distinct
     // grouping strategies in the future.
@@ -449,3 +442,69 @@ impl InteractionGroups {
         );
     }
 }
+
+#[cfg(all(test, feature = "parallel"))]
+mod test {
+    use super::ParallelInteractionGroups;
+    use crate::dynamics::{BallJoint, RigidBodyBuilder};
+    use crate::math::{Isometry, Point, Real, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    fn group_joints(world: &mut PhysicsWorld) -> ParallelInteractionGroups {
+        // Let the pipeline build the islands.
+        world.step(1.0 / 60.0);
+        assert_eq!(world.bodies.num_islands(), 1);
+
+        let mut groups = ParallelInteractionGroups::new();
+        let joints = world.joints.joints_vec_mut();
+        let indices: Vec<_> = (0..joints.len()).collect();
+        groups.group_interactions(0, &world.bodies, joints, &indices);
+        groups
+    }
+
+    #[test]
+    fn more_colors_than_supported() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let hub = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+
+        // All the joints share the hub, so each one needs its own group.
+        for i in 0..150 {
+            let anchor = Point::from(Vector::x() * (i as Real + 1.0));
+            let rb = RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(anchor.coords, na::zero()))
+                .build();
+            let handle = world.bodies.insert(rb);
+            let joint = BallJoint::new(anchor, Point::origin());
+            world.joints.insert(&mut world.bodies, hub, handle, joint);
+        }
+
+        let groups = group_joints(&mut world);
+        assert_eq!(groups.num_groups(), 150);
+        assert!((0..150).all(|i| groups.group(i) == [i]));
+    }
+
+    #[test]
+    fn kinematic_bodies_do_not_conflict() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let platform = world
+            .bodies
+            .insert(RigidBodyBuilder::new_kinematic().build());
+        let rb1 = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let rb2 = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+
+        let joint = || BallJoint::new(Point::origin(), Point::origin());
+        world
+            .joints
+            .insert(&mut world.bodies, platform, rb1, joint());
+        world
+            .joints
+            .insert(&mut world.bodies, platform, rb2, joint());
+        world.joints.insert(&mut world.bodies, rb1, rb2, joint());
+
+        // The two joints attached to the kinematic body can be solved concurrently.
+        let groups = group_joints(&mut world);
+        assert_eq!(groups.num_groups(), 2);
+        assert_eq!(groups.group(0), [0, 1]);
+        assert_eq!(groups.group(1), [2]);
+    }
+}
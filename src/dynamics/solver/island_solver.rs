@@ -53,45 +53,33 @@ impl IslandSolver {
         joints: &mut [JointGraphEdge],
         joint_indices: &[JointIndex],
     ) {
-        let has_constraints = manifold_indices.len() != 0 || joint_indices.len() != 0;
+        // NOTE: the velocity solver is run even if there are no constraints, because it
+        //       integrates and clears the forces, so they don't accumulate across substeps.
+        counters.solver.velocity_assembly_time.resume();
+        self.contact_constraints
+            .init(island_id, params, bodies, manifolds, manifold_indices);
+        self.joint_constraints
+            .init(island_id, params, bodies, joints, joint_indices);
+        counters.solver.velocity_assembly_time.pause();
 
-        if has_constraints {
-            counters.solver.velocity_assembly_time.resume();
-            self.contact_constraints
-                .init(island_id, params, bodies, manifolds, manifold_indices);
-            self.joint_constraints
-                .init(island_id, params, bodies, joints, joint_indices);
-            counters.solver.velocity_assembly_time.pause();
-
-            counters.solver.velocity_resolution_time.resume();
-            self.velocity_solver.solve(
-                island_id,
-                params,
-                bodies,
-                manifolds,
-                joints,
-                &mut self.contact_constraints.velocity_constraints,
-                &mut self.joint_constraints.velocity_constraints,
-            );
-            counters.solver.velocity_resolution_time.pause();
+        counters.solver.velocity_resolution_time.resume();
+        self.velocity_solver.solve(
+            island_id,
+            params,
+            bodies,
+            manifolds,
+            joints,
+            &mut self.contact_constraints.velocity_constraints,
+            &mut self.joint_constraints.velocity_constraints,
+        );
+        counters.solver.velocity_resolution_time.pause();
 
-            counters.solver.velocity_update_time.resume();
-            bodies.foreach_active_island_body_mut_internal(island_id, |_, rb| {
-                rb.apply_damping(params.dt);
-                rb.integrate_next_position(params.dt);
-            });
-            counters.solver.velocity_update_time.pause();
-        } else {
-            self.contact_constraints.clear();
-            self.joint_constraints.clear();
-            counters.solver.velocity_update_time.resume();
-            bodies.foreach_active_island_body_mut_internal(island_id, |_, rb| {
-                // Since we didn't run the velocity solver we need to integrate the accelerations here
-                rb.integrate_accelerations(params.dt);
-                rb.apply_damping(params.dt);
-                rb.integrate_next_position(params.dt);
-            });
-            counters.solver.velocity_update_time.pause();
-        }
+        counters.solver.velocity_update_time.resume();
+        bodies.foreach_active_island_body_mut_internal(island_id, |_, rb| {
+            rb.apply_damping(params.dt);
+            rb.snap_velocities(params);
+            rb.integrate_next_position(params.dt);
+        });
+        counters.solver.velocity_update_time.pause();
     }
 }
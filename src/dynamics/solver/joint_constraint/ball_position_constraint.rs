@@ -1,7 +1,7 @@
 use crate::dynamics::{BallJoint, IntegrationParameters, RigidBody};
 #[cfg(feature = "dim2")]
 use crate::math::SdpMatrix;
-use crate::math::{AngularInertia, Isometry, Point, Real, Rotation};
+use crate::math::{AngularInertia, Isometry, Point, Real, Rotation, Vector};
 use crate::utils::{WAngularInertia, WCross, WCrossMatrix};
 
 #[derive(Debug)]
@@ -12,8 +12,8 @@ pub(crate) struct BallPositionConstraint {
     local_com1: Point<Real>,
     local_com2: Point<Real>,
 
-    im1: Real,
-    im2: Real,
+    im1: Vector<Real>,
+    im2: Vector<Real>,
 
     ii1: AngularInertia<Real>,
     ii2: AngularInertia<Real>,
@@ -27,8 +27,8 @@ impl BallPositionConstraint {
         Self {
             local_com1: rb1.mass_properties.local_com,
             local_com2: rb2.mass_properties.local_com,
-            im1: rb1.effective_inv_mass,
-            im2: rb2.effective_inv_mass,
+            im1: rb1.effective_inv_mass_along_axes,
+            im2: rb2.effective_inv_mass_along_axes,
             ii1: rb1.effective_world_inv_inertia_sqrt.squared(),
             ii2: rb2.effective_world_inv_inertia_sqrt.squared(),
             local_anchor1: cparams.local_anchor1,
@@ -59,27 +59,33 @@ impl BallPositionConstraint {
         // NOTE: the -cmat1 is just a simpler way of doing cmat1.transpose()
         // because it is anti-symmetric.
         #[cfg(feature = "dim3")]
-        let lhs = self.ii1.quadform(&cmat1).add_diagonal(self.im1)
-            + self.ii2.quadform(&cmat2).add_diagonal(self.im2);
+        let lhs = self.ii1.quadform(&cmat1)
+            + crate::utils::sdp_diagonal(&self.im1)
+            + self.ii2.quadform(&cmat2)
+            + crate::utils::sdp_diagonal(&self.im2);
 
         // In 2D we just unroll the computation because
         // it's just easier that way. It is also
         // faster because in 2D lhs will be symmetric.
         #[cfg(feature = "dim2")]
         let lhs = {
-            let m11 =
-                self.im1 + self.im2 + cmat1.x * cmat1.x * self.ii1 + cmat2.x * cmat2.x * self.ii2;
+            let m11 = self.im1.x
+                + self.im2.x
+                + cmat1.x * cmat1.x * self.ii1
+                + cmat2.x * cmat2.x * self.ii2;
             let m12 = cmat1.x * cmat1.y * self.ii1 + cmat2.x * cmat2.y * self.ii2;
-            let m22 =
-                self.im1 + self.im2 + cmat1.y * cmat1.y * self.ii1 + cmat2.y * cmat2.y * self.ii2;
+            let m22 = self.im1.y
+                + self.im2.y
+                + cmat1.y * cmat1.y * self.ii1
+                + cmat2.y * cmat2.y * self.ii2;
             SdpMatrix::new(m11, m12, m22)
         };
 
         let inv_lhs = lhs.inverse_unchecked();
         let impulse = inv_lhs * -(err * params.joint_erp);
 
-        position1.translation.vector += self.im1 * impulse;
-        position2.translation.vector -= self.im2 * impulse;
+        position1.translation.vector += self.im1.component_mul(&impulse);
+        position2.translation.vector -= self.im2.component_mul(&impulse);
 
         let angle1 = self.ii1.transform_vector(centered_anchor1.gcross(impulse));
         let angle2 = self.ii2.transform_vector(centered_anchor2.gcross(-impulse));
@@ -96,7 +102,7 @@ impl BallPositionConstraint {
 pub(crate) struct BallPositionGroundConstraint {
     position2: usize,
     anchor1: Point<Real>,
-    im2: Real,
+    im2: Vector<Real>,
     ii2: AngularInertia<Real>,
     local_anchor2: Point<Real>,
     local_com2: Point<Real>,
@@ -115,7 +121,7 @@ impl BallPositionGroundConstraint {
             // already been flipped by the caller.
             Self {
                 anchor1: rb1.next_position * cparams.local_anchor2,
-                im2: rb2.effective_inv_mass,
+                im2: rb2.effective_inv_mass_along_axes,
                 ii2: rb2.effective_world_inv_inertia_sqrt.squared(),
                 local_anchor2: cparams.local_anchor1,
                 position2: rb2.active_set_offset,
@@ -124,7 +130,7 @@ impl BallPositionGroundConstraint {
         } else {
             Self {
                 anchor1: rb1.next_position * cparams.local_anchor1,
-                im2: rb2.effective_inv_mass,
+                im2: rb2.effective_inv_mass_along_axes,
                 ii2: rb2.effective_world_inv_inertia_sqrt.squared(),
                 local_anchor2: cparams.local_anchor2,
                 position2: rb2.active_set_offset,
@@ -144,19 +150,19 @@ impl BallPositionGroundConstraint {
         let cmat2 = centered_anchor2.gcross_matrix();
 
         #[cfg(feature = "dim3")]
-        let lhs = self.ii2.quadform(&cmat2).add_diagonal(self.im2);
+        let lhs = self.ii2.quadform(&cmat2) + crate::utils::sdp_diagonal(&self.im2);
 
         #[cfg(feature = "dim2")]
         let lhs = {
-            let m11 = self.im2 + cmat2.x * cmat2.x * self.ii2;
+            let m11 = self.im2.x + cmat2.x * cmat2.x * self.ii2;
             let m12 = cmat2.x * cmat2.y * self.ii2;
-            let m22 = self.im2 + cmat2.y * cmat2.y * self.ii2;
+            let m22 = self.im2.y + cmat2.y * cmat2.y * self.ii2;
             SdpMatrix::new(m11, m12, m22)
         };
 
         let inv_lhs = lhs.inverse_unchecked();
         let impulse = inv_lhs * -(err * params.joint_erp);
-        position2.translation.vector -= self.im2 * impulse;
+        position2.translation.vector -= self.im2.component_mul(&impulse);
 
         let angle2 = self.ii2.transform_vector(centered_anchor2.gcross(-impulse));
         position2.rotation = Rotation::new(angle2) * position2.rotation;
@@ -25,8 +25,8 @@ pub(crate) struct BallVelocityConstraint {
     motor_inv_lhs: Option<AngularInertia<Real>>,
     motor_max_impulse: Real,
 
-    im1: Real,
-    im2: Real,
+    im1: Vector<Real>,
+    im2: Vector<Real>,
 
     ii1_sqrt: AngularInertia<Real>,
     ii2_sqrt: AngularInertia<Real>,
@@ -47,8 +47,8 @@ impl BallVelocityConstraint {
 
         let vel1 = rb1.linvel + rb1.angvel.gcross(anchor1);
         let vel2 = rb2.linvel + rb2.angvel.gcross(anchor2);
-        let im1 = rb1.effective_inv_mass;
-        let im2 = rb2.effective_inv_mass;
+        let im1 = rb1.effective_inv_mass_along_axes;
+        let im2 = rb2.effective_inv_mass_along_axes;
 
         let rhs = (vel2 - vel1) * params.velocity_solve_fraction
             + (anchor_world2 - anchor_world1) * params.velocity_based_erp_inv_dt();
@@ -63,12 +63,12 @@ impl BallVelocityConstraint {
                 .effective_world_inv_inertia_sqrt
                 .squared()
                 .quadform(&cmat2)
-                .add_diagonal(im2)
+                + crate::utils::sdp_diagonal(&im2)
                 + rb1
                     .effective_world_inv_inertia_sqrt
                     .squared()
                     .quadform(&cmat1)
-                    .add_diagonal(im1);
+                + crate::utils::sdp_diagonal(&im1);
         }
 
         // In 2D we just unroll the computation because
@@ -77,9 +77,9 @@ impl BallVelocityConstraint {
         {
             let ii1 = rb1.effective_world_inv_inertia_sqrt.squared();
             let ii2 = rb2.effective_world_inv_inertia_sqrt.squared();
-            let m11 = im1 + im2 + cmat1.x * cmat1.x * ii1 + cmat2.x * cmat2.x * ii2;
+            let m11 = im1.x + im2.x + cmat1.x * cmat1.x * ii1 + cmat2.x * cmat2.x * ii2;
             let m12 = cmat1.x * cmat1.y * ii1 + cmat2.x * cmat2.y * ii2;
-            let m22 = im1 + im2 + cmat1.y * cmat1.y * ii1 + cmat2.y * cmat2.y * ii2;
+            let m22 = im1.y + im2.y + cmat1.y * cmat1.y * ii1 + cmat2.y * cmat2.y * ii2;
             lhs = SdpMatrix::new(m11, m12, m22)
         }
 
@@ -173,11 +173,11 @@ impl BallVelocityConstraint {
         let mut mj_lambda1 = mj_lambdas[self.mj_lambda1 as usize];
         let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
 
-        mj_lambda1.linear += self.im1 * self.impulse;
+        mj_lambda1.linear += self.im1.component_mul(&self.impulse);
         mj_lambda1.angular += self
             .ii1_sqrt
             .transform_vector(self.r1.gcross(self.impulse) + self.motor_impulse);
-        mj_lambda2.linear -= self.im2 * self.impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&self.impulse);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(self.r2.gcross(self.impulse) + self.motor_impulse);
@@ -196,10 +196,10 @@ impl BallVelocityConstraint {
         let impulse = self.inv_lhs * dvel;
         self.impulse += impulse;
 
-        mj_lambda1.linear += self.im1 * impulse;
+        mj_lambda1.linear += self.im1.component_mul(&impulse);
         mj_lambda1.angular += self.ii1_sqrt.transform_vector(self.r1.gcross(impulse));
 
-        mj_lambda2.linear -= self.im2 * impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&impulse);
         mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(impulse));
     }
 
@@ -261,7 +261,7 @@ pub(crate) struct BallVelocityGroundConstraint {
     motor_inv_lhs: Option<AngularInertia<Real>>,
     motor_max_impulse: Real,
 
-    im2: Real,
+    im2: Vector<Real>,
     ii2_sqrt: AngularInertia<Real>,
 }
 
@@ -289,7 +289,7 @@ impl BallVelocityGroundConstraint {
         let anchor1 = anchor_world1 - rb1.world_com;
         let anchor2 = anchor_world2 - rb2.world_com;
 
-        let im2 = rb2.effective_inv_mass;
+        let im2 = rb2.effective_inv_mass_along_axes;
         let vel1 = rb1.linvel + rb1.angvel.gcross(anchor1);
         let vel2 = rb2.linvel + rb2.angvel.gcross(anchor2);
 
@@ -306,15 +306,15 @@ impl BallVelocityGroundConstraint {
                 .effective_world_inv_inertia_sqrt
                 .squared()
                 .quadform(&cmat2)
-                .add_diagonal(im2);
+                + crate::utils::sdp_diagonal(&im2);
         }
 
         #[cfg(feature = "dim2")]
         {
             let ii2 = rb2.effective_world_inv_inertia_sqrt.squared();
-            let m11 = im2 + cmat2.x * cmat2.x * ii2;
+            let m11 = im2.x + cmat2.x * cmat2.x * ii2;
             let m12 = cmat2.x * cmat2.y * ii2;
-            let m22 = im2 + cmat2.y * cmat2.y * ii2;
+            let m22 = im2.y + cmat2.y * cmat2.y * ii2;
             lhs = SdpMatrix::new(m11, m12, m22)
         }
 
@@ -400,7 +400,7 @@ impl BallVelocityGroundConstraint {
 
     pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<Real>]) {
         let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
-        mj_lambda2.linear -= self.im2 * self.impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&self.impulse);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(self.r2.gcross(self.impulse) + self.motor_impulse);
@@ -415,7 +415,7 @@ impl BallVelocityGroundConstraint {
         let impulse = self.inv_lhs * dvel;
         self.impulse += impulse;
 
-        mj_lambda2.linear -= self.im2 * impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&impulse);
         mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(impulse));
     }
 
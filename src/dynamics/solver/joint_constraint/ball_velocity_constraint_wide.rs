@@ -23,8 +23,8 @@ pub(crate) struct WBallVelocityConstraint {
 
     inv_lhs: SdpMatrix<SimdReal>,
 
-    im1: SimdReal,
-    im2: SimdReal,
+    im1: Vector<SimdReal>,
+    im2: Vector<SimdReal>,
 
     ii1_sqrt: AngularInertia<SimdReal>,
     ii2_sqrt: AngularInertia<SimdReal>,
@@ -42,7 +42,7 @@ impl WBallVelocityConstraint {
         let linvel1 = Vector::from(array![|ii| rbs1[ii].linvel; SIMD_WIDTH]);
         let angvel1 = AngVector::<SimdReal>::from(array![|ii| rbs1[ii].angvel; SIMD_WIDTH]);
         let world_com1 = Point::from(array![|ii| rbs1[ii].world_com; SIMD_WIDTH]);
-        let im1 = SimdReal::from(array![|ii| rbs1[ii].effective_inv_mass; SIMD_WIDTH]);
+        let im1 = Vector::from(array![|ii| rbs1[ii].effective_inv_mass_along_axes; SIMD_WIDTH]);
         let ii1_sqrt = AngularInertia::<SimdReal>::from(
             array![|ii| rbs1[ii].effective_world_inv_inertia_sqrt; SIMD_WIDTH],
         );
@@ -52,7 +52,7 @@ impl WBallVelocityConstraint {
         let linvel2 = Vector::from(array![|ii| rbs2[ii].linvel; SIMD_WIDTH]);
         let angvel2 = AngVector::<SimdReal>::from(array![|ii| rbs2[ii].angvel; SIMD_WIDTH]);
         let world_com2 = Point::from(array![|ii| rbs2[ii].world_com; SIMD_WIDTH]);
-        let im2 = SimdReal::from(array![|ii| rbs2[ii].effective_inv_mass; SIMD_WIDTH]);
+        let im2 = Vector::from(array![|ii| rbs2[ii].effective_inv_mass_along_axes; SIMD_WIDTH]);
         let ii2_sqrt = AngularInertia::<SimdReal>::from(
             array![|ii| rbs2[ii].effective_world_inv_inertia_sqrt; SIMD_WIDTH],
         );
@@ -78,8 +78,10 @@ impl WBallVelocityConstraint {
 
         #[cfg(feature = "dim3")]
         {
-            lhs = ii2_sqrt.squared().quadform(&cmat2).add_diagonal(im2)
-                + ii1_sqrt.squared().quadform(&cmat1).add_diagonal(im1);
+            lhs = ii2_sqrt.squared().quadform(&cmat2)
+                + crate::utils::sdp_diagonal(&im2)
+                + ii1_sqrt.squared().quadform(&cmat1)
+                + crate::utils::sdp_diagonal(&im1);
         }
 
         // In 2D we just unroll the computation because
@@ -88,9 +90,9 @@ impl WBallVelocityConstraint {
         {
             let ii1 = ii1_sqrt.squared();
             let ii2 = ii2_sqrt.squared();
-            let m11 = im1 + im2 + cmat1.x * cmat1.x * ii1 + cmat2.x * cmat2.x * ii2;
+            let m11 = im1.x + im2.x + cmat1.x * cmat1.x * ii1 + cmat2.x * cmat2.x * ii2;
             let m12 = cmat1.x * cmat1.y * ii1 + cmat2.x * cmat2.y * ii2;
-            let m22 = im1 + im2 + cmat1.y * cmat1.y * ii1 + cmat2.y * cmat2.y * ii2;
+            let m22 = im1.y + im2.y + cmat1.y * cmat1.y * ii1 + cmat2.y * cmat2.y * ii2;
             lhs = SdpMatrix::new(m11, m12, m22)
         }
 
@@ -130,9 +132,9 @@ impl WBallVelocityConstraint {
             ),
         };
 
-        mj_lambda1.linear += self.impulse * self.im1;
+        mj_lambda1.linear += self.impulse.component_mul(&self.im1);
         mj_lambda1.angular += self.ii1_sqrt.transform_vector(self.r1.gcross(self.impulse));
-        mj_lambda2.linear -= self.impulse * self.im2;
+        mj_lambda2.linear -= self.impulse.component_mul(&self.im2);
         mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(self.impulse));
 
         for ii in 0..SIMD_WIDTH {
@@ -172,10 +174,10 @@ impl WBallVelocityConstraint {
         let impulse = self.inv_lhs * dvel;
         self.impulse += impulse;
 
-        mj_lambda1.linear += impulse * self.im1;
+        mj_lambda1.linear += impulse.component_mul(&self.im1);
         mj_lambda1.angular += self.ii1_sqrt.transform_vector(self.r1.gcross(impulse));
 
-        mj_lambda2.linear -= impulse * self.im2;
+        mj_lambda2.linear -= impulse.component_mul(&self.im2);
         mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(impulse));
 
         for ii in 0..SIMD_WIDTH {
@@ -206,7 +208,7 @@ pub(crate) struct WBallVelocityGroundConstraint {
     pub(crate) impulse: Vector<SimdReal>,
     r2: Vector<SimdReal>,
     inv_lhs: SdpMatrix<SimdReal>,
-    im2: SimdReal,
+    im2: Vector<SimdReal>,
     ii2_sqrt: AngularInertia<SimdReal>,
 }
 
@@ -231,7 +233,7 @@ impl WBallVelocityGroundConstraint {
         let linvel2 = Vector::from(array![|ii| rbs2[ii].linvel; SIMD_WIDTH]);
         let angvel2 = AngVector::<SimdReal>::from(array![|ii| rbs2[ii].angvel; SIMD_WIDTH]);
         let world_com2 = Point::from(array![|ii| rbs2[ii].world_com; SIMD_WIDTH]);
-        let im2 = SimdReal::from(array![|ii| rbs2[ii].effective_inv_mass; SIMD_WIDTH]);
+        let im2 = Vector::from(array![|ii| rbs2[ii].effective_inv_mass_along_axes; SIMD_WIDTH]);
         let ii2_sqrt = AngularInertia::<SimdReal>::from(
             array![|ii| rbs2[ii].effective_world_inv_inertia_sqrt; SIMD_WIDTH],
         );
@@ -257,7 +259,7 @@ impl WBallVelocityGroundConstraint {
 
         #[cfg(feature = "dim3")]
         {
-            lhs = ii2_sqrt.squared().quadform(&cmat2).add_diagonal(im2);
+            lhs = ii2_sqrt.squared().quadform(&cmat2) + crate::utils::sdp_diagonal(&im2);
         }
 
         // In 2D we just unroll the computation because
@@ -265,9 +267,9 @@ impl WBallVelocityGroundConstraint {
         #[cfg(feature = "dim2")]
         {
             let ii2 = ii2_sqrt.squared();
-            let m11 = im2 + cmat2.x * cmat2.x * ii2;
+            let m11 = im2.x + cmat2.x * cmat2.x * ii2;
             let m12 = cmat2.x * cmat2.y * ii2;
-            let m22 = im2 + cmat2.y * cmat2.y * ii2;
+            let m22 = im2.y + cmat2.y * cmat2.y * ii2;
             lhs = SdpMatrix::new(m11, m12, m22)
         }
 
@@ -295,7 +297,7 @@ impl WBallVelocityGroundConstraint {
             ),
         };
 
-        mj_lambda2.linear -= self.impulse * self.im2;
+        mj_lambda2.linear -= self.impulse.component_mul(&self.im2);
         mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(self.impulse));
 
         for ii in 0..SIMD_WIDTH {
@@ -321,7 +323,7 @@ impl WBallVelocityGroundConstraint {
         let impulse = self.inv_lhs * dvel;
         self.impulse += impulse;
 
-        mj_lambda2.linear -= impulse * self.im2;
+        mj_lambda2.linear -= impulse.component_mul(&self.im2);
         mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(impulse));
 
         for ii in 0..SIMD_WIDTH {
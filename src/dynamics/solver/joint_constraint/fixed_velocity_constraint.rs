@@ -28,8 +28,8 @@ pub(crate) struct FixedVelocityConstraint {
     #[cfg(feature = "dim2")]
     rhs: Vector3<Real>,
 
-    im1: Real,
-    im2: Real,
+    im1: Vector<Real>,
+    im2: Vector<Real>,
 
     ii1: AngularInertia<Real>,
     ii2: AngularInertia<Real>,
@@ -51,8 +51,8 @@ impl FixedVelocityConstraint {
     ) -> Self {
         let anchor1 = rb1.position * cparams.local_anchor1;
         let anchor2 = rb2.position * cparams.local_anchor2;
-        let im1 = rb1.effective_inv_mass;
-        let im2 = rb2.effective_inv_mass;
+        let im1 = rb1.effective_inv_mass_along_axes;
+        let im2 = rb2.effective_inv_mass_along_axes;
         let ii1 = rb1.effective_world_inv_inertia_sqrt.squared();
         let ii2 = rb2.effective_world_inv_inertia_sqrt.squared();
         let r1 = anchor1.translation.vector - rb1.world_com.coords;
@@ -65,8 +65,10 @@ impl FixedVelocityConstraint {
 
         #[cfg(feature = "dim3")]
         {
-            let lhs00 =
-                ii1.quadform(&rmat1).add_diagonal(im1) + ii2.quadform(&rmat2).add_diagonal(im2);
+            let lhs00 = ii1.quadform(&rmat1)
+                + crate::utils::sdp_diagonal(&im1)
+                + ii2.quadform(&rmat2)
+                + crate::utils::sdp_diagonal(&im2);
             let lhs10 = ii1.transform_matrix(&rmat1) + ii2.transform_matrix(&rmat2);
             let lhs11 = (ii1 + ii2).into_matrix();
 
@@ -83,9 +85,9 @@ impl FixedVelocityConstraint {
         // it's just easier that way.
         #[cfg(feature = "dim2")]
         {
-            let m11 = im1 + im2 + rmat1.x * rmat1.x * ii1 + rmat2.x * rmat2.x * ii2;
+            let m11 = im1.x + im2.x + rmat1.x * rmat1.x * ii1 + rmat2.x * rmat2.x * ii2;
             let m12 = rmat1.x * rmat1.y * ii1 + rmat2.x * rmat2.y * ii2;
-            let m22 = im1 + im2 + rmat1.y * rmat1.y * ii1 + rmat2.y * rmat2.y * ii2;
+            let m22 = im1.y + im2.y + rmat1.y * rmat1.y * ii1 + rmat2.y * rmat2.y * ii2;
             let m13 = rmat1.x * ii1 + rmat2.x * ii2;
             let m23 = rmat1.y * ii1 + rmat2.y * ii2;
             let m33 = ii1 + ii2;
@@ -159,12 +161,12 @@ impl FixedVelocityConstraint {
         #[cfg(feature = "dim3")]
         let ang_impulse = self.impulse.fixed_rows::<U3>(3).into_owned();
 
-        mj_lambda1.linear += self.im1 * lin_impulse;
+        mj_lambda1.linear += self.im1.component_mul(&lin_impulse);
         mj_lambda1.angular += self
             .ii1_sqrt
             .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
 
-        mj_lambda2.linear -= self.im2 * lin_impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&lin_impulse);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
@@ -200,12 +202,12 @@ impl FixedVelocityConstraint {
         #[cfg(feature = "dim3")]
         let ang_impulse = impulse.fixed_rows::<U3>(3).into_owned();
 
-        mj_lambda1.linear += self.im1 * lin_impulse;
+        mj_lambda1.linear += self.im1.component_mul(&lin_impulse);
         mj_lambda1.angular += self
             .ii1_sqrt
             .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
 
-        mj_lambda2.linear -= self.im2 * lin_impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&lin_impulse);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
@@ -240,7 +242,7 @@ pub(crate) struct FixedVelocityGroundConstraint {
     #[cfg(feature = "dim2")]
     rhs: Vector3<Real>,
 
-    im2: Real,
+    im2: Vector<Real>,
     ii2: AngularInertia<Real>,
     ii2_sqrt: AngularInertia<Real>,
     r2: Vector<Real>,
@@ -269,7 +271,7 @@ impl FixedVelocityGroundConstraint {
 
         let r1 = anchor1.translation.vector - rb1.world_com.coords;
 
-        let im2 = rb2.effective_inv_mass;
+        let im2 = rb2.effective_inv_mass_along_axes;
         let ii2 = rb2.effective_world_inv_inertia_sqrt.squared();
         let r2 = anchor2.translation.vector - rb2.world_com.coords;
         let rmat2 = r2.gcross_matrix();
@@ -279,7 +281,7 @@ impl FixedVelocityGroundConstraint {
 
         #[cfg(feature = "dim3")]
         {
-            let lhs00 = ii2.quadform(&rmat2).add_diagonal(im2);
+            let lhs00 = ii2.quadform(&rmat2) + crate::utils::sdp_diagonal(&im2);
             let lhs10 = ii2.transform_matrix(&rmat2);
             let lhs11 = ii2.into_matrix();
 
@@ -296,9 +298,9 @@ impl FixedVelocityGroundConstraint {
         // it's just easier that way.
         #[cfg(feature = "dim2")]
         {
-            let m11 = im2 + rmat2.x * rmat2.x * ii2;
+            let m11 = im2.x + rmat2.x * rmat2.x * ii2;
             let m12 = rmat2.x * rmat2.y * ii2;
-            let m22 = im2 + rmat2.y * rmat2.y * ii2;
+            let m22 = im2.y + rmat2.y * rmat2.y * ii2;
             let m13 = rmat2.x * ii2;
             let m23 = rmat2.y * ii2;
             let m33 = ii2;
@@ -363,7 +365,7 @@ impl FixedVelocityGroundConstraint {
         #[cfg(feature = "dim3")]
         let ang_impulse = self.impulse.fixed_rows::<U3>(3).into_owned();
 
-        mj_lambda2.linear -= self.im2 * lin_impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&lin_impulse);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
@@ -394,7 +396,7 @@ impl FixedVelocityGroundConstraint {
         #[cfg(feature = "dim3")]
         let ang_impulse = impulse.fixed_rows::<U3>(3).into_owned();
 
-        mj_lambda2.linear -= self.im2 * lin_impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&lin_impulse);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
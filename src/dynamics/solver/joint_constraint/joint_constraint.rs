@@ -1,6 +1,7 @@
 use super::{
     BallVelocityConstraint, BallVelocityGroundConstraint, FixedVelocityConstraint,
-    FixedVelocityGroundConstraint, PrismaticVelocityConstraint, PrismaticVelocityGroundConstraint,
+    FixedVelocityGroundConstraint, MouseVelocityConstraint, PrismaticVelocityConstraint,
+    PrismaticVelocityGroundConstraint,
 };
 #[cfg(feature = "dim3")]
 use super::{RevoluteVelocityConstraint, RevoluteVelocityGroundConstraint};
@@ -59,6 +60,7 @@ pub(crate) enum AnyJointVelocityConstraint {
     #[cfg(feature = "dim3")]
     #[cfg(feature = "simd-is-enabled")]
     WRevoluteGroundConstraint(WRevoluteVelocityGroundConstraint),
+    MouseConstraint(MouseVelocityConstraint),
     #[allow(dead_code)] // The Empty variant is only used with parallel code.
     Empty,
 }
@@ -95,6 +97,9 @@ impl AnyJointVelocityConstraint {
             JointParams::RevoluteJoint(p) => AnyJointVelocityConstraint::RevoluteConstraint(
                 RevoluteVelocityConstraint::from_params(params, joint_id, rb1, rb2, p),
             ),
+            JointParams::MouseJoint(p) => AnyJointVelocityConstraint::MouseConstraint(
+                MouseVelocityConstraint::from_params(params, joint_id, rb2, p),
+            ),
         }
     }
 
@@ -142,6 +147,9 @@ impl AnyJointVelocityConstraint {
                     WRevoluteVelocityConstraint::from_params(params, joint_id, rbs1, rbs2, joints),
                 )
             }
+            JointParams::MouseJoint(_) => {
+                unreachable!("Mouse joints do not support SIMD constraints.")
+            }
         }
     }
 
@@ -182,6 +190,9 @@ impl AnyJointVelocityConstraint {
             JointParams::RevoluteJoint(p) => RevoluteVelocityGroundConstraint::from_params(
                 params, joint_id, rb1, rb2, p, flipped,
             ),
+            JointParams::MouseJoint(p) => AnyJointVelocityConstraint::MouseConstraint(
+                MouseVelocityConstraint::from_params(params, joint_id, rb2, p),
+            ),
         }
     }
 
@@ -247,6 +258,9 @@ impl AnyJointVelocityConstraint {
                     ),
                 )
             }
+            JointParams::MouseJoint(_) => {
+                unreachable!("Mouse joints do not support SIMD constraints.")
+            }
         }
     }
 
@@ -286,6 +300,7 @@ impl AnyJointVelocityConstraint {
             #[cfg(feature = "dim3")]
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WRevoluteGroundConstraint(c) => c.warmstart(mj_lambdas),
+            AnyJointVelocityConstraint::MouseConstraint(c) => c.warmstart(mj_lambdas),
             AnyJointVelocityConstraint::Empty => unreachable!(),
         }
     }
@@ -326,6 +341,7 @@ impl AnyJointVelocityConstraint {
             #[cfg(feature = "dim3")]
             #[cfg(feature = "simd-is-enabled")]
             AnyJointVelocityConstraint::WRevoluteGroundConstraint(c) => c.solve(mj_lambdas),
+            AnyJointVelocityConstraint::MouseConstraint(c) => c.solve(mj_lambdas),
             AnyJointVelocityConstraint::Empty => unreachable!(),
         }
     }
@@ -386,6 +402,7 @@ impl AnyJointVelocityConstraint {
             AnyJointVelocityConstraint::WRevoluteGroundConstraint(c) => {
                 c.writeback_impulses(joints_all)
             }
+            AnyJointVelocityConstraint::MouseConstraint(c) => c.writeback_impulses(joints_all),
             AnyJointVelocityConstraint::Empty => unreachable!(),
         }
     }
@@ -51,6 +51,8 @@ pub(crate) enum AnyJointPositionConstraint {
     WRevoluteJoint(WRevolutePositionConstraint),
     #[cfg(all(feature = "dim3", feature = "simd-is-enabled"))]
     WRevoluteGroundConstraint(WRevolutePositionGroundConstraint),
+    // Mouse joints are soft constraints so they don't have any position-based correction.
+    MouseJoint,
     #[allow(dead_code)] // The Empty variant is only used with parallel code.
     Empty,
 }
@@ -77,6 +79,7 @@ impl AnyJointPositionConstraint {
             JointParams::RevoluteJoint(p) => AnyJointPositionConstraint::RevoluteJoint(
                 RevolutePositionConstraint::from_params(rb1, rb2, p),
             ),
+            JointParams::MouseJoint(_) => AnyJointPositionConstraint::MouseJoint,
         }
     }
 
@@ -119,6 +122,9 @@ impl AnyJointPositionConstraint {
                     WRevolutePositionConstraint::from_params(rbs1, rbs2, joints),
                 )
             }
+            JointParams::MouseJoint(_) => {
+                unreachable!("Mouse joints do not support SIMD constraints.")
+            }
         }
     }
 
@@ -150,6 +156,7 @@ impl AnyJointPositionConstraint {
             JointParams::RevoluteJoint(p) => AnyJointPositionConstraint::RevoluteGroundConstraint(
                 RevolutePositionGroundConstraint::from_params(rb1, rb2, p, flipped),
             ),
+            JointParams::MouseJoint(_) => AnyJointPositionConstraint::MouseJoint,
         }
     }
 
@@ -200,6 +207,9 @@ impl AnyJointPositionConstraint {
                     WRevolutePositionGroundConstraint::from_params(rbs1, rbs2, joints, flipped),
                 )
             }
+            JointParams::MouseJoint(_) => {
+                unreachable!("Mouse joints do not support SIMD constraints.")
+            }
         }
     }
 
@@ -237,6 +247,7 @@ impl AnyJointPositionConstraint {
             AnyJointPositionConstraint::WRevoluteJoint(c) => c.solve(params, positions),
             #[cfg(all(feature = "dim3", feature = "simd-is-enabled"))]
             AnyJointPositionConstraint::WRevoluteGroundConstraint(c) => c.solve(params, positions),
+            AnyJointPositionConstraint::MouseJoint => {}
             AnyJointPositionConstraint::Empty => unreachable!(),
         }
     }
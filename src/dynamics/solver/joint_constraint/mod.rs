@@ -35,6 +35,7 @@ pub(self) use fixed_velocity_constraint_wide::{
 
 pub(crate) use joint_constraint::AnyJointVelocityConstraint;
 pub(crate) use joint_position_constraint::AnyJointPositionConstraint;
+pub(self) use mouse_velocity_constraint::MouseVelocityConstraint;
 pub(self) use prismatic_position_constraint::{
     PrismaticPositionConstraint, PrismaticPositionGroundConstraint,
 };
@@ -86,6 +87,7 @@ mod fixed_velocity_constraint_wide;
 // mod generic_velocity_constraint_wide;
 mod joint_constraint;
 mod joint_position_constraint;
+mod mouse_velocity_constraint;
 mod prismatic_position_constraint;
 #[cfg(feature = "simd-is-enabled")]
 mod prismatic_position_constraint_wide;
@@ -0,0 +1,135 @@
+use crate::dynamics::solver::DeltaVel;
+use crate::dynamics::{
+    IntegrationParameters, JointGraphEdge, JointIndex, JointParams, MouseJoint, RigidBody,
+};
+use crate::math::{AngularInertia, Real, SdpMatrix, Vector};
+use crate::utils::{WAngularInertia, WCross, WCrossMatrix};
+
+#[derive(Debug)]
+pub(crate) struct MouseVelocityConstraint {
+    mj_lambda2: usize,
+    joint_id: JointIndex,
+    r2: Vector<Real>,
+
+    rhs: Vector<Real>,
+    impulse: Vector<Real>,
+    inv_lhs: Option<SdpMatrix<Real>>,
+    gamma: Real,
+    max_impulse: Real,
+
+    im2: Vector<Real>,
+    ii2_sqrt: AngularInertia<Real>,
+}
+
+impl MouseVelocityConstraint {
+    // NOTE: `rb2` is the dragged body. A mouse joint never affects the other body.
+    pub fn from_params(
+        params: &IntegrationParameters,
+        joint_id: JointIndex,
+        rb2: &RigidBody,
+        joint: &MouseJoint,
+    ) -> Self {
+        let anchor_world2 = rb2.position * joint.local_anchor;
+        let anchor2 = anchor_world2 - rb2.world_com;
+
+        let im2 = rb2.effective_inv_mass_along_axes;
+        let vel2 = rb2.linvel + rb2.angvel.gcross(anchor2);
+
+        let (stiffness, damping, gamma, keep_lhs) =
+            joint
+                .model
+                .combine_coefficients(params.dt, joint.stiffness, joint.damping);
+
+        let mut rhs = (anchor_world2 - joint.target) * stiffness + vel2 * damping;
+
+        if gamma != 0.0 {
+            rhs /= gamma;
+        }
+
+        let inv_lhs = if keep_lhs {
+            let cmat2 = anchor2.gcross_matrix();
+            let lhs;
+
+            #[cfg(feature = "dim3")]
+            {
+                lhs = rb2
+                    .effective_world_inv_inertia_sqrt
+                    .squared()
+                    .quadform(&cmat2)
+                    + crate::utils::sdp_diagonal(&im2);
+            }
+
+            #[cfg(feature = "dim2")]
+            {
+                let ii2 = rb2.effective_world_inv_inertia_sqrt.squared();
+                let m11 = im2.x + cmat2.x * cmat2.x * ii2;
+                let m12 = cmat2.x * cmat2.y * ii2;
+                let m22 = im2.y + cmat2.y * cmat2.y * ii2;
+                lhs = SdpMatrix::new(m11, m12, m22)
+            }
+
+            Some(lhs.inverse_unchecked())
+        } else {
+            None
+        };
+
+        let max_impulse = joint.max_force * params.dt;
+
+        MouseVelocityConstraint {
+            joint_id,
+            mj_lambda2: rb2.active_set_offset,
+            im2,
+            impulse: joint.impulse.cap_magnitude(max_impulse) * params.warmstart_coeff,
+            r2: anchor2,
+            rhs,
+            inv_lhs,
+            gamma,
+            max_impulse,
+            ii2_sqrt: rb2.effective_world_inv_inertia_sqrt,
+        }
+    }
+
+    pub fn warmstart(&self, mj_lambdas: &mut [DeltaVel<Real>]) {
+        let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+        mj_lambda2.linear -= self.im2.component_mul(&self.impulse);
+        mj_lambda2.angular -= self.ii2_sqrt.transform_vector(self.r2.gcross(self.impulse));
+        mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+    }
+
+    pub fn solve(&mut self, mj_lambdas: &mut [DeltaVel<Real>]) {
+        if self.gamma == 0.0 {
+            // Both the stiffness and damping are zero.
+            return;
+        }
+
+        let mut mj_lambda2 = mj_lambdas[self.mj_lambda2 as usize];
+
+        let angvel = self.ii2_sqrt.transform_vector(mj_lambda2.angular);
+        let vel2 = mj_lambda2.linear + angvel.gcross(self.r2);
+        let dvel = vel2 + self.rhs;
+
+        let mut impulse = dvel * self.gamma;
+
+        if let Some(inv_lhs) = &self.inv_lhs {
+            impulse = *inv_lhs * impulse;
+        }
+
+        let clamped_impulse = (self.impulse + impulse).cap_magnitude(self.max_impulse);
+        let effective_impulse = clamped_impulse - self.impulse;
+        self.impulse = clamped_impulse;
+
+        mj_lambda2.linear -= self.im2.component_mul(&effective_impulse);
+        mj_lambda2.angular -= self
+            .ii2_sqrt
+            .transform_vector(self.r2.gcross(effective_impulse));
+
+        mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
+    }
+
+    pub fn writeback_impulses(&self, joints_all: &mut [JointGraphEdge]) {
+        let joint = &mut joints_all[self.joint_id].weight;
+        if let JointParams::MouseJoint(mouse) = &mut joint.params {
+            mouse.impulse = self.impulse;
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::dynamics::{IntegrationParameters, PrismaticJoint, RigidBody};
 use crate::math::{AngularInertia, Isometry, Point, Real, Rotation, Vector};
-use crate::utils::WAngularInertia;
+use crate::utils::{self, WAngularInertia};
 use na::Unit;
 
 #[derive(Debug)]
@@ -8,13 +8,13 @@ pub(crate) struct PrismaticPositionConstraint {
     position1: usize,
     position2: usize,
 
-    im1: Real,
-    im2: Real,
+    im1: Vector<Real>,
+    im2: Vector<Real>,
 
     ii1: AngularInertia<Real>,
     ii2: AngularInertia<Real>,
 
-    lin_inv_lhs: Real,
+    lin_inv_lhs: Vector<Real>,
     ang_inv_lhs: AngularInertia<Real>,
 
     limits: [Real; 2],
@@ -30,9 +30,9 @@ impl PrismaticPositionConstraint {
     pub fn from_params(rb1: &RigidBody, rb2: &RigidBody, cparams: &PrismaticJoint) -> Self {
         let ii1 = rb1.effective_world_inv_inertia_sqrt.squared();
         let ii2 = rb2.effective_world_inv_inertia_sqrt.squared();
-        let im1 = rb1.effective_inv_mass;
-        let im2 = rb2.effective_inv_mass;
-        let lin_inv_lhs = 1.0 / (im1 + im2);
+        let im1 = rb1.effective_inv_mass_along_axes;
+        let im2 = rb2.effective_inv_mass_along_axes;
+        let lin_inv_lhs = (im1 + im2).map(utils::inv);
         let ang_inv_lhs = (ii1 + ii2).inverse();
 
         Self {
@@ -87,9 +87,9 @@ impl PrismaticPositionConstraint {
             err += *axis1 * (limit_err - self.limits[1]);
         }
 
-        let impulse = err * (self.lin_inv_lhs * params.joint_erp);
-        position1.translation.vector += self.im1 * impulse;
-        position2.translation.vector -= self.im2 * impulse;
+        let impulse = err.component_mul(&self.lin_inv_lhs) * params.joint_erp;
+        position1.translation.vector += self.im1.component_mul(&impulse);
+        position2.translation.vector -= self.im2.component_mul(&impulse);
 
         positions[self.position1 as usize] = position1;
         positions[self.position2 as usize] = position2;
@@ -63,8 +63,8 @@ pub(crate) struct PrismaticVelocityConstraint {
     #[cfg(feature = "dim3")]
     basis1: Matrix3x2<Real>,
 
-    im1: Real,
-    im2: Real,
+    im1: Vector<Real>,
+    im2: Vector<Real>,
 
     ii1_sqrt: AngularInertia<Real>,
     ii2_sqrt: AngularInertia<Real>,
@@ -91,12 +91,12 @@ impl PrismaticVelocityConstraint {
             rb1.position * joint.basis1[1],
         ]);
 
-        let im1 = rb1.effective_inv_mass;
+        let im1 = rb1.effective_inv_mass_along_axes;
         let ii1 = rb1.effective_world_inv_inertia_sqrt.squared();
         let r1 = anchor1 - rb1.world_com;
         let r1_mat = r1.gcross_matrix();
 
-        let im2 = rb2.effective_inv_mass;
+        let im2 = rb2.effective_inv_mass_along_axes;
         let ii2 = rb2.effective_world_inv_inertia_sqrt.squared();
         let r2 = anchor2 - rb2.world_com;
         let r2_mat = r2.gcross_matrix();
@@ -110,8 +110,9 @@ impl PrismaticVelocityConstraint {
             let r2_mat_b1 = r2_mat * basis1;
 
             lhs = Matrix5::zeros();
-            let lhs00 = ii1.quadform3x2(&r1_mat_b1).add_diagonal(im1)
-                + ii2.quadform3x2(&r2_mat_b1).add_diagonal(im2);
+            let lhs00 = ii1.quadform3x2(&r1_mat_b1)
+                + ii2.quadform3x2(&r2_mat_b1)
+                + crate::utils::sdp_diagonal(&(im1 + im2)).quadform3x2(&basis1);
             let lhs10 = ii1 * r1_mat_b1 + ii2 * r2_mat_b1;
             let lhs11 = (ii1 + ii2).into_matrix();
             lhs.fixed_slice_mut::<U2, U2>(0, 0)
@@ -124,7 +125,9 @@ impl PrismaticVelocityConstraint {
         {
             let b1r1 = basis1.dot(&r1_mat);
             let b2r2 = basis1.dot(&r2_mat);
-            let m11 = im1 + im2 + b1r1 * ii1 * b1r1 + b2r2 * ii2 * b2r2;
+            let m11 = basis1.component_mul(&basis1).dot(&(im1 + im2))
+                + b1r1 * ii1 * b1r1
+                + b2r2 * ii2 * b2r2;
             let m12 = basis1.dot(&r1_mat) * ii1 + basis1.dot(&r2_mat) * ii2;
             let m22 = ii1 + ii2;
             lhs = SdpMatrix2::new(m11, m12, m22);
@@ -197,7 +200,13 @@ impl PrismaticVelocityConstraint {
         }
 
         if stiffness != 0.0 || damping != 0.0 {
-            motor_inv_lhs = if keep_lhs { gamma / (im1 + im2) } else { gamma };
+            motor_inv_lhs = if keep_lhs {
+                let motor_im =
+                    axis1.component_mul(&axis1).dot(&im1) + axis2.component_mul(&axis2).dot(&im2);
+                gamma / motor_im
+            } else {
+                gamma
+            };
             motor_rhs /= gamma;
         }
 
@@ -239,7 +248,9 @@ impl PrismaticVelocityConstraint {
                 let gcross1 = r1.gcross(*axis1);
                 let gcross2 = r2.gcross(*axis2);
                 limits_inv_lhs = crate::utils::inv(
-                    im1 + im2
+                    limits_forcedir2
+                        .component_mul(&limits_forcedir2)
+                        .dot(&(im1 + im2))
                         + gcross1.gdot(ii1.transform_vector(gcross1))
                         + gcross2.gdot(ii2.transform_vector(gcross2)),
                 );
@@ -290,29 +301,29 @@ impl PrismaticVelocityConstraint {
         #[cfg(feature = "dim3")]
         let ang_impulse = self.impulse.fixed_rows::<U3>(2).into_owned();
 
-        mj_lambda1.linear += self.im1 * lin_impulse;
+        mj_lambda1.linear += self.im1.component_mul(&lin_impulse);
         mj_lambda1.angular += self
             .ii1_sqrt
             .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
 
-        mj_lambda2.linear -= self.im2 * lin_impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&lin_impulse);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
 
         // Warmstart motors.
-        mj_lambda1.linear += self.motor_axis1 * (self.im1 * self.motor_impulse);
-        mj_lambda2.linear -= self.motor_axis2 * (self.im2 * self.motor_impulse);
+        mj_lambda1.linear += self.motor_axis1.component_mul(&self.im1) * self.motor_impulse;
+        mj_lambda2.linear -= self.motor_axis2.component_mul(&self.im2) * self.motor_impulse;
 
         // Warmstart limits.
         if self.limits_active {
             let limit_impulse1 = -self.limits_forcedir2 * self.limits_impulse;
             let limit_impulse2 = self.limits_forcedir2 * self.limits_impulse;
-            mj_lambda1.linear += self.im1 * limit_impulse1;
+            mj_lambda1.linear += self.im1.component_mul(&limit_impulse1);
             mj_lambda1.angular += self
                 .ii1_sqrt
                 .transform_vector(self.r1.gcross(limit_impulse1));
-            mj_lambda2.linear += self.im2 * limit_impulse2;
+            mj_lambda2.linear += self.im2.component_mul(&limit_impulse2);
             mj_lambda2.angular += self
                 .ii2_sqrt
                 .transform_vector(self.r2.gcross(limit_impulse2));
@@ -342,12 +353,12 @@ impl PrismaticVelocityConstraint {
         #[cfg(feature = "dim3")]
         let ang_impulse = impulse.fixed_rows::<U3>(2).into_owned();
 
-        mj_lambda1.linear += self.im1 * lin_impulse;
+        mj_lambda1.linear += self.im1.component_mul(&lin_impulse);
         mj_lambda1.angular += self
             .ii1_sqrt
             .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
 
-        mj_lambda2.linear -= self.im2 * lin_impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&lin_impulse);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
@@ -373,9 +384,9 @@ impl PrismaticVelocityConstraint {
             let lin_impulse1 = limits_forcedir1 * dimpulse;
             let lin_impulse2 = limits_forcedir2 * dimpulse;
 
-            mj_lambda1.linear += self.im1 * lin_impulse1;
+            mj_lambda1.linear += self.im1.component_mul(&lin_impulse1);
             mj_lambda1.angular += self.ii1_sqrt.transform_vector(self.r1.gcross(lin_impulse1));
-            mj_lambda2.linear += self.im2 * lin_impulse2;
+            mj_lambda2.linear += self.im2.component_mul(&lin_impulse2);
             mj_lambda2.angular += self.ii2_sqrt.transform_vector(self.r2.gcross(lin_impulse2));
         }
     }
@@ -393,8 +404,8 @@ impl PrismaticVelocityConstraint {
             let dimpulse = new_impulse - self.motor_impulse;
             self.motor_impulse = new_impulse;
 
-            mj_lambda1.linear += self.motor_axis1 * (self.im1 * dimpulse);
-            mj_lambda2.linear -= self.motor_axis2 * (self.im2 * dimpulse);
+            mj_lambda1.linear += self.motor_axis1.component_mul(&self.im1) * dimpulse;
+            mj_lambda2.linear -= self.motor_axis2.component_mul(&self.im2) * dimpulse;
         }
     }
 
@@ -460,7 +471,7 @@ pub(crate) struct PrismaticVelocityGroundConstraint {
     #[cfg(feature = "dim3")]
     basis1: Matrix3x2<Real>,
 
-    im2: Real,
+    im2: Vector<Real>,
     ii2_sqrt: AngularInertia<Real>,
 }
 
@@ -527,7 +538,7 @@ impl PrismaticVelocityGroundConstraint {
         // simplifications of the computation without introducing
         // much instabilities.
 
-        let im2 = rb2.effective_inv_mass;
+        let im2 = rb2.effective_inv_mass_along_axes;
         let ii2 = rb2.effective_world_inv_inertia_sqrt.squared();
         let r1 = anchor1 - rb1.world_com;
         let r2 = anchor2 - rb2.world_com;
@@ -541,7 +552,8 @@ impl PrismaticVelocityGroundConstraint {
             let r2_mat_b1 = r2_mat * basis1;
 
             lhs = Matrix5::zeros();
-            let lhs00 = ii2.quadform3x2(&r2_mat_b1).add_diagonal(im2);
+            let lhs00 =
+                ii2.quadform3x2(&r2_mat_b1) + crate::utils::sdp_diagonal(&im2).quadform3x2(&basis1);
             let lhs10 = ii2 * r2_mat_b1;
             let lhs11 = ii2.into_matrix();
             lhs.fixed_slice_mut::<U2, U2>(0, 0)
@@ -553,7 +565,7 @@ impl PrismaticVelocityGroundConstraint {
         #[cfg(feature = "dim2")]
         {
             let b2r2 = basis1.dot(&r2_mat);
-            let m11 = im2 + b2r2 * ii2 * b2r2;
+            let m11 = basis1.component_mul(&basis1).dot(&im2) + b2r2 * ii2 * b2r2;
             let m12 = basis1.dot(&r2_mat) * ii2;
             let m22 = ii2;
             lhs = SdpMatrix2::new(m11, m12, m22);
@@ -632,7 +644,11 @@ impl PrismaticVelocityGroundConstraint {
         }
 
         if stiffness != 0.0 || damping != 0.0 {
-            motor_inv_lhs = if keep_lhs { gamma / im2 } else { gamma };
+            motor_inv_lhs = if keep_lhs {
+                gamma / axis2.component_mul(&axis2).dot(&im2)
+            } else {
+                gamma
+            };
             motor_rhs /= gamma;
         }
 
@@ -711,16 +727,16 @@ impl PrismaticVelocityGroundConstraint {
         #[cfg(feature = "dim3")]
         let ang_impulse = self.impulse.fixed_rows::<U3>(2).into_owned();
 
-        mj_lambda2.linear -= self.im2 * lin_impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&lin_impulse);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
 
         // Warmstart motors.
-        mj_lambda2.linear -= self.axis2 * (self.im2 * self.motor_impulse);
+        mj_lambda2.linear -= self.axis2.component_mul(&self.im2) * self.motor_impulse;
 
         // Warmstart limits.
-        mj_lambda2.linear += self.limits_forcedir2 * (self.im2 * self.limits_impulse);
+        mj_lambda2.linear += self.limits_forcedir2.component_mul(&self.im2) * self.limits_impulse;
 
         mj_lambdas[self.mj_lambda2 as usize] = mj_lambda2;
     }
@@ -743,7 +759,7 @@ impl PrismaticVelocityGroundConstraint {
         #[cfg(feature = "dim3")]
         let ang_impulse = impulse.fixed_rows::<U3>(2).into_owned();
 
-        mj_lambda2.linear -= self.im2 * lin_impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&lin_impulse);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
@@ -757,13 +773,17 @@ impl PrismaticVelocityGroundConstraint {
                 .limits_forcedir2
                 .dot(&(mj_lambda2.linear + ang_vel2.gcross(self.r2)))
                 + self.limits_rhs;
-            let new_impulse = (self.limits_impulse - lin_dvel / self.im2)
+            let limits_im2 = self
+                .limits_forcedir2
+                .component_mul(&self.limits_forcedir2)
+                .dot(&self.im2);
+            let new_impulse = (self.limits_impulse - lin_dvel / limits_im2)
                 .max(self.limits_impulse_limits.0)
                 .min(self.limits_impulse_limits.1);
             let dimpulse = new_impulse - self.limits_impulse;
             self.limits_impulse = new_impulse;
 
-            mj_lambda2.linear += self.limits_forcedir2 * (self.im2 * dimpulse);
+            mj_lambda2.linear += self.limits_forcedir2.component_mul(&self.im2) * dimpulse;
         }
     }
 
@@ -778,7 +798,7 @@ impl PrismaticVelocityGroundConstraint {
             let dimpulse = new_impulse - self.motor_impulse;
             self.motor_impulse = new_impulse;
 
-            mj_lambda2.linear -= self.axis2 * (self.im2 * dimpulse);
+            mj_lambda2.linear -= self.axis2.component_mul(&self.im2) * dimpulse;
         }
     }
 
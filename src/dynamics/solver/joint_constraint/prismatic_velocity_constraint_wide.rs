@@ -60,8 +60,8 @@ pub(crate) struct WPrismaticVelocityConstraint {
     #[cfg(feature = "dim3")]
     basis1: Matrix3x2<SimdReal>,
 
-    im1: SimdReal,
-    im2: SimdReal,
+    im1: Vector<SimdReal>,
+    im2: Vector<SimdReal>,
 
     ii1_sqrt: AngularInertia<SimdReal>,
     ii2_sqrt: AngularInertia<SimdReal>,
@@ -79,7 +79,7 @@ impl WPrismaticVelocityConstraint {
         let linvel1 = Vector::from(array![|ii| rbs1[ii].linvel; SIMD_WIDTH]);
         let angvel1 = AngVector::<SimdReal>::from(array![|ii| rbs1[ii].angvel; SIMD_WIDTH]);
         let world_com1 = Point::from(array![|ii| rbs1[ii].world_com; SIMD_WIDTH]);
-        let im1 = SimdReal::from(array![|ii| rbs1[ii].effective_inv_mass; SIMD_WIDTH]);
+        let im1 = Vector::from(array![|ii| rbs1[ii].effective_inv_mass_along_axes; SIMD_WIDTH]);
         let ii1_sqrt = AngularInertia::<SimdReal>::from(
             array![|ii| rbs1[ii].effective_world_inv_inertia_sqrt; SIMD_WIDTH],
         );
@@ -89,7 +89,7 @@ impl WPrismaticVelocityConstraint {
         let linvel2 = Vector::from(array![|ii| rbs2[ii].linvel; SIMD_WIDTH]);
         let angvel2 = AngVector::<SimdReal>::from(array![|ii| rbs2[ii].angvel; SIMD_WIDTH]);
         let world_com2 = Point::from(array![|ii| rbs2[ii].world_com; SIMD_WIDTH]);
-        let im2 = SimdReal::from(array![|ii| rbs2[ii].effective_inv_mass; SIMD_WIDTH]);
+        let im2 = Vector::from(array![|ii| rbs2[ii].effective_inv_mass_along_axes; SIMD_WIDTH]);
         let ii2_sqrt = AngularInertia::<SimdReal>::from(
             array![|ii| rbs2[ii].effective_world_inv_inertia_sqrt; SIMD_WIDTH],
         );
@@ -155,8 +155,9 @@ impl WPrismaticVelocityConstraint {
             let r2_mat_b1 = r2_mat * basis1;
 
             lhs = Matrix5::zeros();
-            let lhs00 = ii1.quadform3x2(&r1_mat_b1).add_diagonal(im1)
-                + ii2.quadform3x2(&r2_mat_b1).add_diagonal(im2);
+            let lhs00 = ii1.quadform3x2(&r1_mat_b1)
+                + ii2.quadform3x2(&r2_mat_b1)
+                + crate::utils::sdp_diagonal(&(im1 + im2)).quadform3x2(&basis1);
             let lhs10 = ii1 * r1_mat_b1 + ii2 * r2_mat_b1;
             let lhs11 = (ii1 + ii2).into_matrix();
             lhs.fixed_slice_mut::<U2, U2>(0, 0)
@@ -169,7 +170,9 @@ impl WPrismaticVelocityConstraint {
         {
             let b1r1 = basis1.dot(&r1_mat);
             let b2r2 = basis1.dot(&r2_mat);
-            let m11 = im1 + im2 + b1r1 * ii1 * b1r1 + b2r2 * ii2 * b2r2;
+            let m11 = basis1.component_mul(&basis1).dot(&(im1 + im2))
+                + b1r1 * ii1 * b1r1
+                + b2r2 * ii2 * b2r2;
             let m12 = basis1.dot(&r1_mat) * ii1 + basis1.dot(&r2_mat) * ii2;
             let m22 = ii1 + ii2;
             lhs = SdpMatrix2::new(m11, m12, m22);
@@ -271,8 +274,9 @@ impl WPrismaticVelocityConstraint {
                         .simd_min(limits_impulse_limits.1);
 
                 limits_inv_lhs = SimdReal::splat(1.0)
-                    / (im1
-                        + im2
+                    / (limits_forcedir2
+                        .component_mul(&limits_forcedir2)
+                        .dot(&(im1 + im2))
                         + gcross1.gdot(ii1.transform_vector(gcross1))
                         + gcross2.gdot(ii2.transform_vector(gcross2)));
             }
@@ -325,12 +329,12 @@ impl WPrismaticVelocityConstraint {
         #[cfg(feature = "dim3")]
         let ang_impulse = self.impulse.fixed_rows::<U3>(2).into_owned();
 
-        mj_lambda1.linear += lin_impulse * self.im1;
+        mj_lambda1.linear += lin_impulse.component_mul(&self.im1);
         mj_lambda1.angular += self
             .ii1_sqrt
             .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
 
-        mj_lambda2.linear -= lin_impulse * self.im2;
+        mj_lambda2.linear -= lin_impulse.component_mul(&self.im2);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
@@ -340,11 +344,11 @@ impl WPrismaticVelocityConstraint {
             let limit_impulse1 = -self.limits_forcedir2 * self.limits_impulse;
             let limit_impulse2 = self.limits_forcedir2 * self.limits_impulse;
 
-            mj_lambda1.linear += limit_impulse1 * self.im1;
+            mj_lambda1.linear += limit_impulse1.component_mul(&self.im1);
             mj_lambda1.angular += self
                 .ii1_sqrt
                 .transform_vector(self.r1.gcross(limit_impulse1));
-            mj_lambda2.linear += limit_impulse2 * self.im2;
+            mj_lambda2.linear += limit_impulse2.component_mul(&self.im2);
             mj_lambda2.angular += self
                 .ii2_sqrt
                 .transform_vector(self.r2.gcross(limit_impulse2));
@@ -384,12 +388,12 @@ impl WPrismaticVelocityConstraint {
         #[cfg(feature = "dim3")]
         let ang_impulse = impulse.fixed_rows::<U3>(2).into_owned();
 
-        mj_lambda1.linear += lin_impulse * self.im1;
+        mj_lambda1.linear += lin_impulse.component_mul(&self.im1);
         mj_lambda1.angular += self
             .ii1_sqrt
             .transform_vector(ang_impulse + self.r1.gcross(lin_impulse));
 
-        mj_lambda2.linear -= lin_impulse * self.im2;
+        mj_lambda2.linear -= lin_impulse.component_mul(&self.im2);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
@@ -419,9 +423,9 @@ impl WPrismaticVelocityConstraint {
             let lin_impulse1 = limits_forcedir1 * dimpulse;
             let lin_impulse2 = limits_forcedir2 * dimpulse;
 
-            mj_lambda1.linear += lin_impulse1 * self.im1;
+            mj_lambda1.linear += lin_impulse1.component_mul(&self.im1);
             mj_lambda1.angular += self.ii1_sqrt.transform_vector(self.r1.gcross(lin_impulse1));
-            mj_lambda2.linear += lin_impulse2 * self.im2;
+            mj_lambda2.linear += lin_impulse2.component_mul(&self.im2);
             mj_lambda2.angular += self.ii2_sqrt.transform_vector(self.r2.gcross(lin_impulse2));
         }
     }
@@ -502,7 +506,7 @@ pub(crate) struct WPrismaticVelocityGroundConstraint {
     #[cfg(feature = "dim3")]
     basis1: Matrix3x2<SimdReal>,
 
-    im2: SimdReal,
+    im2: Vector<SimdReal>,
     ii2_sqrt: AngularInertia<SimdReal>,
 }
 
@@ -524,7 +528,7 @@ impl WPrismaticVelocityGroundConstraint {
         let linvel2 = Vector::from(array![|ii| rbs2[ii].linvel; SIMD_WIDTH]);
         let angvel2 = AngVector::<SimdReal>::from(array![|ii| rbs2[ii].angvel; SIMD_WIDTH]);
         let world_com2 = Point::from(array![|ii| rbs2[ii].world_com; SIMD_WIDTH]);
-        let im2 = SimdReal::from(array![|ii| rbs2[ii].effective_inv_mass; SIMD_WIDTH]);
+        let im2 = Vector::from(array![|ii| rbs2[ii].effective_inv_mass_along_axes; SIMD_WIDTH]);
         let ii2_sqrt = AngularInertia::<SimdReal>::from(
             array![|ii| rbs2[ii].effective_world_inv_inertia_sqrt; SIMD_WIDTH],
         );
@@ -583,7 +587,8 @@ impl WPrismaticVelocityGroundConstraint {
             let r2_mat_b1 = r2_mat * basis1;
 
             lhs = Matrix5::zeros();
-            let lhs00 = ii2.quadform3x2(&r2_mat_b1).add_diagonal(im2);
+            let lhs00 =
+                ii2.quadform3x2(&r2_mat_b1) + crate::utils::sdp_diagonal(&im2).quadform3x2(&basis1);
             let lhs10 = ii2 * r2_mat_b1;
             let lhs11 = ii2.into_matrix();
             lhs.fixed_slice_mut::<U2, U2>(0, 0)
@@ -595,7 +600,7 @@ impl WPrismaticVelocityGroundConstraint {
         #[cfg(feature = "dim2")]
         {
             let b2r2 = basis1.dot(&r2_mat);
-            let m11 = im2 + b2r2 * ii2 * b2r2;
+            let m11 = basis1.component_mul(&basis1).dot(&im2) + b2r2 * ii2 * b2r2;
             let m12 = basis1.dot(&r2_mat) * ii2;
             let m22 = ii2;
             lhs = SdpMatrix2::new(m11, m12, m22);
@@ -732,12 +737,12 @@ impl WPrismaticVelocityGroundConstraint {
         #[cfg(feature = "dim3")]
         let ang_impulse = self.impulse.fixed_rows::<U3>(2).into_owned();
 
-        mj_lambda2.linear -= lin_impulse * self.im2;
+        mj_lambda2.linear -= lin_impulse.component_mul(&self.im2);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
 
-        mj_lambda2.linear += self.limits_forcedir2 * (self.im2 * self.limits_impulse);
+        mj_lambda2.linear += self.limits_forcedir2.component_mul(&self.im2) * self.limits_impulse;
 
         for ii in 0..SIMD_WIDTH {
             mj_lambdas[self.mj_lambda2[ii] as usize].linear = mj_lambda2.linear.extract(ii);
@@ -763,7 +768,7 @@ impl WPrismaticVelocityGroundConstraint {
         #[cfg(feature = "dim3")]
         let ang_impulse = impulse.fixed_rows::<U3>(2).into_owned();
 
-        mj_lambda2.linear -= lin_impulse * self.im2;
+        mj_lambda2.linear -= lin_impulse.component_mul(&self.im2);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
@@ -779,13 +784,17 @@ impl WPrismaticVelocityGroundConstraint {
                 .limits_forcedir2
                 .dot(&(mj_lambda2.linear + ang_vel2.gcross(self.r2)))
                 + self.limits_rhs;
-            let new_impulse = (self.limits_impulse - lin_dvel / self.im2)
+            let limits_im2 = self
+                .limits_forcedir2
+                .component_mul(&self.limits_forcedir2)
+                .dot(&self.im2);
+            let new_impulse = (self.limits_impulse - lin_dvel / limits_im2)
                 .simd_max(self.limits_impulse_limits.0)
                 .simd_min(self.limits_impulse_limits.1);
             let dimpulse = new_impulse - self.limits_impulse;
             self.limits_impulse = new_impulse;
 
-            mj_lambda2.linear += self.limits_forcedir2 * (self.im2 * dimpulse);
+            mj_lambda2.linear += self.limits_forcedir2.component_mul(&self.im2) * dimpulse;
         }
     }
 
@@ -11,8 +11,8 @@ pub(crate) struct RevolutePositionConstraint {
     local_com1: Point<Real>,
     local_com2: Point<Real>,
 
-    im1: Real,
-    im2: Real,
+    im1: Vector<Real>,
+    im2: Vector<Real>,
 
     ii1: AngularInertia<Real>,
     ii2: AngularInertia<Real>,
@@ -32,8 +32,8 @@ impl RevolutePositionConstraint {
     pub fn from_params(rb1: &RigidBody, rb2: &RigidBody, cparams: &RevoluteJoint) -> Self {
         let ii1 = rb1.effective_world_inv_inertia_sqrt.squared();
         let ii2 = rb2.effective_world_inv_inertia_sqrt.squared();
-        let im1 = rb1.effective_inv_mass;
-        let im2 = rb2.effective_inv_mass;
+        let im1 = rb1.effective_inv_mass_along_axes;
+        let im2 = rb2.effective_inv_mass_along_axes;
         let ang_inv_lhs = (ii1 + ii2).inverse();
 
         Self {
@@ -70,14 +70,10 @@ impl RevolutePositionConstraint {
             let r2 = anchor2 - position2 * self.local_com2;
 
             // TODO: don't do the "to_matrix".
-            let lhs = (self
-                .ii2
-                .quadform(&r2.gcross_matrix())
-                .add_diagonal(self.im2)
-                + self
-                    .ii1
-                    .quadform(&r1.gcross_matrix())
-                    .add_diagonal(self.im1))
+            let lhs = (self.ii2.quadform(&r2.gcross_matrix())
+                + crate::utils::sdp_diagonal(&self.im2)
+                + self.ii1.quadform(&r1.gcross_matrix())
+                + crate::utils::sdp_diagonal(&self.im1))
             .into_matrix();
             let inv_lhs = lhs.try_inverse().unwrap();
 
@@ -89,8 +85,8 @@ impl RevolutePositionConstraint {
             let rot2 = self.ii2 * r2.gcross(lin_impulse);
             position1.rotation = Rotation::new(rot1) * position1.rotation;
             position2.rotation = Rotation::new(-rot2) * position2.rotation;
-            position1.translation.vector += self.im1 * lin_impulse;
-            position2.translation.vector -= self.im2 * lin_impulse;
+            position1.translation.vector += self.im1.component_mul(&lin_impulse);
+            position2.translation.vector -= self.im2.component_mul(&lin_impulse);
         }
 
         /*
@@ -119,7 +115,7 @@ impl RevolutePositionConstraint {
 pub(crate) struct RevolutePositionGroundConstraint {
     position2: usize,
     local_com2: Point<Real>,
-    im2: Real,
+    im2: Vector<Real>,
     ii2: AngularInertia<Real>,
     anchor1: Point<Real>,
     local_anchor2: Point<Real>,
@@ -169,7 +165,7 @@ impl RevolutePositionGroundConstraint {
         Self {
             anchor1,
             local_anchor2,
-            im2: rb2.effective_inv_mass,
+            im2: rb2.effective_inv_mass_along_axes,
             ii2: rb2.effective_world_inv_inertia_sqrt.squared(),
             local_com2: rb2.mass_properties.local_com,
             axis1,
@@ -191,11 +187,8 @@ impl RevolutePositionGroundConstraint {
 
             let r2 = anchor2 - position2 * self.local_com2;
             // TODO: don't the the "to_matrix".
-            let lhs = self
-                .ii2
-                .quadform(&r2.gcross_matrix())
-                .add_diagonal(self.im2)
-                .into_matrix();
+            let lhs = self.ii2.quadform(&r2.gcross_matrix())
+                + crate::utils::sdp_diagonal(&self.im2).into_matrix();
             let inv_lhs = lhs.try_inverse().unwrap();
 
             let delta_tra = anchor2 - self.anchor1;
@@ -204,7 +197,7 @@ impl RevolutePositionGroundConstraint {
 
             let rot2 = self.ii2 * r2.gcross(lin_impulse);
             position2.rotation = Rotation::new(-rot2) * position2.rotation;
-            position2.translation.vector -= self.im2 * lin_impulse;
+            position2.translation.vector -= self.im2.component_mul(&lin_impulse);
         }
 
         /*
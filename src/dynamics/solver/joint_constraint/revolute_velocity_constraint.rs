@@ -32,8 +32,8 @@ pub(crate) struct RevoluteVelocityConstraint {
     basis1: Matrix3x2<Real>,
     basis2: Matrix3x2<Real>,
 
-    im1: Real,
-    im2: Real,
+    im1: Vector<Real>,
+    im2: Vector<Real>,
 
     ii1_sqrt: AngularInertia<Real>,
     ii2_sqrt: AngularInertia<Real>,
@@ -62,8 +62,8 @@ impl RevoluteVelocityConstraint {
         let basis_projection2 = basis2 * basis2.transpose();
         let basis2 = basis_projection2 * basis1;
 
-        let im1 = rb1.effective_inv_mass;
-        let im2 = rb2.effective_inv_mass;
+        let im1 = rb1.effective_inv_mass_along_axes;
+        let im2 = rb2.effective_inv_mass_along_axes;
 
         let ii1 = rb1.effective_world_inv_inertia_sqrt.squared();
         let r1 = anchor1 - rb1.world_com;
@@ -75,8 +75,10 @@ impl RevoluteVelocityConstraint {
 
         let mut lhs = Matrix5::zeros();
 
-        let lhs00 =
-            ii2.quadform(&r2_mat).add_diagonal(im2) + ii1.quadform(&r1_mat).add_diagonal(im1);
+        let lhs00 = ii2.quadform(&r2_mat)
+            + crate::utils::sdp_diagonal(&im2)
+            + ii1.quadform(&r1_mat)
+            + crate::utils::sdp_diagonal(&im1);
         let lhs10 = basis2.tr_mul(&(ii2 * r2_mat)) + basis1.tr_mul(&(ii1 * r1_mat));
         let lhs11 = (ii1.quadform3x2(&basis1) + ii2.quadform3x2(&basis2)).into_matrix();
 
@@ -203,12 +205,12 @@ impl RevoluteVelocityConstraint {
         let ang_impulse1 = self.basis1 * self.impulse.fixed_rows::<U2>(3).into_owned();
         let ang_impulse2 = self.basis2 * self.impulse.fixed_rows::<U2>(3).into_owned();
 
-        mj_lambda1.linear += self.im1 * lin_impulse1;
+        mj_lambda1.linear += self.im1.component_mul(&lin_impulse1);
         mj_lambda1.angular += self
             .ii1_sqrt
             .transform_vector(ang_impulse1 + self.r1.gcross(lin_impulse1));
 
-        mj_lambda2.linear -= self.im2 * lin_impulse2;
+        mj_lambda2.linear -= self.im2.component_mul(&lin_impulse2);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse2 + self.r2.gcross(lin_impulse2));
@@ -245,12 +247,12 @@ impl RevoluteVelocityConstraint {
         let ang_impulse1 = self.basis1 * impulse.fixed_rows::<U2>(3).into_owned();
         let ang_impulse2 = self.basis2 * impulse.fixed_rows::<U2>(3).into_owned();
 
-        mj_lambda1.linear += self.im1 * lin_impulse1;
+        mj_lambda1.linear += self.im1.component_mul(&lin_impulse1);
         mj_lambda1.angular += self
             .ii1_sqrt
             .transform_vector(ang_impulse1 + self.r1.gcross(lin_impulse1));
 
-        mj_lambda2.linear -= self.im2 * lin_impulse2;
+        mj_lambda2.linear -= self.im2.component_mul(&lin_impulse2);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse2 + self.r2.gcross(lin_impulse2));
@@ -321,7 +323,7 @@ pub(crate) struct RevoluteVelocityGroundConstraint {
 
     basis2: Matrix3x2<Real>,
 
-    im2: Real,
+    im2: Vector<Real>,
 
     ii2_sqrt: AngularInertia<Real>,
 }
@@ -372,14 +374,14 @@ impl RevoluteVelocityGroundConstraint {
 
         let basis_projection2 = basis2 * basis2.transpose();
         let basis2 = basis_projection2 * basis1;
-        let im2 = rb2.effective_inv_mass;
+        let im2 = rb2.effective_inv_mass_along_axes;
         let ii2 = rb2.effective_world_inv_inertia_sqrt.squared();
         let r1 = anchor1 - rb1.world_com;
         let r2 = anchor2 - rb2.world_com;
         let r2_mat = r2.gcross_matrix();
 
         let mut lhs = Matrix5::zeros();
-        let lhs00 = ii2.quadform(&r2_mat).add_diagonal(im2);
+        let lhs00 = ii2.quadform(&r2_mat) + crate::utils::sdp_diagonal(&im2);
         let lhs10 = basis2.tr_mul(&(ii2 * r2_mat));
         let lhs11 = ii2.quadform3x2(&basis2).into_matrix();
 
@@ -485,7 +487,7 @@ impl RevoluteVelocityGroundConstraint {
         let lin_impulse = self.impulse.fixed_rows::<U3>(0).into_owned();
         let ang_impulse = self.basis2 * self.impulse.fixed_rows::<U2>(3).into_owned();
 
-        mj_lambda2.linear -= self.im2 * lin_impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&lin_impulse);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
@@ -514,7 +516,7 @@ impl RevoluteVelocityGroundConstraint {
         let lin_impulse = impulse.fixed_rows::<U3>(0).into_owned();
         let ang_impulse = self.basis2 * impulse.fixed_rows::<U2>(3).into_owned();
 
-        mj_lambda2.linear -= self.im2 * lin_impulse;
+        mj_lambda2.linear -= self.im2.component_mul(&lin_impulse);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
@@ -28,8 +28,8 @@ pub(crate) struct WRevoluteVelocityConstraint {
     basis1: Matrix3x2<SimdReal>,
     basis2: Matrix3x2<SimdReal>,
 
-    im1: SimdReal,
-    im2: SimdReal,
+    im1: Vector<SimdReal>,
+    im2: Vector<SimdReal>,
 
     ii1_sqrt: AngularInertia<SimdReal>,
     ii2_sqrt: AngularInertia<SimdReal>,
@@ -47,7 +47,7 @@ impl WRevoluteVelocityConstraint {
         let linvel1 = Vector::from(array![|ii| rbs1[ii].linvel; SIMD_WIDTH]);
         let angvel1 = AngVector::<SimdReal>::from(array![|ii| rbs1[ii].angvel; SIMD_WIDTH]);
         let world_com1 = Point::from(array![|ii| rbs1[ii].world_com; SIMD_WIDTH]);
-        let im1 = SimdReal::from(array![|ii| rbs1[ii].effective_inv_mass; SIMD_WIDTH]);
+        let im1 = Vector::from(array![|ii| rbs1[ii].effective_inv_mass_along_axes; SIMD_WIDTH]);
         let ii1_sqrt = AngularInertia::<SimdReal>::from(
             array![|ii| rbs1[ii].effective_world_inv_inertia_sqrt; SIMD_WIDTH],
         );
@@ -57,7 +57,7 @@ impl WRevoluteVelocityConstraint {
         let linvel2 = Vector::from(array![|ii| rbs2[ii].linvel; SIMD_WIDTH]);
         let angvel2 = AngVector::<SimdReal>::from(array![|ii| rbs2[ii].angvel; SIMD_WIDTH]);
         let world_com2 = Point::from(array![|ii| rbs2[ii].world_com; SIMD_WIDTH]);
-        let im2 = SimdReal::from(array![|ii| rbs2[ii].effective_inv_mass; SIMD_WIDTH]);
+        let im2 = Vector::from(array![|ii| rbs2[ii].effective_inv_mass_along_axes; SIMD_WIDTH]);
         let ii2_sqrt = AngularInertia::<SimdReal>::from(
             array![|ii| rbs2[ii].effective_world_inv_inertia_sqrt; SIMD_WIDTH],
         );
@@ -93,8 +93,10 @@ impl WRevoluteVelocityConstraint {
         let r2_mat = r2.gcross_matrix();
 
         let mut lhs = Matrix5::zeros();
-        let lhs00 =
-            ii2.quadform(&r2_mat).add_diagonal(im2) + ii1.quadform(&r1_mat).add_diagonal(im1);
+        let lhs00 = ii2.quadform(&r2_mat)
+            + crate::utils::sdp_diagonal(&im2)
+            + ii1.quadform(&r1_mat)
+            + crate::utils::sdp_diagonal(&im1);
         let lhs10 = basis1.tr_mul(&(ii2 * r2_mat)) + basis2.tr_mul(&(ii1 * r1_mat));
         let lhs11 = (ii1.quadform3x2(&basis1) + ii2.quadform3x2(&basis2)).into_matrix();
 
@@ -203,12 +205,12 @@ impl WRevoluteVelocityConstraint {
         let ang_impulse1 = self.basis1 * self.impulse.fixed_rows::<U2>(3).into_owned();
         let ang_impulse2 = self.basis2 * self.impulse.fixed_rows::<U2>(3).into_owned();
 
-        mj_lambda1.linear += lin_impulse1 * self.im1;
+        mj_lambda1.linear += lin_impulse1.component_mul(&self.im1);
         mj_lambda1.angular += self
             .ii1_sqrt
             .transform_vector(ang_impulse1 + self.r1.gcross(lin_impulse1));
 
-        mj_lambda2.linear -= lin_impulse2 * self.im2;
+        mj_lambda2.linear -= lin_impulse2.component_mul(&self.im2);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse2 + self.r2.gcross(lin_impulse2));
@@ -256,12 +258,12 @@ impl WRevoluteVelocityConstraint {
         let ang_impulse1 = self.basis1 * impulse.fixed_rows::<U2>(3).into_owned();
         let ang_impulse2 = self.basis2 * impulse.fixed_rows::<U2>(3).into_owned();
 
-        mj_lambda1.linear += lin_impulse1 * self.im1;
+        mj_lambda1.linear += lin_impulse1.component_mul(&self.im1);
         mj_lambda1.angular += self
             .ii1_sqrt
             .transform_vector(ang_impulse1 + self.r1.gcross(lin_impulse1));
 
-        mj_lambda2.linear -= lin_impulse2 * self.im2;
+        mj_lambda2.linear -= lin_impulse2.component_mul(&self.im2);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse2 + self.r2.gcross(lin_impulse2));
@@ -305,7 +307,7 @@ pub(crate) struct WRevoluteVelocityGroundConstraint {
 
     basis2: Matrix3x2<SimdReal>,
 
-    im2: SimdReal,
+    im2: Vector<SimdReal>,
 
     ii2_sqrt: AngularInertia<SimdReal>,
 }
@@ -328,7 +330,7 @@ impl WRevoluteVelocityGroundConstraint {
         let linvel2 = Vector::from(array![|ii| rbs2[ii].linvel; SIMD_WIDTH]);
         let angvel2 = AngVector::<SimdReal>::from(array![|ii| rbs2[ii].angvel; SIMD_WIDTH]);
         let world_com2 = Point::from(array![|ii| rbs2[ii].world_com; SIMD_WIDTH]);
-        let im2 = SimdReal::from(array![|ii| rbs2[ii].effective_inv_mass; SIMD_WIDTH]);
+        let im2 = Vector::from(array![|ii| rbs2[ii].effective_inv_mass_along_axes; SIMD_WIDTH]);
         let ii2_sqrt = AngularInertia::<SimdReal>::from(
             array![|ii| rbs2[ii].effective_world_inv_inertia_sqrt; SIMD_WIDTH],
         );
@@ -373,7 +375,7 @@ impl WRevoluteVelocityGroundConstraint {
         let r2_mat = r2.gcross_matrix();
 
         let mut lhs = Matrix5::zeros();
-        let lhs00 = ii2.quadform(&r2_mat).add_diagonal(im2);
+        let lhs00 = ii2.quadform(&r2_mat) + crate::utils::sdp_diagonal(&im2);
         let lhs10 = basis2.tr_mul(&(ii2 * r2_mat));
         let lhs11 = ii2.quadform3x2(&basis2).into_matrix();
 
@@ -445,7 +447,7 @@ impl WRevoluteVelocityGroundConstraint {
         let lin_impulse = self.impulse.fixed_rows::<U3>(0).into_owned();
         let ang_impulse = self.basis2 * self.impulse.fixed_rows::<U2>(3).into_owned();
 
-        mj_lambda2.linear -= lin_impulse * self.im2;
+        mj_lambda2.linear -= lin_impulse.component_mul(&self.im2);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
@@ -476,7 +478,7 @@ impl WRevoluteVelocityGroundConstraint {
         let lin_impulse = impulse.fixed_rows::<U3>(0).into_owned();
         let ang_impulse = self.basis2 * impulse.fixed_rows::<U2>(3).into_owned();
 
-        mj_lambda2.linear -= lin_impulse * self.im2;
+        mj_lambda2.linear -= lin_impulse.component_mul(&self.im2);
         mj_lambda2.angular -= self
             .ii2_sqrt
             .transform_vector(ang_impulse + self.r2.gcross(lin_impulse));
@@ -307,10 +307,13 @@ impl ParallelIslandSolver {
                             let rb = &mut bodies[handle.0];
                             let dvel = &mut mj_lambdas[rb.active_set_offset];
 
+                            dvel.linear += rb.force.component_mul(&rb.effective_inv_mass_along_axes) * params.dt;
+                            rb.force = na::zero();
+
                             // NOTE: `dvel.angular` is actually storing angular velocity delta multiplied
                             //       by the square root of the inertia tensor:
                             dvel.angular += rb.effective_world_inv_inertia_sqrt * rb.torque * params.dt;
-                            dvel.linear += rb.force * (rb.effective_inv_mass * params.dt);
+                            rb.torque = na::zero();
                         }
                     }
 
@@ -354,6 +357,7 @@ impl ParallelIslandSolver {
                         rb.linvel += dvel.linear;
                         rb.angvel += rb.effective_world_inv_inertia_sqrt.transform_vector(dvel.angular);
                         rb.apply_damping(params.dt);
+                        rb.snap_velocities(params);
                         rb.integrate_next_position(params.dt);
                     }
                 }
@@ -42,8 +42,8 @@ pub(crate) struct PositionConstraint {
     pub dists: [Real; MAX_MANIFOLD_POINTS],
     pub local_n1: Vector<Real>,
     pub num_contacts: u8,
-    pub im1: Real,
-    pub im2: Real,
+    pub im1: Vector<Real>,
+    pub im2: Vector<Real>,
     pub ii1: AngularInertia<Real>,
     pub ii2: AngularInertia<Real>,
     pub erp: Real,
@@ -88,8 +88,8 @@ impl PositionConstraint {
                 local_p2,
                 local_n1: rb1.position.inverse_transform_vector(&manifold.data.normal),
                 dists,
-                im1: rb1.effective_inv_mass,
-                im2: rb2.effective_inv_mass,
+                im1: rb1.effective_inv_mass_along_axes,
+                im2: rb2.effective_inv_mass_along_axes,
                 ii1: rb1.effective_world_inv_inertia_sqrt.squared(),
                 ii2: rb2.effective_world_inv_inertia_sqrt.squared(),
                 num_contacts: manifold_points.len() as u8,
@@ -133,13 +133,14 @@ impl PositionConstraint {
                 let ii_gcross2 = self.ii2.transform_vector(gcross2);
 
                 // Compute impulse.
-                let inv_r =
-                    self.im1 + self.im2 + gcross1.gdot(ii_gcross1) + gcross2.gdot(ii_gcross2);
+                let inv_r = n1.component_mul(&n1).dot(&(self.im1 + self.im2))
+                    + gcross1.gdot(ii_gcross1)
+                    + gcross2.gdot(ii_gcross2);
                 let impulse = err / inv_r;
 
                 // Apply impulse.
-                let tra1 = Translation::from(n1 * (impulse * self.im1));
-                let tra2 = Translation::from(n1 * (-impulse * self.im2));
+                let tra1 = Translation::from(n1.component_mul(&self.im1) * impulse);
+                let tra2 = Translation::from(n1.component_mul(&self.im2) * -impulse);
                 let rot1 = Rotation::new(ii_gcross1 * impulse);
                 let rot2 = Rotation::new(ii_gcross2 * impulse);
 
@@ -18,8 +18,8 @@ pub(crate) struct WPositionConstraint {
     pub local_p2: [Point<SimdReal>; MAX_MANIFOLD_POINTS],
     pub dists: [SimdReal; MAX_MANIFOLD_POINTS],
     pub local_n1: Vector<SimdReal>,
-    pub im1: SimdReal,
-    pub im2: SimdReal,
+    pub im1: Vector<SimdReal>,
+    pub im2: Vector<SimdReal>,
     pub ii1: AngularInertia<SimdReal>,
     pub ii2: AngularInertia<SimdReal>,
     pub erp: SimdReal,
@@ -38,11 +38,11 @@ impl WPositionConstraint {
         let rbs1 = array![|ii| bodies.get(manifolds[ii].data.body_pair.body1).unwrap(); SIMD_WIDTH];
         let rbs2 = array![|ii| bodies.get(manifolds[ii].data.body_pair.body2).unwrap(); SIMD_WIDTH];
 
-        let im1 = SimdReal::from(array![|ii| rbs1[ii].effective_inv_mass; SIMD_WIDTH]);
+        let im1 = Vector::from(array![|ii| rbs1[ii].effective_inv_mass_along_axes; SIMD_WIDTH]);
         let sqrt_ii1: AngularInertia<SimdReal> = AngularInertia::from(
             array![|ii| rbs1[ii].effective_world_inv_inertia_sqrt; SIMD_WIDTH],
         );
-        let im2 = SimdReal::from(array![|ii| rbs2[ii].effective_inv_mass; SIMD_WIDTH]);
+        let im2 = Vector::from(array![|ii| rbs2[ii].effective_inv_mass_along_axes; SIMD_WIDTH]);
         let sqrt_ii2: AngularInertia<SimdReal> = AngularInertia::from(
             array![|ii| rbs2[ii].effective_world_inv_inertia_sqrt; SIMD_WIDTH],
         );
@@ -126,14 +126,17 @@ impl WPositionConstraint {
                 let ii_gcross2 = self.ii2.transform_vector(gcross2);
 
                 // Compute impulse.
-                let inv_r =
-                    self.im1 + self.im2 + gcross1.gdot(ii_gcross1) + gcross2.gdot(ii_gcross2);
+                let inv_r = n1.component_mul(&n1).dot(&(self.im1 + self.im2))
+                    + gcross1.gdot(ii_gcross1)
+                    + gcross2.gdot(ii_gcross2);
                 let impulse = err / inv_r;
 
                 // Apply impulse.
-                pos1.translation = Translation::from(n1 * (impulse * self.im1)) * pos1.translation;
+                pos1.translation =
+                    Translation::from(n1.component_mul(&self.im1) * impulse) * pos1.translation;
                 pos1.rotation = Rotation::new(ii_gcross1 * impulse) * pos1.rotation;
-                pos2.translation = Translation::from(n1 * (-impulse * self.im2)) * pos2.translation;
+                pos2.translation =
+                    Translation::from(n1.component_mul(&self.im2) * -impulse) * pos2.translation;
                 pos2.rotation = Rotation::new(ii_gcross2 * impulse) * pos2.rotation;
             }
         }
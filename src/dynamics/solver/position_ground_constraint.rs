@@ -14,7 +14,7 @@ pub(crate) struct PositionGroundConstraint {
     pub dists: [Real; MAX_MANIFOLD_POINTS],
     pub n1: Vector<Real>,
     pub num_contacts: u8,
-    pub im2: Real,
+    pub im2: Vector<Real>,
     pub ii2: AngularInertia<Real>,
     pub erp: Real,
     pub max_linear_correction: Real,
@@ -63,7 +63,7 @@ impl PositionGroundConstraint {
                 local_p2,
                 n1,
                 dists,
-                im2: rb2.effective_inv_mass,
+                im2: rb2.effective_inv_mass_along_axes,
                 ii2: rb2.effective_world_inv_inertia_sqrt.squared(),
                 num_contacts: manifold_contacts.len() as u8,
                 erp: params.erp,
@@ -101,11 +101,11 @@ impl PositionGroundConstraint {
                 let ii_gcross2 = self.ii2.transform_vector(gcross2);
 
                 // Compute impulse.
-                let inv_r = self.im2 + gcross2.gdot(ii_gcross2);
+                let inv_r = n1.component_mul(&n1).dot(&self.im2) + gcross2.gdot(ii_gcross2);
                 let impulse = err / inv_r;
 
                 // Apply impulse.
-                let tra2 = Translation::from(n1 * (-impulse * self.im2));
+                let tra2 = Translation::from(n1.component_mul(&self.im2) * -impulse);
                 let rot2 = Rotation::new(ii_gcross2 * impulse);
                 pos2 = Isometry::from_parts(tra2 * pos2.translation, rot2 * pos2.rotation);
             }
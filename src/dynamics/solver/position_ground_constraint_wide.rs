@@ -17,7 +17,7 @@ pub(crate) struct WPositionGroundConstraint {
     pub local_p2: [Point<SimdReal>; MAX_MANIFOLD_POINTS],
     pub dists: [SimdReal; MAX_MANIFOLD_POINTS],
     pub n1: Vector<SimdReal>,
-    pub im2: SimdReal,
+    pub im2: Vector<SimdReal>,
     pub ii2: AngularInertia<SimdReal>,
     pub erp: SimdReal,
     pub max_linear_correction: SimdReal,
@@ -45,7 +45,7 @@ impl WPositionGroundConstraint {
             }
         }
 
-        let im2 = SimdReal::from(array![|ii| rbs2[ii].effective_inv_mass; SIMD_WIDTH]);
+        let im2 = Vector::from(array![|ii| rbs2[ii].effective_inv_mass_along_axes; SIMD_WIDTH]);
         let sqrt_ii2: AngularInertia<SimdReal> = AngularInertia::from(
             array![|ii| rbs2[ii].effective_world_inv_inertia_sqrt; SIMD_WIDTH],
         );
@@ -117,11 +117,12 @@ impl WPositionGroundConstraint {
                 let ii_gcross2 = self.ii2.transform_vector(gcross2);
 
                 // Compute impulse.
-                let inv_r = self.im2 + gcross2.gdot(ii_gcross2);
+                let inv_r = n1.component_mul(&n1).dot(&self.im2) + gcross2.gdot(ii_gcross2);
                 let impulse = err / inv_r;
 
                 // Apply impulse.
-                pos2.translation = Translation::from(n1 * (-impulse * self.im2)) * pos2.translation;
+                pos2.translation =
+                    Translation::from(n1.component_mul(&self.im2) * -impulse) * pos2.translation;
                 pos2.rotation = Rotation::new(ii_gcross2 * impulse) * pos2.rotation;
             }
         }
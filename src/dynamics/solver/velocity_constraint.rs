@@ -84,8 +84,8 @@ pub(crate) struct VelocityConstraint {
     pub tangent1: Vector<Real>, // One of the friction force directions.
     #[cfg(feature = "dim3")]
     pub tangent_rot1: na::UnitComplex<Real>, // Orientation of the tangent basis wrt. the reference basis.
-    pub im1: Real,
-    pub im2: Real,
+    pub im1: Vector<Real>,
+    pub im2: Vector<Real>,
     pub limit: Real,
     pub mj_lambda1: usize,
     pub mj_lambda2: usize,
@@ -112,7 +112,7 @@ impl VelocityConstraint {
     ) {
         assert_eq!(manifold.data.relative_dominance, 0);
 
-        let inv_dt = params.inv_dt();
+        let inv_dt = params.step_inv_dt();
         let velocity_based_erp_inv_dt = params.velocity_based_erp_inv_dt();
 
         let rb1 = &bodies[manifold.data.body_pair.body1];
@@ -121,6 +121,7 @@ impl VelocityConstraint {
         let mj_lambda2 = rb2.active_set_offset;
         let force_dir1 = -manifold.data.normal;
         let warmstart_coeff = manifold.data.warmstart_multiplier * params.warmstart_coeff;
+        let im_sum = rb1.effective_inv_mass_along_axes + rb2.effective_inv_mass_along_axes;
 
         #[cfg(feature = "dim2")]
         let tangents1 = force_dir1.orthonormal_basis();
@@ -142,8 +143,8 @@ impl VelocityConstraint {
                 #[cfg(feature = "dim3")]
                 tangent_rot1,
                 elements: [VelocityConstraintElement::zero(); MAX_MANIFOLD_POINTS],
-                im1: rb1.effective_inv_mass,
-                im2: rb2.effective_inv_mass,
+                im1: rb1.effective_inv_mass_along_axes,
+                im2: rb2.effective_inv_mass_along_axes,
                 limit: 0.0,
                 mj_lambda1,
                 mj_lambda2,
@@ -190,8 +191,8 @@ impl VelocityConstraint {
                     constraint.tangent1 = tangents1[0];
                     constraint.tangent_rot1 = tangent_rot1;
                 }
-                constraint.im1 = rb1.effective_inv_mass;
-                constraint.im2 = rb2.effective_inv_mass;
+                constraint.im1 = rb1.effective_inv_mass_along_axes;
+                constraint.im2 = rb2.effective_inv_mass_along_axes;
                 constraint.limit = 0.0;
                 constraint.mj_lambda1 = mj_lambda1;
                 constraint.mj_lambda2 = mj_lambda2;
@@ -223,8 +224,7 @@ impl VelocityConstraint {
                         .transform_vector(dp2.gcross(-force_dir1));
 
                     let r = 1.0
-                        / (rb1.effective_inv_mass
-                            + rb2.effective_inv_mass
+                        / (force_dir1.component_mul(&force_dir1).dot(&im_sum)
                             + gcross1.gdot(gcross1)
                             + gcross2.gdot(gcross2));
 
@@ -268,8 +268,7 @@ impl VelocityConstraint {
                             .effective_world_inv_inertia_sqrt
                             .transform_vector(dp2.gcross(-tangents1[j]));
                         let r = 1.0
-                            / (rb1.effective_inv_mass
-                                + rb2.effective_inv_mass
+                            / (tangents1[j].component_mul(&tangents1[j]).dot(&im_sum)
                                 + gcross1.gdot(gcross1)
                                 + gcross2.gdot(gcross2));
                         let rhs =
@@ -302,8 +301,8 @@ impl VelocityConstraint {
             &self.dir1,
             #[cfg(feature = "dim3")]
             &self.tangent1,
-            self.im1,
-            self.im2,
+            &self.im1,
+            &self.im2,
             &mut mj_lambda1,
             &mut mj_lambda2,
         );
@@ -321,8 +320,8 @@ impl VelocityConstraint {
             &self.dir1,
             #[cfg(feature = "dim3")]
             &self.tangent1,
-            self.im1,
-            self.im2,
+            &self.im1,
+            &self.im2,
             self.limit,
             &mut mj_lambda1,
             &mut mj_lambda2,
@@ -34,8 +34,8 @@ impl<N: SimdRealField> VelocityConstraintTangentPart<N> {
     pub fn warmstart(
         &self,
         tangents1: [&Vector<N>; DIM - 1],
-        im1: N,
-        im2: N,
+        im1: &Vector<N>,
+        im2: &Vector<N>,
         mj_lambda1: &mut DeltaVel<N>,
         mj_lambda2: &mut DeltaVel<N>,
     ) where
@@ -43,10 +43,10 @@ impl<N: SimdRealField> VelocityConstraintTangentPart<N> {
         N::Element: SimdRealField,
     {
         for j in 0..DIM - 1 {
-            mj_lambda1.linear += tangents1[j] * (im1 * self.impulse[j]);
+            mj_lambda1.linear += tangents1[j].component_mul(im1) * self.impulse[j];
             mj_lambda1.angular += self.gcross1[j] * self.impulse[j];
 
-            mj_lambda2.linear += tangents1[j] * (-im2 * self.impulse[j]);
+            mj_lambda2.linear += tangents1[j].component_mul(im2) * -self.impulse[j];
             mj_lambda2.angular += self.gcross2[j] * self.impulse[j];
         }
     }
@@ -55,8 +55,8 @@ impl<N: SimdRealField> VelocityConstraintTangentPart<N> {
     pub fn solve(
         &mut self,
         tangents1: [&Vector<N>; DIM - 1],
-        im1: N,
-        im2: N,
+        im1: &Vector<N>,
+        im2: &Vector<N>,
         limit: N,
         mj_lambda1: &mut DeltaVel<N>,
         mj_lambda2: &mut DeltaVel<N>,
@@ -75,10 +75,10 @@ impl<N: SimdRealField> VelocityConstraintTangentPart<N> {
             let dlambda = new_impulse - self.impulse[0];
             self.impulse[0] = new_impulse;
 
-            mj_lambda1.linear += tangents1[0] * (im1 * dlambda);
+            mj_lambda1.linear += tangents1[0].component_mul(im1) * dlambda;
             mj_lambda1.angular += self.gcross1[0] * dlambda;
 
-            mj_lambda2.linear += tangents1[0] * (-im2 * dlambda);
+            mj_lambda2.linear += tangents1[0].component_mul(im2) * -dlambda;
             mj_lambda2.angular += self.gcross2[0] * dlambda;
         }
 
@@ -103,12 +103,12 @@ impl<N: SimdRealField> VelocityConstraintTangentPart<N> {
             let dlambda = new_impulse - self.impulse;
             self.impulse = new_impulse;
 
-            mj_lambda1.linear +=
-                tangents1[0] * (im1 * dlambda[0]) + tangents1[1] * (im1 * dlambda[1]);
+            mj_lambda1.linear += tangents1[0].component_mul(im1) * dlambda[0]
+                + tangents1[1].component_mul(im1) * dlambda[1];
             mj_lambda1.angular += self.gcross1[0] * dlambda[0] + self.gcross1[1] * dlambda[1];
 
-            mj_lambda2.linear +=
-                tangents1[0] * (-im2 * dlambda[0]) + tangents1[1] * (-im2 * dlambda[1]);
+            mj_lambda2.linear += tangents1[0].component_mul(im2) * -dlambda[0]
+                + tangents1[1].component_mul(im2) * -dlambda[1];
             mj_lambda2.angular += self.gcross2[0] * dlambda[0] + self.gcross2[1] * dlambda[1];
         }
     }
@@ -139,17 +139,17 @@ impl<N: SimdRealField> VelocityConstraintNormalPart<N> {
     pub fn warmstart(
         &self,
         dir1: &Vector<N>,
-        im1: N,
-        im2: N,
+        im1: &Vector<N>,
+        im2: &Vector<N>,
         mj_lambda1: &mut DeltaVel<N>,
         mj_lambda2: &mut DeltaVel<N>,
     ) where
         AngVector<N>: WDot<AngVector<N>, Result = N>,
     {
-        mj_lambda1.linear += dir1 * (im1 * self.impulse);
+        mj_lambda1.linear += dir1.component_mul(im1) * self.impulse;
         mj_lambda1.angular += self.gcross1 * self.impulse;
 
-        mj_lambda2.linear += dir1 * (-im2 * self.impulse);
+        mj_lambda2.linear += dir1.component_mul(im2) * -self.impulse;
         mj_lambda2.angular += self.gcross2 * self.impulse;
     }
 
@@ -157,8 +157,8 @@ impl<N: SimdRealField> VelocityConstraintNormalPart<N> {
     pub fn solve(
         &mut self,
         dir1: &Vector<N>,
-        im1: N,
-        im2: N,
+        im1: &Vector<N>,
+        im2: &Vector<N>,
         mj_lambda1: &mut DeltaVel<N>,
         mj_lambda2: &mut DeltaVel<N>,
     ) where
@@ -172,10 +172,10 @@ impl<N: SimdRealField> VelocityConstraintNormalPart<N> {
         let dlambda = new_impulse - self.impulse;
         self.impulse = new_impulse;
 
-        mj_lambda1.linear += dir1 * (im1 * dlambda);
+        mj_lambda1.linear += dir1.component_mul(im1) * dlambda;
         mj_lambda1.angular += self.gcross1 * dlambda;
 
-        mj_lambda2.linear += dir1 * (-im2 * dlambda);
+        mj_lambda2.linear += dir1.component_mul(im2) * -dlambda;
         mj_lambda2.angular += self.gcross2 * dlambda;
     }
 }
@@ -200,8 +200,8 @@ impl<N: SimdRealField> VelocityConstraintElement<N> {
         elements: &[Self],
         dir1: &Vector<N>,
         #[cfg(feature = "dim3")] tangent1: &Vector<N>,
-        im1: N,
-        im2: N,
+        im1: &Vector<N>,
+        im2: &Vector<N>,
         mj_lambda1: &mut DeltaVel<N>,
         mj_lambda2: &mut DeltaVel<N>,
     ) where
@@ -229,8 +229,8 @@ impl<N: SimdRealField> VelocityConstraintElement<N> {
         elements: &mut [Self],
         dir1: &Vector<N>,
         #[cfg(feature = "dim3")] tangent1: &Vector<N>,
-        im1: N,
-        im2: N,
+        im1: &Vector<N>,
+        im2: &Vector<N>,
         limit: N,
         mj_lambda1: &mut DeltaVel<N>,
         mj_lambda2: &mut DeltaVel<N>,
@@ -16,7 +16,7 @@ pub(crate) struct VelocityGroundConstraint {
     pub dir1: Vector<Real>, // Non-penetration force direction for the first body.
     #[cfg(feature = "dim3")]
     pub tangent1: Vector<Real>, // One of the friction force directions.
-    pub im2: Real,
+    pub im2: Vector<Real>,
     pub limit: Real,
     pub elements: [VelocityGroundConstraintElement<Real>; MAX_MANIFOLD_POINTS],
 
@@ -36,7 +36,7 @@ impl VelocityGroundConstraint {
         out_constraints: &mut Vec<AnyVelocityConstraint>,
         push: bool,
     ) {
-        let inv_dt = params.inv_dt();
+        let inv_dt = params.step_inv_dt();
         let velocity_based_erp_inv_dt = params.velocity_based_erp_inv_dt();
 
         let mut rb1 = &bodies[manifold.data.body_pair.body1];
@@ -73,7 +73,7 @@ impl VelocityGroundConstraint {
                 #[cfg(feature = "dim3")]
                 tangent_rot1,
                 elements: [VelocityGroundConstraintElement::zero(); MAX_MANIFOLD_POINTS],
-                im2: rb2.effective_inv_mass,
+                im2: rb2.effective_inv_mass_along_axes,
                 limit: 0.0,
                 mj_lambda2,
                 manifold_id,
@@ -119,7 +119,7 @@ impl VelocityGroundConstraint {
                     constraint.tangent1 = tangents1[0];
                     constraint.tangent_rot1 = tangent_rot1;
                 }
-                constraint.im2 = rb2.effective_inv_mass;
+                constraint.im2 = rb2.effective_inv_mass_along_axes;
                 constraint.limit = 0.0;
                 constraint.mj_lambda2 = mj_lambda2;
                 constraint.manifold_id = manifold_id;
@@ -144,7 +144,11 @@ impl VelocityGroundConstraint {
                         .effective_world_inv_inertia_sqrt
                         .transform_vector(dp2.gcross(-force_dir1));
 
-                    let r = 1.0 / (rb2.effective_inv_mass + gcross2.gdot(gcross2));
+                    let r = 1.0
+                        / (force_dir1
+                            .component_mul(&force_dir1)
+                            .dot(&rb2.effective_inv_mass_along_axes)
+                            + gcross2.gdot(gcross2));
 
                     let is_bouncy = manifold_point.is_bouncy() as u32 as Real;
                     let is_resting = 1.0 - is_bouncy;
@@ -181,7 +185,11 @@ impl VelocityGroundConstraint {
                         let gcross2 = rb2
                             .effective_world_inv_inertia_sqrt
                             .transform_vector(dp2.gcross(-tangents1[j]));
-                        let r = 1.0 / (rb2.effective_inv_mass + gcross2.gdot(gcross2));
+                        let r = 1.0
+                            / (tangents1[j]
+                                .component_mul(&tangents1[j])
+                                .dot(&rb2.effective_inv_mass_along_axes)
+                                + gcross2.gdot(gcross2));
                         let rhs = (vel1 - vel2
                             + flipped_multiplier * manifold_point.tangent_velocity)
                             .dot(&tangents1[j]);
@@ -211,7 +219,7 @@ impl VelocityGroundConstraint {
             &self.dir1,
             #[cfg(feature = "dim3")]
             &self.tangent1,
-            self.im2,
+            &self.im2,
             &mut mj_lambda2,
         );
 
@@ -227,7 +235,7 @@ impl VelocityGroundConstraint {
             &self.dir1,
             #[cfg(feature = "dim3")]
             &self.tangent1,
-            self.im2,
+            &self.im2,
             self.limit,
             &mut mj_lambda2,
         );
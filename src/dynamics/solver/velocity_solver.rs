@@ -36,7 +36,7 @@ impl VelocitySolver {
         bodies.foreach_active_island_body_mut_internal(island_id, |_, rb| {
             let dvel = &mut self.mj_lambdas[rb.active_set_offset];
 
-            dvel.linear += rb.force * (rb.effective_inv_mass * params.dt);
+            dvel.linear += rb.force.component_mul(&rb.effective_inv_mass_along_axes) * params.dt;
             rb.force = na::zero();
 
             // dvel.angular is actually storing angular velocity delta multiplied by the square root of the inertia tensor:
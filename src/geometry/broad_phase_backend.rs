@@ -0,0 +1,51 @@
+use crate::dynamics::RigidBodySet;
+use crate::geometry::{BroadPhasePairEvent, ColliderHandle, ColliderSet, AABB};
+use crate::math::{Real, Vector};
+
+/// An algorithm finding the pairs of colliders with overlapping AABBs.
+///
+/// The physics and collision pipelines accept any broad-phase backend, so the algorithm can be
+/// chosen depending on the scene:
+/// - `BroadPhase`, the default hierarchical Sweep-and-Prune, is very efficient for spatially
+///   coherent scenes where colliders move by small amounts at each timestep.
+/// - `BvhBroadPhase` rebuilds a bounding volume hierarchy whenever colliders move. Its cost
+///   doesn't depend on how far the colliders moved, so it is better suited to scenes where
+///   colliders are teleported, or tightly clustered. It also keeps the static colliders
+///   apart, so scenes with huge numbers of static colliders are cheap to update.
+///
+/// A given broad-phase must always be updated with the same collider set.
+pub trait BroadPhaseBackend: Send + Sync {
+    /// Updates the broad-phase, taking into account the colliders inserted, removed, or modified
+    /// since the last update.
+    ///
+    /// The rigid-bodies are given so that the broad-phase can treat the colliders differently
+    /// depending on the status of their parent.
+    ///
+    /// The AABB of each collider is enlarged by half the `prediction_distance` and by its
+    /// contact skin. An event is pushed to `events` for each pair of colliders with enlarged
+    /// AABBs that started or stopped overlapping.
+    fn update(
+        &mut self,
+        prediction_distance: Real,
+        bodies: &RigidBodySet,
+        colliders: &mut ColliderSet,
+        events: &mut Vec<BroadPhasePairEvent>,
+    );
+
+    /// Translates the AABBs of all the colliders known by this broad-phase by `shift`.
+    ///
+    /// This is meant to be called after translating all the colliders by `shift`, as done by
+    /// `pipeline::shift_origin`. The relative positions of the colliders don't change, so no pair
+    /// starts or stops overlapping and no event is generated.
+    fn shift_origin(&mut self, shift: &Vector<Real>, colliders: &mut ColliderSet);
+
+    /// Finds the handles of all the colliders with a broad-phase AABB intersecting the given AABB.
+    ///
+    /// The AABBs tested are the enlarged AABBs computed during the last call to `self.update`.
+    /// The traversal stops as soon as `callback` returns `false`.
+    fn colliders_with_aabb_intersecting_aabb(
+        &self,
+        aabb: &AABB,
+        callback: &mut dyn FnMut(&ColliderHandle) -> bool,
+    );
+}
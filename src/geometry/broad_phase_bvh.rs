@@ -0,0 +1,633 @@
+use crate::data::pubsub::Subscription;
+use crate::dynamics::RigidBodySet;
+use crate::geometry::{
+    BroadPhaseBackend, BroadPhasePairEvent, ColliderHandle, ColliderPair, ColliderSet,
+    RemovedCollider, SimdQuadTree, AABB,
+};
+use crate::math::{Real, Vector};
+use parry::bounding_volume::BoundingVolume;
+use parry::query::visitors::BoundingVolumeIntersectionsVisitor;
+use parry::utils::hashmap::HashMap;
+
+// The hierarchy of a set of colliders is rebuilt from scratch once more than one collider
+// out of `MAX_DRIFTED_RATIO` drifted away from the place it had when it was last built.
+const MAX_DRIFTED_RATIO: usize = 4;
+
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug)]
+struct BvhProxy {
+    aabb: AABB,
+    // The AABB of the collider when the hierarchy containing it was last rebuilt.
+    built_aabb: AABB,
+    // The colliders with an AABB overlapping this one.
+    neighbors: Vec<ColliderHandle>,
+}
+
+impl BvhProxy {
+    fn new(aabb: AABB) -> Self {
+        Self {
+            aabb,
+            built_aabb: aabb,
+            neighbors: vec![],
+        }
+    }
+
+    // Refitting the hierarchy keeps each collider in the subtree it was placed in when the
+    // hierarchy was built. This degrades the quality of the hierarchy once the collider moved
+    // farther than its own size from that place.
+    fn has_drifted(&self) -> bool {
+        let margin = self.built_aabb.half_extents().max();
+        !self.built_aabb.loosened(margin).contains(&self.aabb)
+    }
+}
+
+// A set of proxies, and the hierarchy of their AABBs.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+struct BvhTree {
+    tree: SimdQuadTree<ColliderHandle>,
+    proxies: HashMap<ColliderHandle, BvhProxy>,
+    // The number of proxies for which `BvhProxy::has_drifted` returns `true`.
+    num_drifted: usize,
+    // Set if a proxy was inserted or removed since the last rebuild of the hierarchy.
+    needs_rebuild: bool,
+}
+
+impl BvhTree {
+    fn new() -> Self {
+        Self {
+            tree: SimdQuadTree::new(),
+            proxies: HashMap::default(),
+            num_drifted: 0,
+            needs_rebuild: false,
+        }
+    }
+
+    fn insert(&mut self, handle: ColliderHandle, mut proxy: BvhProxy) {
+        proxy.built_aabb = proxy.aabb;
+        self.proxies.insert(handle, proxy);
+        self.needs_rebuild = true;
+    }
+
+    fn remove(&mut self, handle: &ColliderHandle) -> Option<BvhProxy> {
+        let proxy = self.proxies.remove(handle)?;
+
+        if proxy.has_drifted() {
+            self.num_drifted -= 1;
+        }
+
+        self.needs_rebuild = true;
+        Some(proxy)
+    }
+
+    fn set_aabb(&mut self, handle: &ColliderHandle, aabb: AABB) {
+        let proxy = self.proxies.get_mut(handle).unwrap();
+        let was_drifted = proxy.has_drifted();
+        proxy.aabb = aabb;
+
+        match (was_drifted, proxy.has_drifted()) {
+            (false, true) => self.num_drifted += 1,
+            (true, false) => self.num_drifted -= 1,
+            _ => {}
+        }
+    }
+
+    // Brings the hierarchy up to date with the AABBs of the proxies, assuming only the proxies
+    // listed in `moved` changed since the last call to this method.
+    fn maintain(&mut self, moved: &[ColliderHandle]) {
+        if self.needs_rebuild || self.num_drifted * MAX_DRIFTED_RATIO > self.proxies.len() {
+            for proxy in self.proxies.values_mut() {
+                proxy.built_aabb = proxy.aabb;
+            }
+
+            let data = self.proxies.iter().map(|(h, proxy)| (*h, proxy.aabb));
+            self.tree.clear_and_rebuild(data, 0.0);
+            self.num_drifted = 0;
+            self.needs_rebuild = false;
+        } else {
+            // Refit the nodes containing the moved proxies, in O(log n) each.
+            let mut refit = false;
+
+            for handle in moved {
+                if self.proxies.contains_key(handle) {
+                    self.tree.pre_update(*handle);
+                    refit = true;
+                }
+            }
+
+            if refit {
+                let proxies = &self.proxies;
+                self.tree.update(|h| proxies[h].aabb, 0.0);
+            }
+        }
+    }
+
+    fn shift_origin(&mut self, shift: &Vector<Real>) {
+        for (handle, proxy) in self.proxies.iter_mut() {
+            proxy.aabb.mins += *shift;
+            proxy.aabb.maxs += *shift;
+            proxy.built_aabb.mins += *shift;
+            proxy.built_aabb.maxs += *shift;
+
+            if !self.needs_rebuild {
+                self.tree.pre_update(*handle);
+            }
+        }
+
+        let proxies = &self.proxies;
+        self.tree.update(|h| proxies[h].aabb, 0.0);
+    }
+}
+
+/// A broad-phase based on bounding volume hierarchies.
+///
+/// The colliders attached to static rigid-bodies and the other colliders are stored in two
+/// separate hierarchies. Whenever colliders move, the nodes containing them are refitted, and
+/// they are tested against both hierarchies. Unlike with a Sweep-and-Prune, the cost of an
+/// update doesn't depend on how far the colliders moved, nor on how many other colliders they
+/// crossed on the way, which makes it robust to teleportation and to tightly clustered
+/// colliders. A hierarchy is rebuilt from scratch when colliders are inserted into it or removed
+/// from it, or once enough of its colliders moved far away from where they were when it was
+/// last built.
+///
+/// Since static colliders rarely move, the hierarchy of static colliders is rarely updated:
+/// the cost of a typical update only depends on the number of non-static colliders, even for
+/// scenes with hundreds of thousands of static colliders. The pairs of colliders that are both
+/// static are not reported. A collider moves to the other hierarchy whenever the status of its
+/// parent changes.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+pub struct BvhBroadPhase {
+    static_tree: BvhTree,
+    dynamic_tree: BvhTree,
+    removed_colliders: Option<Subscription<RemovedCollider>>,
+    // Workspaces.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    moved: Vec<ColliderHandle>,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    overlaps: Vec<ColliderHandle>,
+}
+
+impl Default for BvhBroadPhase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BvhBroadPhase {
+    /// Creates a new empty broad-phase.
+    pub fn new() -> Self {
+        Self {
+            static_tree: BvhTree::new(),
+            dynamic_tree: BvhTree::new(),
+            removed_colliders: None,
+            moved: vec![],
+            overlaps: vec![],
+        }
+    }
+
+    fn proxy_mut(&mut self, handle: &ColliderHandle) -> Option<&mut BvhProxy> {
+        self.static_tree
+            .proxies
+            .get_mut(handle)
+            .or(self.dynamic_tree.proxies.get_mut(handle))
+    }
+
+    fn handle_removed_colliders(&mut self, colliders: &mut ColliderSet) {
+        // Ensure we already subscribed the collider-removed events.
+        if self.removed_colliders.is_none() {
+            self.removed_colliders = Some(colliders.removed_colliders.subscribe());
+        }
+
+        let cursor = self.removed_colliders.take().unwrap();
+
+        for collider in colliders.removed_colliders.read(&cursor) {
+            let proxy = match self.static_tree.remove(&collider.handle) {
+                Some(proxy) => proxy,
+                None => match self.dynamic_tree.remove(&collider.handle) {
+                    Some(proxy) => proxy,
+                    None => continue,
+                },
+            };
+
+            // The narrow-phase removes the pairs involving removed colliders by itself,
+            // so no event is needed here.
+            for neighbor in proxy.neighbors {
+                if let Some(neighbor) = self.proxy_mut(&neighbor) {
+                    neighbor.neighbors.retain(|h| *h != collider.handle);
+                }
+            }
+        }
+
+        colliders.removed_colliders.ack(&cursor);
+        self.removed_colliders = Some(cursor);
+    }
+
+    // Finds the pairs of colliders that started or stopped overlapping with the collider
+    // `handle`, which moved since the last update.
+    fn update_pairs(&mut self, handle: ColliderHandle, events: &mut Vec<BroadPhasePairEvent>) {
+        let (aabb, is_static) = match self.static_tree.proxies.get(&handle) {
+            Some(proxy) => (proxy.aabb, true),
+            None => (self.dynamic_tree.proxies[&handle].aabb, false),
+        };
+        let mut overlaps = std::mem::take(&mut self.overlaps);
+        overlaps.clear();
+
+        let mut callback = |other: &ColliderHandle| {
+            if *other != handle {
+                overlaps.push(*other);
+            }
+            true
+        };
+        let mut visitor = BoundingVolumeIntersectionsVisitor::new(&aabb, &mut callback);
+        self.dynamic_tree.tree.traverse_depth_first(&mut visitor);
+
+        if !is_static {
+            self.static_tree.tree.traverse_depth_first(&mut visitor);
+        }
+
+        // Sorted so the events don't depend on the layout of the trees.
+        overlaps.sort_unstable_by_key(|h| h.into_raw_parts());
+
+        let old_neighbors = std::mem::take(&mut self.proxy_mut(&handle).unwrap().neighbors);
+
+        for neighbor in &old_neighbors {
+            let key = neighbor.into_raw_parts();
+
+            if overlaps
+                .binary_search_by_key(&key, |h| h.into_raw_parts())
+                .is_err()
+            {
+                if let Some(other) = self.proxy_mut(neighbor) {
+                    other.neighbors.retain(|h| *h != handle);
+                }
+
+                events.push(BroadPhasePairEvent::DeletePair(ColliderPair::new(
+                    handle, *neighbor,
+                )));
+            }
+        }
+
+        for neighbor in &overlaps {
+            if !old_neighbors.contains(neighbor) {
+                let other = self.proxy_mut(neighbor).unwrap();
+
+                // The pair may already be known if the other collider moved too, and was
+                // processed first.
+                if !other.neighbors.contains(&handle) {
+                    other.neighbors.push(handle);
+                    events.push(BroadPhasePairEvent::AddPair(ColliderPair::new(
+                        handle, *neighbor,
+                    )));
+                }
+            }
+        }
+
+        self.proxy_mut(&handle).unwrap().neighbors = overlaps;
+        // Reuse the buffer of the old neighbors as the workspace of the next update.
+        self.overlaps = old_neighbors;
+    }
+}
+
+impl BroadPhaseBackend for BvhBroadPhase {
+    fn update(
+        &mut self,
+        prediction_distance: Real,
+        bodies: &RigidBodySet,
+        colliders: &mut ColliderSet,
+        events: &mut Vec<BroadPhasePairEvent>,
+    ) {
+        self.handle_removed_colliders(colliders);
+
+        let static_tree = &mut self.static_tree;
+        let dynamic_tree = &mut self.dynamic_tree;
+        let moved = &mut self.moved;
+        moved.clear();
+
+        colliders.foreach_modified_colliders(|handle, collider| {
+            if !collider.changes.needs_broad_phase_update() {
+                return;
+            }
+
+            let aabb = collider
+                .compute_aabb()
+                .loosened(prediction_distance / 2.0 + collider.contact_skin);
+            let is_static = bodies
+                .get(collider.parent())
+                .map(|rb| rb.is_static())
+                .unwrap_or(false);
+            let (tree, other_tree) = if is_static {
+                (&mut *static_tree, &mut *dynamic_tree)
+            } else {
+                (&mut *dynamic_tree, &mut *static_tree)
+            };
+
+            if tree.proxies.contains_key(&handle) {
+                tree.set_aabb(&handle, aabb);
+            } else {
+                // The proxy is moved to the other tree if the status of the parent changed.
+                let mut proxy = other_tree
+                    .remove(&handle)
+                    .unwrap_or_else(|| BvhProxy::new(aabb));
+                proxy.aabb = aabb;
+                tree.insert(handle, proxy);
+            }
+
+            moved.push(handle);
+        });
+
+        self.static_tree.maintain(&self.moved);
+        self.dynamic_tree.maintain(&self.moved);
+
+        let moved = std::mem::take(&mut self.moved);
+
+        for handle in &moved {
+            self.update_pairs(*handle, events);
+        }
+
+        self.moved = moved;
+    }
+
+    fn shift_origin(&mut self, shift: &Vector<Real>, _: &mut ColliderSet) {
+        self.static_tree.shift_origin(shift);
+        self.dynamic_tree.shift_origin(shift);
+    }
+
+    fn colliders_with_aabb_intersecting_aabb(
+        &self,
+        aabb: &AABB,
+        callback: &mut dyn FnMut(&ColliderHandle) -> bool,
+    ) {
+        // Don't call the callback anymore once it returned `false` for the dynamic tree.
+        let mut stopped = false;
+        let mut callback = |handle: &ColliderHandle| {
+            stopped = stopped || !callback(handle);
+            !stopped
+        };
+        let mut visitor = BoundingVolumeIntersectionsVisitor::new(aabb, &mut callback);
+        self.dynamic_tree.tree.traverse_depth_first(&mut visitor);
+        self.static_tree.tree.traverse_depth_first(&mut visitor);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BvhBroadPhase;
+    use crate::dynamics::{BodyStatus, JointSet, RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::{
+        BroadPhaseBackend, BroadPhasePairEvent, ColliderBuilder, ColliderPair, ColliderSet, AABB,
+    };
+    use crate::math::{Isometry, Point, Real, Vector};
+
+    #[test]
+    fn pairs_of_teleported_colliders() {
+        let mut broad_phase = BvhBroadPhase::new();
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut joints = JointSet::new();
+
+        let rb = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let co1 = colliders.insert(ColliderBuilder::ball(0.5).build(), rb, &mut bodies);
+        let co2 = colliders.insert(
+            ColliderBuilder::ball(0.5)
+                .position(Isometry::new(Vector::x() * 100.0, na::zero()))
+                .build(),
+            rb,
+            &mut bodies,
+        );
+
+        let mut events = Vec::new();
+        let mut update = |bodies: &RigidBodySet, colliders: &mut ColliderSet| {
+            events.clear();
+            broad_phase.update(0.0, bodies, colliders, &mut events);
+            colliders.clear_modified_colliders();
+            events.clone()
+        };
+
+        assert!(update(&bodies, &mut colliders).is_empty());
+
+        // Teleport the second collider onto the first one, and back.
+        colliders
+            .get_mut(co2)
+            .unwrap()
+            .set_position(Isometry::new(Vector::x() * 0.5, na::zero()));
+        assert_eq!(
+            update(&bodies, &mut colliders),
+            vec![BroadPhasePairEvent::AddPair(ColliderPair::new(co2, co1))]
+        );
+        assert!(update(&bodies, &mut colliders).is_empty());
+
+        colliders
+            .get_mut(co2)
+            .unwrap()
+            .set_position(Isometry::new(Vector::x() * -100.0, na::zero()));
+        assert_eq!(
+            update(&bodies, &mut colliders),
+            vec![BroadPhasePairEvent::DeletePair(ColliderPair::new(co2, co1))]
+        );
+
+        // Removed colliders are no longer reported.
+        colliders
+            .get_mut(co2)
+            .unwrap()
+            .set_position(Isometry::identity());
+        assert_eq!(update(&bodies, &mut colliders).len(), 1);
+        bodies.remove(rb, &mut colliders, &mut joints);
+        assert!(update(&bodies, &mut colliders).is_empty());
+
+        let aabb = AABB::new(
+            Point::from(Vector::repeat(-1000.0)),
+            Point::from(Vector::repeat(1000.0)),
+        );
+        let mut found = vec![];
+        broad_phase.colliders_with_aabb_intersecting_aabb(&aabb, &mut |handle| {
+            found.push(*handle);
+            true
+        });
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn static_colliders_only_pair_with_non_static_colliders() {
+        let mut broad_phase = BvhBroadPhase::new();
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut events = Vec::new();
+
+        let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+        let wall1 = colliders.insert(ColliderBuilder::ball(0.5).build(), ground, &mut bodies);
+        let wall2 = colliders.insert(ColliderBuilder::ball(0.5).build(), ground, &mut bodies);
+        broad_phase.update(0.0, &bodies, &mut colliders, &mut events);
+        colliders.clear_modified_colliders();
+        assert!(events.is_empty());
+
+        let rb = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let co = colliders.insert(ColliderBuilder::ball(0.5).build(), rb, &mut bodies);
+        broad_phase.update(0.0, &bodies, &mut colliders, &mut events);
+        assert_eq!(
+            events,
+            vec![
+                BroadPhasePairEvent::AddPair(ColliderPair::new(co, wall1)),
+                BroadPhasePairEvent::AddPair(ColliderPair::new(co, wall2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn stack_rests_on_ground_with_bvh_broad_phase() {
+        use crate::dynamics::{CCDSolver, IntegrationParameters};
+        use crate::geometry::NarrowPhase;
+        use crate::pipeline::PhysicsPipeline;
+
+        let mut pipeline = PhysicsPipeline::new();
+        let mut broad_phase = BvhBroadPhase::new();
+        let mut narrow_phase = NarrowPhase::new();
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut joints = JointSet::new();
+        let mut ccd = CCDSolver::new();
+
+        let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+        let ground_collider = ColliderBuilder::halfspace(Vector::y_axis()).build();
+        colliders.insert(ground_collider, ground, &mut bodies);
+
+        let handles: Vec<_> = (0..3)
+            .map(|i| {
+                #[cfg(feature = "dim2")]
+                let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.5 + i as Real);
+                #[cfg(feature = "dim3")]
+                let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.5 + i as Real, 0.0);
+                let handle = bodies.insert(rb.build());
+                colliders.insert(ColliderBuilder::ball(0.5).build(), handle, &mut bodies);
+                handle
+            })
+            .collect();
+
+        for _ in 0..120 {
+            pipeline.step(
+                &(Vector::y() * -9.81),
+                1.0 / 60.0,
+                &IntegrationParameters::default(),
+                &mut broad_phase,
+                &mut narrow_phase,
+                &mut bodies,
+                &mut colliders,
+                &mut joints,
+                &mut ccd,
+                &(),
+                &(),
+            );
+        }
+
+        for (i, handle) in handles.iter().enumerate() {
+            let y = bodies[*handle].position().translation.y;
+            assert!((y - (0.5 + i as Real)).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn moved_colliders_refit_the_hierarchy() {
+        let mut broad_phase = BvhBroadPhase::new();
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut events = Vec::new();
+
+        let rb = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let position = Isometry::new(Vector::x() * 10.0 * i as Real, na::zero());
+                let collider = ColliderBuilder::ball(0.5).position(position).build();
+                colliders.insert(collider, rb, &mut bodies)
+            })
+            .collect();
+        broad_phase.update(0.0, &bodies, &mut colliders, &mut events);
+        colliders.clear_modified_colliders();
+        assert!(events.is_empty());
+
+        // Teleport a collider onto another one: the hierarchy is refitted, not rebuilt.
+        colliders
+            .get_mut(handles[7])
+            .unwrap()
+            .set_position(Isometry::new(Vector::x() * 0.5, na::zero()));
+        broad_phase.update(0.0, &bodies, &mut colliders, &mut events);
+        colliders.clear_modified_colliders();
+        assert_eq!(
+            events,
+            vec![BroadPhasePairEvent::AddPair(ColliderPair::new(
+                handles[7], handles[0]
+            ))]
+        );
+        assert_eq!(broad_phase.dynamic_tree.num_drifted, 1);
+
+        // The hierarchy is rebuilt once too many colliders drifted.
+        for handle in &handles[1..3] {
+            colliders
+                .get_mut(*handle)
+                .unwrap()
+                .set_position(Isometry::new(Vector::y() * 100.0, na::zero()));
+        }
+        events.clear();
+        broad_phase.update(0.0, &bodies, &mut colliders, &mut events);
+        colliders.clear_modified_colliders();
+        assert_eq!(
+            events,
+            vec![BroadPhasePairEvent::AddPair(ColliderPair::new(
+                handles[1], handles[2]
+            ))]
+        );
+        assert_eq!(broad_phase.dynamic_tree.num_drifted, 0);
+
+        // Shifting the origin translates the hierarchy.
+        let shift = Vector::repeat(1000.0);
+        broad_phase.shift_origin(&shift, &mut colliders);
+        let aabb = AABB::new(Point::from(shift), Point::from(shift));
+        let mut found = vec![];
+        broad_phase.colliders_with_aabb_intersecting_aabb(&aabb, &mut |handle| {
+            found.push(*handle);
+            true
+        });
+        found.sort_by_key(|h| h.into_raw_parts());
+        assert_eq!(found, vec![handles[0], handles[7]]);
+    }
+
+    #[test]
+    fn colliders_are_reclassified_when_the_parent_status_changes() {
+        let mut broad_phase = BvhBroadPhase::new();
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+        let mut events = Vec::new();
+
+        let rb = bodies.insert(RigidBodyBuilder::new_static().build());
+        let co1 = colliders.insert(ColliderBuilder::ball(0.5).build(), rb, &mut bodies);
+        let co2 = colliders.insert(ColliderBuilder::ball(0.5).build(), rb, &mut bodies);
+        bodies.handle_user_changes(&mut colliders);
+        broad_phase.update(0.0, &bodies, &mut colliders, &mut events);
+        colliders.clear_modified_colliders();
+        assert!(events.is_empty());
+
+        // The colliders didn't move, but they are no longer static.
+        bodies
+            .get_mut(rb)
+            .unwrap()
+            .set_body_status(BodyStatus::Dynamic);
+        bodies.handle_user_changes(&mut colliders);
+        broad_phase.update(0.0, &bodies, &mut colliders, &mut events);
+        colliders.clear_modified_colliders();
+        assert_eq!(
+            events,
+            vec![BroadPhasePairEvent::AddPair(ColliderPair::new(co1, co2))]
+        );
+
+        events.clear();
+        bodies
+            .get_mut(rb)
+            .unwrap()
+            .set_body_status(BodyStatus::Static);
+        bodies.handle_user_changes(&mut colliders);
+        broad_phase.update(0.0, &bodies, &mut colliders, &mut events);
+        assert_eq!(
+            events,
+            vec![BroadPhasePairEvent::DeletePair(ColliderPair::new(co1, co2))]
+        );
+    }
+}
@@ -2,14 +2,24 @@ use super::{
     BroadPhasePairEvent, ColliderPair, SAPLayer, SAPProxies, SAPProxy, SAPProxyData, SAPRegionPool,
 };
 use crate::data::pubsub::Subscription;
+use crate::dynamics::RigidBodySet;
 use crate::geometry::broad_phase_multi_sap::SAPProxyIndex;
 use crate::geometry::collider::ColliderChanges;
-use crate::geometry::{ColliderSet, RemovedCollider};
-use crate::math::Real;
+use crate::geometry::{
+    BroadPhaseBackend, Collider, ColliderHandle, ColliderSet, MotionQuality, RemovedCollider, AABB,
+};
+use crate::math::{Real, Vector};
 use crate::utils::IndexMut2;
 use parry::bounding_volume::BoundingVolume;
 use parry::utils::hashmap::HashMap;
 
+// The margin added to the AABBs of colliders with a `MotionQuality::Slow` hint, relative to
+// their half-extents.
+const SLOW_MOTION_MARGIN_RATIO: Real = 0.25;
+// The motions of the colliders with a `MotionQuality::Static` hint are only taken into account
+// once every `STATIC_MOTION_UPDATE_PERIOD` broad-phase updates.
+const STATIC_MOTION_UPDATE_PERIOD: u32 = 8;
+
 /// A broad-phase combining a Hierarchical Grid and Sweep-and-Prune.
 ///
 /// The basic Sweep-and-Prune (SAP) algorithm has one significant flaws:
@@ -80,6 +90,9 @@ pub struct BroadPhase {
     largest_layer: u8,
     removed_colliders: Option<Subscription<RemovedCollider>>,
     deleted_any: bool,
+    // The colliders with a `MotionQuality::Static` hint that moved since their last update.
+    pending_static_motions: Vec<ColliderHandle>,
+    num_updates: u32,
     #[cfg_attr(feature = "serde-serialize", serde(skip))]
     region_pool: SAPRegionPool, // To avoid repeated allocations.
     // We could think serializing this workspace is useless.
@@ -115,6 +128,8 @@ impl BroadPhase {
             region_pool: Vec::new(),
             reporting: HashMap::default(),
             deleted_any: false,
+            pending_static_motions: Vec::new(),
+            num_updates: 0,
         }
     }
 
@@ -336,6 +351,110 @@ impl BroadPhase {
         }
     }
 
+    /// Finds the handles of all the colliders with a broad-phase AABB intersecting the given AABB.
+    ///
+    /// This traverses the broad-phase regions directly, so it doesn't require a `QueryPipeline`.
+    /// The AABBs tested are the ones computed during the last call to `self.update`, enlarged
+    /// by the prediction distance and contact skins. Colliders inserted after that last update
+    /// are not reported. The traversal stops as soon as `callback` returns `false`.
+    pub fn colliders_with_aabb_intersecting_aabb(
+        &self,
+        aabb: &AABB,
+        mut callback: impl FnMut(&ColliderHandle) -> bool,
+    ) {
+        for layer in &self.layers {
+            if !layer.colliders_with_aabb_intersecting_aabb(aabb, &self.proxies, &mut callback) {
+                return;
+            }
+        }
+    }
+
+    /// Pre-updates the proxy of a modified collider, creating it if it doesn't exist yet.
+    ///
+    /// The motions of colliders with a `MotionQuality::Static` hint are deferred until the
+    /// next multiple of `STATIC_MOTION_UPDATE_PERIOD` updates, unless `deferred` is `true`.
+    /// Returns `true` if new regions were created and need to be propagated to larger layers.
+    fn preupdate_proxy(
+        &mut self,
+        handle: ColliderHandle,
+        collider: &mut Collider,
+        prediction_distance: Real,
+        deferred: bool,
+    ) -> bool {
+        let only_moved = !collider
+            .changes
+            .intersects(ColliderChanges::SHAPE | ColliderChanges::HEIGHTFIELD);
+        let has_proxy = self.proxies.get(collider.proxy_index).is_some();
+
+        if collider.motion_quality == MotionQuality::Static && only_moved && has_proxy && !deferred
+        {
+            if !self.pending_static_motions.contains(&handle) {
+                self.pending_static_motions.push(handle);
+            }
+            return false;
+        }
+
+        let mut aabb = collider
+            .compute_aabb()
+            .loosened(prediction_distance / 2.0 + collider.contact_skin);
+
+        if collider.motion_quality == MotionQuality::Slow {
+            if only_moved {
+                if let Some(proxy) = self.proxies.get(collider.proxy_index) {
+                    if proxy.aabb.contains(&aabb) {
+                        // The collider didn't leave its enlarged AABB: nothing to update.
+                        return false;
+                    }
+                }
+            }
+
+            let margin = aabb.half_extents().max() * SLOW_MOTION_MARGIN_RATIO;
+            aabb = aabb.loosened(margin);
+        }
+
+        aabb.mins = super::clamp_point(aabb.mins);
+        aabb.maxs = super::clamp_point(aabb.maxs);
+
+        let layer_id = if let Some(proxy) = self.proxies.get_mut(collider.proxy_index) {
+            let mut layer_id = proxy.layer_id;
+            proxy.aabb = aabb;
+
+            if !only_moved {
+                // If the shape was changed, then we need to see if this proxy should be
+                // migrated to a larger layer. Indeed, if the shape was replaced by
+                // a much larger shape, we need to promote the proxy to a bigger layer
+                // to avoid the O(n²) discretization problem.
+                let new_layer_depth = super::layer_containing_aabb(&aabb);
+                if new_layer_depth > proxy.layer_depth {
+                    self.layers[proxy.layer_id as usize].proper_proxy_moved_to_bigger_layer(
+                        &mut self.proxies,
+                        collider.proxy_index,
+                    );
+
+                    // We need to promote the proxy to the bigger layer.
+                    layer_id = self.ensure_layer_exists(new_layer_depth);
+                    self.proxies[collider.proxy_index].layer_id = layer_id;
+                }
+            }
+
+            layer_id
+        } else {
+            let layer_depth = super::layer_containing_aabb(&aabb);
+            let layer_id = self.ensure_layer_exists(layer_depth);
+
+            // Create the proxy.
+            let proxy = SAPProxy::collider(handle, aabb, layer_id, layer_depth);
+            collider.proxy_index = self.proxies.insert(proxy);
+            layer_id
+        };
+
+        let layer = &mut self.layers[layer_id as usize];
+
+        // Preupdate the collider in the layer.
+        layer.preupdate_collider(collider, &aabb, &mut self.proxies, &mut self.region_pool);
+        !layer.created_regions.is_empty()
+    }
+
     /// Updates the broad-phase, taking into account the new collider positions.
     pub fn update(
         &mut self,
@@ -350,53 +469,23 @@ impl BroadPhase {
 
         // Phase 2: pre-delete the collisions that have been deleted.
         colliders.foreach_modified_colliders_mut_internal(|handle, collider| {
-            if !collider.changes.needs_broad_phase_update() {
-                return;
+            if collider.changes.needs_broad_phase_update() {
+                need_region_propagation |=
+                    self.preupdate_proxy(handle, collider, prediction_distance, false);
             }
+        });
 
-            let mut aabb = collider.compute_aabb().loosened(prediction_distance / 2.0);
-            aabb.mins = super::clamp_point(aabb.mins);
-            aabb.maxs = super::clamp_point(aabb.maxs);
-
-            let layer_id = if let Some(proxy) = self.proxies.get_mut(collider.proxy_index) {
-                let mut layer_id = proxy.layer_id;
-                proxy.aabb = aabb;
-
-                if collider.changes.contains(ColliderChanges::SHAPE) {
-                    // If the shape was changed, then we need to see if this proxy should be
-                    // migrated to a larger layer. Indeed, if the shape was replaced by
-                    // a much larger shape, we need to promote the proxy to a bigger layer
-                    // to avoid the O(n²) discretization problem.
-                    let new_layer_depth = super::layer_containing_aabb(&aabb);
-                    if new_layer_depth > proxy.layer_depth {
-                        self.layers[proxy.layer_id as usize].proper_proxy_moved_to_bigger_layer(
-                            &mut self.proxies,
-                            collider.proxy_index,
-                        );
-
-                        // We need to promote the proxy to the bigger layer.
-                        layer_id = self.ensure_layer_exists(new_layer_depth);
-                        self.proxies[collider.proxy_index].layer_id = layer_id;
-                    }
+        // Apply the deferred motions of the colliders with a `MotionQuality::Static` hint.
+        self.num_updates = self.num_updates.wrapping_add(1);
+        if self.num_updates % STATIC_MOTION_UPDATE_PERIOD == 0 {
+            let pending = std::mem::replace(&mut self.pending_static_motions, Vec::new());
+            for handle in pending {
+                if let Some(collider) = colliders.get_mut_internal(handle) {
+                    need_region_propagation |=
+                        self.preupdate_proxy(handle, collider, prediction_distance, true);
                 }
-
-                layer_id
-            } else {
-                let layer_depth = super::layer_containing_aabb(&aabb);
-                let layer_id = self.ensure_layer_exists(layer_depth);
-
-                // Create the proxy.
-                let proxy = SAPProxy::collider(handle, aabb, layer_id, layer_depth);
-                collider.proxy_index = self.proxies.insert(proxy);
-                layer_id
-            };
-
-            let layer = &mut self.layers[layer_id as usize];
-
-            // Preupdate the collider in the layer.
-            layer.preupdate_collider(collider, &aabb, &mut self.proxies, &mut self.region_pool);
-            need_region_propagation = need_region_propagation || !layer.created_regions.is_empty();
-        });
+            }
+        }
 
         // Phase 3: bottom-up pass to propagate new regions from smaller layers to larger layers.
         if need_region_propagation {
@@ -411,6 +500,60 @@ impl BroadPhase {
         self.complete_removals(colliders);
     }
 
+    /// Translates the AABBs of all the colliders of this broad-phase by `shift`.
+    ///
+    /// The regions are aligned on a grid that doesn't move with the colliders, so the whole
+    /// hierarchical grid is rebuilt from the translated AABBs. The pairs of colliders don't change
+    /// since they are all translated by the same amount, so the pair events found by this rebuild
+    /// are discarded.
+    pub fn shift_origin(&mut self, shift: &Vector<Real>, colliders: &mut ColliderSet) {
+        // The removed colliders are simply not re-inserted, so their removal events have been
+        // handled.
+        if let Some(cursor) = &self.removed_colliders {
+            colliders.removed_colliders.ack(cursor);
+        }
+
+        let mut shifted = vec![];
+
+        for (handle, collider) in colliders.iter() {
+            if let Some(proxy) = self.proxies.get(collider.proxy_index) {
+                let mut aabb = proxy.aabb;
+                aabb.mins = super::clamp_point(aabb.mins + *shift);
+                aabb.maxs = super::clamp_point(aabb.maxs + *shift);
+                shifted.push((handle, aabb, proxy.layer_depth));
+            }
+        }
+
+        // Keep the workspaces so their capacities are unchanged.
+        let removed_colliders = self.removed_colliders.take();
+        let region_pool = std::mem::take(&mut self.region_pool);
+        let reporting = std::mem::take(&mut self.reporting);
+        *self = BroadPhase {
+            removed_colliders,
+            region_pool,
+            reporting,
+            ..BroadPhase::new()
+        };
+
+        for (handle, aabb, layer_depth) in shifted {
+            let layer_id = self.ensure_layer_exists(layer_depth);
+            let proxy = SAPProxy::collider(handle, aabb, layer_id, layer_depth);
+            let collider = colliders.get_mut_internal(handle).unwrap();
+            collider.proxy_index = self.proxies.insert(proxy);
+            self.layers[layer_id as usize].preupdate_collider(
+                collider,
+                &aabb,
+                &mut self.proxies,
+                &mut self.region_pool,
+            );
+        }
+
+        if !self.layers.is_empty() {
+            self.propagate_created_regions();
+            self.update_layers_and_find_pairs(&mut vec![]);
+        }
+    }
+
     /// Propagate regions from the smallest layers up to the larger layers.
     ///
     /// Whenever a region is created on a layer `n`, then its AABB must be
@@ -521,6 +664,30 @@ impl BroadPhase {
     }
 }
 
+impl BroadPhaseBackend for BroadPhase {
+    fn update(
+        &mut self,
+        prediction_distance: Real,
+        _: &RigidBodySet,
+        colliders: &mut ColliderSet,
+        events: &mut Vec<BroadPhasePairEvent>,
+    ) {
+        BroadPhase::update(self, prediction_distance, colliders, events)
+    }
+
+    fn shift_origin(&mut self, shift: &Vector<Real>, colliders: &mut ColliderSet) {
+        BroadPhase::shift_origin(self, shift, colliders)
+    }
+
+    fn colliders_with_aabb_intersecting_aabb(
+        &self,
+        aabb: &AABB,
+        callback: &mut dyn FnMut(&ColliderHandle) -> bool,
+    ) {
+        BroadPhase::colliders_with_aabb_intersecting_aabb(self, aabb, callback)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::dynamics::{JointSet, RigidBodyBuilder, RigidBodySet};
@@ -553,4 +720,172 @@ mod test {
         // Make sure the proxy handles is recycled properly.
         broad_phase.update(0.0, &mut colliders, &mut events);
     }
+
+    #[test]
+    fn slow_motion_quality() {
+        use crate::geometry::MotionQuality;
+        use crate::math::{Isometry, Vector};
+        use parry::bounding_volume::BoundingVolume;
+
+        let mut broad_phase = BroadPhase::new();
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let rb = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let co = ColliderBuilder::ball(0.5)
+            .motion_quality(MotionQuality::Slow)
+            .build();
+        let co = colliders.insert(co, rb, &mut bodies);
+
+        let mut events = Vec::new();
+        let mut move_collider = |colliders: &mut ColliderSet, x| {
+            colliders
+                .get_mut(co)
+                .unwrap()
+                .set_position(Isometry::new(Vector::x() * x, na::zero()));
+            broad_phase.update(0.0, colliders, &mut events);
+            colliders.clear_modified_colliders();
+            broad_phase.proxies[colliders[co].proxy_index].aabb
+        };
+
+        // The AABB is enlarged when the collider is inserted.
+        let initial_aabb = move_collider(&mut colliders, 0.0);
+        assert!(initial_aabb.contains(&colliders[co].compute_aabb().loosened(0.1)));
+
+        // A small displacement stays within the enlarged AABB.
+        assert_eq!(move_collider(&mut colliders, 0.05), initial_aabb);
+
+        // A large displacement doesn't.
+        let moved_aabb = move_collider(&mut colliders, 2.0);
+        assert_ne!(moved_aabb, initial_aabb);
+        assert!(moved_aabb.contains(&colliders[co].compute_aabb()));
+    }
+
+    #[test]
+    fn fast_motion_quality() {
+        use crate::geometry::MotionQuality;
+        use crate::math::{Isometry, Vector};
+
+        let mut broad_phase = BroadPhase::new();
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let rb = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let co = ColliderBuilder::ball(0.5)
+            .motion_quality(MotionQuality::Fast)
+            .build();
+        let co = colliders.insert(co, rb, &mut bodies);
+
+        let mut events = Vec::new();
+        for x in &[0.0, 0.05, 2.0] {
+            colliders
+                .get_mut(co)
+                .unwrap()
+                .set_position(Isometry::new(Vector::x() * *x, na::zero()));
+            broad_phase.update(0.0, &mut colliders, &mut events);
+            colliders.clear_modified_colliders();
+
+            // The AABB is never enlarged, and follows every displacement.
+            assert_eq!(
+                broad_phase.proxies[colliders[co].proxy_index].aabb,
+                colliders[co].compute_aabb()
+            );
+        }
+    }
+
+    #[test]
+    fn colliders_with_aabb_intersecting_aabb() {
+        use crate::geometry::AABB;
+        use crate::math::{Isometry, Point, Real, Vector};
+
+        let mut broad_phase = BroadPhase::new();
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let rb = bodies.insert(RigidBodyBuilder::new_static().build());
+        // Small balls, as well as a large one living on another layer.
+        let handles: Vec<_> = (0..10)
+            .map(|i| {
+                let co = ColliderBuilder::ball(0.5)
+                    .position(Isometry::new(Vector::x() * (i as Real * 3.0), na::zero()))
+                    .build();
+                colliders.insert(co, rb, &mut bodies)
+            })
+            .collect();
+        let large = colliders.insert(
+            ColliderBuilder::ball(20.0)
+                .position(Isometry::new(Vector::y() * 100.0, na::zero()))
+                .build(),
+            rb,
+            &mut bodies,
+        );
+
+        let mut events = Vec::new();
+        broad_phase.update(0.0, &mut colliders, &mut events);
+
+        let query = |aabb: AABB| {
+            let mut result = Vec::new();
+            broad_phase.colliders_with_aabb_intersecting_aabb(&aabb, |handle| {
+                result.push(*handle);
+                true
+            });
+            result.sort_by_key(|h| h.into_raw_parts());
+            result
+        };
+
+        let aabb = AABB::new(Point::origin(), Point::from(Vector::repeat(7.0)));
+        assert_eq!(query(aabb), handles[0..3].to_vec());
+
+        let aabb = AABB::new(
+            Point::from(Vector::repeat(-1000.0)),
+            Point::from(Vector::repeat(1000.0)),
+        );
+        let mut expected = handles.clone();
+        expected.push(large);
+        assert_eq!(query(aabb), expected);
+
+        let aabb = AABB::new(
+            Point::from(Vector::y() * 90.0),
+            Point::from(Vector::y() * 90.0 + Vector::repeat(1.0)),
+        );
+        assert_eq!(query(aabb), vec![large]);
+    }
+
+    #[test]
+    fn static_motion_quality() {
+        use super::STATIC_MOTION_UPDATE_PERIOD;
+        use crate::geometry::MotionQuality;
+        use crate::math::{Isometry, Vector};
+
+        let mut broad_phase = BroadPhase::new();
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let rb = bodies.insert(RigidBodyBuilder::new_static().build());
+        let co = ColliderBuilder::ball(0.5)
+            .motion_quality(MotionQuality::Static)
+            .build();
+        let co = colliders.insert(co, rb, &mut bodies);
+
+        let mut events = Vec::new();
+        let mut update = |colliders: &mut ColliderSet| {
+            broad_phase.update(0.0, colliders, &mut events);
+            colliders.clear_modified_colliders();
+            broad_phase.proxies[colliders[co].proxy_index].aabb
+        };
+
+        // The AABB isn't enlarged when the collider is inserted.
+        let initial_aabb = update(&mut colliders);
+        assert_eq!(initial_aabb, colliders[co].compute_aabb());
+
+        // Its motion is ignored until the next periodic update, where it is caught up.
+        colliders
+            .get_mut(co)
+            .unwrap()
+            .set_position(Isometry::new(Vector::x() * 2.0, na::zero()));
+        for _ in 1..STATIC_MOTION_UPDATE_PERIOD - 1 {
+            assert_eq!(update(&mut colliders), initial_aabb);
+        }
+        assert_eq!(update(&mut colliders), colliders[co].compute_aabb());
+    }
 }
@@ -2,12 +2,16 @@ use crate::geometry::ColliderHandle;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A pair of colliders.
 pub struct ColliderPair {
+    /// The first collider of the pair.
     pub collider1: ColliderHandle,
+    /// The second collider of the pair.
     pub collider2: ColliderHandle,
 }
 
 impl ColliderPair {
+    /// Creates a new pair of colliders.
     pub fn new(collider1: ColliderHandle, collider2: ColliderHandle) -> Self {
         ColliderPair {
             collider1,
@@ -15,10 +19,12 @@ impl ColliderPair {
         }
     }
 
+    /// Swaps the two colliders of this pair.
     pub fn swap(self) -> Self {
         Self::new(self.collider2, self.collider1)
     }
 
+    /// A pair with two collider handles with zero index and generation.
     pub fn zero() -> Self {
         Self {
             collider1: ColliderHandle::from_raw_parts(0, 0),
@@ -27,7 +33,12 @@ impl ColliderPair {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+/// Events occurring when the broad-phase starts or stops reporting a pair of colliders
+/// with overlapping AABBs.
 pub enum BroadPhasePairEvent {
+    /// The AABBs of the two colliders started overlapping.
     AddPair(ColliderPair),
+    /// The AABBs of the two colliders stopped overlapping, or one of the colliders was removed.
     DeletePair(ColliderPair),
 }
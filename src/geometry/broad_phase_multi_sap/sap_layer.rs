@@ -1,7 +1,7 @@
-use super::{SAPProxies, SAPProxy, SAPRegion, SAPRegionPool};
+use super::{SAPProxies, SAPProxy, SAPProxyData, SAPRegion, SAPRegionPool};
 use crate::geometry::broad_phase_multi_sap::DELETED_AABB_VALUE;
-use crate::geometry::{Collider, SAPProxyIndex, AABB};
-use crate::math::{Point, Real};
+use crate::geometry::{Collider, ColliderHandle, SAPProxyIndex, AABB};
+use crate::math::{Point, Real, DIM};
 use parry::utils::hashmap::{Entry, HashMap};
 
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -369,4 +369,86 @@ impl SAPLayer {
             }
         }
     }
+
+    /// Calls `callback` on the handle of each collider proper to this layer with a proxy
+    /// AABB intersecting `aabb`.
+    ///
+    /// Returns `false` if the traversal was interrupted because `callback` returned `false`.
+    pub fn colliders_with_aabb_intersecting_aabb(
+        &self,
+        aabb: &AABB,
+        proxies: &SAPProxies,
+        callback: &mut impl FnMut(&ColliderHandle) -> bool,
+    ) -> bool {
+        let start = super::point_key(aabb.mins, self.region_width);
+        let end = super::point_key(aabb.maxs, self.region_width);
+
+        let mut visit_region = |region_key: Point<i32>, region_id: SAPProxyIndex| {
+            let region = proxies[region_id].data.as_region();
+
+            for endpoint in &region.axes[0].endpoints {
+                if endpoint.is_sentinel() || !endpoint.is_start() {
+                    continue;
+                }
+
+                let proxy = &proxies[endpoint.proxy()];
+
+                // Colliders from other layers will be reported by their own layer.
+                if proxy.layer_id != self.layer_id {
+                    continue;
+                }
+
+                if let SAPProxyData::Collider(handle) = &proxy.data {
+                    // A collider is part of all the regions its AABB intersects. So only
+                    // report it from the region containing the lower corner of the
+                    // intersection between its AABB and `aabb`.
+                    if proxy.aabb.intersects(aabb)
+                        && super::point_key(proxy.aabb.mins.sup(&aabb.mins), self.region_width)
+                            == region_key
+                        && !callback(handle)
+                    {
+                        return false;
+                    }
+                }
+            }
+
+            true
+        };
+
+        let num_keys = (0..DIM).fold(1.0, |acc, i| acc * ((end[i] - start[i]) as Real + 1.0));
+
+        if num_keys > self.regions.len() as Real {
+            // The AABB spans more grid cells than there are regions.
+            for (region_key, region_id) in &self.regions {
+                if (0..DIM).all(|i| region_key[i] >= start[i] && region_key[i] <= end[i])
+                    && !visit_region(*region_key, *region_id)
+                {
+                    return false;
+                }
+            }
+        } else {
+            #[cfg(feature = "dim2")]
+            let k_range = 0..1;
+            #[cfg(feature = "dim3")]
+            let k_range = start.z..=end.z;
+
+            for i in start.x..=end.x {
+                for j in start.y..=end.y {
+                    for _k in k_range.clone() {
+                        #[cfg(feature = "dim2")]
+                        let region_key = Point::new(i, j);
+                        #[cfg(feature = "dim3")]
+                        let region_key = Point::new(i, j, _k);
+                        if let Some(region_id) = self.regions.get(&region_key) {
+                            if !visit_region(region_key, *region_id) {
+                                return false;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        true
+    }
 }
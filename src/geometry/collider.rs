@@ -1,10 +1,17 @@
-use crate::dynamics::{CoefficientCombineRule, MassProperties, RigidBodyHandle};
-use crate::geometry::{InteractionGroups, SAPProxyIndex, SharedShape, SolverFlags};
+use crate::dynamics::{CoefficientCombineRule, MassProperties, RigidBody, RigidBodyHandle};
+use crate::geometry::internal_edges::MeshTopology;
+#[cfg(feature = "dim3")]
+use crate::geometry::HeightFieldCellStatus;
+use crate::geometry::{
+    sanitize_trimesh, Capsule, HeightField, InteractionGroups, MultiSphere, SAPProxyIndex,
+    SharedShape, SolverFlags, SurfaceLocation, TriMeshFlags, VHACDParameters, Voxels,
+};
 use crate::math::{AngVector, Isometry, Point, Real, Rotation, Vector, DIM};
-use crate::parry::transformation::vhacd::VHACDParameters;
 use na::Unit;
 use parry::bounding_volume::{BoundingVolume, AABB};
 use parry::shape::Shape;
+use std::any::Any;
+use std::sync::Arc;
 
 bitflags::bitflags! {
     #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -15,6 +22,9 @@ bitflags::bitflags! {
         const FRICTION_COMBINE_RULE_10 = 1 << 2;
         const RESTITUTION_COMBINE_RULE_01 = 1 << 3;
         const RESTITUTION_COMBINE_RULE_10 = 1 << 4;
+        const INTERNAL_EDGE_CORRECTION = 1 << 5;
+        const ONE_SIDED = 1 << 6;
+        const DETAILED_CONTACT_EVENTS = 1 << 7;
     }
 }
 
@@ -42,6 +52,25 @@ impl ColliderFlags {
     }
 }
 
+bitflags::bitflags! {
+    #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+    /// The additional kinds of colliders a sensor reports intersections with.
+    ///
+    /// By default, no intersection is computed between two colliders if none of them is
+    /// attached to a dynamic rigid-body. These flags allow a sensor to lift this restriction
+    /// for some kinds of colliders. It is enough for one of the two colliders involved to
+    /// have the relevant flag set. These flags have no effect on non-sensor colliders, and
+    /// are ignored if the physics hooks filter the intersection pairs.
+    pub struct ActiveIntersectionTypes: u8 {
+        /// Report intersections with other sensors attached to non-dynamic rigid-bodies.
+        const SENSORS   = 1 << 0;
+        /// Report intersections with non-sensor colliders attached to static rigid-bodies.
+        const STATIC    = 1 << 1;
+        /// Report intersections with non-sensor colliders attached to kinematic rigid-bodies.
+        const KINEMATIC = 1 << 2;
+    }
+}
+
 #[derive(Clone)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 enum MassInfo {
@@ -52,29 +81,51 @@ enum MassInfo {
 
 bitflags::bitflags! {
     #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
-    /// Flags describing how the collider has been modified by the user.
-    pub(crate) struct ColliderChanges: u32 {
+    /// Flags describing how a collider has been modified.
+    ///
+    /// These flags are set as soon as the collider is modified, and are accumulated until
+    /// `ColliderSet::clear_modified_flags` is called. A collider that was just inserted into
+    /// a `ColliderSet` has all its flags set. See `Collider::changes` for details.
+    pub struct ColliderChanges: u32 {
+        /// The collider was borrowed mutably from its `ColliderSet`, or modified by the
+        /// physics pipeline.
         const MODIFIED             = 1 << 0;
+        /// The position of the collider relative to its parent changed.
         const POSITION_WRT_PARENT  = 1 << 1; // => BF & NF updates.
+        /// The world-space position of the collider changed, e.g., because its parent moved.
         const POSITION             = 1 << 2; // => BF & NF updates.
+        /// The collision groups or the active intersection types of the collider changed.
         const COLLISION_GROUPS     = 1 << 3; // => NF update.
+        /// The solver groups of the collider changed.
         const SOLVER_GROUPS        = 1 << 4; // => NF update.
+        /// The shape of the collider was replaced.
         const SHAPE                = 1 << 5; // => BF & NF update. NF pair workspace invalidation.
+        /// The collider became a sensor, or stopped being one.
         const SENSOR               = 1 << 6; // => NF update. NF pair invalidation.
+        /// The dominance group of the parent of the collider changed.
+        const PARENT_DOMINANCE     = 1 << 7; // => NF update.
+        /// The mass properties of the collider changed.
+        const MASS_PROPERTIES      = 1 << 8; // => Parent mass properties update.
+        /// Some heights of the heightfield, or some voxels, of the collider changed.
+        const HEIGHTFIELD          = 1 << 9; // => BF & NF update. NF pair workspace invalidation near the modified cells.
+        /// The status (dynamic, static, kinematic) of the parent of the collider changed.
+        const PARENT_STATUS        = 1 << 10; // => BF & NF update.
     }
 }
 
 impl ColliderChanges {
-    pub fn needs_broad_phase_update(self) -> bool {
+    pub(crate) fn needs_broad_phase_update(self) -> bool {
         self.intersects(
             ColliderChanges::POSITION_WRT_PARENT
                 | ColliderChanges::POSITION
-                | ColliderChanges::SHAPE,
+                | ColliderChanges::SHAPE
+                | ColliderChanges::HEIGHTFIELD
+                | ColliderChanges::PARENT_STATUS,
         )
     }
 
-    pub fn needs_narrow_phase_update(self) -> bool {
-        self.bits() > 1
+    pub(crate) fn needs_narrow_phase_update(self) -> bool {
+        (self - ColliderChanges::MASS_PROPERTIES).bits() > 1
     }
 }
 
@@ -84,12 +135,21 @@ impl ColliderChanges {
 ///
 /// To build a new collider, use the `ColliderBuilder` structure.
 pub struct Collider {
-    shape: SharedShape,
+    pub(crate) shape: SharedShape,
     mass_info: MassInfo,
+    // The mass properties (expressed in the parent's local-space) contributed to the
+    // parent before the last modification of the collider's mass or relative position.
+    pub(crate) prev_mass_properties: Option<Box<MassProperties>>,
     pub(crate) flags: ColliderFlags,
     pub(crate) solver_flags: SolverFlags,
     pub(crate) changes: ColliderChanges,
+    // The changes already handled by the physics pipeline, but not yet cleared by the user.
+    pub(crate) tracked_changes: ColliderChanges,
     pub(crate) parent: RigidBodyHandle,
+    // The parent known by the narrow-phase, if the parent changed since the last timestep.
+    pub(crate) prev_parent: Option<RigidBodyHandle>,
+    // The local-space region covering the heightfield cells or voxels modified since the last timestep.
+    pub(crate) heightfield_changes: Option<AABB>,
     pub(crate) delta: Isometry<Real>,
     pub(crate) position: Isometry<Real>,
     /// The friction coefficient of this collider.
@@ -98,9 +158,170 @@ pub struct Collider {
     pub restitution: Real,
     pub(crate) collision_groups: InteractionGroups,
     pub(crate) solver_groups: InteractionGroups,
+    pub(crate) active_intersection_types: ActiveIntersectionTypes,
     pub(crate) proxy_index: SAPProxyIndex,
+    pub(crate) one_way_platform: Option<OneWayPlatform>,
+    pub(crate) part_materials: Option<Vec<ColliderMaterial>>,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub(crate) surface_material: Option<Arc<dyn SurfaceMaterial>>,
+    pub(crate) motion_quality: MotionQuality,
+    pub(crate) contact_skin: Real,
+    pub(crate) contact_force_event_threshold: Real,
+    // The adjacency information used for internal edge correction, if enabled.
+    pub(crate) mesh_topology: Option<MeshTopology>,
     /// User-defined data associated to this rigid-body.
     pub user_data: u128,
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    user_object: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+fn shape_ccd_thickness(shape: &dyn Shape) -> Real {
+    if let Some(compound) = shape.as_compound() {
+        return compound
+            .shapes()
+            .iter()
+            .map(|(_, part)| shape_ccd_thickness(&**part))
+            .fold(Real::MAX, Real::min);
+    }
+
+    let round = shape
+        .as_round_cuboid()
+        .map(|s| (s.base_shape.ccd_thickness(), s.border_radius))
+        .or_else(|| {
+            shape
+                .as_round_triangle()
+                .map(|s| (s.base_shape.ccd_thickness(), s.border_radius))
+        });
+    #[cfg(feature = "dim2")]
+    let round = round.or_else(|| {
+        shape
+            .as_round_convex_polygon()
+            .map(|s| (s.base_shape.ccd_thickness(), s.border_radius))
+    });
+    #[cfg(feature = "dim3")]
+    let round = round
+        .or_else(|| {
+            shape
+                .as_round_convex_polyhedron()
+                .map(|s| (s.base_shape.ccd_thickness(), s.border_radius))
+        })
+        .or_else(|| {
+            shape
+                .as_round_cylinder()
+                .map(|s| (s.base_shape.ccd_thickness(), s.border_radius))
+        })
+        .or_else(|| {
+            shape
+                .as_round_cone()
+                .map(|s| (s.base_shape.ccd_thickness(), s.border_radius))
+        });
+
+    round
+        .map(|(thickness, border_radius)| thickness + border_radius)
+        .unwrap_or_else(|| shape.ccd_thickness())
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// The configuration of a collider behaving as a one-way platform.
+///
+/// Contacts with a one-way platform are only taken into account by the constraints solver if
+/// the contact normal pointing toward the exterior of the platform is close enough to an allowed
+/// direction. For example, a platform with the allowed normal `Vector::y()` will support the
+/// objects falling on top of it, while letting objects jump through it from below.
+pub struct OneWayPlatform {
+    /// The allowed contact normal, expressed in the local-space of the platform collider.
+    pub allowed_local_normal: Vector<Real>,
+    /// The maximum angle between the allowed normal and the contact normal.
+    pub allowed_angle: Real,
+}
+
+impl OneWayPlatform {
+    /// Creates a one-way platform configuration with the given allowed normal and angle.
+    pub fn new(allowed_local_normal: Vector<Real>, allowed_angle: Real) -> Self {
+        Self {
+            allowed_local_normal,
+            allowed_angle,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// The material of one part of a compound collider.
+pub struct ColliderMaterial {
+    /// The friction coefficient of this part.
+    pub friction: Real,
+    /// The restitution coefficient of this part.
+    pub restitution: Real,
+}
+
+impl ColliderMaterial {
+    /// Creates a material with the given friction and restitution coefficients.
+    pub fn new(friction: Real, restitution: Real) -> Self {
+        Self {
+            friction,
+            restitution,
+        }
+    }
+}
+
+/// A user-defined material varying across the surface of a collider.
+///
+/// This is queried for each contact point involving the collider it is attached to, so the
+/// friction and restitution can vary across a single collider, e.g., ice patches on a
+/// heightfield, or per-triangle materials on a triangle mesh. This is implemented for all
+/// closures with the same signature as `SurfaceMaterial::material_at`.
+pub trait SurfaceMaterial: Send + Sync {
+    /// The material at a contact point on the surface of the collider.
+    ///
+    /// The `subshape` is the index of the part of the collider's shape involved in the contact,
+    /// e.g., the index of a triangle of a triangle mesh, or of a part of a compound shape. It
+    /// is always zero for shapes that aren't composite. The `local_point` is the contact point
+    /// expressed in the local-space of the collider.
+    fn material_at(&self, subshape: u32, local_point: &Point<Real>) -> ColliderMaterial;
+}
+
+impl<F> SurfaceMaterial for F
+where
+    F: Fn(u32, &Point<Real>) -> ColliderMaterial + Send + Sync,
+{
+    fn material_at(&self, subshape: u32, local_point: &Point<Real>) -> ColliderMaterial {
+        self(subshape, local_point)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+/// A hint describing how a collider is expected to move.
+///
+/// This is used by the broad-phase to choose how the AABB of the collider is maintained.
+///
+/// There is no dedicated mode for teleporting colliders: use `Fast` for them. Re-inserting
+/// the proxy of a teleported collider isn't cheaper than moving it, because the hierarchical
+/// sweep-and-prune already moves a proxy directly to the regions of its new position.
+pub enum MotionQuality {
+    /// The collider is not expected to move, e.g., a building or a piece of terrain.
+    ///
+    /// Its AABB is not enlarged. If it moves anyway, its AABB is only updated once every few
+    /// timesteps, so the contacts at its new position may be detected with a small delay. The
+    /// changes of its shape are taken into account immediately.
+    Static,
+    /// The collider moves slowly, e.g., a drifting debris or a floating crate.
+    ///
+    /// Its AABB is enlarged by a margin proportional to its size. The broad-phase skips
+    /// its update as long as its current AABB remains inside of this enlarged AABB.
+    Slow,
+    /// The collider may move fast, or its position may change discontinuously.
+    ///
+    /// Its AABB is not enlarged, and is recomputed at each timestep where it moves.
+    Fast,
+}
+
+impl Default for MotionQuality {
+    fn default() -> Self {
+        MotionQuality::Fast
+    }
 }
 
 impl Collider {
@@ -108,6 +329,129 @@ impl Collider {
         self.parent = RigidBodyHandle::invalid();
         self.proxy_index = crate::INVALID_U32;
         self.changes = ColliderChanges::empty();
+        self.tracked_changes = ColliderChanges::empty();
+        self.prev_mass_properties = None;
+        self.prev_parent = None;
+        self.heightfield_changes = None;
+    }
+
+    /// The one-way platform configuration of this collider, if it is a one-way platform.
+    pub fn one_way_platform(&self) -> Option<&OneWayPlatform> {
+        self.one_way_platform.as_ref()
+    }
+
+    /// Sets whether this collider behaves as a one-way platform.
+    pub fn set_one_way_platform(&mut self, one_way_platform: Option<OneWayPlatform>) {
+        self.one_way_platform = one_way_platform;
+    }
+
+    /// The materials of each part of this collider, if it is a compound shape with per-part materials.
+    pub fn part_materials(&self) -> Option<&[ColliderMaterial]> {
+        self.part_materials.as_deref()
+    }
+
+    /// Sets the materials of each part of this compound collider.
+    ///
+    /// The i-th material applies to the contacts involving the i-th part of the compound
+    /// shape. The contacts involving parts without a material, or involving a collider that
+    /// isn't a compound shape, use the `friction` and `restitution` of this collider.
+    pub fn set_part_materials(&mut self, part_materials: Option<Vec<ColliderMaterial>>) {
+        self.part_materials = part_materials;
+    }
+
+    /// The user-defined material varying across the surface of this collider, if any.
+    pub fn surface_material(&self) -> Option<&Arc<dyn SurfaceMaterial>> {
+        self.surface_material.as_ref()
+    }
+
+    /// Sets the user-defined material varying across the surface of this collider.
+    ///
+    /// If set, the surface material takes precedence over the part materials and over the
+    /// `friction` and `restitution` of this collider. It is not serialized, so it must be set
+    /// again after deserializing this collider.
+    pub fn set_surface_material(&mut self, material: Option<Arc<dyn SurfaceMaterial>>) {
+        self.surface_material = material;
+    }
+
+    // The friction and restitution coefficients of the given sub-shape of this collider.
+    pub(crate) fn subshape_material(&self, subshape: u32) -> (Real, Real) {
+        let part_material = self
+            .part_materials
+            .as_ref()
+            .filter(|_| self.shape.as_compound().is_some())
+            .and_then(|materials| materials.get(subshape as usize));
+
+        match part_material {
+            Some(material) => (material.friction, material.restitution),
+            None => (self.friction, self.restitution),
+        }
+    }
+
+    // The friction and restitution coefficients at a contact point, given in the local-space
+    // of the given sub-shape of this collider.
+    pub(crate) fn surface_material_at(
+        &self,
+        subshape: u32,
+        subshape_pos: Option<&Isometry<Real>>,
+        point: &Point<Real>,
+    ) -> (Real, Real) {
+        match &self.surface_material {
+            Some(material) => {
+                let local_point = subshape_pos.map(|pos| pos * point).unwrap_or(*point);
+                let material = material.material_at(subshape, &local_point);
+                (material.friction, material.restitution)
+            }
+            None => self.subshape_material(subshape),
+        }
+    }
+
+    /// The contact skin of this collider.
+    pub fn contact_skin(&self) -> Real {
+        self.contact_skin
+    }
+
+    /// Sets the contact skin of this collider.
+    ///
+    /// The contact skin is a margin added around the shape of this collider: contacts are
+    /// generated as soon as another collider gets closer than this margin, and are resolved
+    /// so that both colliders remain at this distance from each other. A small skin leaves a
+    /// visible gap between the colliders but improves the stability of stacks.
+    ///
+    /// The skin is ignored by the continuous collision detection: the time of impact is
+    /// computed between the actual shapes, so a fast collider stopped by CCD may end up closer
+    /// to the obstacle than its skin for one timestep.
+    ///
+    /// The broad-phase will take the new skin into account the next time this collider moves.
+    pub fn set_contact_skin(&mut self, skin: Real) {
+        assert!(skin >= 0.0, "The contact skin must be positive or zero.");
+        self.contact_skin = skin;
+    }
+
+    /// The contact force event threshold of this collider.
+    pub fn contact_force_event_threshold(&self) -> Real {
+        self.contact_force_event_threshold
+    }
+
+    /// Sets the contact force event threshold of this collider.
+    ///
+    /// After each resolution of the velocity constraints, a `ContactForceEvent` is emitted for
+    /// each contact pair involving this collider where the total contact force exceeds this
+    /// threshold. Set it to `Real::MAX` (the default) to disable these events.
+    pub fn set_contact_force_event_threshold(&mut self, threshold: Real) {
+        self.contact_force_event_threshold = threshold;
+    }
+
+    /// The hint describing how this collider is expected to move.
+    pub fn motion_quality(&self) -> MotionQuality {
+        self.motion_quality
+    }
+
+    /// Sets the hint describing how this collider is expected to move.
+    ///
+    /// This will be taken into account by the broad-phase the next time it has to update this
+    /// collider. See [`MotionQuality`] for details.
+    pub fn set_motion_quality(&mut self, motion_quality: MotionQuality) {
+        self.motion_quality = motion_quality;
     }
 
     /// The rigid body this collider is attached to.
@@ -115,11 +459,87 @@ impl Collider {
         self.parent
     }
 
+    /// The modifications made to this collider since the last call to
+    /// `ColliderSet::clear_modified_flags`.
+    ///
+    /// This includes the modifications made by the user (through `ColliderSet::get_mut` and
+    /// the setters of this collider), as well as the motion of this collider during the
+    /// timesteps of the physics pipeline. Note that the colliders attached to awake
+    /// rigid-bodies are reported as moved at each timestep.
+    pub fn changes(&self) -> ColliderChanges {
+        self.changes | self.tracked_changes
+    }
+
     /// Is this collider a sensor?
     pub fn is_sensor(&self) -> bool {
         self.flags.is_sensor()
     }
 
+    /// Is the internal edge correction enabled for this collider?
+    pub fn is_internal_edge_correction_enabled(&self) -> bool {
+        self.flags.contains(ColliderFlags::INTERNAL_EDGE_CORRECTION)
+    }
+
+    /// Enables or disables the internal edge correction for this collider.
+    ///
+    /// If this collider is a triangle mesh (3D) or a polyline (2D), the contacts generated
+    /// by the edges (3D) or vertices (2D) shared by two of its primitives will use the normal
+    /// of the primitive instead. This prevents objects sliding on a tessellated floor from
+    /// catching on the seams between triangles (a.k.a. ghost collisions). This assumes the
+    /// primitives of the mesh are consistently oriented.
+    ///
+    /// This has no effect on the other shapes.
+    pub fn enable_internal_edge_correction(&mut self, enabled: bool) {
+        self.flags
+            .set(ColliderFlags::INTERNAL_EDGE_CORRECTION, enabled);
+        self.update_mesh_topology();
+    }
+
+    /// Are detailed contact events enabled for this collider?
+    pub fn is_detailed_contact_events_enabled(&self) -> bool {
+        self.flags.contains(ColliderFlags::DETAILED_CONTACT_EVENTS)
+    }
+
+    /// Enables or disables the detailed contact events for this collider.
+    ///
+    /// If enabled, a `DetailedContactEvent` carrying the contact points of the contact pair is
+    /// emitted after each contact event involving this collider. Collecting these points
+    /// allocates memory, so they are disabled by default.
+    pub fn enable_detailed_contact_events(&mut self, enabled: bool) {
+        self.flags
+            .set(ColliderFlags::DETAILED_CONTACT_EVENTS, enabled);
+    }
+
+    /// Is this collider a one-sided polyline?
+    #[cfg(feature = "dim2")]
+    pub fn is_one_sided(&self) -> bool {
+        self.flags.contains(ColliderFlags::ONE_SIDED)
+    }
+
+    /// Makes this collider one-sided or two-sided.
+    ///
+    /// If this collider is a polyline, a segment only collides with the colliders whose position
+    /// (i.e. the origin of their local-space) is on the left side of this segment, i.e., above it
+    /// if its first vertex is to the left of its second vertex. This lets objects pass through the polyline from
+    /// its back side, and prevents objects pushed deep into the polyline from being ejected
+    /// on the wrong side. The left side of the segments of a closed polyline is its interior
+    /// if its vertices are ordered counterclockwise.
+    ///
+    /// This has no effect on the other shapes. The continuous collision detection doesn't take
+    /// this into account.
+    #[cfg(feature = "dim2")]
+    pub fn set_one_sided(&mut self, one_sided: bool) {
+        self.flags.set(ColliderFlags::ONE_SIDED, one_sided);
+    }
+
+    pub(crate) fn update_mesh_topology(&mut self) {
+        self.mesh_topology = if self.is_internal_edge_correction_enabled() {
+            MeshTopology::new(self.shape())
+        } else {
+            None
+        };
+    }
+
     /// The combine rule used by this collider to combine its friction
     /// coefficient with the friction coefficient of the other collider it
     /// is in contact with.
@@ -156,6 +576,42 @@ impl Collider {
         }
     }
 
+    /// The additional kinds of colliders this sensor reports intersections with.
+    pub fn active_intersection_types(&self) -> ActiveIntersectionTypes {
+        self.active_intersection_types
+    }
+
+    /// Sets the additional kinds of colliders this sensor reports intersections with.
+    ///
+    /// See [`ActiveIntersectionTypes`] for details.
+    pub fn set_active_intersection_types(&mut self, types: ActiveIntersectionTypes) {
+        if self.active_intersection_types != types {
+            self.changes.insert(ColliderChanges::COLLISION_GROUPS);
+            self.active_intersection_types = types;
+        }
+    }
+
+    // Does this collider report its intersections with `other`, attached to the non-dynamic
+    // `other_parent`, even if its own parent isn't dynamic?
+    pub(crate) fn reports_non_dynamic_intersection(
+        &self,
+        other: &Collider,
+        other_parent: &RigidBody,
+    ) -> bool {
+        if !self.is_sensor() {
+            false
+        } else if other.is_sensor() {
+            self.active_intersection_types
+                .contains(ActiveIntersectionTypes::SENSORS)
+        } else if other_parent.is_static() {
+            self.active_intersection_types
+                .contains(ActiveIntersectionTypes::STATIC)
+        } else {
+            self.active_intersection_types
+                .contains(ActiveIntersectionTypes::KINEMATIC)
+        }
+    }
+
     #[doc(hidden)]
     pub fn set_position_debug(&mut self, position: Isometry<Real>) {
         self.position = position;
@@ -184,7 +640,11 @@ impl Collider {
     }
 
     /// Sets the position of this collider wrt. its parent rigid-body.
+    ///
+    /// The world-space position of this collider and the mass properties of its
+    /// parent rigid-body will be updated accordingly at the next timestep.
     pub fn set_position_wrt_parent(&mut self, position: Isometry<Real>) {
+        self.mark_mass_properties_as_modified();
         self.changes.insert(ColliderChanges::POSITION_WRT_PARENT);
         self.delta = position;
     }
@@ -223,39 +683,454 @@ impl Collider {
         }
     }
 
+    /// Sets the uniform density of this collider.
+    ///
+    /// This overrides any mass properties previously set with `set_mass_properties`.
+    /// The mass properties of the parent rigid-body will be updated accordingly
+    /// at the next timestep.
+    pub fn set_density(&mut self, density: Real) {
+        self.mark_mass_properties_as_modified();
+        self.mass_info = MassInfo::Density(density);
+    }
+
+    /// Sets the mass properties of this collider.
+    ///
+    /// This overrides any density previously set with `set_density`.
+    /// The mass properties of the parent rigid-body will be updated accordingly
+    /// at the next timestep.
+    pub fn set_mass_properties(&mut self, mass_properties: MassProperties) {
+        self.mark_mass_properties_as_modified();
+        self.mass_info = MassInfo::MassProperties(Box::new(mass_properties));
+    }
+
+    /// Sets the mass of this collider.
+    ///
+    /// The center of mass and angular inertia are computed from the current shape of this
+    /// collider, scaled to match the given mass. They won't be updated if the shape is modified
+    /// afterwards. This overrides any density or mass properties previously set.
+    pub fn set_mass(&mut self, mass: Real) {
+        let mass_properties = mass_properties_with_mass(&*self.shape, mass);
+        self.set_mass_properties(mass_properties);
+    }
+
+    fn mark_mass_properties_as_modified(&mut self) {
+        // Only keep the mass properties from before the first modification
+        // since this is what the parent rigid-body currently accounts for.
+        if self.prev_mass_properties.is_none() {
+            self.prev_mass_properties =
+                Some(Box::new(self.mass_properties().transform_by(&self.delta)));
+        }
+
+        self.changes.insert(ColliderChanges::MASS_PROPERTIES);
+    }
+
+    /// The user-defined object attached to this collider, if it is of type `T`.
+    pub fn user_object<T: Any>(&self) -> Option<&T> {
+        self.user_object.as_ref()?.downcast_ref()
+    }
+
+    /// A mutable reference to the user-defined object attached to this collider, if it is of type `T`.
+    ///
+    /// Returns `None` if the object is shared with a clone of this collider.
+    pub fn user_object_mut<T: Any>(&mut self) -> Option<&mut T> {
+        Arc::get_mut(self.user_object.as_mut()?)?.downcast_mut()
+    }
+
+    /// Attaches an arbitrary user-defined object to this collider, replacing the previous one.
+    ///
+    /// Unlike `user_data`, this object is not serialized. Cloning this collider
+    /// will share the object between the clones.
+    pub fn set_user_object<T: Any + Send + Sync>(&mut self, object: T) {
+        self.user_object = Some(Arc::new(object));
+    }
+
+    /// Detaches the user-defined object attached to this collider, if any.
+    pub fn remove_user_object(&mut self) -> Option<Arc<dyn Any + Send + Sync>> {
+        self.user_object.take()
+    }
+
     /// The geometric shape of this collider.
     pub fn shape(&self) -> &dyn Shape {
         &*self.shape.0
     }
 
+    /// The thickness of this collider used by the continuous collision detection.
+    ///
+    /// Unlike `Shape::ccd_thickness`, this includes the border radius of round shapes (and of
+    /// the round parts of compound shapes). This makes the CCD less conservative for small
+    /// round shapes, e.g., a round triangle is as thick as a ball of radius `border_radius`.
+    pub fn ccd_thickness(&self) -> Real {
+        shape_ccd_thickness(self.shape())
+    }
+
     /// A mutable reference to the geometric shape of this collider.
     ///
     /// If that shape is shared by multiple colliders, it will be
     /// cloned first so that `self` contains a unique copy of that
     /// shape that you can modify.
+    ///
+    /// The mass properties (if they are computed from the collider's density) and CCD
+    /// parameters of the parent rigid-body will be updated accordingly at the next timestep.
     pub fn shape_mut(&mut self) -> &mut dyn Shape {
-        self.changes.insert(ColliderChanges::SHAPE);
+        self.mark_shape_as_modified();
         self.shape.make_mut()
     }
 
     /// Sets the shape of this collider.
+    ///
+    /// The mass properties (if they are computed from the collider's density) and CCD
+    /// parameters of the parent rigid-body will be updated accordingly at the next timestep.
+    /// Contacts involving this collider are preserved as long as the collider still
+    /// touches the same colliders.
     pub fn set_shape(&mut self, shape: SharedShape) {
-        self.changes.insert(ColliderChanges::SHAPE);
+        self.mark_shape_as_modified();
         self.shape = shape;
     }
 
+    /// A capsule with the same bottom and axis as the capsule shape of this collider,
+    /// but with the given half-height and radius.
+    ///
+    /// The bottom of the capsule is the extremity of the cap around `capsule.segment.a`,
+    /// so a character resting on the ground keeps resting on it once resized.
+    ///
+    /// Returns `None` if the shape of this collider is not a capsule.
+    pub fn resized_capsule(&self, half_height: Real, radius: Real) -> Option<Capsule> {
+        let capsule = self.shape.as_capsule()?;
+        let axis = (capsule.segment.b - capsule.segment.a)
+            .try_normalize(Real::EPSILON)
+            .unwrap_or_else(Vector::y);
+        let bottom = capsule.segment.a - axis * capsule.radius;
+        let a = bottom + axis * radius;
+
+        Some(Capsule::new(a, a + axis * (half_height * 2.0), radius))
+    }
+
+    /// Changes the half-height and radius of the capsule shape of this collider.
+    ///
+    /// The bottom and axis of the capsule are preserved (see `Collider::resized_capsule`).
+    /// This is typically used for making a character crouch. See
+    /// `QueryPipeline::capsule_resize_obstacle` for checking if the resized capsule would
+    /// overlap another collider (e.g. a ceiling).
+    ///
+    /// The mass properties (if they are computed from the collider's density) and CCD
+    /// parameters of the parent rigid-body will be updated accordingly at the next timestep.
+    ///
+    /// Returns the capsule before the resize, or `None` (and leaves the shape unchanged)
+    /// if the shape of this collider is not a capsule.
+    pub fn set_capsule_dimensions(&mut self, half_height: Real, radius: Real) -> Option<Capsule> {
+        let capsule = self.resized_capsule(half_height, radius)?;
+        let old_capsule = *self.shape.as_capsule()?;
+        self.set_shape(SharedShape::new(capsule));
+        Some(old_capsule)
+    }
+
+    /// Replaces a block of the heights of the heightfield shape of this collider.
+    ///
+    /// The heights starting at index `first` are replaced by `heights`. Only the contacts
+    /// involving the heightfield cells adjacent to the modified heights are invalidated at
+    /// the next timestep, which makes this suitable for terrain deformation.
+    ///
+    /// This rebuilds the whole heightfield, so its cost is proportional to the total number of
+    /// heights, not to the size of the block. Prefer a single call with a larger block over
+    /// many small calls.
+    ///
+    /// Panics if the shape of this collider is not a heightfield, or if the block of
+    /// heights doesn't fit inside of the heightfield.
+    #[cfg(feature = "dim2")]
+    pub fn set_heightfield_heights(&mut self, first: usize, heights: &na::DVector<Real>) {
+        if heights.is_empty() {
+            return;
+        }
+
+        let heightfield = self
+            .shape
+            .as_heightfield()
+            .expect("The collider shape is not a heightfield.");
+        let scale = *heightfield.scale();
+        let mut new_heights = heightfield.heights().clone();
+        new_heights
+            .rows_mut(first, heights.len())
+            .copy_from(heights);
+
+        // The modified segments are the ones adjacent to the modified heights.
+        let num_segments = new_heights.len() - 1;
+        let step = scale.x / num_segments as Real;
+        let min_x = -scale.x / 2.0 + first.saturating_sub(1) as Real * step;
+        let max_x = -scale.x / 2.0 + (first + heights.len()).min(num_segments) as Real * step;
+
+        let mut new_heightfield = HeightField::new(new_heights, scale);
+
+        // Keep the holes of the original heightfield.
+        for i in 0..num_segments {
+            if heightfield.is_segment_removed(i) {
+                new_heightfield.set_segment_removed(i, true);
+            }
+        }
+
+        let y_range = self
+            .shape
+            .compute_local_aabb()
+            .merged(&new_heightfield.compute_local_aabb());
+        let p1 = Point::new(min_x, y_range.mins.y);
+        let p2 = Point::new(max_x, y_range.maxs.y);
+
+        self.shape = SharedShape::new(new_heightfield);
+        self.mark_heightfield_region_as_modified(AABB::new(p1.inf(&p2), p1.sup(&p2)));
+    }
+
+    /// Replaces a block of the heights of the heightfield shape of this collider.
+    ///
+    /// The heights starting at the row `first_row` and column `first_col` are replaced
+    /// by `heights`. Only the contacts involving the heightfield cells adjacent to the
+    /// modified heights are invalidated at the next timestep, which makes this suitable
+    /// for terrain deformation.
+    ///
+    /// This rebuilds the whole heightfield, so its cost is proportional to the total number of
+    /// heights, not to the size of the block. Prefer a single call with a larger block over
+    /// many small calls.
+    ///
+    /// Panics if the shape of this collider is not a heightfield, or if the block of
+    /// heights doesn't fit inside of the heightfield.
+    #[cfg(feature = "dim3")]
+    pub fn set_heightfield_heights(
+        &mut self,
+        first_row: usize,
+        first_col: usize,
+        heights: &na::DMatrix<Real>,
+    ) {
+        if heights.is_empty() {
+            return;
+        }
+
+        let heightfield = self
+            .shape
+            .as_heightfield()
+            .expect("The collider shape is not a heightfield.");
+        let scale = *heightfield.scale();
+        let mut new_heights = heightfield.heights().clone();
+        new_heights
+            .slice_mut((first_row, first_col), heights.shape())
+            .copy_from(heights);
+
+        // The modified triangles are the ones adjacent to the modified heights.
+        let nrows = new_heights.nrows();
+        let ncols = new_heights.ncols();
+        let row_step = scale.z / (nrows - 1) as Real;
+        let col_step = scale.x / (ncols - 1) as Real;
+        let min_z = -scale.z / 2.0 + first_row.saturating_sub(1) as Real * row_step;
+        let max_z =
+            -scale.z / 2.0 + (first_row + heights.nrows()).min(nrows - 1) as Real * row_step;
+        let min_x = -scale.x / 2.0 + first_col.saturating_sub(1) as Real * col_step;
+        let max_x =
+            -scale.x / 2.0 + (first_col + heights.ncols()).min(ncols - 1) as Real * col_step;
+
+        let mut new_heightfield = HeightField::new(new_heights, scale);
+
+        // Keep the holes, and other cell statuses, of the original heightfield.
+        for i in 0..nrows - 1 {
+            for j in 0..ncols - 1 {
+                new_heightfield.set_cell_status(i, j, heightfield.cell_status(i, j));
+            }
+        }
+
+        let y_range = self
+            .shape
+            .compute_local_aabb()
+            .merged(&new_heightfield.compute_local_aabb());
+        let p1 = Point::new(min_x, y_range.mins.y, min_z);
+        let p2 = Point::new(max_x, y_range.maxs.y, max_z);
+
+        self.shape = SharedShape::new(new_heightfield);
+        self.mark_heightfield_region_as_modified(AABB::new(p1.inf(&p2), p1.sup(&p2)));
+    }
+
+    /// Is the segment `i` of the heightfield shape of this collider a hole?
+    ///
+    /// Panics if the shape of this collider is not a heightfield.
+    #[cfg(feature = "dim2")]
+    pub fn is_heightfield_hole(&self, i: usize) -> bool {
+        self.shape
+            .as_heightfield()
+            .expect("The collider shape is not a heightfield.")
+            .is_segment_removed(i)
+    }
+
+    /// Marks the segment `i` of the heightfield shape of this collider as a hole, or removes
+    /// this hole.
+    ///
+    /// Holes don't generate any contact, and are ignored by the CCD. Only the contacts involving
+    /// the modified segment are invalidated at the next timestep.
+    ///
+    /// This copies the whole heightfield, so its cost is proportional to its number of heights.
+    ///
+    /// Panics if the shape of this collider is not a heightfield.
+    #[cfg(feature = "dim2")]
+    pub fn set_heightfield_hole(&mut self, i: usize, is_hole: bool) {
+        let mut new_heightfield = self
+            .shape
+            .as_heightfield()
+            .expect("The collider shape is not a heightfield.")
+            .clone();
+        new_heightfield.set_segment_removed(i, is_hole);
+
+        let scale = *new_heightfield.scale();
+        let step = scale.x / (new_heightfield.heights().len() - 1) as Real;
+        let y_range = new_heightfield.compute_local_aabb();
+        let min_x = -scale.x / 2.0 + i as Real * step;
+        let p1 = Point::new(min_x, y_range.mins.y);
+        let p2 = Point::new(min_x + step, y_range.maxs.y);
+
+        self.shape = SharedShape::new(new_heightfield);
+        self.mark_heightfield_region_as_modified(AABB::new(p1, p2));
+    }
+
+    /// Is the cell at the row `i` and column `j` of the heightfield shape of this collider a hole?
+    ///
+    /// Panics if the shape of this collider is not a heightfield.
+    #[cfg(feature = "dim3")]
+    pub fn is_heightfield_hole(&self, i: usize, j: usize) -> bool {
+        self.shape
+            .as_heightfield()
+            .expect("The collider shape is not a heightfield.")
+            .cell_status(i, j)
+            .contains(HeightFieldCellStatus::CELL_REMOVED)
+    }
+
+    /// Marks the cell at the row `i` and column `j` of the heightfield shape of this collider
+    /// as a hole, or removes this hole.
+    ///
+    /// Both triangles of a hole don't generate any contact, and are ignored by the CCD. Only the
+    /// contacts involving the modified cell are invalidated at the next timestep, which makes this
+    /// suitable for cutting caves or tunnels into terrains.
+    ///
+    /// This copies the whole heightfield, so its cost is proportional to its number of heights.
+    ///
+    /// Panics if the shape of this collider is not a heightfield.
+    #[cfg(feature = "dim3")]
+    pub fn set_heightfield_hole(&mut self, i: usize, j: usize, is_hole: bool) {
+        let mut new_heightfield = self
+            .shape
+            .as_heightfield()
+            .expect("The collider shape is not a heightfield.")
+            .clone();
+        let mut status = new_heightfield.cell_status(i, j);
+        status.set(HeightFieldCellStatus::CELL_REMOVED, is_hole);
+        new_heightfield.set_cell_status(i, j, status);
+
+        let scale = *new_heightfield.scale();
+        let heights = new_heightfield.heights();
+        let row_step = scale.z / (heights.nrows() - 1) as Real;
+        let col_step = scale.x / (heights.ncols() - 1) as Real;
+        let y_range = new_heightfield.compute_local_aabb();
+        let min_z = -scale.z / 2.0 + i as Real * row_step;
+        let min_x = -scale.x / 2.0 + j as Real * col_step;
+        let p1 = Point::new(min_x, y_range.mins.y, min_z);
+        let p2 = Point::new(min_x + col_step, y_range.maxs.y, min_z + row_step);
+
+        self.shape = SharedShape::new(new_heightfield);
+        self.mark_heightfield_region_as_modified(AABB::new(p1, p2));
+    }
+
+    /// Fills or empties some of the voxels of the voxels shape of this collider.
+    ///
+    /// Each element of `voxels` is the key of a voxel, and whether or not it is filled. Only
+    /// the contacts involving the modified voxels are affected at the next timestep, which makes
+    /// this suitable for destructible terrains. The mass properties (if they are computed from
+    /// the collider's density) of the parent rigid-body will be updated accordingly at the next
+    /// timestep.
+    ///
+    /// Panics if the shape of this collider is not a `Voxels`, or if one of the keys is
+    /// outside of its grid.
+    pub fn set_voxels(&mut self, voxels: &[(Point<u32>, bool)]) {
+        if voxels.is_empty() {
+            return;
+        }
+
+        let mut new_voxels = self
+            .shape
+            .as_shape::<Voxels>()
+            .expect("The collider shape is not a voxels shape.")
+            .clone();
+        let mut region = new_voxels.voxel_aabb(voxels[0].0);
+
+        for (key, filled) in voxels {
+            new_voxels.set_filled(*key, *filled);
+            region.merge(&new_voxels.voxel_aabb(*key));
+        }
+
+        if let MassInfo::Density(_) = self.mass_info {
+            self.mark_mass_properties_as_modified();
+        }
+
+        self.shape = SharedShape::new(new_voxels);
+        self.mark_heightfield_region_as_modified(region);
+    }
+
+    fn mark_heightfield_region_as_modified(&mut self, region: AABB) {
+        self.changes.insert(ColliderChanges::HEIGHTFIELD);
+        self.heightfield_changes = Some(match self.heightfield_changes {
+            Some(prev_region) => prev_region.merged(&region),
+            None => region,
+        });
+    }
+
+    /// Checks if the AABB of `other`, enlarged by `margin`, intersects the
+    /// heightfield region modified since the last timestep.
+    pub(crate) fn heightfield_changes_intersect(&self, other: &Collider, margin: Real) -> bool {
+        if let Some(region) = &self.heightfield_changes {
+            let pos12 = self.position.inv_mul(&other.position);
+            other
+                .shape
+                .compute_aabb(&pos12)
+                .loosened(margin)
+                .intersects(region)
+        } else {
+            false
+        }
+    }
+
+    fn mark_shape_as_modified(&mut self) {
+        if let MassInfo::Density(_) = self.mass_info {
+            self.mark_mass_properties_as_modified();
+        }
+
+        self.changes.insert(ColliderChanges::SHAPE);
+    }
+
+    /// The location of a world-space point on the surface of this collider, e.g., the
+    /// triangle of a triangle mesh hit by a ray, with the barycentric coordinates of the hit.
+    pub fn surface_location(&self, point: &Point<Real>) -> SurfaceLocation {
+        SurfaceLocation::new(self.shape(), &self.position, point)
+    }
+
     /// Compute the axis-aligned bounding box of this collider.
     pub fn compute_aabb(&self) -> AABB {
         self.shape.compute_aabb(&self.position)
     }
 
-    /// Compute the axis-aligned bounding box of this collider.
+    /// Compute the axis-aligned bounding box enclosing this collider while it moves from its
+    /// current position to the given world-space `next_position`.
+    ///
+    /// This is the conservative bound used by the CCD and the query pipeline (in the
+    /// `QueryPipelineMode::SweepTest*` modes) for colliders attached to moving rigid-bodies.
     pub fn compute_swept_aabb(&self, next_position: &Isometry<Real>) -> AABB {
         let aabb1 = self.shape.compute_aabb(&self.position);
         let aabb2 = self.shape.compute_aabb(next_position);
         aabb1.merged(&aabb2)
     }
 
+    /// Compute the axis-aligned bounding box enclosing this collider while its parent rigid-body
+    /// moves from its current position to the given world-space `next_parent_position`.
+    ///
+    /// For example, passing `RigidBody::next_position` gives the same bounds as the ones used
+    /// by the CCD for the timestep being simulated.
+    pub fn compute_swept_aabb_with_parent_position(
+        &self,
+        next_parent_position: &Isometry<Real>,
+    ) -> AABB {
+        self.compute_swept_aabb(&(next_parent_position * self.position_wrt_parent()))
+    }
+
     /// Compute the local-space mass properties of this collider.
     pub fn mass_properties(&self) -> MassProperties {
         match &self.mass_info {
@@ -291,12 +1166,37 @@ pub struct ColliderBuilder {
     /// Do we have to always call the contact modifier
     /// on this collider?
     pub modify_solver_contacts: bool,
+    /// The one-way platform configuration of the collider being built.
+    pub one_way_platform: Option<OneWayPlatform>,
+    /// The materials of each part of the compound collider being built.
+    pub part_materials: Option<Vec<ColliderMaterial>>,
+    /// The user-defined material varying across the surface of the collider being built.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub surface_material: Option<Arc<dyn SurfaceMaterial>>,
+    /// The hint describing how the collider being built is expected to move.
+    pub motion_quality: MotionQuality,
+    /// The contact skin of the collider being built.
+    pub contact_skin: Real,
+    /// The contact force event threshold of the collider being built.
+    pub contact_force_event_threshold: Real,
+    /// Is the internal edge correction enabled for the collider being built?
+    pub internal_edge_correction_enabled: bool,
+    /// Are detailed contact events enabled for the collider being built?
+    pub detailed_contact_events_enabled: bool,
+    /// Is the collider being built a one-sided polyline?
+    #[cfg(feature = "dim2")]
+    pub one_sided: bool,
     /// The user-data of the collider being built.
     pub user_data: u128,
+    /// The user-defined object attached to the collider being built.
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    pub user_object: Option<Arc<dyn Any + Send + Sync>>,
     /// The collision groups for the collider being built.
     pub collision_groups: InteractionGroups,
     /// The solver groups for the collider being built.
     pub solver_groups: InteractionGroups,
+    /// The additional kinds of colliders the sensor being built reports intersections with.
+    pub active_intersection_types: ActiveIntersectionTypes,
 }
 
 impl ColliderBuilder {
@@ -311,11 +1211,23 @@ impl ColliderBuilder {
             delta: Isometry::identity(),
             is_sensor: false,
             user_data: 0,
+            user_object: None,
             collision_groups: InteractionGroups::all(),
             solver_groups: InteractionGroups::all(),
+            active_intersection_types: ActiveIntersectionTypes::empty(),
             friction_combine_rule: CoefficientCombineRule::Average,
             restitution_combine_rule: CoefficientCombineRule::Average,
             modify_solver_contacts: false,
+            one_way_platform: None,
+            part_materials: None,
+            surface_material: None,
+            motion_quality: MotionQuality::default(),
+            contact_skin: 0.0,
+            contact_force_event_threshold: Real::MAX,
+            internal_edge_correction_enabled: false,
+            detailed_contact_events_enabled: false,
+            #[cfg(feature = "dim2")]
+            one_sided: false,
         }
     }
 
@@ -324,6 +1236,38 @@ impl ColliderBuilder {
         Self::new(SharedShape::compound(shapes))
     }
 
+    /// Initialize a new collider builder with a compound shape where each part has its own
+    /// friction and restitution coefficients.
+    ///
+    /// Each part is given as `(position, shape, friction, restitution)`. The friction and
+    /// restitution of a part are used for all the contacts involving this part, and are
+    /// combined with the coefficients of the other collider using the combine rules of
+    /// this builder.
+    pub fn compound_with_materials(parts: Vec<(Isometry<Real>, SharedShape, Real, Real)>) -> Self {
+        let (shapes, materials) = parts
+            .into_iter()
+            .map(|(pos, shape, friction, restitution)| {
+                ((pos, shape), ColliderMaterial::new(friction, restitution))
+            })
+            .unzip();
+        let mut builder = Self::compound(shapes);
+        builder.part_materials = Some(materials);
+        builder
+    }
+
+    /// Initialize a new collider builder with a voxels shape.
+    pub fn voxels(voxels: Voxels) -> Self {
+        Self::new(SharedShape::new(voxels))
+    }
+
+    /// Initialize a new collider builder with a multi-sphere shape: the convex hull of the given
+    /// balls, each defined by its center and radius.
+    ///
+    /// See [MultiSphere] for details.
+    pub fn multi_sphere(spheres: Vec<(Point<Real>, Real)>) -> Self {
+        Self::new(SharedShape::new(MultiSphere::new(spheres)))
+    }
+
     /// Initialize a new collider builder with a ball shape defined by its radius.
     pub fn ball(radius: Real) -> Self {
         Self::new(SharedShape::ball(radius))
@@ -331,6 +1275,10 @@ impl ColliderBuilder {
 
     /// Initialize a new collider build with a half-space shape defined by the outward normal
     /// of its planar boundary.
+    ///
+    /// The boundary of the half-space passes through the origin of the collider. This is the
+    /// preferred shape for infinite ground planes attached to static rigid-bodies: unlike a
+    /// very large cuboid, it doesn't affect the precision of the contacts computed against it.
     pub fn halfspace(outward_normal: Unit<Vector<Real>>) -> Self {
         Self::new(SharedShape::halfspace(outward_normal))
     }
@@ -424,7 +1372,8 @@ impl ColliderBuilder {
         Self::new(SharedShape::triangle(a, b, c))
     }
 
-    /// Initializes a collider builder with a triangle shape with round corners.
+    /// Initializes a collider builder with a triangle shape with round corners, i.e., dilated
+    /// by a sphere of radius `border_radius`.
     pub fn round_triangle(
         a: Point<Real>,
         b: Point<Real>,
@@ -439,11 +1388,50 @@ impl ColliderBuilder {
         Self::new(SharedShape::polyline(vertices, indices))
     }
 
+    /// Initializes a collider builder with a one-sided chain of segments joining the given
+    /// vertices, optionally joining the last vertex to the first one.
+    ///
+    /// This is a polyline with the internal edge correction enabled, so objects sliding along
+    /// the chain don't catch on the joins between segments, and with one-sided collisions: the
+    /// segments only collide with objects located on their left side. For example, a terrain
+    /// with vertices ordered from left to right collides with the objects above it only.
+    /// See `Collider::enable_internal_edge_correction` and `Collider::set_one_sided` for details.
+    #[cfg(feature = "dim2")]
+    pub fn chain(vertices: Vec<Point<Real>>, closed: bool) -> Self {
+        let num_segments = if closed {
+            vertices.len()
+        } else {
+            vertices.len().saturating_sub(1)
+        };
+        let indices = (0..num_segments)
+            .map(|i| [i as u32, ((i + 1) % vertices.len()) as u32])
+            .collect();
+
+        Self::polyline(vertices, Some(indices))
+            .internal_edge_correction_enabled(true)
+            .one_sided(true)
+    }
+
     /// Initializes a collider builder with a triangle mesh shape defined by its vertex and index buffers.
     pub fn trimesh(vertices: Vec<Point<Real>>, indices: Vec<[u32; 3]>) -> Self {
         Self::new(SharedShape::trimesh(vertices, indices))
     }
 
+    /// Initializes a collider builder with a triangle mesh shape defined by its vertex and index
+    /// buffers, after applying the cleanup operations selected by `flags`.
+    ///
+    /// This is useful for meshes coming from art assets, which may contain duplicate vertices,
+    /// degenerate triangles, or inconsistently oriented triangles resulting in bad contact
+    /// normals. See `sanitize_trimesh` for details.
+    pub fn trimesh_with_flags(
+        vertices: Vec<Point<Real>>,
+        indices: Vec<[u32; 3]>,
+        flags: TriMeshFlags,
+    ) -> Self {
+        let (vertices, indices) = sanitize_trimesh(vertices, indices, flags);
+        Self::trimesh(vertices, indices)
+    }
+
     /// Initializes a collider builder with a compound shape obtained from the decomposition of
     /// the given trimesh (in 3D) or polyline (in 2D) into convex parts.
     pub fn convex_decomposition(vertices: &[Point<Real>], indices: &[[u32; DIM]]) -> Self {
@@ -566,6 +1554,14 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets an arbitrary user-defined object attached to the colliders built by this builder.
+    ///
+    /// All the colliders built by this builder will share the same object.
+    pub fn user_object<T: Any + Send + Sync>(mut self, object: T) -> Self {
+        self.user_object = Some(Arc::new(object));
+        self
+    }
+
     /// Sets the collision groups used by this collider.
     ///
     /// Two colliders will interact iff. their collision groups are compatible.
@@ -593,6 +1589,72 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets the additional kinds of colliders the sensor being built reports intersections with.
+    ///
+    /// See [`ActiveIntersectionTypes`] for details.
+    pub fn active_intersection_types(mut self, types: ActiveIntersectionTypes) -> Self {
+        self.active_intersection_types = types;
+        self
+    }
+
+    /// Sets the user-defined material varying across the surface of the collider being built.
+    ///
+    /// See [`SurfaceMaterial`] for details.
+    pub fn surface_material(mut self, material: impl SurfaceMaterial + 'static) -> Self {
+        self.surface_material = Some(Arc::new(material));
+        self
+    }
+
+    /// Sets the contact skin of the collider being built.
+    ///
+    /// See `Collider::set_contact_skin` for details.
+    pub fn contact_skin(mut self, skin: Real) -> Self {
+        assert!(skin >= 0.0, "The contact skin must be positive or zero.");
+        self.contact_skin = skin;
+        self
+    }
+
+    /// Sets the contact force event threshold of the collider being built.
+    ///
+    /// See `Collider::set_contact_force_event_threshold` for details.
+    pub fn contact_force_event_threshold(mut self, threshold: Real) -> Self {
+        self.contact_force_event_threshold = threshold;
+        self
+    }
+
+    /// Sets the hint describing how the collider being built is expected to move.
+    ///
+    /// See [`MotionQuality`] for details.
+    pub fn motion_quality(mut self, motion_quality: MotionQuality) -> Self {
+        self.motion_quality = motion_quality;
+        self
+    }
+
+    /// Enables or disables the internal edge correction for the collider being built.
+    ///
+    /// See `Collider::enable_internal_edge_correction` for details.
+    pub fn internal_edge_correction_enabled(mut self, enabled: bool) -> Self {
+        self.internal_edge_correction_enabled = enabled;
+        self
+    }
+
+    /// Enables or disables the detailed contact events for the collider being built.
+    ///
+    /// See `Collider::enable_detailed_contact_events` for details.
+    pub fn detailed_contact_events_enabled(mut self, enabled: bool) -> Self {
+        self.detailed_contact_events_enabled = enabled;
+        self
+    }
+
+    /// Makes the collider being built one-sided or two-sided.
+    ///
+    /// See `Collider::set_one_sided` for details.
+    #[cfg(feature = "dim2")]
+    pub fn one_sided(mut self, one_sided: bool) -> Self {
+        self.one_sided = one_sided;
+        self
+    }
+
     /// If set to `true` then the physics hooks will always run to modify
     /// contacts involving this collider.
     pub fn modify_solver_contacts(mut self, modify_solver_contacts: bool) -> Self {
@@ -600,6 +1662,18 @@ impl ColliderBuilder {
         self
     }
 
+    /// Makes the collider behave as a one-way platform.
+    ///
+    /// See `OneWayPlatform` for details.
+    pub fn one_way_platform(
+        mut self,
+        allowed_local_normal: Vector<Real>,
+        allowed_angle: Real,
+    ) -> Self {
+        self.one_way_platform = Some(OneWayPlatform::new(allowed_local_normal, allowed_angle));
+        self
+    }
+
     /// Sets the friction coefficient of the collider this builder will build.
     pub fn friction(mut self, friction: Real) -> Self {
         self.friction = friction;
@@ -642,6 +1716,17 @@ impl ColliderBuilder {
         self
     }
 
+    /// Sets the mass of the collider this builder will build.
+    ///
+    /// The center of mass and angular inertia are computed from the shape of this builder,
+    /// scaled to match the given mass. This is equivalent to calling [`Self::mass_properties`]
+    /// with these mass properties, so it only makes sense to call either [`Self::density`],
+    /// [`Self::mass_properties`], or [`Self::mass`].
+    pub fn mass(mut self, mass: Real) -> Self {
+        self.mass_properties = Some(mass_properties_with_mass(&*self.shape, mass));
+        self
+    }
+
     /// Sets the initial translation of the collider to be created,
     /// relative to the rigid-body it is attached to.
     #[cfg(feature = "dim2")]
@@ -702,6 +1787,16 @@ impl ColliderBuilder {
 
         let mut flags = ColliderFlags::empty();
         flags.set(ColliderFlags::SENSOR, self.is_sensor);
+        flags.set(
+            ColliderFlags::INTERNAL_EDGE_CORRECTION,
+            self.internal_edge_correction_enabled,
+        );
+        flags.set(
+            ColliderFlags::DETAILED_CONTACT_EVENTS,
+            self.detailed_contact_events_enabled,
+        );
+        #[cfg(feature = "dim2")]
+        flags.set(ColliderFlags::ONE_SIDED, self.one_sided);
         flags = flags
             .with_friction_combine_rule(self.friction_combine_rule)
             .with_restitution_combine_rule(self.restitution_combine_rule);
@@ -714,18 +1809,458 @@ impl ColliderBuilder {
         Collider {
             shape: self.shape.clone(),
             mass_info,
+            prev_mass_properties: None,
             friction: self.friction,
             restitution: self.restitution,
             delta: self.delta,
             flags,
             solver_flags,
             changes: ColliderChanges::all(),
+            tracked_changes: ColliderChanges::empty(),
             parent: RigidBodyHandle::invalid(),
+            prev_parent: None,
+            heightfield_changes: None,
             position: Isometry::identity(),
             proxy_index: crate::INVALID_U32,
             collision_groups: self.collision_groups,
             solver_groups: self.solver_groups,
+            active_intersection_types: self.active_intersection_types,
+            one_way_platform: self.one_way_platform,
+            part_materials: self.part_materials.clone(),
+            surface_material: self.surface_material.clone(),
+            motion_quality: self.motion_quality,
+            contact_skin: self.contact_skin,
+            contact_force_event_threshold: self.contact_force_event_threshold,
+            // NOTE: the mesh topology will be computed when the collider is inserted
+            //       into a collider set, because its `SHAPE` change flag is set.
+            mesh_topology: None,
             user_data: self.user_data,
+            user_object: self.user_object.clone(),
+        }
+    }
+}
+
+// The mass properties of a shape with a uniform density such that its mass equals `mass`.
+//
+// Shapes without any volume get all their mass at their local origin, without angular inertia.
+fn mass_properties_with_mass(shape: &dyn Shape, mass: Real) -> MassProperties {
+    let unit_mass_properties = shape.mass_properties(1.0);
+    let volume = unit_mass_properties.mass();
+
+    if volume > 0.0 {
+        shape.mass_properties(mass / volume)
+    } else {
+        MassProperties::new(Point::origin(), mass, na::zero())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::{ColliderBuilder, SharedShape};
+    use crate::math::{Isometry, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn heightfield_edits_keep_the_holes() {
+        #[cfg(feature = "dim2")]
+        {
+            let heights = na::DVector::zeros(11);
+            let mut co = ColliderBuilder::heightfield(heights, Vector::new(10.0, 1.0)).build();
+            co.set_heightfield_hole(5, true);
+            co.set_heightfield_heights(4, &na::DVector::repeat(3, 0.5));
+            assert!(co.is_heightfield_hole(5));
+            assert!(!co.is_heightfield_hole(4));
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            let heights = na::DMatrix::zeros(11, 11);
+            let scale = Vector::new(10.0, 1.0, 10.0);
+            let mut co = ColliderBuilder::heightfield(heights, scale).build();
+            co.set_heightfield_hole(5, 5, true);
+            co.set_heightfield_heights(4, 4, &na::DMatrix::repeat(3, 3, 0.5));
+            assert!(co.is_heightfield_hole(5, 5));
+            assert!(!co.is_heightfield_hole(4, 5));
+        }
+    }
+
+    #[test]
+    fn set_shape() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let body = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let collider =
+            world
+                .colliders
+                .insert(ColliderBuilder::ball(0.5).build(), body, &mut world.bodies);
+        let neighbor = world.bodies.insert(
+            RigidBodyBuilder::new_static()
+                .position(Isometry::new(Vector::x() * 2.0, na::zero()))
+                .build(),
+        );
+        let other = world.colliders.insert(
+            ColliderBuilder::ball(0.5).build(),
+            neighbor,
+            &mut world.bodies,
+        );
+
+        world.step(1.0 / 60.0);
+        let mass = world.bodies[body].mass();
+        let ccd_thickness = world.bodies[body].ccd_thickness();
+        assert!(world.narrow_phase.contact_pair(collider, other).is_none());
+
+        // Grow the ball until it reaches its neighbor, keeping the same handle.
+        world.colliders[collider].set_shape(SharedShape::ball(1.6));
+        world.step(1.0 / 60.0);
+
+        #[cfg(feature = "dim2")]
+        let expected_mass = mass * 3.2 * 3.2;
+        #[cfg(feature = "dim3")]
+        let expected_mass = mass * 3.2 * 3.2 * 3.2;
+        assert!((world.bodies[body].mass() - expected_mass).abs() < expected_mass * 1.0e-4);
+        assert!(world.bodies[body].ccd_thickness() > ccd_thickness);
+        assert!(world.narrow_phase.has_any_active_contact(collider, other));
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_contact_skin() {
+        let _ = ColliderBuilder::ball(0.5).contact_skin(-0.1);
+    }
+
+    #[test]
+    fn heightfield_modification() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+
+        // A flat heightfield with 10 cells along each axis, spanning [-5, 5].
+        #[cfg(feature = "dim2")]
+        let (heights, scale) = (na::DVector::zeros(11), Vector::new(10.0, 1.0));
+        #[cfg(feature = "dim3")]
+        let (heights, scale) = (na::DMatrix::zeros(11, 11), Vector::new(10.0, 1.0, 10.0));
+
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let hf = world.colliders.insert(
+            ColliderBuilder::heightfield(heights, scale).build(),
+            ground,
+            &mut world.bodies,
+        );
+        let (_, near) = world.insert_dynamic(Vector::x() * -4.5, ColliderBuilder::ball(0.1));
+        let (_, far) = world.insert_dynamic(Vector::x() * 4.5, ColliderBuilder::ball(0.1));
+
+        let colliders = &mut world.colliders;
+        #[cfg(feature = "dim2")]
+        colliders[hf].set_heightfield_heights(0, &na::DVector::repeat(2, 0.5));
+        #[cfg(feature = "dim3")]
+        colliders[hf].set_heightfield_heights(0, 0, &na::DMatrix::repeat(11, 2, 0.5));
+
+        let new_heights = colliders[hf].shape().as_heightfield().unwrap().heights();
+        #[cfg(feature = "dim2")]
+        assert_eq!((new_heights[1], new_heights[2]), (0.5, 0.0));
+        #[cfg(feature = "dim3")]
+        assert_eq!((new_heights[(5, 1)], new_heights[(5, 2)]), (0.5, 0.0));
+        assert!(colliders[hf].heightfield_changes_intersect(&colliders[near], 0.0));
+        assert!(!colliders[hf].heightfield_changes_intersect(&colliders[far], 0.0));
+
+        world.run(1);
+        assert!(world.colliders[hf].heightfield_changes.is_none());
+    }
+
+    #[test]
+    fn heightfield_holes() {
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+
+        // A flat heightfield with 10 cells along each axis, spanning [-5, 5].
+        #[cfg(feature = "dim2")]
+        let (heights, scale) = (na::DVector::zeros(11), Vector::new(10.0, 1.0));
+        #[cfg(feature = "dim3")]
+        let (heights, scale) = (na::DMatrix::zeros(11, 11), Vector::new(10.0, 1.0, 10.0));
+
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let hf = world.colliders.insert(
+            ColliderBuilder::heightfield(heights, scale).build(),
+            ground,
+            &mut world.bodies,
+        );
+
+        // A ball above the center of the cell covering [0, 1] along each horizontal axis.
+        let (rb, _) = world.insert_dynamic(
+            Vector::repeat(0.5) + Vector::y() * 0.5,
+            ColliderBuilder::ball(0.25),
+        );
+
+        // The ball rests on the heightfield.
+        world.run(60);
+        assert!((world.bodies[rb].position().translation.y - 0.25).abs() < 0.02);
+
+        // Cut a hole under the ball.
+        #[cfg(feature = "dim2")]
+        world.colliders[hf].set_heightfield_hole(5, true);
+        #[cfg(feature = "dim3")]
+        world.colliders[hf].set_heightfield_hole(5, 5, true);
+
+        // The ball falls through the hole.
+        world.run(60);
+        assert!(world.bodies[rb].position().translation.y < -1.0);
+    }
+
+    #[test]
+    fn collider_position_wrt_parent_modification() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let (rb, co1) = world.insert_dynamic(Vector::zeros(), ColliderBuilder::ball(0.5));
+        let co2 = world
+            .colliders
+            .insert(ColliderBuilder::ball(0.5).build(), rb, &mut world.bodies);
+        let mass = world.bodies[rb].mass();
+
+        world.colliders[co2].set_position_wrt_parent(Isometry::new(Vector::x() * 2.0, na::zero()));
+        world.run(1);
+
+        let local_com = world.bodies[rb].mass_properties().local_com;
+        assert!((world.bodies[rb].mass() - mass).abs() < 1.0e-5);
+        assert!((local_com.x - 1.0).abs() < 1.0e-5);
+        let dx = world.colliders[co2].position().translation.x
+            - world.colliders[co1].position().translation.x;
+        assert!((dx - 2.0).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn compound_part_materials() {
+        use crate::math::Real;
+
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let parts = vec![
+            (
+                Isometry::new(Vector::x() * -2.0, na::zero()),
+                SharedShape::ball(1.0),
+                0.1,
+                0.0,
+            ),
+            (
+                Isometry::new(Vector::x() * 2.0, na::zero()),
+                SharedShape::ball(1.0),
+                0.9,
+                0.4,
+            ),
+        ];
+        let compound = world.colliders.insert(
+            ColliderBuilder::compound_with_materials(parts).build(),
+            ground,
+            &mut world.bodies,
+        );
+
+        let xs: [Real; 2] = [-2.0, 2.0];
+        let balls: Vec<_> = xs
+            .iter()
+            .map(|x| {
+                let translation = Vector::x() * *x + Vector::y() * 1.4;
+                let ball = ColliderBuilder::ball(0.5).friction(0.5);
+                world.insert_dynamic(translation, ball).1
+            })
+            .collect();
+        world.run(1);
+
+        // The part materials are averaged with the material of the ball.
+        let expected = [(0.3, 0.0), (0.7, 0.2)];
+        for (ball, (friction, restitution)) in balls.iter().zip(expected.iter()) {
+            let pair = world.narrow_phase.contact_pair(compound, *ball).unwrap();
+            let contact = &pair.manifolds[0].data.solver_contacts[0];
+            assert!((contact.friction - friction).abs() < 1.0e-5);
+            assert!((contact.restitution - restitution).abs() < 1.0e-5);
+        }
+    }
+
+    #[test]
+    fn surface_material() {
+        use crate::geometry::ColliderMaterial;
+        use crate::math::{Point, Real};
+
+        let mut world = PhysicsWorld::new(Vector::zeros());
+
+        // An icy ground for negative x coordinates.
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let co = ColliderBuilder::halfspace(Vector::y_axis())
+            .surface_material(|_: u32, pt: &Point<Real>| {
+                let friction = if pt.x < 0.0 { 0.0 } else { 1.0 };
+                ColliderMaterial::new(friction, 0.5)
+            })
+            .build();
+        let ground = world.colliders.insert(co, ground, &mut world.bodies);
+
+        let xs: [Real; 2] = [-2.0, 2.0];
+        let balls: Vec<_> = xs
+            .iter()
+            .map(|x| {
+                let translation = Vector::x() * *x + Vector::y() * 0.4;
+                let ball = ColliderBuilder::ball(0.5).friction(0.5);
+                world.insert_dynamic(translation, ball).1
+            })
+            .collect();
+        world.run(1);
+
+        let expected = [0.25, 0.75];
+        for (ball, friction) in balls.iter().zip(expected.iter()) {
+            let pair = world.narrow_phase.contact_pair(ground, *ball).unwrap();
+            let contact = &pair.manifolds[0].data.solver_contacts[0];
+            assert!((contact.friction - friction).abs() < 1.0e-5);
+            assert!((contact.restitution - 0.25).abs() < 1.0e-5);
         }
     }
+
+    #[test]
+    fn contact_skin() {
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        let ground = world.insert_ground();
+        let ball = ColliderBuilder::ball(0.5).contact_skin(0.1);
+        let (rb, co) = world.insert_dynamic(Vector::y(), ball);
+        let (plain_rb, _) =
+            world.insert_dynamic(Vector::x() * 5.0 + Vector::y(), ColliderBuilder::ball(0.5));
+        world.run(120);
+
+        // The ball rests on its skin, above the ground, unlike the ball without skin.
+        let y = world.bodies[rb].position().translation.y;
+        assert!((y - 0.6).abs() < 0.02);
+        let plain_y = world.bodies[plain_rb].position().translation.y;
+        assert!((plain_y - 0.5).abs() < 0.02);
+
+        // The skin is subtracted from the distance seen by the solver.
+        let manifold = &world
+            .narrow_phase
+            .contact_pair(ground, co)
+            .unwrap()
+            .manifolds[0];
+        assert!((manifold.points[0].dist - 0.1).abs() < 0.02);
+        assert!(manifold.data.solver_contacts[0].dist.abs() < 0.02);
+    }
+
+    #[test]
+    fn halfspace_ground() {
+        use crate::math::Real;
+
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        let ground = world.insert_ground();
+
+        #[cfg(feature = "dim2")]
+        let cuboid = ColliderBuilder::cuboid(0.5, 0.5);
+        #[cfg(feature = "dim3")]
+        let cuboid = ColliderBuilder::cuboid(0.5, 0.5, 0.5);
+        let compound = ColliderBuilder::compound(vec![
+            (
+                Isometry::new(Vector::x() * -0.5, na::zero()),
+                SharedShape::ball(0.5),
+            ),
+            (
+                Isometry::new(Vector::x() * 0.5, na::zero()),
+                SharedShape::ball(0.5),
+            ),
+        ]);
+        let shapes = vec![
+            ColliderBuilder::ball(0.5),
+            ColliderBuilder::capsule_y(0.5, 0.5),
+            cuboid,
+            compound,
+        ];
+
+        let mut handles: Vec<_> = shapes
+            .into_iter()
+            .enumerate()
+            .map(|(i, co)| {
+                let translation = Vector::x() * (i as Real * 3.0) + Vector::y() * 2.0;
+                world.insert_dynamic(translation, co).1
+            })
+            .collect();
+        // The half-space is infinite: a ball far from its origin still lands on it.
+        let far = Vector::x() * -1000.0 + Vector::y() * 2.0;
+        handles.push(world.insert_dynamic(far, ColliderBuilder::ball(0.5)).1);
+        world.run(120);
+
+        // All the shapes rest on the ground.
+        for handle in handles {
+            let lowest = world.colliders[handle].compute_aabb().mins.y;
+            assert!(lowest.abs() < 0.05);
+            assert!(world.narrow_phase.has_any_active_contact(ground, handle));
+        }
+    }
+
+    #[test]
+    fn collider_swept_aabb_with_parent_position() {
+        use crate::dynamics::RigidBodySet;
+        use crate::geometry::ColliderSet;
+        use crate::math::Real;
+        use parry::bounding_volume::BoundingVolume;
+
+        let mut colliders = ColliderSet::new();
+        let mut bodies = RigidBodySet::new();
+
+        let rb = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let co = ColliderBuilder::ball(0.5)
+            .position_wrt_parent(Isometry::new(Vector::y() * 1.0, na::zero()))
+            .build();
+        let co = colliders.insert(co, rb, &mut bodies);
+
+        let next_parent_position = Isometry::new(Vector::x() * 10.0, na::zero());
+        let aabb = colliders[co].compute_swept_aabb_with_parent_position(&next_parent_position);
+        let expected = colliders[co]
+            .compute_aabb()
+            .merged(&colliders[co].shape().compute_aabb(&Isometry::new(
+                Vector::x() * 10.0 + Vector::y() * 1.0,
+                na::zero(),
+            )));
+
+        assert_eq!(aabb, expected);
+        assert!((aabb.maxs.x - 10.5 as Real).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn round_shapes_ccd_thickness() {
+        use crate::dynamics::RigidBodySet;
+        use crate::geometry::ColliderSet;
+        use crate::math::Point;
+
+        let mut colliders = ColliderSet::new();
+        let mut bodies = RigidBodySet::new();
+
+        #[cfg(feature = "dim2")]
+        let round_cuboid = ColliderBuilder::round_cuboid(1.0, 0.5, 0.1).build();
+        #[cfg(feature = "dim3")]
+        let round_cuboid = ColliderBuilder::round_cuboid(1.0, 0.5, 2.0, 0.1).build();
+        assert!((round_cuboid.ccd_thickness() - 0.6).abs() < 1.0e-5);
+
+        // A flat triangle is as thick as its border.
+        let round_triangle = ColliderBuilder::round_triangle(
+            Point::origin(),
+            Point::from(Vector::x()),
+            Point::from(Vector::y()),
+            0.1,
+        )
+        .build();
+        let thickness = round_triangle.ccd_thickness();
+        assert!(thickness >= 0.1 && thickness < 0.2);
+
+        let rb = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        colliders.insert(round_cuboid, rb, &mut bodies);
+        colliders.insert(round_triangle, rb, &mut bodies);
+        assert_eq!(bodies[rb].ccd_thickness(), thickness);
+    }
+
+    #[test]
+    fn collider_mass_override() {
+        let ball = ColliderBuilder::ball(0.5).density(100.0).mass(3.0);
+        // The density is ignored, and the angular inertia is scaled to match the mass.
+        let built = ball.build();
+        let volume = built.shape().mass_properties(1.0).mass();
+        assert_eq!(
+            built.mass_properties(),
+            built.shape().mass_properties(3.0 / volume)
+        );
+
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let (rb, co) = world.insert_dynamic(Vector::zeros(), ball);
+        world.run(1);
+        assert!((world.bodies[rb].mass() - 3.0).abs() < 1.0e-5);
+
+        world.colliders[co].set_mass(5.0);
+        world.run(1);
+        assert!((world.bodies[rb].mass() - 5.0).abs() < 1.0e-5);
+    }
 }
@@ -2,9 +2,11 @@ use crate::data::arena::Arena;
 use crate::data::pubsub::PubSub;
 use crate::dynamics::{RigidBodyHandle, RigidBodySet};
 use crate::geometry::collider::ColliderChanges;
-use crate::geometry::{Collider, SAPProxyIndex};
+use crate::geometry::{Collider, SAPProxyIndex, ShapeDeduplicator, ShapeSharingStats};
+use crate::math::{Isometry, Real, Vector};
 use parry::partitioning::IndexedData;
 use std::ops::{Index, IndexMut};
+use std::sync::Arc;
 
 /// The unique identifier of a collider added to a collider set.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
@@ -57,6 +59,8 @@ pub struct ColliderSet {
     pub(crate) colliders: Arena<Collider>,
     pub(crate) modified_colliders: Vec<ColliderHandle>,
     pub(crate) modified_all_colliders: bool,
+    // The colliders with changes already handled by the physics pipeline, but not yet cleared.
+    tracked_colliders: Vec<ColliderHandle>,
 }
 
 impl ColliderSet {
@@ -67,6 +71,7 @@ impl ColliderSet {
             colliders: Arena::new(),
             modified_colliders: Vec::new(),
             modified_all_colliders: false,
+            tracked_colliders: Vec::new(),
         }
     }
 
@@ -90,6 +95,24 @@ impl ColliderSet {
             .map(|(h, b)| (ColliderHandle(h), b))
     }
 
+    /// Marks all the colliders of this set as if they were just inserted.
+    ///
+    /// This must be called after replacing the broad-phase and narrow-phase by new
+    /// ones, so that all the colliders get registered to them at the next timestep.
+    /// The previous broad-phase must no longer be used after calling this.
+    pub fn mark_all_as_new(&mut self) {
+        for (handle, collider) in self.colliders.iter_mut() {
+            Self::mark_as_modified(
+                ColliderHandle(handle),
+                collider,
+                &mut self.modified_colliders,
+                self.modified_all_colliders,
+            );
+            collider.changes = ColliderChanges::all();
+            collider.proxy_index = crate::INVALID_U32;
+        }
+    }
+
     #[inline(always)]
     pub(crate) fn foreach_modified_colliders(&self, mut f: impl FnMut(ColliderHandle, &Collider)) {
         for handle in &self.modified_colliders {
@@ -121,6 +144,45 @@ impl ColliderSet {
         self.colliders.is_empty()
     }
 
+    /// Computes statistics about the shapes shared between the colliders of this set.
+    ///
+    /// This can be used to detect identical shapes (e.g. large triangle meshes) that were
+    /// cloned instead of being shared. See `deduplicate_shapes` for merging them.
+    pub fn shape_sharing_stats(&self) -> ShapeSharingStats {
+        let mut deduplicator = ShapeDeduplicator::new();
+
+        for (_, collider) in self.colliders.iter() {
+            let _ = deduplicator.representative(&collider.shape);
+        }
+
+        ShapeSharingStats {
+            num_colliders: self.colliders.len(),
+            num_shape_instances: deduplicator.num_instances(),
+            num_unique_shapes: deduplicator.num_unique_shapes(),
+        }
+    }
+
+    /// Makes all the colliders with identical shapes share the same `SharedShape`.
+    ///
+    /// This reduces the memory usage of worlds where identical shapes were allocated
+    /// multiple times. Because the replaced shapes are geometrically identical, this does
+    /// not affect the simulation. Returns the number of colliders that had their shape replaced.
+    pub fn deduplicate_shapes(&mut self) -> usize {
+        let mut deduplicator = ShapeDeduplicator::new();
+        let mut num_replaced = 0;
+
+        for (_, collider) in self.colliders.iter_mut() {
+            let representative = deduplicator.representative(&collider.shape);
+
+            if !Arc::ptr_eq(&representative.0, &collider.shape.0) {
+                collider.shape = representative;
+                num_replaced += 1;
+            }
+        }
+
+        num_replaced
+    }
+
     /// Is this collider handle valid?
     pub fn contains(&self, handle: ColliderHandle) -> bool {
         self.colliders.contains(handle.0)
@@ -133,7 +195,14 @@ impl ColliderSet {
     pub(crate) fn clear_modified_colliders(&mut self) {
         if self.modified_all_colliders {
             for collider in self.colliders.iter_mut() {
+                Self::track_changes(
+                    ColliderHandle(collider.0),
+                    collider.1,
+                    &mut self.tracked_colliders,
+                );
                 collider.1.changes = ColliderChanges::empty();
+                collider.1.prev_parent = None;
+                collider.1.heightfield_changes = None;
             }
             self.modified_colliders.clear();
             self.modified_all_colliders = false;
@@ -143,12 +212,68 @@ impl ColliderSet {
                 //       a an update, then it will no longer exist in `self.colliders`
                 //       so we need to do this `if let`.
                 if let Some(co) = self.colliders.get_mut(handle.0) {
+                    Self::track_changes(handle, co, &mut self.tracked_colliders);
                     co.changes = ColliderChanges::empty();
+                    co.prev_parent = None;
+                    co.heightfield_changes = None;
                 }
             }
         }
     }
 
+    // Keep track of the changes of the collider until the user clears them.
+    fn track_changes(
+        handle: ColliderHandle,
+        collider: &mut Collider,
+        tracked_colliders: &mut Vec<ColliderHandle>,
+    ) {
+        if collider.tracked_changes.is_empty() {
+            tracked_colliders.push(handle);
+        }
+        collider.tracked_changes |= collider.changes | ColliderChanges::MODIFIED;
+    }
+
+    /// Iterates through all the colliders modified since the last call to
+    /// `Self::clear_modified_flags`.
+    ///
+    /// See `Collider::changes` for details on the modifications being tracked.
+    pub fn modified_colliders(&self) -> impl Iterator<Item = (ColliderHandle, &Collider)> {
+        let pending: Box<dyn Iterator<Item = ColliderHandle> + '_> = if self.modified_all_colliders
+        {
+            Box::new(self.colliders.iter().map(|(h, _)| ColliderHandle(h)))
+        } else {
+            Box::new(self.modified_colliders.iter().copied())
+        };
+
+        // Pending colliders with tracked changes are already part of `self.tracked_colliders`.
+        let pending = pending.filter_map(move |handle| {
+            let co = self.colliders.get(handle.0)?;
+            if co.tracked_changes.is_empty() {
+                Some((handle, co))
+            } else {
+                None
+            }
+        });
+
+        self.tracked_colliders
+            .iter()
+            .filter_map(move |handle| Some((*handle, self.colliders.get(handle.0)?)))
+            .chain(pending)
+    }
+
+    /// Clears the modification flags of all the colliders.
+    ///
+    /// This only clears the modifications already taken into account by a timestep of the
+    /// physics pipeline. The modifications made since the last timestep will still be
+    /// reported until the next timestep is executed, and this method is called again.
+    pub fn clear_modified_flags(&mut self) {
+        for handle in self.tracked_colliders.drain(..) {
+            if let Some(co) = self.colliders.get_mut(handle.0) {
+                co.tracked_changes = ColliderChanges::empty();
+            }
+        }
+    }
+
     /// Inserts a new collider to this set and retrieve its handle.
     pub fn insert(
         &mut self,
@@ -214,6 +339,75 @@ impl ColliderSet {
         Some(collider)
     }
 
+    /// Attaches a collider to another rigid-body.
+    ///
+    /// The collider keeps its world-space position, and its handle remains valid. The mass
+    /// properties of both rigid-bodies are updated accordingly. The contacts between the collider
+    /// and the other colliders attached to its new parent will be removed at the next timestep,
+    /// and the contacts with the colliders attached to its former parent will be computed.
+    ///
+    /// If `wake_up` is `true`, both rigid-bodies will be woken up.
+    ///
+    /// Panics if the collider or the new parent rigid-body don't exist.
+    pub fn set_parent(
+        &mut self,
+        handle: ColliderHandle,
+        new_parent_handle: RigidBodyHandle,
+        bodies: &mut RigidBodySet,
+        wake_up: bool,
+    ) {
+        let collider = self
+            .colliders
+            .get_mut(handle.0)
+            .expect("Collider not found.");
+        let old_parent_handle = collider.parent;
+
+        if old_parent_handle == new_parent_handle {
+            return;
+        }
+
+        if let Some(old_parent) =
+            bodies.get_mut_internal_with_modification_tracking(old_parent_handle)
+        {
+            old_parent.remove_collider_internal(handle, collider);
+        }
+
+        // The mass properties of the new parent will account for
+        // the current mass properties of the collider.
+        collider.prev_mass_properties = None;
+
+        let new_parent = bodies
+            .get_mut_internal_with_modification_tracking(new_parent_handle)
+            .expect("Parent rigid body not found.");
+
+        Self::mark_as_modified(
+            handle,
+            collider,
+            &mut self.modified_colliders,
+            self.modified_all_colliders,
+        );
+        collider
+            .changes
+            .insert(ColliderChanges::POSITION_WRT_PARENT);
+        collider.delta = new_parent.position.inv_mul(&collider.position);
+        collider.parent = new_parent_handle;
+
+        if collider.prev_parent.is_none() {
+            collider.prev_parent = Some(old_parent_handle);
+        }
+
+        new_parent.add_collider(handle, collider);
+
+        if let Some(old_parent) = bodies.get_mut_internal(old_parent_handle) {
+            old_parent.update_colliders_ccd_data(self);
+        }
+
+        if wake_up {
+            bodies.wake_up(old_parent_handle, true);
+            bodies.wake_up(new_parent_handle, true);
+        }
+    }
+
     /// Gets the collider with the given handle without a known generation.
     ///
     /// This is useful when you know you want the collider at position `i` but
@@ -251,6 +445,13 @@ impl ColliderSet {
         Some((collider, handle))
     }
 
+    /// Translates all the colliders of this set by `shift`, without marking them as modified.
+    pub(crate) fn shift_origin(&mut self, shift: &Vector<Real>) {
+        for (_, collider) in self.colliders.iter_mut() {
+            collider.position.translation.vector += shift;
+        }
+    }
+
     /// Get the collider with the given handle.
     pub fn get(&self, handle: ColliderHandle) -> Option<&Collider> {
         self.colliders.get(handle.0)
@@ -301,8 +502,73 @@ impl ColliderSet {
         Some(result)
     }
 
+    /// Sets the world-space positions of several colliders attached to static rigid-bodies.
+    ///
+    /// This is intended for moving a large number of static colliders at once, e.g., when
+    /// shifting chunks for level streaming. Unlike `Collider::set_position_wrt_parent`, this
+    /// sets the world-space position of each collider right away and skips the update of the
+    /// mass properties of its parent, which is useless for static rigid-bodies. The broad-phase
+    /// then handles all these colliders during its next update, re-sorting the endpoints of each
+    /// of its regions once instead of once per collider.
+    ///
+    /// Invalid collider handles are ignored. Panics if one of the colliders isn't attached to
+    /// a static rigid-body, in which case none of the colliders is moved.
+    pub fn set_positions_bulk(
+        &mut self,
+        bodies: &RigidBodySet,
+        positions: impl IntoIterator<Item = (ColliderHandle, Isometry<Real>)>,
+    ) {
+        let positions: Vec<_> = positions
+            .into_iter()
+            .filter(|(handle, _)| self.colliders.contains(handle.0))
+            .collect();
+
+        for (handle, _) in &positions {
+            let parent = bodies.get(self.colliders[handle.0].parent);
+            assert!(
+                parent.map(|rb| rb.is_static()).unwrap_or(true),
+                "set_positions_bulk can only move colliders attached to static rigid-bodies."
+            );
+        }
+
+        if !self.modified_all_colliders {
+            self.modified_colliders.reserve(positions.len());
+        }
+
+        for (handle, position) in positions {
+            let collider = &mut self.colliders[handle.0];
+            Self::mark_as_modified(
+                handle,
+                collider,
+                &mut self.modified_colliders,
+                self.modified_all_colliders,
+            );
+
+            if let Some(parent) = bodies.get(collider.parent) {
+                collider.delta = parent.position.inv_mul(&position);
+            }
+
+            collider.set_position(position);
+        }
+    }
+
     // Utility function to avoid some borrowing issue in the `maintain` method.
-    fn maintain_one(bodies: &mut RigidBodySet, collider: &mut Collider) {
+    fn maintain_one(
+        bodies: &mut RigidBodySet,
+        collider: &mut Collider,
+        reshaped_parents: &mut Vec<RigidBodyHandle>,
+    ) {
+        if let Some(prev_mass_properties) = collider.prev_mass_properties.take() {
+            if let Some(parent) = bodies.get_mut_internal(collider.parent()) {
+                parent.update_collider_mass_properties(collider, &prev_mass_properties);
+            }
+        }
+
+        if collider.changes.contains(ColliderChanges::SHAPE) {
+            reshaped_parents.push(collider.parent());
+            collider.update_mesh_topology();
+        }
+
         if collider
             .changes
             .contains(ColliderChanges::POSITION_WRT_PARENT)
@@ -317,17 +583,27 @@ impl ColliderSet {
     }
 
     pub(crate) fn handle_user_changes(&mut self, bodies: &mut RigidBodySet) {
+        let mut reshaped_parents = Vec::new();
+
         if self.modified_all_colliders {
             for (_, rb) in self.colliders.iter_mut() {
-                Self::maintain_one(bodies, rb)
+                Self::maintain_one(bodies, rb, &mut reshaped_parents)
             }
         } else {
             for handle in self.modified_colliders.iter() {
                 if let Some(rb) = self.colliders.get_mut(handle.0) {
-                    Self::maintain_one(bodies, rb)
+                    Self::maintain_one(bodies, rb, &mut reshaped_parents)
                 }
             }
         }
+
+        // The CCD data depend on all the colliders attached to the rigid-body, so
+        // they can only be updated once all the colliders have been maintained.
+        for handle in reshaped_parents {
+            if let Some(parent) = bodies.get_mut_internal(handle) {
+                parent.update_colliders_ccd_data(self);
+            }
+        }
     }
 }
 
@@ -352,3 +628,92 @@ impl IndexMut<ColliderHandle> for ColliderSet {
         collider
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Isometry, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn set_positions_bulk_moves_static_colliders() {
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        let ground = world.bodies.insert(
+            RigidBodyBuilder::new_static()
+                .position(Isometry::new(Vector::x() * 2.0, na::zero()))
+                .build(),
+        );
+        let floor = world.colliders.insert(
+            ColliderBuilder::halfspace(Vector::y_axis()).build(),
+            ground,
+            &mut world.bodies,
+        );
+        let ball = world.bodies.insert(
+            RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(Vector::y() * 0.5, na::zero()))
+                .build(),
+        );
+        world
+            .colliders
+            .insert(ColliderBuilder::ball(0.5).build(), ball, &mut world.bodies);
+
+        for _ in 0..10 {
+            world.step(1.0 / 60.0);
+        }
+        assert!(world.bodies[ball].position().translation.y > 0.4);
+
+        // Move the floor far below the ball: the ball falls.
+        let new_pos = Isometry::new(Vector::y() * -100.0, na::zero());
+        world
+            .colliders
+            .set_positions_bulk(&world.bodies, vec![(floor, new_pos)]);
+        assert_eq!(*world.colliders[floor].position(), new_pos);
+        assert_eq!(
+            world.colliders[floor]
+                .position_wrt_parent()
+                .translation
+                .vector,
+            Vector::x() * -2.0 + Vector::y() * -100.0
+        );
+
+        for _ in 0..60 {
+            world.step(1.0 / 60.0);
+        }
+        assert!(world.bodies[ball].position().translation.y < -1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_positions_bulk_rejects_dynamic_colliders() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let body = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        let collider =
+            world
+                .colliders
+                .insert(ColliderBuilder::ball(0.5).build(), body, &mut world.bodies);
+
+        world
+            .colliders
+            .set_positions_bulk(&world.bodies, vec![(collider, Isometry::identity())]);
+    }
+
+    #[test]
+    fn collider_reparenting() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let (rb1, co1) = world.insert_dynamic(Vector::zeros(), ColliderBuilder::ball(0.5));
+        let (rb2, co2) = world.insert_dynamic(Vector::x() * 0.5, ColliderBuilder::ball(0.5));
+        let mass = world.bodies[rb2].mass();
+
+        world.run(1);
+        world
+            .colliders
+            .set_parent(co1, rb2, &mut world.bodies, true);
+        world.run(1);
+
+        assert_eq!(world.colliders[co1].parent(), rb2);
+        assert!(world.bodies[rb1].mass() < 1.0e-5);
+        assert!((world.bodies[rb2].mass() - mass * 2.0).abs() < 1.0e-5);
+        assert!(!world.narrow_phase.has_any_active_contact(co1, co2));
+    }
+}
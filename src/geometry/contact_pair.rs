@@ -1,7 +1,8 @@
 use crate::dynamics::{BodyPair, RigidBodyHandle};
-use crate::geometry::{ColliderPair, Contact, ContactManifold};
-use crate::math::{Point, Real, Vector};
+use crate::geometry::{ColliderPair, Contact, ContactEventPoint, ContactManifold};
+use crate::math::{Isometry, Point, Real, Vector};
 use parry::query::ContactManifoldsWorkspace;
+use parry::utils::IsometryOpt;
 
 bitflags::bitflags! {
     #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
@@ -63,6 +64,9 @@ pub struct ContactPair {
     /// All contact manifold contain themselves contact points between the colliders.
     pub manifolds: Vec<ContactManifold>,
     /// Is there any active contact in this contact pair?
+    ///
+    /// This is `true` if at least one contact point of this pair had a distance smaller than
+    /// the prediction distance during the last timestep.
     pub has_any_active_contact: bool,
     pub(crate) workspace: Option<ContactManifoldsWorkspace>,
 }
@@ -77,6 +81,26 @@ impl ContactPair {
         }
     }
 
+    // All the contact points of this pair in world-space, given the position of its first collider.
+    pub(crate) fn contact_event_points(&self, pos1: &Isometry<Real>) -> Vec<ContactEventPoint> {
+        self.manifolds
+            .iter()
+            .flat_map(|manifold| {
+                let world_pos1 = manifold.subshape_pos1.prepend_to(pos1);
+                let normal = manifold.data.normal;
+                manifold
+                    .points
+                    .iter()
+                    .map(move |contact| ContactEventPoint {
+                        point: world_pos1 * contact.local_p1 + normal * contact.dist / 2.0,
+                        normal,
+                        dist: contact.dist,
+                        impulse: contact.data.impulse,
+                    })
+            })
+            .collect()
+    }
+
     /// Finds the contact with the smallest signed distance.
     ///
     /// If the colliders involved in this contact pair are penetrating, then
@@ -153,6 +177,8 @@ pub struct ContactManifoldData {
     pub relative_dominance: i16,
     /// A user-defined piece of data.
     pub user_data: u32,
+    // The state of the one-way platform filtering, if one of the colliders is a one-way platform.
+    pub(crate) one_way_state: u32,
 }
 
 /// A contact seen by the constraints solver for computing forces.
@@ -226,6 +252,7 @@ impl ContactManifoldData {
             solver_contacts: Vec::new(),
             relative_dominance: 0,
             user_data: 0,
+            one_way_state: 0,
         }
     }
 
@@ -0,0 +1,349 @@
+use crate::geometry::{
+    ContactData, ContactManifold, ContactManifoldData, Cuboid, Shape, TrackedContact,
+};
+use crate::math::{Isometry, Point, Real, Vector};
+use parry::query::PersistentQueryDispatcher;
+use parry::shape::{HalfSpace, PackedFeatureId};
+
+// Two normals with a dot product smaller than `-1.0 + FLAT_DOT_EPSILON` are considered
+// opposite, i.e., the faces they belong to are considered flat against each other.
+const FLAT_DOT_EPSILON: Real = 1.0e-2;
+
+// A contact point: its location in the local-space of the frustum, its location in the
+// local-space of the other shape, their feature identifiers, and the contact distance.
+type FrustumContact = (
+    Point<Real>,
+    Point<Real>,
+    PackedFeatureId,
+    PackedFeatureId,
+    Real,
+);
+
+/// A cylinder or a cone, described as the convex hull of two disks orthogonal to the `y` axis.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Frustum {
+    // The height and radius of the bottom and top disks.
+    disks: [(Real, Real); 2],
+}
+
+impl Frustum {
+    /// The frustum matching the given shape, if it is a cylinder or a cone.
+    pub fn from_shape(shape: &dyn Shape) -> Option<Self> {
+        if let Some(cylinder) = shape.as_cylinder() {
+            Some(Self {
+                disks: [
+                    (-cylinder.half_height, cylinder.radius),
+                    (cylinder.half_height, cylinder.radius),
+                ],
+            })
+        } else {
+            shape.as_cone().map(|cone| Self {
+                disks: [(-cone.half_height, cone.radius), (cone.half_height, 0.0)],
+            })
+        }
+    }
+
+    // Is the given disk flat against a plane with the given normal, expressed in the
+    // local-space of the frustum?
+    fn is_disk_flat(&self, disk: usize, local_plane_normal: &Vector<Real>) -> bool {
+        let (_, radius) = self.disks[disk];
+        let outward_y = if disk == 0 { -1.0 } else { 1.0 };
+        radius > 0.0 && outward_y * local_plane_normal.y <= -1.0 + FLAT_DOT_EPSILON
+    }
+
+    // Computes the contact points between this frustum and the plane with the given normal,
+    // at the distance `plane_offset` from the origin.
+    //
+    // The plane normal is expressed in the local-space of the plane, and `pos` is the position
+    // of the frustum relative to the plane. Each disk flat against the plane generates four
+    // points of its rim, fixed relative to the frustum. The other disks generate the point of
+    // their rim closest to the plane.
+    fn plane_contacts(
+        &self,
+        pos: &Isometry<Real>,
+        plane_normal: &Vector<Real>,
+        plane_offset: Real,
+        prediction: Real,
+        out: &mut Vec<FrustumContact>,
+    ) {
+        let local_n = pos.inverse_transform_vector(plane_normal);
+        let rim_dirs = [Vector::x(), Vector::z(), -Vector::x(), -Vector::z()];
+        let closest_rim_dir = Vector::new(-local_n.x, 0.0, -local_n.z)
+            .try_normalize(Real::EPSILON)
+            .unwrap_or_else(Vector::zeros);
+
+        for (k, (y, radius)) in self.disks.iter().enumerate() {
+            let center = Point::new(0.0, *y, 0.0);
+            let mut candidates = [None; 4];
+
+            if self.is_disk_flat(k, &local_n) {
+                for (j, dir) in rim_dirs.iter().enumerate() {
+                    candidates[j] = Some((center + dir * *radius, k * 5 + j));
+                }
+            } else {
+                candidates[0] = Some((center + closest_rim_dir * *radius, k * 5 + 4));
+            }
+
+            for (local_pt, id) in candidates.iter().flatten() {
+                let pt = pos * local_pt;
+                let dist = pt.coords.dot(plane_normal) - plane_offset;
+
+                if dist <= prediction {
+                    out.push((
+                        *local_pt,
+                        pt - plane_normal * dist,
+                        PackedFeatureId::face(*id as u32),
+                        PackedFeatureId::UNKNOWN,
+                        dist,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Computes the contact manifold between a cylinder or cone, and a half-space.
+///
+/// Unlike the generic contact manifold computation, this generates one contact per disk
+/// of the frustum touching the half-space (so that a cylinder lying on its side touches the
+/// ground at both ends), and four contacts on the rim of a disk lying flat on the half-space.
+///
+/// If `flipped` is `false`, then `pos12` is the position of the half-space relative to the
+/// frustum, and the first shape of the manifold is the frustum. Otherwise, `pos12` is the
+/// position of the frustum relative to the half-space, and the first shape of the manifold
+/// is the half-space.
+pub(crate) fn contact_manifolds_frustum_halfspace(
+    pos12: &Isometry<Real>,
+    frustum: &Frustum,
+    halfspace: &HalfSpace,
+    prediction: Real,
+    manifolds: &mut Vec<ContactManifold>,
+    flipped: bool,
+) {
+    // Position of the frustum relative to the half-space.
+    let pos_hs_frustum = if flipped { *pos12 } else { pos12.inverse() };
+    let normal = *halfspace.normal;
+    let mut points = Vec::new();
+    frustum.plane_contacts(&pos_hs_frustum, &normal, 0.0, prediction, &mut points);
+
+    update_manifold(&pos_hs_frustum, &normal, points, manifolds, flipped);
+}
+
+/// Computes the contact manifold between a cylinder or cone, and a cuboid.
+///
+/// If the contact normal is close to the normal of a face of the cuboid, the contacts between
+/// the frustum and the plane of this face are computed like for a half-space, and clipped to
+/// this face. The corners of this face located under a disk lying flat on it are added as well.
+/// Otherwise, a single contact is generated by the query dispatcher.
+///
+/// The `frustum_shape` must be the shape `frustum` was created from. If `flipped` is `false`,
+/// then `pos12` is the position of the cuboid relative to the frustum, and the first shape of
+/// the manifold is the frustum. Otherwise, `pos12` is the position of the frustum relative to
+/// the cuboid, and the first shape of the manifold is the cuboid.
+pub(crate) fn contact_manifolds_frustum_cuboid(
+    dispatcher: &dyn PersistentQueryDispatcher<ContactManifoldData, ContactData>,
+    pos12: &Isometry<Real>,
+    frustum_shape: &dyn Shape,
+    frustum: &Frustum,
+    cuboid: &Cuboid,
+    prediction: Real,
+    manifolds: &mut Vec<ContactManifold>,
+    flipped: bool,
+) {
+    // Position of the frustum relative to the cuboid.
+    let pos_cuboid_frustum = if flipped { *pos12 } else { pos12.inverse() };
+    let contact = match dispatcher.contact(&pos_cuboid_frustum, cuboid, frustum_shape, prediction) {
+        Ok(Some(contact)) => contact,
+        _ => {
+            manifolds.clear();
+            return;
+        }
+    };
+
+    // The face of the cuboid closest to the contact normal.
+    let n = *contact.normal1;
+    let i = n.iamax();
+    let sign = if n[i] < 0.0 { -1.0 } else { 1.0 };
+    let face_normal = Vector::ith(i, sign);
+    let he = cuboid.half_extents;
+    let mut points = Vec::new();
+
+    if n.dot(&face_normal) >= 1.0 - FLAT_DOT_EPSILON {
+        frustum.plane_contacts(
+            &pos_cuboid_frustum,
+            &face_normal,
+            he[i],
+            prediction,
+            &mut points,
+        );
+
+        let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+        points.retain(|(_, pt, ..)| pt[j].abs() <= he[j] && pt[k].abs() <= he[k]);
+
+        // The corners of the face located under a disk flat on it.
+        let local_n = pos_cuboid_frustum.inverse_transform_vector(&face_normal);
+
+        for (disk, (y, radius)) in frustum.disks.iter().enumerate() {
+            if !frustum.is_disk_flat(disk, &local_n) {
+                continue;
+            }
+
+            let outward_y = if disk == 0 { -1.0 } else { 1.0 };
+
+            for (c, (sj, sk)) in [(1.0, 1.0), (-1.0, 1.0), (-1.0, -1.0), (1.0, -1.0)]
+                .iter()
+                .enumerate()
+            {
+                let mut corner = Point::origin();
+                corner[i] = sign * he[i];
+                corner[j] = sj * he[j];
+                corner[k] = sk * he[k];
+
+                let local_corner = pos_cuboid_frustum.inverse_transform_point(&corner);
+                let dist = (local_corner.y - y) * outward_y;
+
+                if local_corner.x * local_corner.x + local_corner.z * local_corner.z
+                    <= radius * radius
+                    && dist <= prediction
+                {
+                    points.push((
+                        Point::new(local_corner.x, *y, local_corner.z),
+                        corner,
+                        PackedFeatureId::face((10 + disk * 4 + c) as u32),
+                        PackedFeatureId::vertex(c as u32),
+                        dist,
+                    ));
+                }
+            }
+        }
+    }
+
+    if points.is_empty() {
+        points.push((
+            contact.point2,
+            contact.point1,
+            PackedFeatureId::UNKNOWN,
+            PackedFeatureId::UNKNOWN,
+            contact.dist,
+        ));
+        update_manifold(&pos_cuboid_frustum, &n, points, manifolds, flipped);
+    } else {
+        update_manifold(
+            &pos_cuboid_frustum,
+            &face_normal,
+            points,
+            manifolds,
+            flipped,
+        );
+    }
+}
+
+// Replaces the content of `manifolds` by a single manifold with the given contacts.
+//
+// The `normal` is the contact normal in the local-space of the other shape, and `pos` is the
+// position of the frustum relative to the other shape.
+fn update_manifold(
+    pos: &Isometry<Real>,
+    normal: &Vector<Real>,
+    points: Vec<FrustumContact>,
+    manifolds: &mut Vec<ContactManifold>,
+    flipped: bool,
+) {
+    if points.is_empty() {
+        manifolds.clear();
+        return;
+    }
+
+    manifolds.truncate(1);
+    let mut manifold = manifolds
+        .pop()
+        .unwrap_or_else(|| ContactManifold::with_data(0, 0, ContactManifoldData::default()));
+    let old_points = std::mem::replace(&mut manifold.points, Vec::new());
+    let frustum_normal = pos.inverse_transform_vector(&-normal);
+
+    if flipped {
+        manifold.local_n1 = *normal;
+        manifold.local_n2 = frustum_normal;
+    } else {
+        manifold.local_n1 = frustum_normal;
+        manifold.local_n2 = *normal;
+    }
+
+    for (p_frustum, p_other, fid_frustum, fid_other, dist) in points {
+        let mut contact = if flipped {
+            TrackedContact::new(p_other, p_frustum, fid_other, fid_frustum, dist)
+        } else {
+            TrackedContact::new(p_frustum, p_other, fid_frustum, fid_other, dist)
+        };
+
+        // Transfer the contact impulses computed at the previous step for the same features.
+        if let Some(old) = old_points
+            .iter()
+            .find(|old| old.fid1 == contact.fid1 && old.fid2 == contact.fid2)
+        {
+            contact.data = old.data;
+        }
+
+        manifold.points.push(contact);
+    }
+
+    manifolds.push(manifold);
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Isometry, Real, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn cylinder_cone_manifolds() {
+        let grounds = vec![
+            ColliderBuilder::halfspace(Vector::y_axis()),
+            ColliderBuilder::cuboid(10.0, 0.5, 10.0).translation(0.0, -0.5, 0.0),
+        ];
+
+        for ground_co in grounds {
+            let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+            let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+            let ground_co = world
+                .colliders
+                .insert(ground_co.build(), ground, &mut world.bodies);
+
+            // A standing cylinder, a cylinder lying on its side, and a standing cone,
+            // with their expected number of contacts.
+            let shapes = vec![
+                (ColliderBuilder::cylinder(0.5, 0.5), na::zero(), 4),
+                (
+                    ColliderBuilder::cylinder(0.5, 0.5),
+                    Vector::x() * std::f32::consts::FRAC_PI_2 as Real,
+                    2,
+                ),
+                (ColliderBuilder::cone(0.5, 0.5), na::zero(), 4),
+            ];
+
+            let handles: Vec<_> = shapes
+                .into_iter()
+                .enumerate()
+                .map(|(i, (co, rot, num_contacts))| {
+                    let rb = RigidBodyBuilder::new_dynamic()
+                        .position(Isometry::new(Vector::new(i as Real * 3.0, 0.6, 0.0), rot))
+                        .build();
+                    let rb = world.bodies.insert(rb);
+                    let co = world.colliders.insert(co.build(), rb, &mut world.bodies);
+                    (rb, co, num_contacts)
+                })
+                .collect();
+            world.run(120);
+
+            // The shapes rest without jittering, with the expected number of contacts.
+            for (rb, co, num_contacts) in handles {
+                assert!(world.bodies[rb].angvel().norm() < 1.0e-2);
+                assert!(world.colliders[co].compute_aabb().mins.y.abs() < 0.05);
+                let pair = world.narrow_phase.contact_pair(ground_co, co).unwrap();
+                assert_eq!(pair.manifolds[0].points.len(), num_contacts);
+            }
+        }
+    }
+}
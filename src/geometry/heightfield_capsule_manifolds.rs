@@ -0,0 +1,154 @@
+use crate::geometry::{Capsule, ContactManifold, ContactManifoldData, HeightField, Shape};
+use crate::math::{Isometry, Real};
+use parry::query::contact_manifolds::contact_manifold_pfm_pfm;
+
+/// Computes the contact manifolds between a heightfield and a capsule.
+///
+/// This is a specialization of the generic heightfield-vs-shape contact manifold
+/// computation. Each triangle of the heightfield overlapping the capsule AABB is
+/// first tested against the capsule with a cheap plane test, and only the triangles
+/// that pass this test are given to the polygonal feature-based contact manifold
+/// computation (which generates at most two contacts per triangle). No workspace
+/// is needed: the contact manifolds are matched to their triangles using the
+/// sub-shape identifier stored in the manifolds themselves.
+///
+/// If `flipped` is `false`, then `pos12` is the position of the capsule relative to the
+/// heightfield, and the first shape of the manifolds is the heightfield. Otherwise, `pos12`
+/// is the position of the heightfield relative to the capsule, and the first shape of the
+/// manifolds is the capsule.
+pub(crate) fn contact_manifolds_heightfield_capsule(
+    pos12: &Isometry<Real>,
+    heightfield: &HeightField,
+    capsule: &Capsule,
+    prediction: Real,
+    manifolds: &mut Vec<ContactManifold>,
+    flipped: bool,
+) {
+    // Position of the capsule relative to the heightfield.
+    let pos_hf_capsule = if flipped { pos12.inverse() } else { *pos12 };
+    let aabb = capsule.compute_aabb(&pos_hf_capsule).loosened(prediction);
+    let seg_a = pos_hf_capsule * capsule.segment.a;
+    let seg_b = pos_hf_capsule * capsule.segment.b;
+    let max_dist = capsule.radius + prediction;
+
+    let mut old_manifolds = std::mem::replace(manifolds, Vec::new());
+
+    heightfield.map_elements_in_local_aabb(&aabb, &mut |tri_id, triangle| {
+        // Early-out: if both ends of the capsule segment are on the same side
+        // of the triangle plane and far enough from it, there can't be any contact.
+        if let Some(normal) = triangle.normal() {
+            let dist_a = (seg_a - triangle.a).dot(&normal);
+            let dist_b = (seg_b - triangle.a).dot(&normal);
+
+            if dist_a * dist_b > 0.0 && dist_a.abs().min(dist_b.abs()) > max_dist {
+                return;
+            }
+        }
+
+        // Reuse the manifold computed at the previous step for this triangle (if any)
+        // so its contact impulses can be transferred to the new contacts.
+        let old_id = old_manifolds.iter().position(|m| {
+            if flipped {
+                m.subshape2 == tri_id
+            } else {
+                m.subshape1 == tri_id
+            }
+        });
+        let mut manifold = match old_id {
+            Some(id) => old_manifolds.swap_remove(id),
+            None if flipped => {
+                ContactManifold::with_data(0, tri_id, ContactManifoldData::default())
+            }
+            None => ContactManifold::with_data(tri_id, 0, ContactManifoldData::default()),
+        };
+
+        if flipped {
+            contact_manifold_pfm_pfm(
+                pos12,
+                &capsule.segment,
+                capsule.radius,
+                triangle,
+                0.0,
+                prediction,
+                &mut manifold,
+            );
+        } else {
+            contact_manifold_pfm_pfm(
+                pos12,
+                triangle,
+                0.0,
+                &capsule.segment,
+                capsule.radius,
+                prediction,
+                &mut manifold,
+            );
+        }
+
+        if !manifold.points.is_empty() {
+            manifolds.push(manifold);
+        }
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::contact_manifolds_heightfield_capsule;
+    use crate::geometry::{Capsule, HeightField};
+    use crate::math::{Isometry, Point, Vector};
+
+    #[test]
+    fn capsule_on_flat_heightfield() {
+        let heightfield = HeightField::new(na::DMatrix::zeros(5, 5), Vector::new(10.0, 1.0, 10.0));
+        let capsule = Capsule::new(Point::new(0.0, -0.5, 0.0), Point::new(0.0, 0.5, 0.0), 0.2);
+        // The bottom of the capsule penetrates the heightfield by 0.05, far from the edges
+        // of its triangle.
+        let pos_capsule = Isometry::translation(1.7, 0.65, 0.3);
+
+        let mut manifolds = Vec::new();
+        contact_manifolds_heightfield_capsule(
+            &pos_capsule,
+            &heightfield,
+            &capsule,
+            0.0,
+            &mut manifolds,
+            false,
+        );
+
+        assert_eq!(manifolds.len(), 1);
+        for manifold in &manifolds {
+            assert!(manifold.points.len() <= 2);
+            assert!((manifold.local_n1 - Vector::y()).norm() < 1.0e-5);
+            for point in &manifold.points {
+                assert!((point.dist + 0.05).abs() < 1.0e-5);
+            }
+        }
+
+        // Same result with the shapes swapped.
+        let mut flipped_manifolds = Vec::new();
+        contact_manifolds_heightfield_capsule(
+            &pos_capsule.inverse(),
+            &heightfield,
+            &capsule,
+            0.0,
+            &mut flipped_manifolds,
+            true,
+        );
+
+        assert_eq!(flipped_manifolds.len(), manifolds.len());
+        for manifold in &flipped_manifolds {
+            assert!((manifold.local_n1 + Vector::y()).norm() < 1.0e-5);
+            assert_eq!(manifold.subshape1, 0);
+        }
+
+        // The manifolds are updated in-place, and removed when the capsule leaves.
+        contact_manifolds_heightfield_capsule(
+            &Isometry::translation(1.7, 5.0, 0.3),
+            &heightfield,
+            &capsule,
+            0.0,
+            &mut manifolds,
+            false,
+        );
+        assert!(manifolds.is_empty());
+    }
+}
@@ -1,56 +1,77 @@
-#[repr(transparent)]
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 /// Pairwise filtering using bit masks.
 ///
-/// This filtering method is based on two 16-bit values:
-/// - The interaction groups (the 16 left-most bits of `self.0`).
-/// - The interaction mask (the 16 right-most bits of `self.0`).
+/// This filtering method is based on two 32-bit values:
+/// - The interaction groups memberships.
+/// - The interaction groups filter.
 ///
 /// An interaction is allowed between two filters `a` and `b` when two conditions
 /// are met simultaneously:
-/// - The interaction groups of `a` has at least one bit set to `1` in common with the interaction mask of `b`.
-/// - The interaction groups of `b` has at least one bit set to `1` in common with the interaction mask of `a`.
+/// - The groups membership of `a` has at least one bit set to `1` in common with the groups filter of `b`.
+/// - The groups membership of `b` has at least one bit set to `1` in common with the groups filter of `a`.
 ///
 /// In other words, interactions are allowed between two filter iff. the following condition is met:
 /// ```ignore
-/// ((self.0 >> 16) & rhs.0) != 0 && ((rhs.0 >> 16) & self.0) != 0
+/// (self.memberships & rhs.filter) != 0 && (rhs.memberships & self.filter) != 0
 /// ```
-pub struct InteractionGroups(pub u32);
+///
+/// See [CollisionLayers] for giving names to the groups.
+pub struct InteractionGroups {
+    /// Groups memberships.
+    pub memberships: u32,
+    /// Groups filter.
+    pub filter: u32,
+}
 
 impl InteractionGroups {
-    /// Initializes with the given interaction groups and interaction mask.
-    pub const fn new(groups: u16, masks: u16) -> Self {
-        Self::none().with_groups(groups).with_mask(masks)
+    /// Initializes with the given interaction groups memberships and filter.
+    pub const fn new(memberships: u32, filter: u32) -> Self {
+        Self {
+            memberships,
+            filter,
+        }
     }
 
     /// Allow interaction with everything.
     pub const fn all() -> Self {
-        Self(u32::MAX)
+        Self::new(u32::MAX, u32::MAX)
     }
 
     /// Prevent all interactions.
     pub const fn none() -> Self {
-        Self(0)
+        Self::new(0, 0)
     }
 
     /// Sets the group this filter is part of.
-    pub const fn with_groups(self, groups: u16) -> Self {
-        Self((self.0 & 0x0000ffff) | ((groups as u32) << 16))
+    pub const fn with_memberships(self, memberships: u32) -> Self {
+        Self::new(memberships, self.filter)
     }
 
     /// Sets the interaction mask of this filter.
-    pub const fn with_mask(self, mask: u16) -> Self {
-        Self((self.0 & 0xffff0000) | (mask as u32))
+    pub const fn with_filter(self, filter: u32) -> Self {
+        Self::new(self.memberships, filter)
+    }
+
+    /// Sets the group this filter is part of.
+    #[deprecated = "Use `InteractionGroups::with_memberships` instead"]
+    pub const fn with_groups(self, groups: u32) -> Self {
+        self.with_memberships(groups)
     }
 
-    /// Check if interactions should be allowed based on the interaction groups and mask.
+    /// Sets the interaction mask of this filter.
+    #[deprecated = "Use `InteractionGroups::with_filter` instead"]
+    pub const fn with_mask(self, mask: u32) -> Self {
+        self.with_filter(mask)
+    }
+
+    /// Check if interactions should be allowed based on the interaction memberships and filter.
     ///
-    /// An interaction is allowed iff. the groups of `self` contain at least one bit set to 1 in common
-    /// with the mask of `rhs`, and vice-versa.
+    /// An interaction is allowed iff. the memberships of `self` contain at least one bit set to 1 in common
+    /// with the filter of `rhs`, and vice-versa.
     #[inline]
     pub const fn test(self, rhs: Self) -> bool {
-        ((self.0 >> 16) & rhs.0) != 0 && ((rhs.0 >> 16) & self.0) != 0
+        (self.memberships & rhs.filter) != 0 && (rhs.memberships & self.filter) != 0
     }
 }
 
@@ -59,3 +80,121 @@ impl Default for InteractionGroups {
         Self::all()
     }
 }
+
+/// A registry of named collision layers, each being mapped to one of the 32 bits of
+/// the [InteractionGroups] memberships and filter.
+///
+/// ```
+/// # #[cfg(all(feature = "dim2", feature = "f32"))]
+/// # use rapier2d::geometry::CollisionLayers;
+/// # #[cfg(all(feature = "dim2", feature = "f64"))]
+/// # use rapier2d_f64::geometry::CollisionLayers;
+/// # #[cfg(all(feature = "dim3", feature = "f32"))]
+/// # use rapier3d::geometry::CollisionLayers;
+/// # #[cfg(all(feature = "dim3", feature = "f64"))]
+/// # use rapier3d_f64::geometry::CollisionLayers;
+/// let mut layers = CollisionLayers::new();
+/// let player = layers.register("player");
+/// let enemy = layers.register("enemy");
+/// let projectile = layers.register("projectile");
+/// // Projectiles hit everything except other projectiles.
+/// let groups = layers.groups(&["projectile"], &["player", "enemy"]);
+/// assert_eq!(groups.memberships, projectile);
+/// assert_eq!(groups.filter, player | enemy);
+/// assert!(!groups.test(groups));
+/// ```
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default)]
+pub struct CollisionLayers {
+    names: Vec<String>,
+}
+
+impl CollisionLayers {
+    /// Creates a registry without any layer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a new layer with the given name and returns its bit mask.
+    ///
+    /// If a layer with this name already exists, its bit mask is returned instead.
+    /// Panics if 32 layers are already registered.
+    pub fn register(&mut self, name: &str) -> u32 {
+        if let Some(mask) = self.layer(name) {
+            return mask;
+        }
+
+        assert!(
+            self.names.len() < 32,
+            "Cannot register more than 32 collision layers."
+        );
+        self.names.push(name.to_string());
+        1 << (self.names.len() - 1)
+    }
+
+    /// The bit mask of the layer with the given name, if it was registered.
+    pub fn layer(&self, name: &str) -> Option<u32> {
+        self.names.iter().position(|n| n == name).map(|i| 1 << i)
+    }
+
+    /// The name of the layer with the given bit index (from 0 to 31), if it was registered.
+    pub fn name(&self, bit: u32) -> Option<&str> {
+        self.names.get(bit as usize).map(|n| n.as_str())
+    }
+
+    /// The number of registered layers.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Is this registry empty?
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// The union of the bit masks of the layers with the given names.
+    ///
+    /// Panics if one of these layers was not registered.
+    pub fn mask(&self, names: &[&str]) -> u32 {
+        names.iter().fold(0, |mask, name| {
+            mask | self
+                .layer(name)
+                .unwrap_or_else(|| panic!("Unknown collision layer: {}", name))
+        })
+    }
+
+    /// The interaction groups belonging to the `memberships` layers, and interacting with
+    /// the `filter` layers.
+    ///
+    /// Panics if one of these layers was not registered.
+    pub fn groups(&self, memberships: &[&str], filter: &[&str]) -> InteractionGroups {
+        InteractionGroups::new(self.mask(memberships), self.mask(filter))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CollisionLayers, InteractionGroups};
+
+    #[test]
+    fn collision_layers() {
+        let mut layers = CollisionLayers::new();
+        for i in 0..30 {
+            layers.register(&format!("layer{}", i));
+        }
+
+        assert_eq!(layers.register("player"), 1 << 30);
+        assert_eq!(layers.register("enemy"), 1 << 31);
+        assert_eq!(layers.register("player"), 1 << 30);
+        assert_eq!(layers.len(), 32);
+        assert_eq!(layers.name(31), Some("enemy"));
+
+        let player = layers.groups(&["player"], &["enemy", "layer0"]);
+        let enemy = layers.groups(&["enemy"], &["player"]);
+        let other = layers.groups(&["layer1"], &["player", "enemy"]);
+        assert_eq!(player, InteractionGroups::new(1 << 30, (1 << 31) | 1));
+        assert!(player.test(enemy));
+        assert!(!player.test(other));
+        assert!(!enemy.test(enemy));
+    }
+}
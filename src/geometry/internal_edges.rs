@@ -0,0 +1,387 @@
+use crate::geometry::ContactManifold;
+use crate::math::{Isometry, Point, Real, Vector};
+use parry::shape::Shape;
+
+// Two normals with a dot product greater than this are considered equal.
+const NORMAL_DOT_EPSILON: Real = 1.0e-4;
+
+/// The adjacency information of the triangles of a triangle mesh (3D), or
+/// of the segments of a polyline (2D).
+///
+/// This is used to detect contacts generated by internal edges (3D) or
+/// internal vertices (2D), a.k.a. ghost collisions.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub(crate) struct MeshTopology {
+    // The two primitives sharing each internal edge (3D) or vertex (2D), sorted by key.
+    // Features belonging to a single primitive are on the boundary of the mesh, and are
+    // not stored here.
+    adjacency: Vec<(u64, [u32; 2])>,
+}
+
+// The geometry of a mesh primitive around a contact point.
+struct LocalGeometry {
+    // The (non-oriented) normal of the primitive.
+    normal: Vector<Real>,
+    // For the feature closest to the contact point: a point on this feature, as well
+    // as the (non-oriented) normal and the opposite vertex of the other primitive sharing it.
+    neighbor: Option<(Point<Real>, Vector<Real>, Point<Real>)>,
+}
+
+impl MeshTopology {
+    /// Computes the topology of the given shape.
+    ///
+    /// Returns `None` if the shape is not a triangle mesh (3D) or a polyline (2D).
+    pub fn new(shape: &dyn Shape) -> Option<Self> {
+        let mut features = Vec::new();
+
+        #[cfg(feature = "dim2")]
+        for (i, idx) in shape.as_polyline()?.indices().iter().enumerate() {
+            features.push((idx[0] as u64, i as u32));
+            features.push((idx[1] as u64, i as u32));
+        }
+
+        #[cfg(feature = "dim3")]
+        for (i, idx) in shape.as_trimesh()?.indices().iter().enumerate() {
+            for k in 0..3 {
+                features.push((edge_key(idx[k], idx[(k + 1) % 3]), i as u32));
+            }
+        }
+
+        features.sort_unstable();
+
+        let adjacency = features
+            .windows(2)
+            .filter(|w| w[0].0 == w[1].0)
+            .map(|w| (w[0].0, [w[0].1, w[1].1]))
+            .collect();
+
+        Some(Self { adjacency })
+    }
+
+    fn neighbor(&self, key: u64, primitive: u32) -> Option<u32> {
+        let i = self
+            .adjacency
+            .binary_search_by_key(&key, |entry| entry.0)
+            .ok()?;
+        let [a, b] = self.adjacency[i].1;
+
+        if a == primitive {
+            Some(b)
+        } else if b == primitive {
+            Some(a)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "dim2")]
+    fn local_geometry(
+        &self,
+        shape: &dyn Shape,
+        id: u32,
+        pt: &Point<Real>,
+    ) -> Option<LocalGeometry> {
+        let polyline = shape.as_polyline()?;
+        let idx = polyline.indices().get(id as usize)?;
+        let segment = polyline.segment(id);
+        let normal = segment_normal(&segment.a, &segment.b);
+
+        // The vertex closest to the contact point.
+        let k = if na::distance_squared(pt, &segment.a) < na::distance_squared(pt, &segment.b) {
+            0
+        } else {
+            1
+        };
+
+        let neighbor = self.neighbor(idx[k] as u64, id).map(|nb| {
+            let nb_idx = polyline.indices()[nb as usize];
+            let nb_segment = polyline.segment(nb);
+            let opposite = if nb_idx[0] == idx[k] {
+                nb_segment.b
+            } else {
+                nb_segment.a
+            };
+            let vertex = if k == 0 { segment.a } else { segment.b };
+
+            (
+                vertex,
+                segment_normal(&nb_segment.a, &nb_segment.b),
+                opposite,
+            )
+        });
+
+        Some(LocalGeometry { normal, neighbor })
+    }
+
+    #[cfg(feature = "dim3")]
+    fn local_geometry(
+        &self,
+        shape: &dyn Shape,
+        id: u32,
+        pt: &Point<Real>,
+    ) -> Option<LocalGeometry> {
+        let trimesh = shape.as_trimesh()?;
+        let idx = trimesh.indices().get(id as usize)?;
+        let triangle = trimesh.triangle(id);
+        let vertices = [triangle.a, triangle.b, triangle.c];
+        let normal = triangle_normal(&vertices);
+
+        // The edge closest to the contact point.
+        let k = (0..3)
+            .map(|k| {
+                (
+                    k,
+                    segment_distance(pt, &vertices[k], &vertices[(k + 1) % 3]),
+                )
+            })
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))?
+            .0;
+
+        let neighbor = self
+            .neighbor(edge_key(idx[k], idx[(k + 1) % 3]), id)
+            .map(|nb| {
+                let nb_idx = trimesh.indices()[nb as usize];
+                let nb_triangle = trimesh.triangle(nb);
+                let nb_vertices = [nb_triangle.a, nb_triangle.b, nb_triangle.c];
+                let opposite = (0..3)
+                    .find(|i| nb_idx[*i] != idx[k] && nb_idx[*i] != idx[(k + 1) % 3])
+                    .map(|i| nb_vertices[i])
+                    .unwrap_or(nb_vertices[0]);
+
+                (vertices[k], triangle_normal(&nb_vertices), opposite)
+            });
+
+        Some(LocalGeometry { normal, neighbor })
+    }
+}
+
+/// Replaces the normal of a contact manifold generated by an internal edge (3D) or
+/// internal vertex (2D) of a mesh by the normal of the mesh primitive involved.
+///
+/// The `mesh` must be the shape `topology` was computed from. If `mesh_is_first` is `true`,
+/// then `mesh` is the first shape of the manifold, and `pos12` is the position of the
+/// second sub-shape relative to the mesh. Otherwise `mesh` is the second shape of the
+/// manifold, and `pos12` is the position of the mesh relative to the first sub-shape.
+pub(crate) fn correct_internal_edge_normal(
+    topology: &MeshTopology,
+    mesh: &dyn Shape,
+    pos12: &Isometry<Real>,
+    mesh_is_first: bool,
+    manifold: &mut ContactManifold,
+) {
+    let (id, n) = if mesh_is_first {
+        (manifold.subshape1, manifold.local_n1)
+    } else {
+        (manifold.subshape2, manifold.local_n2)
+    };
+
+    let mut corrected_normal = None;
+
+    for contact in &manifold.points {
+        let pt = if mesh_is_first {
+            contact.local_p1
+        } else {
+            contact.local_p2
+        };
+
+        let geometry = match topology.local_geometry(mesh, id, &pt) {
+            Some(geometry) => geometry,
+            None => return,
+        };
+
+        if geometry.normal == Vector::zeros() {
+            // Degenerate primitive.
+            return;
+        }
+
+        // Orient the primitive normal toward the other shape.
+        let sign = if geometry.normal.dot(&n) < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        let face_normal = geometry.normal * sign;
+
+        if n.dot(&face_normal) >= 1.0 - NORMAL_DOT_EPSILON {
+            // This is a face contact.
+            return;
+        }
+
+        if let Some((feature_pt, nb_normal, opposite)) = geometry.neighbor {
+            // NOTE: this assumes the mesh primitives are consistently oriented.
+            let nb_normal = nb_normal * sign;
+            let cos_angle = face_normal.dot(&nb_normal);
+            let is_concave = (opposite - feature_pt).dot(&face_normal) > 0.0;
+            let is_flat = cos_angle >= 1.0 - NORMAL_DOT_EPSILON;
+            // On a convex feature, the valid normals lie between the two primitive normals.
+            let is_in_cone = n.dot(&face_normal) >= cos_angle - NORMAL_DOT_EPSILON
+                && n.dot(&nb_normal) >= cos_angle - NORMAL_DOT_EPSILON;
+
+            if is_concave || is_flat || !is_in_cone {
+                corrected_normal = Some(face_normal);
+            }
+        }
+    }
+
+    if let Some(normal) = corrected_normal {
+        let (n1, n2) = if mesh_is_first {
+            (normal, pos12.inverse_transform_vector(&-normal))
+        } else {
+            (pos12.transform_vector(&-normal), normal)
+        };
+
+        manifold.local_n1 = n1;
+        manifold.local_n2 = n2;
+
+        for contact in &mut manifold.points {
+            contact.dist = (pos12 * contact.local_p2 - contact.local_p1).dot(&n1);
+        }
+    }
+}
+
+/// Removes the contacts generated by a segment of a one-sided polyline if the other collider
+/// is behind this segment.
+///
+/// The `polyline` must be the first shape of the manifold if `polyline_is_first` is `true`,
+/// and the second one otherwise. The `other_origin` is the origin of the other collider,
+/// expressed in the local-space of the polyline.
+#[cfg(feature = "dim2")]
+pub(crate) fn remove_one_sided_back_contacts(
+    polyline: &dyn Shape,
+    other_origin: &Point<Real>,
+    polyline_is_first: bool,
+    manifold: &mut ContactManifold,
+) {
+    let polyline = match polyline.as_polyline() {
+        Some(polyline) => polyline,
+        None => return,
+    };
+    let id = if polyline_is_first {
+        manifold.subshape1
+    } else {
+        manifold.subshape2
+    };
+
+    if id as usize >= polyline.indices().len() {
+        return;
+    }
+
+    let segment = polyline.segment(id);
+    // The front side of the segment is its left side.
+    let front_normal = -segment_normal(&segment.a, &segment.b);
+
+    if (other_origin - segment.a).dot(&front_normal) < 0.0 {
+        manifold.points.clear();
+    }
+}
+
+#[cfg(feature = "dim2")]
+fn segment_normal(a: &Point<Real>, b: &Point<Real>) -> Vector<Real> {
+    let dir = b - a;
+    Vector::new(dir.y, -dir.x)
+        .try_normalize(Real::EPSILON)
+        .unwrap_or_else(Vector::zeros)
+}
+
+#[cfg(feature = "dim3")]
+fn triangle_normal(vertices: &[Point<Real>; 3]) -> Vector<Real> {
+    (vertices[1] - vertices[0])
+        .cross(&(vertices[2] - vertices[0]))
+        .try_normalize(Real::EPSILON)
+        .unwrap_or_else(Vector::zeros)
+}
+
+#[cfg(feature = "dim3")]
+fn segment_distance(pt: &Point<Real>, a: &Point<Real>, b: &Point<Real>) -> Real {
+    let ab = b - a;
+    let t = ((pt - a).dot(&ab) / ab.norm_squared().max(Real::EPSILON))
+        .max(0.0)
+        .min(1.0);
+    na::distance(pt, &(a + ab * t))
+}
+
+#[cfg(feature = "dim3")]
+fn edge_key(i: u32, j: u32) -> u64 {
+    ((i.min(j) as u64) << 32) | i.max(j) as u64
+}
+
+#[cfg(test)]
+mod test {
+    use super::MeshTopology;
+    use crate::geometry::SharedShape;
+    use crate::math::Point;
+
+    #[test]
+    #[cfg(feature = "dim2")]
+    fn polyline_topology() {
+        let vertices = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 0.0),
+        ];
+        let polyline = SharedShape::polyline(vertices, None);
+        let topology = MeshTopology::new(&*polyline).unwrap();
+
+        // Only the middle vertex is shared.
+        assert_eq!(topology.neighbor(1, 0), Some(1));
+        assert_eq!(topology.neighbor(1, 1), Some(0));
+        assert_eq!(topology.neighbor(0, 0), None);
+        assert_eq!(topology.neighbor(2, 1), None);
+    }
+
+    #[test]
+    #[cfg(feature = "dim3")]
+    fn trimesh_topology() {
+        use super::edge_key;
+
+        let vertices = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 1.0),
+            Point::new(0.0, 0.0, 1.0),
+        ];
+        let trimesh = SharedShape::trimesh(vertices, vec![[0, 2, 1], [0, 3, 2]]);
+        let topology = MeshTopology::new(&*trimesh).unwrap();
+
+        // Only the diagonal edge is shared.
+        assert_eq!(topology.neighbor(edge_key(0, 2), 0), Some(1));
+        assert_eq!(topology.neighbor(edge_key(2, 0), 1), Some(0));
+        assert_eq!(topology.neighbor(edge_key(0, 1), 0), None);
+        assert_eq!(topology.neighbor(edge_key(2, 3), 1), None);
+    }
+
+    #[test]
+    #[cfg(feature = "dim2")]
+    fn one_sided_chain() {
+        use crate::dynamics::{CoefficientCombineRule, RigidBodyBuilder};
+        use crate::geometry::ColliderBuilder;
+        use crate::math::{Real, Vector};
+        use crate::pipeline::PhysicsWorld;
+
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+
+        // A flat terrain made of several segments, ordered from left to right.
+        let vertices = (-5..=5).map(|i| Point::new(i as Real, 0.0)).collect();
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let co = ColliderBuilder::chain(vertices, false).build();
+        world.colliders.insert(co, ground, &mut world.bodies);
+
+        // A box sliding on top of the terrain, and a ball thrown upward from below it.
+        let slider = ColliderBuilder::cuboid(0.25, 0.25)
+            .friction(0.0)
+            .friction_combine_rule(CoefficientCombineRule::Min);
+        let (slider, _) = world.insert_dynamic(Vector::new(-3.0, 0.25), slider);
+        world.bodies[slider].set_linvel(Vector::new(5.0, 0.0), true);
+        let (thrown, _) = world.insert_dynamic(Vector::new(3.0, -1.0), ColliderBuilder::ball(0.25));
+        world.bodies[thrown].set_linvel(Vector::new(0.0, 10.0), true);
+        world.run(30);
+
+        // The box crossed the joins between segments without slowing down.
+        let slider = &world.bodies[slider];
+        assert!((slider.linvel().x - 5.0).abs() < 0.1);
+        assert!((slider.position().translation.y - 0.25).abs() < 0.05);
+        // The ball went through the terrain from below.
+        assert!(world.bodies[thrown].position().translation.y > 1.0);
+    }
+}
@@ -1,18 +1,32 @@
 //! Structures related to geometry: colliders, shapes, etc.
 
+pub use self::broad_phase_backend::BroadPhaseBackend;
+pub use self::broad_phase_bvh::BvhBroadPhase;
 pub use self::broad_phase_multi_sap::BroadPhase;
-pub use self::collider::{Collider, ColliderBuilder};
+pub use self::collider::{
+    ActiveIntersectionTypes, Collider, ColliderBuilder, ColliderChanges, ColliderMaterial,
+    MotionQuality, OneWayPlatform, SurfaceMaterial,
+};
 pub use self::collider_set::{ColliderHandle, ColliderSet};
 pub use self::contact_pair::{ContactData, ContactManifoldData};
 pub use self::contact_pair::{ContactPair, SolverContact, SolverFlags};
 pub use self::interaction_graph::{
     ColliderGraphIndex, InteractionGraph, RigidBodyGraphIndex, TemporaryInteractionIndex,
 };
-pub use self::interaction_groups::InteractionGroups;
-pub use self::narrow_phase::NarrowPhase;
+pub use self::interaction_groups::{CollisionLayers, InteractionGroups};
+pub use self::multi_sphere::{MultiSphere, MULTI_SPHERE_SHAPE_ID};
+pub use self::narrow_phase::{ContactPairCache, NarrowPhase};
+#[cfg(feature = "serde-serialize")]
+pub use self::shape_cooking::{ShapeCooking, ShapeCookingError, COOKED_SHAPE_VERSION};
+pub use self::shape_sharing::ShapeSharingStats;
+pub use self::surface_location::SurfaceLocation;
+pub use self::trimesh_sanitization::{sanitize_trimesh, TriMeshFlags};
+pub use self::voxels::{Voxels, VOXELS_SHAPE_ID};
 
 pub use parry::query::TrackedContact;
 
+use crate::math::{Point, Real, Vector};
+
 /// A contact between two colliders.
 pub type Contact = parry::query::TrackedContact<ContactData>;
 /// A contact manifold between two colliders.
@@ -45,7 +59,10 @@ pub type RayIntersection = parry::query::RayIntersection;
 pub type PointProjection = parry::query::PointProjection;
 /// The the time of impact between two shapes.
 pub type TOI = parry::query::TOI;
+/// The closest points between two shapes.
+pub type ClosestPoints = parry::query::ClosestPoints;
 pub use parry::shape::SharedShape;
+pub use parry::transformation::vhacd::VHACDParameters;
 
 #[derive(Copy, Clone, Hash, Debug)]
 /// Events occurring when two collision objects start or stop being in contact (or penetration).
@@ -60,6 +77,42 @@ pub enum ContactEvent {
     Stopped(ColliderHandle, ColliderHandle),
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// Event occurring when the contact forces between two colliders exceed the contact force event
+/// threshold of one of them.
+pub struct ContactForceEvent {
+    /// The first collider involved in the contact.
+    pub collider1: ColliderHandle,
+    /// The second collider involved in the contact.
+    pub collider2: ColliderHandle,
+    /// The sum of the magnitudes of the forces applied at each contact point between the two
+    /// colliders.
+    pub total_force_magnitude: Real,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+/// A contact point reported by a `DetailedContactEvent`, expressed in world-space.
+pub struct ContactEventPoint {
+    /// The contact point, located halfway between the two colliders.
+    pub point: Point<Real>,
+    /// The contact normal, pointing from the first collider toward the second collider.
+    pub normal: Vector<Real>,
+    /// The distance between the two colliders at this point (negative if they are penetrating).
+    pub dist: Real,
+    /// The impulse applied along the normal at this contact point during the last resolution.
+    pub impulse: Real,
+}
+
+#[derive(Clone, Debug)]
+/// A contact event, together with the contact points of the contact pair when it occurred.
+pub struct DetailedContactEvent {
+    /// The contact event.
+    pub event: ContactEvent,
+    /// All the contact points of the contact pair when the event occurred, including the
+    /// speculative contacts with a positive distance.
+    pub points: Vec<ContactEventPoint>,
+}
+
 #[derive(Copy, Clone, Debug)]
 /// Events occurring when two collision objects start or stop being in close proximity, contact, or disjoint.
 pub struct IntersectionEvent {
@@ -84,9 +137,11 @@ impl IntersectionEvent {
     }
 }
 
-pub(crate) use self::broad_phase_multi_sap::{BroadPhasePairEvent, ColliderPair, SAPProxyIndex};
+pub(crate) use self::broad_phase_multi_sap::SAPProxyIndex;
+pub use self::broad_phase_multi_sap::{BroadPhasePairEvent, ColliderPair};
 pub(crate) use self::collider_set::RemovedCollider;
 pub(crate) use self::narrow_phase::ContactManifoldIndex;
+pub(crate) use self::shape_sharing::ShapeDeduplicator;
 pub(crate) use parry::partitioning::SimdQuadTree;
 pub use parry::shape::*;
 
@@ -101,10 +156,26 @@ pub(crate) fn default_query_dispatcher() -> std::sync::Arc<dyn parry::query::Que
     std::sync::Arc::new(parry::query::DefaultQueryDispatcher)
 }
 
+mod broad_phase_backend;
+mod broad_phase_bvh;
 mod broad_phase_multi_sap;
 mod collider;
 mod collider_set;
 mod contact_pair;
+#[cfg(feature = "dim3")]
+mod cylinder_cone_manifolds;
+#[cfg(feature = "dim3")]
+mod heightfield_capsule_manifolds;
 mod interaction_graph;
 mod interaction_groups;
+mod internal_edges;
+mod multi_sphere;
+mod multi_sphere_manifolds;
 mod narrow_phase;
+#[cfg(feature = "serde-serialize")]
+mod shape_cooking;
+mod shape_sharing;
+mod surface_location;
+mod trimesh_sanitization;
+mod voxels;
+mod voxels_manifolds;
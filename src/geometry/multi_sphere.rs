@@ -0,0 +1,202 @@
+use crate::dynamics::MassProperties;
+use crate::geometry::{PointProjection, Ray, RayIntersection, AABB};
+use crate::math::{Isometry, Point, Real, Vector};
+use na::RealField;
+use parry::bounding_volume::BoundingSphere;
+use parry::query::details::{
+    local_point_projection_on_support_map, local_ray_intersection_with_support_map_with_params,
+};
+use parry::query::gjk::VoronoiSimplex;
+use parry::query::{PointQuery, RayCast};
+use parry::shape::{FeatureId, Shape, ShapeType, SupportMap, TypedShape};
+
+/// The identifier reported by `TypedShape::Custom` for the `MultiSphere` shape.
+pub const MULTI_SPHERE_SHAPE_ID: u32 = 1;
+
+// The number of points sampled on the boundary of each sphere for computing
+// the approximate mass properties of a multi-sphere.
+#[cfg(feature = "dim2")]
+const NUM_MASS_SAMPLES: usize = 32;
+#[cfg(feature = "dim3")]
+const NUM_MASS_SAMPLES: usize = 64;
+
+/// A convex shape defined as the convex hull of a set of balls (disks in 2D) with possibly
+/// different radii.
+///
+/// Unlike a capsule, both ends of a multi-sphere can have different radii, and more than two
+/// balls can be used. This makes it a cheap rounded proxy for characters (e.g. a small ball
+/// at the feet and a larger one for the torso) or vehicle wheels, that slides over steps instead
+/// of catching their edges.
+///
+/// The contacts with a collider using this shape are computed by a dedicated contact generator
+/// of the narrow-phase. Against convex shapes with a support map (balls, cuboids, capsules,
+/// convex polyhedra, etc.), the contact manifold contains the deepest point of the convex hull,
+/// as well as the contacts of the balls touching the other shape along the same normal, so that
+/// a multi-sphere lying on the ground rests stably on several points. Against other shapes
+/// (half-spaces, triangle meshes, heightfields, compound shapes, etc.) only the contacts of the
+/// individual balls are computed: the regions of the convex hull between them are ignored.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct MultiSphere {
+    spheres: Vec<(Point<Real>, Real)>,
+}
+
+impl MultiSphere {
+    /// Creates the convex hull of the given balls, each given by its center and radius.
+    ///
+    /// Panics if `spheres` is empty or if one radius is negative.
+    pub fn new(spheres: Vec<(Point<Real>, Real)>) -> Self {
+        assert!(
+            !spheres.is_empty(),
+            "A multi-sphere must contain at least one sphere."
+        );
+        assert!(
+            spheres.iter().all(|(_, radius)| *radius >= 0.0),
+            "The radii of a multi-sphere must not be negative."
+        );
+
+        Self { spheres }
+    }
+
+    /// The center and radius of each ball of this multi-sphere.
+    pub fn spheres(&self) -> &[(Point<Real>, Real)] {
+        &self.spheres
+    }
+
+    // Sample points on the boundary of each ball.
+    fn boundary_samples(&self) -> Vec<Point<Real>> {
+        let mut samples = Vec::with_capacity(self.spheres.len() * NUM_MASS_SAMPLES);
+
+        for (center, radius) in &self.spheres {
+            for i in 0..NUM_MASS_SAMPLES {
+                #[cfg(feature = "dim2")]
+                let dir = {
+                    let angle = Real::two_pi() * i as Real / NUM_MASS_SAMPLES as Real;
+                    Vector::new(angle.cos(), angle.sin())
+                };
+                #[cfg(feature = "dim3")]
+                let dir = {
+                    // Fibonacci lattice on the unit sphere.
+                    let y = 1.0 - 2.0 * (i as Real + 0.5) / NUM_MASS_SAMPLES as Real;
+                    let r = (1.0 - y * y).max(0.0).sqrt();
+                    let angle = Real::pi() * (3.0 - (5.0 as Real).sqrt()) * i as Real;
+                    Vector::new(angle.cos() * r, y, angle.sin() * r)
+                };
+
+                samples.push(center + dir * *radius);
+            }
+        }
+
+        samples
+    }
+}
+
+#[cfg(feature = "dim2")]
+fn convex_hull_mass_properties(density: Real, points: &[Point<Real>]) -> MassProperties {
+    let vertices = parry::transformation::convex_hull(points);
+    MassProperties::from_convex_polygon(density, &vertices)
+}
+
+#[cfg(feature = "dim3")]
+fn convex_hull_mass_properties(density: Real, points: &[Point<Real>]) -> MassProperties {
+    let (vertices, indices) = parry::transformation::convex_hull(points);
+    MassProperties::from_convex_polyhedron(density, &vertices, &indices)
+}
+
+impl SupportMap for MultiSphere {
+    fn local_support_point(&self, dir: &Vector<Real>) -> Point<Real> {
+        let dir = dir.try_normalize(Real::EPSILON).unwrap_or_else(Vector::y);
+        let (center, radius) = self
+            .spheres
+            .iter()
+            .max_by(|a, b| {
+                (a.0.coords.dot(&dir) + a.1)
+                    .partial_cmp(&(b.0.coords.dot(&dir) + b.1))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap();
+
+        center + dir * *radius
+    }
+}
+
+impl RayCast for MultiSphere {
+    fn cast_local_ray_and_get_normal(
+        &self,
+        ray: &Ray,
+        max_toi: Real,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        local_ray_intersection_with_support_map_with_params(
+            self,
+            &mut VoronoiSimplex::new(),
+            ray,
+            max_toi,
+            solid,
+        )
+    }
+}
+
+impl PointQuery for MultiSphere {
+    fn project_local_point(&self, pt: &Point<Real>, solid: bool) -> PointProjection {
+        local_point_projection_on_support_map(self, &mut VoronoiSimplex::new(), pt, solid)
+    }
+
+    fn project_local_point_and_get_feature(
+        &self,
+        pt: &Point<Real>,
+    ) -> (PointProjection, FeatureId) {
+        (self.project_local_point(pt, false), FeatureId::Unknown)
+    }
+}
+
+impl Shape for MultiSphere {
+    fn compute_local_aabb(&self) -> AABB {
+        let mut aabb = AABB::new_invalid();
+
+        for (center, radius) in &self.spheres {
+            aabb.mins = aabb.mins.inf(&(center - Vector::repeat(*radius)));
+            aabb.maxs = aabb.maxs.sup(&(center + Vector::repeat(*radius)));
+        }
+
+        aabb
+    }
+
+    fn compute_local_bounding_sphere(&self) -> BoundingSphere {
+        self.compute_local_aabb().bounding_sphere()
+    }
+
+    // NOTE: except for multi-spheres made of a single ball, the mass properties are
+    //       approximated by those of the convex hull of points sampled on each ball.
+    fn mass_properties(&self, density: Real) -> MassProperties {
+        if let [(center, radius)] = self.spheres[..] {
+            return MassProperties::from_ball(density, radius)
+                .transform_by(&Isometry::new(center.coords, na::zero()));
+        }
+
+        convex_hull_mass_properties(density, &self.boundary_samples())
+    }
+
+    fn shape_type(&self) -> ShapeType {
+        ShapeType::Custom
+    }
+
+    fn as_typed_shape(&self) -> TypedShape {
+        TypedShape::Custom(MULTI_SPHERE_SHAPE_ID)
+    }
+
+    fn ccd_thickness(&self) -> Real {
+        self.spheres
+            .iter()
+            .map(|(_, radius)| *radius)
+            .fold(0.0, Real::max)
+    }
+
+    fn ccd_angular_thickness(&self) -> Real {
+        Real::frac_pi_4()
+    }
+
+    fn as_support_map(&self) -> Option<&dyn SupportMap> {
+        Some(self as &dyn SupportMap)
+    }
+}
@@ -0,0 +1,287 @@
+use crate::geometry::{
+    Ball, ContactData, ContactManifold, ContactManifoldData, MultiSphere, Shape, TrackedContact,
+};
+use crate::math::{Isometry, Real};
+use parry::query::PersistentQueryDispatcher;
+use parry::shape::PackedFeatureId;
+
+// Two normals with a dot product greater than this are considered equal.
+const NORMAL_DOT_EPSILON: Real = 1.0e-3;
+
+/// Computes the contact manifolds between a multi-sphere and another shape.
+///
+/// If the other shape has a support map, a single contact manifold is generated. Its normal
+/// is given by the deepest point of the convex hull of the balls, and it also contains the
+/// contacts of the other balls touching the other shape along this normal. Otherwise, each
+/// ball is given to the query dispatcher, and the resulting contact manifolds are identified
+/// by the index of their ball stored as their sub-shape identifier.
+///
+/// If `flipped` is `false`, then `pos12` is the position of the other shape relative to the
+/// multi-sphere, and the first shape of the manifolds is the multi-sphere. Otherwise, `pos12`
+/// is the position of the multi-sphere relative to the other shape, and the first shape of
+/// the manifolds is the other shape.
+pub(crate) fn contact_manifolds_multi_sphere_shape(
+    dispatcher: &dyn PersistentQueryDispatcher<ContactManifoldData, ContactData>,
+    pos12: &Isometry<Real>,
+    multi_sphere: &MultiSphere,
+    shape: &dyn Shape,
+    prediction: Real,
+    manifolds: &mut Vec<ContactManifold>,
+    flipped: bool,
+) {
+    if shape.as_support_map().is_some() {
+        contact_manifold_multi_sphere_convex(
+            dispatcher,
+            pos12,
+            multi_sphere,
+            shape,
+            prediction,
+            manifolds,
+            flipped,
+        )
+    } else {
+        contact_manifolds_multi_sphere_balls(
+            dispatcher,
+            pos12,
+            multi_sphere,
+            shape,
+            prediction,
+            manifolds,
+            flipped,
+        )
+    }
+}
+
+fn contact_manifold_multi_sphere_convex(
+    dispatcher: &dyn PersistentQueryDispatcher<ContactManifoldData, ContactData>,
+    pos12: &Isometry<Real>,
+    multi_sphere: &MultiSphere,
+    shape: &dyn Shape,
+    prediction: Real,
+    manifolds: &mut Vec<ContactManifold>,
+    flipped: bool,
+) {
+    // Position of the other shape relative to the multi-sphere.
+    let pos_ms_shape = if flipped { pos12.inverse() } else { *pos12 };
+    let hull_contact = match dispatcher.contact(&pos_ms_shape, multi_sphere, shape, prediction) {
+        Ok(Some(contact)) => contact,
+        _ => {
+            manifolds.clear();
+            return;
+        }
+    };
+
+    let normal = *hull_contact.normal1;
+    let spheres = multi_sphere.spheres();
+
+    // The ball the deepest point of the convex hull belongs to.
+    let deepest_ball = (0..spheres.len())
+        .max_by(|i, j| {
+            let support_i = spheres[*i].0.coords.dot(&normal) + spheres[*i].1;
+            let support_j = spheres[*j].0.coords.dot(&normal) + spheres[*j].1;
+            support_i
+                .partial_cmp(&support_j)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap_or(0);
+
+    // The contact points, expressed in the local-space of the multi-sphere and of the other
+    // shape respectively, with the index of their ball.
+    let mut points = vec![(
+        hull_contact.point1,
+        hull_contact.point2,
+        deepest_ball,
+        hull_contact.dist,
+    )];
+
+    for (i, (center, radius)) in spheres.iter().enumerate() {
+        if i == deepest_ball {
+            continue;
+        }
+
+        let ball_pos = Isometry::new(center.coords, na::zero());
+        let contact = dispatcher.contact(
+            &ball_pos.inv_mul(&pos_ms_shape),
+            &Ball::new(*radius),
+            shape,
+            prediction,
+        );
+
+        if let Ok(Some(contact)) = contact {
+            if contact.normal1.dot(&normal) >= 1.0 - NORMAL_DOT_EPSILON {
+                points.push((ball_pos * contact.point1, contact.point2, i, contact.dist));
+            }
+        }
+    }
+
+    manifolds.truncate(1);
+    let mut manifold = manifolds
+        .pop()
+        .unwrap_or_else(|| ContactManifold::with_data(0, 0, ContactManifoldData::default()));
+    let old_points = std::mem::replace(&mut manifold.points, Vec::new());
+
+    if flipped {
+        manifold.local_n1 = *hull_contact.normal2;
+        manifold.local_n2 = normal;
+    } else {
+        manifold.local_n1 = normal;
+        manifold.local_n2 = *hull_contact.normal2;
+    }
+
+    for (p_ms, p_shape, ball_id, dist) in points {
+        let (local_p1, local_p2, fid1, fid2) = if flipped {
+            (
+                p_shape,
+                p_ms,
+                PackedFeatureId::UNKNOWN,
+                PackedFeatureId::face(ball_id as u32),
+            )
+        } else {
+            (
+                p_ms,
+                p_shape,
+                PackedFeatureId::face(ball_id as u32),
+                PackedFeatureId::UNKNOWN,
+            )
+        };
+
+        let mut contact = TrackedContact::new(local_p1, local_p2, fid1, fid2, dist);
+
+        // Transfer the contact impulses computed at the previous step for the same ball.
+        if let Some(old) = old_points
+            .iter()
+            .find(|old| old.fid1 == fid1 && old.fid2 == fid2)
+        {
+            contact.data = old.data;
+        }
+
+        manifold.points.push(contact);
+    }
+
+    manifolds.push(manifold);
+}
+
+fn contact_manifolds_multi_sphere_balls(
+    dispatcher: &dyn PersistentQueryDispatcher<ContactManifoldData, ContactData>,
+    pos12: &Isometry<Real>,
+    multi_sphere: &MultiSphere,
+    shape: &dyn Shape,
+    prediction: Real,
+    manifolds: &mut Vec<ContactManifold>,
+    flipped: bool,
+) {
+    let mut old_manifolds = std::mem::replace(manifolds, Vec::new());
+    let mut ball_manifolds = Vec::new();
+
+    for (i, (center, radius)) in multi_sphere.spheres().iter().enumerate() {
+        let ball_id = i as u32;
+        let ball = Ball::new(*radius);
+        let ball_pos = Isometry::new(center.coords, na::zero());
+
+        // Reuse the manifolds computed at the previous step for this ball (if any)
+        // so their contact impulses can be transferred to the new contacts.
+        let mut j = 0;
+        while j < old_manifolds.len() {
+            let manifold_ball_id = if flipped {
+                old_manifolds[j].subshape2
+            } else {
+                old_manifolds[j].subshape1
+            };
+
+            if manifold_ball_id == ball_id {
+                ball_manifolds.push(old_manifolds.swap_remove(j));
+            } else {
+                j += 1;
+            }
+        }
+
+        // NOTE: no workspace is kept for the other shape, so the sub-shape manifolds of
+        //       composite shapes are recomputed from scratch at each step.
+        let result = if flipped {
+            dispatcher.contact_manifolds(
+                &(pos12 * ball_pos),
+                shape,
+                &ball,
+                prediction,
+                &mut ball_manifolds,
+                &mut None,
+            )
+        } else {
+            dispatcher.contact_manifolds(
+                &ball_pos.inv_mul(pos12),
+                &ball,
+                shape,
+                prediction,
+                &mut ball_manifolds,
+                &mut None,
+            )
+        };
+
+        if result.is_err() {
+            // This shape is not supported (e.g. voxels).
+            ball_manifolds.clear();
+            continue;
+        }
+
+        for mut manifold in ball_manifolds.drain(..) {
+            if flipped {
+                manifold.subshape2 = ball_id;
+                manifold.subshape_pos2 = Some(ball_pos);
+            } else {
+                manifold.subshape1 = ball_id;
+                manifold.subshape_pos1 = Some(ball_pos);
+            }
+
+            manifolds.push(manifold);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Point, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn multi_sphere_ground() {
+        #[cfg(feature = "dim2")]
+        let cuboid = ColliderBuilder::cuboid(10.0, 0.5).translation(0.0, -0.5);
+        #[cfg(feature = "dim3")]
+        let cuboid = ColliderBuilder::cuboid(10.0, 0.5, 10.0).translation(0.0, -0.5, 0.0);
+        let grounds = vec![
+            // The other shape has a support map.
+            cuboid,
+            // The other shape doesn't have a support map.
+            ColliderBuilder::halfspace(Vector::y_axis()),
+        ];
+
+        for ground_co in grounds {
+            let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+            let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+            let ground_co = world
+                .colliders
+                .insert(ground_co.build(), ground, &mut world.bodies);
+            let multi_sphere = ColliderBuilder::multi_sphere(vec![
+                (Point::from(Vector::x() * -0.5), 0.25),
+                (Point::from(Vector::x() * 0.5), 0.25),
+            ]);
+            let (_, co) = world.insert_dynamic(Vector::y(), multi_sphere);
+            world.run(120);
+
+            // The multi-sphere lies on the ground, touching it with both balls.
+            let lowest = world.colliders[co].compute_aabb().mins.y;
+            assert!(lowest.abs() < 0.05);
+            let num_points: usize = world
+                .narrow_phase
+                .contact_pair(ground_co, co)
+                .unwrap()
+                .manifolds
+                .iter()
+                .map(|m| m.points.len())
+                .sum();
+            assert_eq!(num_points, 2);
+        }
+    }
+}
@@ -5,17 +5,35 @@ use crate::data::pubsub::Subscription;
 use crate::data::Coarena;
 use crate::dynamics::{BodyPair, CoefficientCombineRule, RigidBodySet};
 use crate::geometry::collider::ColliderChanges;
+#[cfg(feature = "dim3")]
+use crate::geometry::cylinder_cone_manifolds::{
+    contact_manifolds_frustum_cuboid, contact_manifolds_frustum_halfspace, Frustum,
+};
+#[cfg(feature = "dim3")]
+use crate::geometry::heightfield_capsule_manifolds::contact_manifolds_heightfield_capsule;
+use crate::geometry::internal_edges::correct_internal_edge_normal;
+#[cfg(feature = "dim2")]
+use crate::geometry::internal_edges::remove_one_sided_back_contacts;
+use crate::geometry::multi_sphere_manifolds::contact_manifolds_multi_sphere_shape;
+use crate::geometry::voxels_manifolds::contact_manifolds_voxels_shape;
 use crate::geometry::{
     BroadPhasePairEvent, ColliderGraphIndex, ColliderHandle, ColliderPair, ColliderSet,
-    ContactData, ContactEvent, ContactManifold, ContactManifoldData, ContactPair, InteractionGraph,
-    IntersectionEvent, RemovedCollider, SolverContact, SolverFlags,
+    ContactData, ContactEvent, ContactManifold, ContactManifoldData, ContactPair,
+    DetailedContactEvent, InteractionGraph, IntersectionEvent, MultiSphere, RemovedCollider, Shape,
+    SolverContact, SolverFlags, TemporaryInteractionIndex, Voxels,
 };
-use crate::math::{Real, Vector};
+#[cfg(feature = "dim2")]
+use crate::math::Point;
+use crate::math::{Isometry, Real, Vector};
+use crate::pipeline::update_oneway_contacts;
 use crate::pipeline::{
     ContactModificationContext, EventHandler, PairFilterContext, PhysicsHooks, PhysicsHooksFlags,
+    SolverContactInspectionContext,
 };
+use parry::bounding_volume::BoundingVolume;
 use parry::query::{DefaultQueryDispatcher, PersistentQueryDispatcher};
 use parry::utils::IsometryOpt;
+use std::any::TypeId;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -42,6 +60,38 @@ enum PairRemovalMode {
     Auto,
 }
 
+/// A snapshot of the contact pairs of a narrow-phase.
+///
+/// This is used to transfer the contact manifolds, and their warmstart impulses, from one
+/// narrow-phase to another one, e.g., when the broad-phase and narrow-phase are rebuilt
+/// from scratch. See `NarrowPhase::export_contact_pair_cache`.
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+#[derive(Clone, Default)]
+pub struct ContactPairCache {
+    pairs: HashMap<(ColliderHandle, ColliderHandle), ContactPair>,
+}
+
+impl ContactPairCache {
+    /// The number of contact pairs stored in this cache.
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Is this cache empty?
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    // The key of a pair of colliders, independent from the order of the colliders.
+    fn key(pair: &ColliderPair) -> (ColliderHandle, ColliderHandle) {
+        if pair.collider1.into_raw_parts() <= pair.collider2.into_raw_parts() {
+            (pair.collider1, pair.collider2)
+        } else {
+            (pair.collider2, pair.collider1)
+        }
+    }
+}
+
 /// The narrow-phase responsible for computing precise contact information between colliders.
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone)]
@@ -51,10 +101,14 @@ pub struct NarrowPhase {
         serde(skip, default = "crate::geometry::default_persistent_query_dispatcher")
     )]
     query_dispatcher: Arc<dyn PersistentQueryDispatcher<ContactManifoldData, ContactData>>,
+    // Is `query_dispatcher` a user-defined dispatcher, instead of the `DefaultQueryDispatcher`?
+    #[cfg_attr(feature = "serde-serialize", serde(skip))]
+    uses_custom_dispatcher: bool,
     contact_graph: InteractionGraph<ColliderHandle, ContactPair>,
     intersection_graph: InteractionGraph<ColliderHandle, bool>,
     graph_indices: Coarena<ColliderGraphIndices>,
     removed_colliders: Option<Subscription<RemovedCollider>>,
+    contact_pair_cache: ContactPairCache,
 }
 
 pub(crate) type ContactManifoldIndex = usize;
@@ -66,16 +120,25 @@ impl NarrowPhase {
     }
 
     /// Creates a new empty narrow-phase with a custom query dispatcher.
+    ///
+    /// Use this constructor in order to use a custom `PersistentQueryDispatcher` that is aware
+    /// of your own user-defined shapes, and of the contact manifold generators they need.
+    ///
+    /// A custom dispatcher computes the contacts of every pair of shapes. Rapier's specialized
+    /// contact manifold generators, e.g. for voxels and multi-spheres, are only used for the
+    /// pairs of shapes it doesn't support.
     pub fn with_query_dispatcher<D>(d: D) -> Self
     where
         D: 'static + PersistentQueryDispatcher<ContactManifoldData, ContactData>,
     {
         Self {
             query_dispatcher: Arc::new(d),
+            uses_custom_dispatcher: TypeId::of::<D>() != TypeId::of::<DefaultQueryDispatcher>(),
             contact_graph: InteractionGraph::new(),
             intersection_graph: InteractionGraph::new(),
             graph_indices: Coarena::new(),
             removed_colliders: None,
+            contact_pair_cache: ContactPairCache::default(),
         }
     }
 
@@ -87,6 +150,61 @@ impl NarrowPhase {
         &*self.query_dispatcher
     }
 
+    /// Replaces the query dispatcher used by this narrow-phase.
+    ///
+    /// This is useful to register a custom `PersistentQueryDispatcher` aware of user-defined
+    /// shapes on an existing narrow-phase, e.g., after it has been deserialized (the query
+    /// dispatcher is not serialized, so a deserialized narrow-phase always uses the default
+    /// one). The contact manifolds are kept for warmstarting, but the contact manifold
+    /// workspaces of all the contact pairs are reset since they depend on the query dispatcher.
+    pub fn set_query_dispatcher<D>(&mut self, d: D)
+    where
+        D: 'static + PersistentQueryDispatcher<ContactManifoldData, ContactData>,
+    {
+        self.query_dispatcher = Arc::new(d);
+        self.uses_custom_dispatcher = TypeId::of::<D>() != TypeId::of::<DefaultQueryDispatcher>();
+
+        for edge in self.contact_graph.graph.edges.iter_mut() {
+            edge.weight.workspace = None;
+        }
+    }
+
+    /// Exports all the contact pairs of this narrow-phase that have at least one contact manifold.
+    ///
+    /// The result can be given to the `import_contact_pair_cache` method of another narrow-phase
+    /// so it reuses the contact impulses computed by this narrow-phase for warmstarting the
+    /// constraints solver. This avoids a visible settling of the simulation when the broad-phase
+    /// and narrow-phase have to be rebuilt from scratch.
+    pub fn export_contact_pair_cache(&self) -> ContactPairCache {
+        let pairs = self
+            .contact_pairs()
+            .filter(|pair| !pair.manifolds.is_empty())
+            .map(|pair| {
+                let mut pair = pair.clone();
+                // The workspace depends on the query dispatcher, which may be different
+                // for the narrow-phase this cache will be imported to.
+                pair.workspace = None;
+                pair.has_any_active_contact = false;
+                (ContactPairCache::key(&pair.pair), pair)
+            })
+            .collect();
+
+        ContactPairCache { pairs }
+    }
+
+    /// Imports contact pairs previously exported from another narrow-phase.
+    ///
+    /// The imported contact pairs will be used as the initial state of the corresponding
+    /// contact pairs once they are detected by the broad-phase at the next timestep. Any
+    /// imported contact pair not detected by the broad-phase at the next timestep is discarded.
+    ///
+    /// Note that a newly created broad-phase will only detect the pairs of colliders it knows
+    /// about. Call `ColliderSet::mark_all_as_new` so that all the colliders are registered
+    /// to the new broad-phase and narrow-phase.
+    pub fn import_contact_pair_cache(&mut self, cache: ContactPairCache) {
+        self.contact_pair_cache.pairs.extend(cache.pairs);
+    }
+
     /// The contact graph containing all contact pairs and their contact information.
     pub fn contact_graph(&self) -> &InteractionGraph<ColliderHandle, ContactPair> {
         &self.contact_graph
@@ -98,6 +216,11 @@ impl NarrowPhase {
     }
 
     /// All the contacts involving the given collider.
+    ///
+    /// This includes the contact pairs with no active contact, i.e., the pairs of colliders
+    /// with intersecting broad-phase AABBs but not touching. Check the
+    /// [`ContactPair::has_any_active_contact`] field to filter them out. Returns `None` if
+    /// the collider has not been taken into account by this narrow-phase yet.
     pub fn contacts_with(
         &self,
         collider: ColliderHandle,
@@ -107,6 +230,9 @@ impl NarrowPhase {
     }
 
     /// All the intersections involving the given collider.
+    ///
+    /// The boolean of each element indicates if the two colliders are actually intersecting.
+    /// Returns `None` if the collider has not been taken into account by this narrow-phase yet.
     pub fn intersections_with(
         &self,
         collider: ColliderHandle,
@@ -123,7 +249,7 @@ impl NarrowPhase {
     ///
     /// If this returns `None`, there is no contact between the two colliders.
     /// If this returns `Some`, then there may be a contact between the two colliders. Check the
-    /// result [`ContactPair::has_any_active_collider`] method to see if there is an actual contact.
+    /// result [`ContactPair::has_any_active_contact`] field to see if there is an actual contact.
     pub fn contact_pair(
         &self,
         collider1: ColliderHandle,
@@ -136,6 +262,20 @@ impl NarrowPhase {
             .map(|c| c.2)
     }
 
+    /// Is there any active contact between two specific colliders?
+    ///
+    /// This is a cheaper alternative to iterating through the contact manifolds of the contact
+    /// pair. It returns `false` if the two colliders are not in the same contact pair.
+    pub fn has_any_active_contact(
+        &self,
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+    ) -> bool {
+        self.contact_pair(collider1, collider2)
+            .map(|pair| pair.has_any_active_contact)
+            .unwrap_or(false)
+    }
+
     /// The intersection pair involving two specific colliders.
     ///
     /// If this returns `None` or `Some(false)`, then there is no intersection between the two colliders.
@@ -157,6 +297,14 @@ impl NarrowPhase {
         self.contact_graph.interactions()
     }
 
+    pub(crate) fn contact_pairs_mut(&mut self) -> impl Iterator<Item = &mut ContactPair> {
+        self.contact_graph
+            .graph
+            .edges
+            .iter_mut()
+            .map(|edge| &mut edge.weight)
+    }
+
     /// All the intersection pairs maintained by this narrow-phase.
     pub fn intersection_pairs(
         &self,
@@ -221,9 +369,78 @@ impl NarrowPhase {
         colliders.removed_colliders.ack(&cursor);
         self.removed_colliders = Some(cursor);
 
+        self.handle_reparented_colliders(colliders, bodies, events);
         self.handle_modified_colliders(colliders, bodies, events);
     }
 
+    fn handle_reparented_colliders(
+        &mut self,
+        colliders: &mut ColliderSet,
+        bodies: &mut RigidBodySet,
+        events: &dyn EventHandler,
+    ) {
+        let mut pairs_to_remove = vec![];
+        let mut pairs_to_add = vec![];
+
+        colliders.foreach_modified_colliders(|handle, collider| {
+            let prev_parent = match collider.prev_parent {
+                Some(prev_parent) => prev_parent,
+                None => return,
+            };
+
+            // Remove the pairs with the colliders attached to the new parent.
+            if let Some(gid) = self.graph_indices.get(handle.0) {
+                for inter in self
+                    .contact_graph
+                    .interactions_with(gid.contact_graph_index)
+                {
+                    let other = crate::utils::select_other((inter.0, inter.1), handle);
+                    if colliders.get(other).map(|co| co.parent) == Some(collider.parent) {
+                        pairs_to_remove.push((
+                            ColliderPair::new(inter.0, inter.1),
+                            PairRemovalMode::FromContactGraph,
+                        ));
+                    }
+                }
+
+                for inter in self
+                    .intersection_graph
+                    .interactions_with(gid.intersection_graph_index)
+                {
+                    let other = crate::utils::select_other((inter.0, inter.1), handle);
+                    if colliders.get(other).map(|co| co.parent) == Some(collider.parent) {
+                        pairs_to_remove.push((
+                            ColliderPair::new(inter.0, inter.1),
+                            PairRemovalMode::FromIntersectionGraph,
+                        ));
+                    }
+                }
+            }
+
+            // Add the pairs with the colliders attached to the previous parent. The broad-phase
+            // won't report them since their AABBs may already be overlapping.
+            if let Some(prev_parent) = bodies.get(prev_parent) {
+                let aabb = collider.compute_aabb();
+
+                for other in prev_parent.colliders() {
+                    if let Some(other_collider) = colliders.get(*other) {
+                        if other_collider.compute_aabb().intersects(&aabb) {
+                            pairs_to_add.push(ColliderPair::new(handle, *other));
+                        }
+                    }
+                }
+            }
+        });
+
+        for pair in &pairs_to_remove {
+            self.remove_pair(colliders, bodies, &pair.0, events, pair.1);
+        }
+
+        for pair in &pairs_to_add {
+            self.add_pair(colliders, pair);
+        }
+    }
+
     pub(crate) fn remove_collider(
         &mut self,
         intersection_graph_id: ColliderGraphIndex,
@@ -282,7 +499,15 @@ impl NarrowPhase {
                 // so that the narrow-phase properly takes into account the change in, e.g.,
                 // collision groups. Waking up the modified collider's parent isn't enough because
                 // it could be a static or kinematic body which don't propagate the wake-up state.
-                bodies.wake_up(collider.parent, true);
+                //
+                // If only some heightfield cells were modified, we only wake-up the bodies close
+                // to these cells so that a local terrain deformation doesn't wake-up everything.
+                let heightfield_only =
+                    collider.changes - ColliderChanges::MODIFIED == ColliderChanges::HEIGHTFIELD;
+
+                if !heightfield_only {
+                    bodies.wake_up(collider.parent, true);
+                }
 
                 for inter in self
                     .contact_graph
@@ -290,7 +515,11 @@ impl NarrowPhase {
                 {
                     let other_handle = if handle == inter.0 { inter.1 } else { inter.0 };
                     if let Some(other_collider) = colliders.get(other_handle) {
-                        bodies.wake_up(other_collider.parent, true);
+                        if !heightfield_only
+                            || collider.heightfield_changes_intersect(other_collider, 0.0)
+                        {
+                            bodies.wake_up(other_collider.parent, true);
+                        }
                     }
                 }
 
@@ -384,11 +613,27 @@ impl NarrowPhase {
                         if ctct.has_any_active_contact {
                             bodies.wake_up(co1.parent, true);
                             bodies.wake_up(co2.parent, true);
-
-                            events.handle_contact_event(ContactEvent::Stopped(
-                                pair.collider1,
-                                pair.collider2,
-                            ))
+                            bodies.reset_resting_steps(co1.parent);
+                            bodies.reset_resting_steps(co2.parent);
+
+                            // NOTE: the contact pair may list its colliders in the reverse order.
+                            let (co1, co2) = if ctct.pair.collider1 == pair.collider1 {
+                                (co1, co2)
+                            } else {
+                                (co2, co1)
+                            };
+                            let event =
+                                ContactEvent::Stopped(ctct.pair.collider1, ctct.pair.collider2);
+                            events.handle_contact_event(event);
+
+                            if co1.is_detailed_contact_events_enabled()
+                                || co2.is_detailed_contact_events_enabled()
+                            {
+                                events.handle_detailed_contact_event(DetailedContactEvent {
+                                    event,
+                                    points: ctct.contact_event_points(co1.position()),
+                                });
+                            }
                         }
                     }
                 }
@@ -458,12 +703,19 @@ impl NarrowPhase {
                     .find_edge(gid1.contact_graph_index, gid2.contact_graph_index)
                     .is_none()
                 {
-                    let interaction = ContactPair::new(*pair);
-                    let _ = self.contact_graph.add_edge(
-                        gid1.contact_graph_index,
-                        gid2.contact_graph_index,
-                        interaction,
-                    );
+                    let interaction = self
+                        .contact_pair_cache
+                        .pairs
+                        .remove(&ContactPairCache::key(pair))
+                        .unwrap_or_else(|| ContactPair::new(*pair));
+
+                    // NOTE: the cached pair may list its colliders in the reverse order.
+                    let (node1, node2) = if interaction.pair == *pair {
+                        (gid1.contact_graph_index, gid2.contact_graph_index)
+                    } else {
+                        (gid2.contact_graph_index, gid1.contact_graph_index)
+                    };
+                    let _ = self.contact_graph.add_edge(node1, node2, interaction);
                 }
             }
         }
@@ -486,6 +738,9 @@ impl NarrowPhase {
                 }
             }
         }
+
+        // The imported contact pairs that were not detected by the broad-phase are obsolete.
+        self.contact_pair_cache.pairs.clear();
     }
 
     pub(crate) fn compute_intersections(
@@ -503,81 +758,91 @@ impl NarrowPhase {
         let query_dispatcher = &*self.query_dispatcher;
         let active_hooks = hooks.active_hooks();
 
+        // NOTE: the events are collected and emitted afterwards, in the order of the graph
+        //       edges, so their order doesn't depend on the thread scheduling.
         // TODO: don't iterate on all the edges.
-        par_iter_mut!(&mut self.intersection_graph.graph.edges).for_each(|edge| {
-            let handle1 = nodes[edge.source().index()].weight;
-            let handle2 = nodes[edge.target().index()].weight;
-            let co1 = &colliders[handle1];
-            let co2 = &colliders[handle2];
-
-            if !co1.changes.needs_narrow_phase_update() && !co2.changes.needs_narrow_phase_update()
-            {
-                // No update needed for these colliders.
-                return;
-            }
+        let intersection_events: Vec<_> = par_iter_mut!(&mut self.intersection_graph.graph.edges)
+            .filter_map(|edge| {
+                let handle1 = nodes[edge.source().index()].weight;
+                let handle2 = nodes[edge.target().index()].weight;
+                let co1 = &colliders[handle1];
+                let co2 = &colliders[handle2];
+
+                if !co1.changes.needs_narrow_phase_update()
+                    && !co2.changes.needs_narrow_phase_update()
+                {
+                    // No update needed for these colliders.
+                    return None;
+                }
 
-            // TODO: avoid lookup into bodies.
-            let rb1 = &bodies[co1.parent];
-            let rb2 = &bodies[co2.parent];
+                // TODO: avoid lookup into bodies.
+                let rb1 = &bodies[co1.parent];
+                let rb2 = &bodies[co2.parent];
 
-            if (rb1.is_sleeping() && rb2.is_static())
-                || (rb2.is_sleeping() && rb1.is_static())
-                || (rb1.is_sleeping() && rb2.is_sleeping())
-            {
-                // No need to update this intersection because nothing moved.
-                return;
-            }
+                if (rb1.is_sleeping() && rb2.is_static())
+                    || (rb2.is_sleeping() && rb1.is_static())
+                    || (rb1.is_sleeping() && rb2.is_sleeping())
+                {
+                    // No need to update this intersection because nothing moved.
+                    return None;
+                }
 
-            if !co1.collision_groups.test(co2.collision_groups) {
-                // The intersection is not allowed.
-                return;
-            }
+                if !co1.collision_groups.test(co2.collision_groups) {
+                    // The intersection is not allowed.
+                    return None;
+                }
 
-            if !active_hooks.contains(PhysicsHooksFlags::FILTER_INTERSECTION_PAIR)
-                && !rb1.is_dynamic()
-                && !rb2.is_dynamic()
-            {
-                // Default filtering rule: no intersection between two non-dynamic bodies.
-                return;
-            }
+                if !active_hooks.contains(PhysicsHooksFlags::FILTER_INTERSECTION_PAIR)
+                    && !rb1.is_dynamic()
+                    && !rb2.is_dynamic()
+                    && !co1.reports_non_dynamic_intersection(co2, rb2)
+                    && !co2.reports_non_dynamic_intersection(co1, rb1)
+                {
+                    // Default filtering rule: no intersection between two non-dynamic bodies,
+                    // unless one of the sensors opted-in.
+                    return None;
+                }
 
-            if active_hooks.contains(PhysicsHooksFlags::FILTER_INTERSECTION_PAIR) {
-                let context = PairFilterContext {
-                    rigid_body1: rb1,
-                    rigid_body2: rb2,
-                    collider_handle1: handle1,
-                    collider_handle2: handle2,
-                    collider1: co1,
-                    collider2: co2,
-                };
+                if active_hooks.contains(PhysicsHooksFlags::FILTER_INTERSECTION_PAIR) {
+                    let context = PairFilterContext {
+                        rigid_body1: rb1,
+                        rigid_body2: rb2,
+                        collider_handle1: handle1,
+                        collider_handle2: handle2,
+                        collider1: co1,
+                        collider2: co2,
+                    };
 
-                if !hooks.filter_intersection_pair(&context) {
-                    // No intersection allowed.
-                    return;
+                    if !hooks.filter_intersection_pair(&context) {
+                        // No intersection allowed.
+                        return None;
+                    }
                 }
-            }
 
-            let pos12 = co1.position().inv_mul(co2.position());
+                let pos12 = co1.position().inv_mul(co2.position());
 
-            if let Ok(intersection) =
-                query_dispatcher.intersection_test(&pos12, co1.shape(), co2.shape())
-            {
-                if intersection != edge.weight {
-                    edge.weight = intersection;
-                    events.handle_intersection_event(IntersectionEvent::new(
-                        handle1,
-                        handle2,
-                        intersection,
-                    ));
+                if let Ok(intersection) =
+                    query_dispatcher.intersection_test(&pos12, co1.shape(), co2.shape())
+                {
+                    if intersection != edge.weight {
+                        edge.weight = intersection;
+                        return Some(IntersectionEvent::new(handle1, handle2, intersection));
+                    }
                 }
-            }
-        });
+
+                None
+            })
+            .collect();
+
+        for event in intersection_events {
+            events.handle_intersection_event(event);
+        }
     }
 
     pub(crate) fn compute_contacts(
         &mut self,
         prediction_distance: Real,
-        bodies: &RigidBodySet,
+        bodies: &mut RigidBodySet,
         colliders: &ColliderSet,
         hooks: &dyn PhysicsHooks,
         events: &dyn EventHandler,
@@ -587,187 +852,494 @@ impl NarrowPhase {
         }
 
         let query_dispatcher = &*self.query_dispatcher;
+        let uses_custom_dispatcher = self.uses_custom_dispatcher;
         let active_hooks = hooks.active_hooks();
 
+        // NOTE: the events are collected and emitted afterwards, in the order of the graph
+        //       edges, so their order doesn't depend on the thread scheduling.
         // TODO: don't iterate on all the edges.
-        par_iter_mut!(&mut self.contact_graph.graph.edges).for_each(|edge| {
-            let pair = &mut edge.weight;
-            let co1 = &colliders[pair.pair.collider1];
-            let co2 = &colliders[pair.pair.collider2];
+        let contact_events: Vec<_> = par_iter_mut!(&mut self.contact_graph.graph.edges)
+            .filter_map(|edge| {
+                let pair = &mut edge.weight;
+                let co1 = &colliders[pair.pair.collider1];
+                let co2 = &colliders[pair.pair.collider2];
+
+                if !co1.changes.needs_narrow_phase_update()
+                    && !co2.changes.needs_narrow_phase_update()
+                {
+                    // No update needed for these colliders.
+                    return None;
+                }
 
-            if !co1.changes.needs_narrow_phase_update() && !co2.changes.needs_narrow_phase_update()
-            {
-                // No update needed for these colliders.
-                return;
-            }
+                // TODO: avoid lookup into bodies.
+                let rb1 = &bodies[co1.parent];
+                let rb2 = &bodies[co2.parent];
 
-            // TODO: avoid lookup into bodies.
-            let rb1 = &bodies[co1.parent];
-            let rb2 = &bodies[co2.parent];
+                if (rb1.is_sleeping() && rb2.is_static())
+                    || (rb2.is_sleeping() && rb1.is_static())
+                    || (rb1.is_sleeping() && rb2.is_sleeping())
+                {
+                    // No need to update this contact because nothing moved.
+                    return None;
+                }
 
-            if (rb1.is_sleeping() && rb2.is_static())
-                || (rb2.is_sleeping() && rb1.is_static())
-                || (rb1.is_sleeping() && rb2.is_sleeping())
-            {
-                // No need to update this contact because nothing moved.
-                return;
-            }
+                if !co1.collision_groups.test(co2.collision_groups) {
+                    // The collision is not allowed.
+                    return None;
+                }
 
-            if !co1.collision_groups.test(co2.collision_groups) {
-                // The collision is not allowed.
-                return;
-            }
+                if !active_hooks.contains(PhysicsHooksFlags::FILTER_CONTACT_PAIR)
+                    && !rb1.is_dynamic()
+                    && !rb2.is_dynamic()
+                {
+                    // Default filtering rule: no contact between two non-dynamic bodies.
+                    return None;
+                }
 
-            if !active_hooks.contains(PhysicsHooksFlags::FILTER_CONTACT_PAIR)
-                && !rb1.is_dynamic()
-                && !rb2.is_dynamic()
-            {
-                // Default filtering rule: no contact between two non-dynamic bodies.
-                return;
-            }
+                let mut solver_flags =
+                    if active_hooks.contains(PhysicsHooksFlags::FILTER_CONTACT_PAIR) {
+                        let context = PairFilterContext {
+                            rigid_body1: rb1,
+                            rigid_body2: rb2,
+                            collider_handle1: pair.pair.collider1,
+                            collider_handle2: pair.pair.collider2,
+                            collider1: co1,
+                            collider2: co2,
+                        };
 
-            let mut solver_flags = if active_hooks.contains(PhysicsHooksFlags::FILTER_CONTACT_PAIR)
-            {
-                let context = PairFilterContext {
-                    rigid_body1: rb1,
-                    rigid_body2: rb2,
-                    collider_handle1: pair.pair.collider1,
-                    collider_handle2: pair.pair.collider2,
-                    collider1: co1,
-                    collider2: co2,
-                };
+                        if let Some(solver_flags) = hooks.filter_contact_pair(&context) {
+                            solver_flags
+                        } else {
+                            // No contact allowed.
+                            return None;
+                        }
+                    } else {
+                        co1.solver_flags | co2.solver_flags
+                    };
 
-                if let Some(solver_flags) = hooks.filter_contact_pair(&context) {
-                    solver_flags
-                } else {
-                    // No contact allowed.
-                    return;
+                if !co1.solver_groups.test(co2.solver_groups) {
+                    solver_flags.remove(SolverFlags::COMPUTE_IMPULSES);
                 }
-            } else {
-                co1.solver_flags | co2.solver_flags
-            };
 
-            if !co1.solver_groups.test(co2.solver_groups) {
-                solver_flags.remove(SolverFlags::COMPUTE_IMPULSES);
-            }
+                // The contact skins are added to the prediction distance, and subtracted from
+                // the distance of the solver contacts.
+                let skin = co1.contact_skin + co2.contact_skin;
+                let pair_prediction_distance = prediction_distance + skin;
 
-            if co1.changes.contains(ColliderChanges::SHAPE)
-                || co2.changes.contains(ColliderChanges::SHAPE)
-            {
-                // The shape changed so the workspace is no longer valid.
-                pair.workspace = None;
-            }
+                if co1.changes.contains(ColliderChanges::SHAPE)
+                    || co2.changes.contains(ColliderChanges::SHAPE)
+                {
+                    // The shape changed so the workspace is no longer valid.
+                    pair.workspace = None;
+                } else if co1.heightfield_changes_intersect(co2, pair_prediction_distance)
+                    || co2.heightfield_changes_intersect(co1, pair_prediction_distance)
+                {
+                    // The heightfield cells close to the other collider changed, so
+                    // the sub-shape contact manifolds cached by the workspace are no longer valid.
+                    pair.workspace = None;
+                }
 
-            let pos12 = co1.position().inv_mul(co2.position());
-            let _ = query_dispatcher.contact_manifolds(
-                &pos12,
-                co1.shape(),
-                co2.shape(),
-                prediction_distance,
-                &mut pair.manifolds,
-                &mut pair.workspace,
-            );
+                let pos12 = co1.position().inv_mul(co2.position());
+
+                let compute_specialized_contact_manifolds =
+                    |manifolds: &mut Vec<ContactManifold>| {
+                        Self::compute_specialized_contact_manifolds(
+                            query_dispatcher,
+                            &pos12,
+                            co1.shape(),
+                            co2.shape(),
+                            pair_prediction_distance,
+                            manifolds,
+                        )
+                    };
 
-            let mut has_any_active_contact = false;
+                // The specialized implementations take precedence over the default dispatcher,
+                // but a custom dispatcher is only bypassed for the shapes it doesn't support.
+                if uses_custom_dispatcher
+                    || !compute_specialized_contact_manifolds(&mut pair.manifolds)
+                {
+                    let supported = query_dispatcher.contact_manifolds(
+                        &pos12,
+                        co1.shape(),
+                        co2.shape(),
+                        pair_prediction_distance,
+                        &mut pair.manifolds,
+                        &mut pair.workspace,
+                    );
 
-            let friction = CoefficientCombineRule::combine(
-                co1.friction,
-                co2.friction,
-                co1.flags.friction_combine_rule_value(),
-                co2.flags.friction_combine_rule_value(),
-            );
-            let restitution = CoefficientCombineRule::combine(
-                co1.restitution,
-                co2.restitution,
-                co1.flags.restitution_combine_rule_value(),
-                co2.flags.restitution_combine_rule_value(),
-            );
+                    if supported.is_err() && uses_custom_dispatcher {
+                        let _ = compute_specialized_contact_manifolds(&mut pair.manifolds);
+                    }
+                }
+
+                // Fix the normals of the contacts generated by internal edges.
+                if co1.mesh_topology.is_some() || co2.mesh_topology.is_some() {
+                    for manifold in &mut pair.manifolds {
+                        let world_pos1 = manifold.subshape_pos1.prepend_to(co1.position());
+                        let world_pos2 = manifold.subshape_pos2.prepend_to(co2.position());
+                        let pos12 = world_pos1.inv_mul(&world_pos2);
+
+                        if let Some(topology) = &co1.mesh_topology {
+                            correct_internal_edge_normal(
+                                topology,
+                                co1.shape(),
+                                &pos12,
+                                true,
+                                manifold,
+                            );
+                        }
 
-            for manifold in &mut pair.manifolds {
-                let world_pos1 = manifold.subshape_pos1.prepend_to(co1.position());
-                manifold.data.solver_contacts.clear();
-                manifold.data.body_pair = BodyPair::new(co1.parent(), co2.parent());
-                manifold.data.solver_flags = solver_flags;
-                manifold.data.relative_dominance =
-                    rb1.effective_dominance_group() - rb2.effective_dominance_group();
-                manifold.data.normal = world_pos1 * manifold.local_n1;
-
-                // Generate solver contacts.
-                for (contact_id, contact) in manifold.points.iter().enumerate() {
-                    assert!(
-                        contact_id <= u8::MAX as usize,
-                        "A contact manifold cannot contain more than 255 contacts currently."
+                        if let Some(topology) = &co2.mesh_topology {
+                            correct_internal_edge_normal(
+                                topology,
+                                co2.shape(),
+                                &pos12,
+                                false,
+                                manifold,
+                            );
+                        }
+                    }
+                }
+
+                // Remove the contacts with the back side of one-sided polylines.
+                #[cfg(feature = "dim2")]
+                if co1.is_one_sided() || co2.is_one_sided() {
+                    for manifold in &mut pair.manifolds {
+                        if co1.is_one_sided() {
+                            let origin2 = pos12 * Point::origin();
+                            remove_one_sided_back_contacts(co1.shape(), &origin2, true, manifold);
+                        }
+
+                        if co2.is_one_sided() {
+                            let origin1 = pos12.inverse_transform_point(&Point::origin());
+                            remove_one_sided_back_contacts(co2.shape(), &origin1, false, manifold);
+                        }
+                    }
+                }
+
+                let mut has_any_active_contact = false;
+                let has_surface_material =
+                    co1.surface_material.is_some() || co2.surface_material.is_some();
+                let combine_materials = |material1: (Real, Real), material2: (Real, Real)| {
+                    let friction = CoefficientCombineRule::combine(
+                        material1.0,
+                        material2.0,
+                        co1.flags.friction_combine_rule_value(),
+                        co2.flags.friction_combine_rule_value(),
                     );
+                    let restitution = CoefficientCombineRule::combine(
+                        material1.1,
+                        material2.1,
+                        co1.flags.restitution_combine_rule_value(),
+                        co2.flags.restitution_combine_rule_value(),
+                    );
+                    (friction, restitution)
+                };
 
-                    if contact.dist < prediction_distance {
-                        // Generate the solver contact.
-                        let solver_contact = SolverContact {
-                            contact_id: contact_id as u8,
-                            point: world_pos1 * contact.local_p1
-                                + manifold.data.normal * contact.dist / 2.0,
-                            dist: contact.dist,
-                            friction,
-                            restitution,
-                            tangent_velocity: Vector::zeros(),
-                            warmstart_impulse: contact.data.impulse,
-                            warmstart_tangent_impulse: contact.data.tangent_impulse,
-                            prev_rhs: contact.data.rhs,
+                for manifold in &mut pair.manifolds {
+                    // Compound colliders may have a different material for each part.
+                    let (friction, restitution) = combine_materials(
+                        co1.subshape_material(manifold.subshape1),
+                        co2.subshape_material(manifold.subshape2),
+                    );
+                    let world_pos1 = manifold.subshape_pos1.prepend_to(co1.position());
+                    manifold.data.solver_contacts.clear();
+                    manifold.data.body_pair = BodyPair::new(co1.parent(), co2.parent());
+                    manifold.data.solver_flags = solver_flags;
+                    manifold.data.relative_dominance =
+                        rb1.effective_dominance_group() - rb2.effective_dominance_group();
+                    manifold.data.normal = world_pos1 * manifold.local_n1;
+
+                    // Generate solver contacts.
+                    for (contact_id, contact) in manifold.points.iter().enumerate() {
+                        assert!(
+                            contact_id <= u8::MAX as usize,
+                            "A contact manifold cannot contain more than 255 contacts currently."
+                        );
+
+                        if contact.dist < pair_prediction_distance {
+                            // Query the user-defined surface materials, if any.
+                            let (friction, restitution) = if has_surface_material {
+                                combine_materials(
+                                    co1.surface_material_at(
+                                        manifold.subshape1,
+                                        manifold.subshape_pos1.as_ref(),
+                                        &contact.local_p1,
+                                    ),
+                                    co2.surface_material_at(
+                                        manifold.subshape2,
+                                        manifold.subshape_pos2.as_ref(),
+                                        &contact.local_p2,
+                                    ),
+                                )
+                            } else {
+                                (friction, restitution)
+                            };
+
+                            // Generate the solver contact.
+                            let solver_contact = SolverContact {
+                                contact_id: contact_id as u8,
+                                point: world_pos1 * contact.local_p1
+                                    + manifold.data.normal * contact.dist / 2.0,
+                                dist: contact.dist - skin,
+                                friction,
+                                restitution,
+                                tangent_velocity: Vector::zeros(),
+                                warmstart_impulse: contact.data.impulse,
+                                warmstart_tangent_impulse: contact.data.tangent_impulse,
+                                prev_rhs: contact.data.rhs,
+                            };
+
+                            manifold.data.solver_contacts.push(solver_contact);
+                            has_any_active_contact = true;
+                        }
+                    }
+
+                    // Apply the built-in one-way platform behavior.
+                    let one_way = co1
+                        .one_way_platform
+                        .map(|one_way| (one_way, manifold.local_n1))
+                        .or_else(|| {
+                            co2.one_way_platform
+                                .map(|one_way| (one_way, manifold.local_n2))
+                        });
+
+                    if let Some((one_way, local_n)) = one_way {
+                        update_oneway_contacts(
+                            &local_n,
+                            &one_way.allowed_local_normal,
+                            one_way.allowed_angle,
+                            &mut manifold.data.solver_contacts,
+                            &mut manifold.data.one_way_state,
+                        );
+                    }
+
+                    // Apply the user-defined contact modification.
+                    if active_hooks.contains(PhysicsHooksFlags::MODIFY_SOLVER_CONTACTS)
+                        && manifold
+                            .data
+                            .solver_flags
+                            .contains(SolverFlags::MODIFY_SOLVER_CONTACTS)
+                    {
+                        let mut modifiable_solver_contacts =
+                            std::mem::replace(&mut manifold.data.solver_contacts, Vec::new());
+                        let mut modifiable_user_data = manifold.data.user_data;
+                        let mut modifiable_normal = manifold.data.normal;
+
+                        let mut context = ContactModificationContext {
+                            rigid_body1: rb1,
+                            rigid_body2: rb2,
+                            collider_handle1: pair.pair.collider1,
+                            collider_handle2: pair.pair.collider2,
+                            collider1: co1,
+                            collider2: co2,
+                            manifold,
+                            solver_contacts: &mut modifiable_solver_contacts,
+                            normal: &mut modifiable_normal,
+                            user_data: &mut modifiable_user_data,
                         };
 
-                        manifold.data.solver_contacts.push(solver_contact);
-                        has_any_active_contact = true;
+                        hooks.modify_solver_contacts(&mut context);
+
+                        manifold.data.solver_contacts = modifiable_solver_contacts;
+                        manifold.data.normal = modifiable_normal;
+                        manifold.data.user_data = modifiable_user_data;
                     }
                 }
 
-                // Apply the user-defined contact modification.
-                if active_hooks.contains(PhysicsHooksFlags::MODIFY_SOLVER_CONTACTS)
-                    && manifold
-                        .data
-                        .solver_flags
-                        .contains(SolverFlags::MODIFY_SOLVER_CONTACTS)
-                {
-                    let mut modifiable_solver_contacts =
-                        std::mem::replace(&mut manifold.data.solver_contacts, Vec::new());
-                    let mut modifiable_user_data = manifold.data.user_data;
-                    let mut modifiable_normal = manifold.data.normal;
+                if has_any_active_contact != pair.has_any_active_contact {
+                    let event = if has_any_active_contact {
+                        ContactEvent::Started(pair.pair.collider1, pair.pair.collider2)
+                    } else {
+                        ContactEvent::Stopped(pair.pair.collider1, pair.pair.collider2)
+                    };
 
-                    let mut context = ContactModificationContext {
-                        rigid_body1: rb1,
-                        rigid_body2: rb2,
-                        collider_handle1: pair.pair.collider1,
-                        collider_handle2: pair.pair.collider2,
-                        collider1: co1,
-                        collider2: co2,
-                        manifold,
-                        solver_contacts: &mut modifiable_solver_contacts,
-                        normal: &mut modifiable_normal,
-                        user_data: &mut modifiable_user_data,
+                    pair.has_any_active_contact = has_any_active_contact;
+
+                    // NOTE: only collect the contact points if they are actually needed.
+                    let points = if co1.is_detailed_contact_events_enabled()
+                        || co2.is_detailed_contact_events_enabled()
+                    {
+                        Some(pair.contact_event_points(co1.position()))
+                    } else {
+                        None
                     };
+                    return Some((event, points));
+                }
+
+                None
+            })
+            .collect();
+
+        for (event, points) in contact_events {
+            // The bodies that started or stopped touching something aren't resting.
+            let (collider1, collider2) = match event {
+                ContactEvent::Started(c1, c2) | ContactEvent::Stopped(c1, c2) => (c1, c2),
+            };
+            bodies.reset_resting_steps(colliders[collider1].parent);
+            bodies.reset_resting_steps(colliders[collider2].parent);
+
+            events.handle_contact_event(event);
+
+            if let Some(points) = points {
+                events.handle_detailed_contact_event(DetailedContactEvent { event, points });
+            }
+        }
+    }
+
+    /// Computes the contact manifolds of the pairs of shapes that have a specialized
+    /// implementation faster than the generic one from the query dispatcher, or that
+    /// are not supported by the query dispatcher.
+    ///
+    /// Returns `false` if there is no specialized implementation for these shapes.
+    fn compute_specialized_contact_manifolds(
+        query_dispatcher: &dyn PersistentQueryDispatcher<ContactManifoldData, ContactData>,
+        pos12: &Isometry<Real>,
+        shape1: &dyn Shape,
+        shape2: &dyn Shape,
+        prediction_distance: Real,
+        manifolds: &mut Vec<ContactManifold>,
+    ) -> bool {
+        if let Some(voxels) = shape1.as_shape::<Voxels>() {
+            contact_manifolds_voxels_shape(
+                query_dispatcher,
+                pos12,
+                voxels,
+                shape2,
+                prediction_distance,
+                manifolds,
+                false,
+            );
+            return true;
+        }
+
+        if let Some(voxels) = shape2.as_shape::<Voxels>() {
+            contact_manifolds_voxels_shape(
+                query_dispatcher,
+                pos12,
+                voxels,
+                shape1,
+                prediction_distance,
+                manifolds,
+                true,
+            );
+            return true;
+        }
+
+        if let Some(multi_sphere) = shape1.as_shape::<MultiSphere>() {
+            contact_manifolds_multi_sphere_shape(
+                query_dispatcher,
+                pos12,
+                multi_sphere,
+                shape2,
+                prediction_distance,
+                manifolds,
+                false,
+            );
+            return true;
+        }
+
+        if let Some(multi_sphere) = shape2.as_shape::<MultiSphere>() {
+            contact_manifolds_multi_sphere_shape(
+                query_dispatcher,
+                pos12,
+                multi_sphere,
+                shape1,
+                prediction_distance,
+                manifolds,
+                true,
+            );
+            return true;
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            if let (Some(heightfield), Some(capsule)) =
+                (shape1.as_heightfield(), shape2.as_capsule())
+            {
+                contact_manifolds_heightfield_capsule(
+                    pos12,
+                    heightfield,
+                    capsule,
+                    prediction_distance,
+                    manifolds,
+                    false,
+                );
+                return true;
+            }
 
-                    hooks.modify_solver_contacts(&mut context);
+            if let (Some(capsule), Some(heightfield)) =
+                (shape1.as_capsule(), shape2.as_heightfield())
+            {
+                contact_manifolds_heightfield_capsule(
+                    pos12,
+                    heightfield,
+                    capsule,
+                    prediction_distance,
+                    manifolds,
+                    true,
+                );
+                return true;
+            }
 
-                    manifold.data.solver_contacts = modifiable_solver_contacts;
-                    manifold.data.normal = modifiable_normal;
-                    manifold.data.user_data = modifiable_user_data;
+            if let Some(frustum) = Frustum::from_shape(shape1) {
+                if let Some(halfspace) = shape2.as_halfspace() {
+                    contact_manifolds_frustum_halfspace(
+                        pos12,
+                        &frustum,
+                        halfspace,
+                        prediction_distance,
+                        manifolds,
+                        false,
+                    );
+                    return true;
+                }
+
+                if let Some(cuboid) = shape2.as_cuboid() {
+                    contact_manifolds_frustum_cuboid(
+                        query_dispatcher,
+                        pos12,
+                        shape1,
+                        &frustum,
+                        cuboid,
+                        prediction_distance,
+                        manifolds,
+                        false,
+                    );
+                    return true;
                 }
             }
 
-            if has_any_active_contact != pair.has_any_active_contact {
-                if has_any_active_contact {
-                    events.handle_contact_event(ContactEvent::Started(
-                        pair.pair.collider1,
-                        pair.pair.collider2,
-                    ));
-                } else {
-                    events.handle_contact_event(ContactEvent::Stopped(
-                        pair.pair.collider1,
-                        pair.pair.collider2,
-                    ));
+            if let Some(frustum) = Frustum::from_shape(shape2) {
+                if let Some(halfspace) = shape1.as_halfspace() {
+                    contact_manifolds_frustum_halfspace(
+                        pos12,
+                        &frustum,
+                        halfspace,
+                        prediction_distance,
+                        manifolds,
+                        true,
+                    );
+                    return true;
                 }
 
-                pair.has_any_active_contact = has_any_active_contact;
+                if let Some(cuboid) = shape1.as_cuboid() {
+                    contact_manifolds_frustum_cuboid(
+                        query_dispatcher,
+                        pos12,
+                        shape2,
+                        &frustum,
+                        cuboid,
+                        prediction_distance,
+                        manifolds,
+                        true,
+                    );
+                    return true;
+                }
             }
-        });
+        }
+
+        false
     }
 
     /// Retrieve all the interactions with at least one contact point, happening between two active bodies.
@@ -775,15 +1347,26 @@ impl NarrowPhase {
     pub(crate) fn select_active_contacts<'a>(
         &'a mut self,
         bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        hooks: &dyn PhysicsHooks,
         out_manifolds: &mut Vec<&'a mut ContactManifold>,
         out: &mut Vec<Vec<ContactManifoldIndex>>,
+        out_force_event_pairs: &mut Vec<TemporaryInteractionIndex>,
     ) {
         for out_island in &mut out[..bodies.num_islands()] {
             out_island.clear();
         }
+        out_force_event_pairs.clear();
+
+        let inspect_solver_contacts = hooks
+            .active_hooks()
+            .contains(PhysicsHooksFlags::INSPECT_SOLVER_CONTACTS);
 
         // TODO: don't iterate through all the interactions.
-        for inter in self.contact_graph.graph.edges.iter_mut() {
+        for (id, inter) in self.contact_graph.graph.edges.iter_mut().enumerate() {
+            let pair = inter.weight.pair;
+            let num_selected_manifolds = out_manifolds.len();
+
             for manifold in &mut inter.weight.manifolds {
                 let rb1 = &bodies[manifold.data.body_pair.body1];
                 let rb2 = &bodies[manifold.data.body_pair.body2];
@@ -802,10 +1385,452 @@ impl NarrowPhase {
                         rb1.active_island_id
                     };
 
+                    if inspect_solver_contacts {
+                        let context = SolverContactInspectionContext {
+                            collider_handle1: pair.collider1,
+                            collider_handle2: pair.collider2,
+                            manifold: &*manifold,
+                        };
+                        hooks.inspect_solver_contacts(&context);
+                    }
+
                     out[island_index].push(out_manifolds.len());
                     out_manifolds.push(manifold);
                 }
             }
+
+            // Only the pairs with at least one solved manifold can emit a contact force event.
+            if out_manifolds.len() != num_selected_manifolds
+                && colliders[pair.collider1]
+                    .contact_force_event_threshold
+                    .min(colliders[pair.collider2].contact_force_event_threshold)
+                    != Real::MAX
+            {
+                out_force_event_pairs.push(TemporaryInteractionIndex::new(id as u32));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NarrowPhase;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::{
+        BroadPhasePairEvent, ColliderBuilder, ContactData, ContactManifold, ContactManifoldData,
+        Shape,
+    };
+    use crate::math::{Isometry, Real, Vector};
+    use crate::pipeline::PhysicsWorld;
+    use parry::query::{
+        ClosestPoints, Contact, ContactManifoldsWorkspace, DefaultQueryDispatcher,
+        NonlinearRigidMotion, PersistentQueryDispatcher, QueryDispatcher, Unsupported, TOI,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    // A query dispatcher counting the contact manifold computations it is asked for.
+    struct CountingDispatcher(Arc<AtomicUsize>);
+
+    impl QueryDispatcher for CountingDispatcher {
+        fn intersection_test(
+            &self,
+            pos12: &Isometry<Real>,
+            g1: &dyn Shape,
+            g2: &dyn Shape,
+        ) -> Result<bool, Unsupported> {
+            DefaultQueryDispatcher.intersection_test(pos12, g1, g2)
+        }
+
+        fn distance(
+            &self,
+            pos12: &Isometry<Real>,
+            g1: &dyn Shape,
+            g2: &dyn Shape,
+        ) -> Result<Real, Unsupported> {
+            DefaultQueryDispatcher.distance(pos12, g1, g2)
+        }
+
+        fn contact(
+            &self,
+            pos12: &Isometry<Real>,
+            g1: &dyn Shape,
+            g2: &dyn Shape,
+            prediction: Real,
+        ) -> Result<Option<Contact>, Unsupported> {
+            DefaultQueryDispatcher.contact(pos12, g1, g2, prediction)
+        }
+
+        fn closest_points(
+            &self,
+            pos12: &Isometry<Real>,
+            g1: &dyn Shape,
+            g2: &dyn Shape,
+            max_dist: Real,
+        ) -> Result<ClosestPoints, Unsupported> {
+            DefaultQueryDispatcher.closest_points(pos12, g1, g2, max_dist)
+        }
+
+        fn time_of_impact(
+            &self,
+            pos12: &Isometry<Real>,
+            local_vel12: &Vector<Real>,
+            g1: &dyn Shape,
+            g2: &dyn Shape,
+            max_toi: Real,
+        ) -> Result<Option<TOI>, Unsupported> {
+            DefaultQueryDispatcher.time_of_impact(pos12, local_vel12, g1, g2, max_toi)
+        }
+
+        fn nonlinear_time_of_impact(
+            &self,
+            motion1: &NonlinearRigidMotion,
+            g1: &dyn Shape,
+            motion2: &NonlinearRigidMotion,
+            g2: &dyn Shape,
+            start_time: Real,
+            end_time: Real,
+            stop_at_penetration: bool,
+        ) -> Result<Option<TOI>, Unsupported> {
+            DefaultQueryDispatcher.nonlinear_time_of_impact(
+                motion1,
+                g1,
+                motion2,
+                g2,
+                start_time,
+                end_time,
+                stop_at_penetration,
+            )
+        }
+    }
+
+    impl PersistentQueryDispatcher<ContactManifoldData, ContactData> for CountingDispatcher {
+        fn contact_manifolds(
+            &self,
+            pos12: &Isometry<Real>,
+            g1: &dyn Shape,
+            g2: &dyn Shape,
+            prediction: Real,
+            manifolds: &mut Vec<ContactManifold>,
+            workspace: &mut Option<ContactManifoldsWorkspace>,
+        ) -> Result<(), Unsupported> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            DefaultQueryDispatcher
+                .contact_manifolds(pos12, g1, g2, prediction, manifolds, workspace)
+        }
+
+        fn contact_manifold_convex_convex(
+            &self,
+            pos12: &Isometry<Real>,
+            g1: &dyn Shape,
+            g2: &dyn Shape,
+            prediction: Real,
+            manifold: &mut ContactManifold,
+        ) -> Result<(), Unsupported> {
+            DefaultQueryDispatcher
+                .contact_manifold_convex_convex(pos12, g1, g2, prediction, manifold)
+        }
+    }
+
+    #[test]
+    fn imported_contact_pairs_ignore_the_collider_order() {
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let collider1 = world.colliders.insert(
+            ColliderBuilder::halfspace(Vector::y_axis()).build(),
+            ground,
+            &mut world.bodies,
+        );
+        let ball = world.bodies.insert(
+            RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(Vector::y() * 0.5, na::zero()))
+                .build(),
+        );
+        let collider2 =
+            world
+                .colliders
+                .insert(ColliderBuilder::ball(0.5).build(), ball, &mut world.bodies);
+
+        for _ in 0..10 {
+            world.step(1.0 / 60.0);
+        }
+
+        let pair = world
+            .narrow_phase
+            .contact_pair(collider1, collider2)
+            .unwrap()
+            .pair;
+        let cache = world.narrow_phase.export_contact_pair_cache();
+        assert_eq!(cache.len(), 1);
+
+        // The new broad-phase reports the pair with its colliders swapped.
+        let mut narrow_phase = NarrowPhase::new();
+        narrow_phase.import_contact_pair_cache(cache);
+        narrow_phase.register_pairs(
+            &mut world.colliders,
+            &mut world.bodies,
+            &[BroadPhasePairEvent::AddPair(pair.swap())],
+            &(),
+        );
+
+        let imported = narrow_phase.contact_pair(collider1, collider2).unwrap();
+        assert_eq!(imported.pair, pair);
+        assert!(!imported.manifolds.is_empty());
+    }
+
+    #[test]
+    fn one_way_platform() {
+        use crate::math::Real;
+
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        #[cfg(feature = "dim2")]
+        let platform = ColliderBuilder::cuboid(5.0, 0.1);
+        #[cfg(feature = "dim3")]
+        let platform = ColliderBuilder::cuboid(5.0, 0.1, 5.0);
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        world.colliders.insert(
+            platform.one_way_platform(Vector::y(), 0.1).build(),
+            ground,
+            &mut world.bodies,
+        );
+
+        // One ball falls on the platform, the other one jumps through it from below.
+        let mut add_ball = |x: Real, y: Real, vy: Real| {
+            let mut rb = RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(Vector::x() * x + Vector::y() * y, na::zero()))
+                .build();
+            rb.set_linvel(Vector::y() * vy, true);
+            let handle = world.bodies.insert(rb);
+            world.colliders.insert(
+                ColliderBuilder::ball(0.5).build(),
+                handle,
+                &mut world.bodies,
+            );
+            handle
+        };
+        let falling = add_ball(-2.0, 2.0, 0.0);
+        let jumping = add_ball(2.0, -2.0, 10.0);
+
+        let mut max_jump_height = -2.0;
+        for _ in 0..300 {
+            world.step(1.0 / 60.0);
+            max_jump_height = world.bodies[jumping]
+                .position()
+                .translation
+                .y
+                .max(max_jump_height);
+        }
+
+        assert!(max_jump_height > 2.0);
+        // Both balls end up resting on top of the platform.
+        for handle in &[falling, jumping] {
+            assert!((world.bodies[*handle].position().translation.y - 0.6).abs() < 0.05);
+        }
+    }
+
+    #[test]
+    fn set_query_dispatcher() {
+        use crate::geometry::SharedShape;
+
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        // Compound shapes need a contact manifold workspace.
+        let ground_shape = SharedShape::compound(vec![
+            (Isometry::identity(), SharedShape::ball(1.0)),
+            (
+                Isometry::new(Vector::x() * 2.0, na::zero()),
+                SharedShape::ball(1.0),
+            ),
+        ]);
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let co1 = world.colliders.insert(
+            ColliderBuilder::new(ground_shape).build(),
+            ground,
+            &mut world.bodies,
+        );
+        let ball = world.bodies.insert(
+            RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(Vector::y() * 1.5, na::zero()))
+                .build(),
+        );
+        let co2 =
+            world
+                .colliders
+                .insert(ColliderBuilder::ball(0.5).build(), ball, &mut world.bodies);
+
+        let workspaces = |narrow_phase: &NarrowPhase| {
+            narrow_phase
+                .contact_graph
+                .graph
+                .edges
+                .iter()
+                .filter(|edge| edge.weight.workspace.is_some())
+                .count()
+        };
+
+        world.step(1.0 / 60.0);
+        assert!(world.narrow_phase.has_any_active_contact(co1, co2));
+        assert_eq!(workspaces(&world.narrow_phase), 1);
+
+        // The workspaces depend on the dispatcher, so they are reset.
+        let count = Arc::new(AtomicUsize::new(0));
+        world
+            .narrow_phase
+            .set_query_dispatcher(CountingDispatcher(count.clone()));
+        assert_eq!(workspaces(&world.narrow_phase), 0);
+
+        // The new dispatcher is used from now on.
+        world.step(1.0 / 60.0);
+        assert!(world.narrow_phase.has_any_active_contact(co1, co2));
+        assert_eq!(workspaces(&world.narrow_phase), 1);
+        assert!(count.load(Ordering::SeqCst) > 0);
+    }
+
+    #[test]
+    fn custom_query_dispatcher_is_not_bypassed_by_specialized_shapes() {
+        use crate::math::Point;
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        world.narrow_phase = NarrowPhase::with_query_dispatcher(CountingDispatcher(count.clone()));
+        let ground = world.insert_ground();
+
+        // Multi-spheres have a specialized contact manifold generator.
+        let spheres = vec![(Point::origin(), 0.5), (Point::from(Vector::x()), 0.5)];
+        let (_, multi_sphere) =
+            world.insert_dynamic(Vector::y() * 0.6, ColliderBuilder::multi_sphere(spheres));
+        world.run(10);
+
+        assert!(world
+            .narrow_phase
+            .has_any_active_contact(ground, multi_sphere));
+        assert!(count.load(Ordering::SeqCst) >= 10);
+    }
+
+    #[test]
+    fn detailed_contact_events() {
+        use crate::geometry::ContactEvent;
+        use crate::pipeline::ChannelEventCollector;
+
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        world.insert_ground();
+
+        // Only the first ball has detailed contact events enabled.
+        for (x, detailed) in &[(0.0, true), (5.0, false)] {
+            let ball = ColliderBuilder::ball(0.5).detailed_contact_events_enabled(*detailed);
+            world.insert_dynamic(Vector::x() * *x + Vector::y() * 0.6, ball);
         }
+
+        let (intersection_send, _intersection_recv) = crossbeam::channel::unbounded();
+        let (contact_send, contact_recv) = crossbeam::channel::unbounded();
+        let (detailed_send, detailed_recv) = crossbeam::channel::unbounded();
+        world.events = Box::new(
+            ChannelEventCollector::new(intersection_send, contact_send)
+                .with_detailed_contact_event_sender(detailed_send),
+        );
+        world.run(30);
+
+        assert_eq!(contact_recv.try_iter().count(), 2);
+        let event = detailed_recv.try_recv().unwrap();
+        assert!(matches!(event.event, ContactEvent::Started(..)));
+        assert!(!event.points.is_empty());
+        for point in &event.points {
+            assert!(point.normal.y.abs() > 0.99);
+            assert!(point.point.y.abs() < 0.2);
+        }
+        assert!(detailed_recv.try_recv().is_err());
+    }
+
+    #[test]
+    fn interaction_queries() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let ground = world.insert_ground();
+        let static_rb = world.colliders[ground].parent();
+        let sensor = world.colliders.insert(
+            ColliderBuilder::ball(0.5)
+                .sensor(true)
+                .position(Isometry::new(Vector::y() * 1.0, na::zero()))
+                .build(),
+            static_rb,
+            &mut world.bodies,
+        );
+        let (_, resting) = world.insert_dynamic(Vector::y() * 0.4, ColliderBuilder::ball(0.5));
+        let (_, far) = world.insert_dynamic(Vector::y() * 100.0, ColliderBuilder::ball(0.5));
+        world.run(1);
+
+        let nf = &world.narrow_phase;
+        let ground_contacts: Vec<_> = nf
+            .contacts_with(ground)
+            .unwrap()
+            .filter(|(_, _, pair)| pair.has_any_active_contact)
+            .map(|(h1, h2, _)| if h1 == ground { h2 } else { h1 })
+            .collect();
+        assert_eq!(ground_contacts, vec![resting]);
+        assert!(nf.contact_pair(ground, resting).is_some());
+        assert!(nf.contact_pair(ground, far).is_none());
+        assert_eq!(nf.contacts_with(far).unwrap().count(), 0);
+
+        let sensor_intersections: Vec<_> = nf
+            .intersections_with(sensor)
+            .unwrap()
+            .filter(|(_, _, intersecting)| *intersecting)
+            .map(|(h1, h2, _)| if h1 == sensor { h2 } else { h1 })
+            .collect();
+        assert_eq!(sensor_intersections, vec![resting]);
+        assert_eq!(nf.intersection_pair(sensor, resting), Some(true));
+        assert_eq!(nf.intersection_pair(sensor, far), None);
+    }
+
+    #[test]
+    fn non_dynamic_sensor_intersections() {
+        use crate::geometry::ActiveIntersectionTypes;
+
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let ground = world.insert_ground();
+
+        let mut sensor = |x, rb: RigidBodyBuilder, types| {
+            let rb = world.bodies.insert(
+                rb.position(Isometry::new(Vector::x() * x, na::zero()))
+                    .build(),
+            );
+            let co = ColliderBuilder::ball(0.5)
+                .sensor(true)
+                .active_intersection_types(types)
+                .build();
+            world.colliders.insert(co, rb, &mut world.bodies)
+        };
+
+        // Two sensors touching the ground, only one of them opted-in.
+        let static_sensor = sensor(
+            0.0,
+            RigidBodyBuilder::new_static(),
+            ActiveIntersectionTypes::STATIC,
+        );
+        let default_sensor = sensor(
+            10.0,
+            RigidBodyBuilder::new_static(),
+            ActiveIntersectionTypes::empty(),
+        );
+        // Two intersecting sensors, neither attached to a dynamic body.
+        let kinematic_sensor = sensor(
+            20.0,
+            RigidBodyBuilder::new_kinematic(),
+            ActiveIntersectionTypes::SENSORS,
+        );
+        let other_sensor = sensor(
+            20.5,
+            RigidBodyBuilder::new_static(),
+            ActiveIntersectionTypes::empty(),
+        );
+        world.run(1);
+
+        let nf = &world.narrow_phase;
+        assert_eq!(nf.intersection_pair(static_sensor, ground), Some(true));
+        assert_eq!(nf.intersection_pair(default_sensor, ground), Some(false));
+        assert_eq!(
+            nf.intersection_pair(kinematic_sensor, other_sensor),
+            Some(true)
+        );
+        // The kinematic sensor only opted-in for other sensors.
+        assert_eq!(nf.intersection_pair(kinematic_sensor, ground), Some(false));
     }
 }
@@ -0,0 +1,134 @@
+use crate::geometry::SharedShape;
+use crate::math::{Real, DIM};
+use std::fmt;
+
+/// The magic bytes at the beginning of every cooked shape.
+const COOKED_SHAPE_MAGIC: &[u8; 4] = b"RPCK";
+/// The version of the cooked shape format.
+///
+/// This must be incremented whenever the layout of the cooked data changes, including when
+/// the serialized representation of the shapes changes on the parry side.
+pub const COOKED_SHAPE_VERSION: u32 = 1;
+/// The length of the header preceding the cooked shape data.
+const HEADER_LEN: usize = 10;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Error returned when a shape could not be cooked or uncooked.
+pub enum ShapeCookingError {
+    /// The data does not start with a valid cooked shape header.
+    InvalidHeader,
+    /// The data was cooked with an unsupported version of the cooked shape format.
+    UnsupportedVersion(u32),
+    /// The data was cooked for a different dimension or floating-point precision.
+    IncompatibleBuild,
+    /// The shape could not be serialized or deserialized.
+    Serialization(String),
+}
+
+impl fmt::Display for ShapeCookingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "the data is not a cooked shape"),
+            Self::UnsupportedVersion(v) => {
+                write!(f, "unsupported cooked shape format version {}", v)
+            }
+            Self::IncompatibleBuild => write!(
+                f,
+                "the shape was cooked for another dimension or floating-point precision"
+            ),
+            Self::Serialization(e) => write!(f, "shape serialization failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ShapeCookingError {}
+
+/// Conversion of shapes from and to a binary "cooked" representation.
+///
+/// Creating some shapes is expensive: triangle meshes and heightfields need to build their
+/// acceleration structure, and compound shapes may result from a costly convex decomposition.
+/// The cooked representation contains the shape together with all these precomputed data,
+/// so it can be saved once (e.g. when building the assets of a game level) and loaded
+/// without performing these computations again.
+///
+/// The cooked data start with a versioned header. Data cooked with another version of the
+/// format, or with another dimension or floating-point precision, are rejected instead of
+/// being misinterpreted.
+pub trait ShapeCooking: Sized {
+    /// Converts this shape to its cooked binary representation.
+    fn to_cooked(&self) -> Result<Vec<u8>, ShapeCookingError>;
+
+    /// Loads a shape from the binary representation output by `Self::to_cooked`.
+    fn from_cooked(bytes: &[u8]) -> Result<Self, ShapeCookingError>;
+}
+
+impl ShapeCooking for SharedShape {
+    fn to_cooked(&self) -> Result<Vec<u8>, ShapeCookingError> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN);
+        bytes.extend_from_slice(COOKED_SHAPE_MAGIC);
+        bytes.extend_from_slice(&COOKED_SHAPE_VERSION.to_le_bytes());
+        bytes.push(DIM as u8);
+        bytes.push(std::mem::size_of::<Real>() as u8);
+
+        bincode::serialize_into(&mut bytes, self)
+            .map_err(|e| ShapeCookingError::Serialization(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    fn from_cooked(bytes: &[u8]) -> Result<Self, ShapeCookingError> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != COOKED_SHAPE_MAGIC {
+            return Err(ShapeCookingError::InvalidHeader);
+        }
+
+        let mut version = [0; 4];
+        version.copy_from_slice(&bytes[4..8]);
+        let version = u32::from_le_bytes(version);
+
+        if version != COOKED_SHAPE_VERSION {
+            return Err(ShapeCookingError::UnsupportedVersion(version));
+        }
+
+        if bytes[8] as usize != DIM || bytes[9] as usize != std::mem::size_of::<Real>() {
+            return Err(ShapeCookingError::IncompatibleBuild);
+        }
+
+        bincode::deserialize(&bytes[HEADER_LEN..])
+            .map_err(|e| ShapeCookingError::Serialization(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ShapeCooking, ShapeCookingError};
+    use crate::geometry::SharedShape;
+    use crate::math::{Point, Vector};
+
+    #[test]
+    fn cooked_trimesh_roundtrip() {
+        let vertices = vec![
+            Point::origin(),
+            Point::from(Vector::x()),
+            Point::from(Vector::y()),
+        ];
+        let shape = SharedShape::trimesh(vertices.clone(), vec![[0, 1, 2]]);
+        let cooked = shape.to_cooked().unwrap();
+        let uncooked = SharedShape::from_cooked(&cooked).unwrap();
+        let trimesh = uncooked.as_trimesh().unwrap();
+        assert_eq!(trimesh.vertices(), &vertices[..]);
+        assert_eq!(trimesh.indices(), &[[0, 1, 2]]);
+    }
+
+    #[test]
+    fn cooked_shape_version_mismatch() {
+        let mut cooked = SharedShape::ball(1.0).to_cooked().unwrap();
+        cooked[4] += 1;
+        assert_eq!(
+            SharedShape::from_cooked(&cooked).err(),
+            Some(ShapeCookingError::UnsupportedVersion(2))
+        );
+        assert_eq!(
+            SharedShape::from_cooked(b"garbage").err(),
+            Some(ShapeCookingError::InvalidHeader)
+        );
+    }
+}
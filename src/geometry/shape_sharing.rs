@@ -0,0 +1,278 @@
+use crate::geometry::{MultiSphere, Shape, SharedShape, Voxels};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Statistics about the sharing of shapes between the colliders of a `ColliderSet`.
+///
+/// See `ColliderSet::shape_sharing_stats`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShapeSharingStats {
+    /// The number of colliders in the collider set.
+    pub num_colliders: usize,
+    /// The number of distinct shape allocations referenced by the colliders.
+    pub num_shape_instances: usize,
+    /// The number of geometrically distinct shapes referenced by the colliders.
+    pub num_unique_shapes: usize,
+}
+
+impl ShapeSharingStats {
+    /// The number of shape allocations that are identical to another one, and could
+    /// be freed by `ColliderSet::deduplicate_shapes`.
+    pub fn num_duplicated_shapes(&self) -> usize {
+        self.num_shape_instances - self.num_unique_shapes
+    }
+}
+
+/// Groups identical shapes so they can share the same allocation.
+pub(crate) struct ShapeDeduplicator {
+    // Maps the address of each shape seen so far to the shape it should be replaced with.
+    instances: HashMap<usize, SharedShape>,
+    // Distinct shapes, indexed by their local AABB so we only need to compare
+    // shapes that are likely to be identical.
+    buckets: HashMap<Vec<u64>, Vec<SharedShape>>,
+}
+
+impl ShapeDeduplicator {
+    pub fn new() -> Self {
+        Self {
+            instances: HashMap::new(),
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// The number of distinct shape allocations given to `self.representative`.
+    pub fn num_instances(&self) -> usize {
+        self.instances.len()
+    }
+
+    /// The number of geometrically distinct shapes given to `self.representative`.
+    pub fn num_unique_shapes(&self) -> usize {
+        self.buckets.values().map(|bucket| bucket.len()).sum()
+    }
+
+    /// Returns the first shape given to this method that is identical to `shape`.
+    pub fn representative(&mut self, shape: &SharedShape) -> SharedShape {
+        let address = Arc::as_ptr(&shape.0) as *const u8 as usize;
+
+        if let Some(representative) = self.instances.get(&address) {
+            return representative.clone();
+        }
+
+        let aabb = shape.compute_local_aabb();
+        let key = aabb
+            .mins
+            .iter()
+            .chain(aabb.maxs.iter())
+            .map(|x| (*x as f64).to_bits())
+            .collect();
+        let bucket = self.buckets.entry(key).or_insert_with(Vec::new);
+        let representative = match bucket
+            .iter()
+            .find(|candidate| shapes_are_identical(&*candidate.0, &*shape.0))
+        {
+            Some(candidate) => candidate.clone(),
+            None => {
+                bucket.push(shape.clone());
+                shape.clone()
+            }
+        };
+
+        let _ = self.instances.insert(address, representative.clone());
+        representative
+    }
+}
+
+/// Checks if two shapes are geometrically identical.
+///
+/// This supports all the shapes of Rapier, including compound and round shapes. User-defined
+/// shapes are never considered identical to another one.
+pub(crate) fn shapes_are_identical(shape1: &dyn Shape, shape2: &dyn Shape) -> bool {
+    if let (Some(s1), Some(s2)) = (shape1.as_compound(), shape2.as_compound()) {
+        return s1.shapes().len() == s2.shapes().len()
+            && s1
+                .shapes()
+                .iter()
+                .zip(s2.shapes().iter())
+                .all(|((pos1, part1), (pos2, part2))| {
+                    pos1 == pos2 && shapes_are_identical(&**part1, &**part2)
+                });
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_ball(), shape2.as_ball()) {
+        return s1.radius == s2.radius;
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_cuboid(), shape2.as_cuboid()) {
+        return s1.half_extents == s2.half_extents;
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_capsule(), shape2.as_capsule()) {
+        return s1.segment.a == s2.segment.a
+            && s1.segment.b == s2.segment.b
+            && s1.radius == s2.radius;
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_segment(), shape2.as_segment()) {
+        return s1.a == s2.a && s1.b == s2.b;
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_triangle(), shape2.as_triangle()) {
+        return s1.a == s2.a && s1.b == s2.b && s1.c == s2.c;
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_halfspace(), shape2.as_halfspace()) {
+        return s1.normal == s2.normal;
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_trimesh(), shape2.as_trimesh()) {
+        return s1.vertices() == s2.vertices() && s1.indices() == s2.indices();
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_polyline(), shape2.as_polyline()) {
+        return s1.vertices() == s2.vertices() && s1.indices() == s2.indices();
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_heightfield(), shape2.as_heightfield()) {
+        return s1.heights() == s2.heights() && s1.scale() == s2.scale();
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_round_cuboid(), shape2.as_round_cuboid()) {
+        return s1.border_radius == s2.border_radius
+            && shapes_are_identical(&s1.base_shape, &s2.base_shape);
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_round_triangle(), shape2.as_round_triangle()) {
+        return s1.border_radius == s2.border_radius
+            && shapes_are_identical(&s1.base_shape, &s2.base_shape);
+    }
+
+    if let (Some(s1), Some(s2)) = (
+        shape1.as_shape::<MultiSphere>(),
+        shape2.as_shape::<MultiSphere>(),
+    ) {
+        return s1.spheres() == s2.spheres();
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_shape::<Voxels>(), shape2.as_shape::<Voxels>()) {
+        return s1.dimensions() == s2.dimensions()
+            && s1.voxel_size() == s2.voxel_size()
+            && s1
+                .filled_voxels()
+                .map(|(id, _)| id)
+                .eq(s2.filled_voxels().map(|(id, _)| id));
+    }
+
+    dim_specific_shapes_are_identical(shape1, shape2)
+}
+
+#[cfg(feature = "dim2")]
+fn dim_specific_shapes_are_identical(shape1: &dyn Shape, shape2: &dyn Shape) -> bool {
+    if let (Some(s1), Some(s2)) = (shape1.as_convex_polygon(), shape2.as_convex_polygon()) {
+        return s1.points() == s2.points();
+    }
+
+    if let (Some(s1), Some(s2)) = (
+        shape1.as_round_convex_polygon(),
+        shape2.as_round_convex_polygon(),
+    ) {
+        return s1.border_radius == s2.border_radius
+            && shapes_are_identical(&s1.base_shape, &s2.base_shape);
+    }
+
+    false
+}
+
+#[cfg(feature = "dim3")]
+fn dim_specific_shapes_are_identical(shape1: &dyn Shape, shape2: &dyn Shape) -> bool {
+    if let (Some(s1), Some(s2)) = (shape1.as_convex_polyhedron(), shape2.as_convex_polyhedron()) {
+        return s1.points() == s2.points();
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_cylinder(), shape2.as_cylinder()) {
+        return s1.half_height == s2.half_height && s1.radius == s2.radius;
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_cone(), shape2.as_cone()) {
+        return s1.half_height == s2.half_height && s1.radius == s2.radius;
+    }
+
+    if let (Some(s1), Some(s2)) = (
+        shape1.as_round_convex_polyhedron(),
+        shape2.as_round_convex_polyhedron(),
+    ) {
+        return s1.border_radius == s2.border_radius
+            && shapes_are_identical(&s1.base_shape, &s2.base_shape);
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_round_cylinder(), shape2.as_round_cylinder()) {
+        return s1.border_radius == s2.border_radius
+            && shapes_are_identical(&s1.base_shape, &s2.base_shape);
+    }
+
+    if let (Some(s1), Some(s2)) = (shape1.as_round_cone(), shape2.as_round_cone()) {
+        return s1.border_radius == s2.border_radius
+            && shapes_are_identical(&s1.base_shape, &s2.base_shape);
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::ShapeDeduplicator;
+    use crate::geometry::{MultiSphere, SharedShape};
+    use crate::math::{Isometry, Point, Vector};
+    use std::sync::Arc;
+
+    #[test]
+    fn identical_shapes_are_deduplicated() {
+        let make_shapes = || {
+            #[allow(unused_mut)]
+            let mut shapes = vec![
+                SharedShape::ball(0.5),
+                SharedShape::segment(Point::origin(), Point::from(Vector::x())),
+                SharedShape::halfspace(Vector::y_axis()),
+                SharedShape::new(MultiSphere::new(vec![(Point::origin(), 0.5)])),
+                SharedShape::compound(vec![
+                    (Isometry::identity(), SharedShape::ball(0.5)),
+                    (
+                        Isometry::new(Vector::x(), na::zero()),
+                        SharedShape::ball(0.25),
+                    ),
+                ]),
+            ];
+            #[cfg(feature = "dim3")]
+            shapes.extend(vec![
+                SharedShape::cylinder(1.0, 0.5),
+                SharedShape::cone(1.0, 0.5),
+                SharedShape::round_cylinder(1.0, 0.5, 0.1),
+                SharedShape::round_cone(1.0, 0.5, 0.1),
+            ]);
+            shapes
+        };
+
+        let mut deduplicator = ShapeDeduplicator::new();
+        let shapes1 = make_shapes();
+        let shapes2 = make_shapes();
+
+        for (shape1, shape2) in shapes1.iter().zip(shapes2.iter()) {
+            let representative1 = deduplicator.representative(shape1);
+            let representative2 = deduplicator.representative(shape2);
+            assert!(Arc::ptr_eq(&representative1.0, &shape1.0));
+            assert!(Arc::ptr_eq(&representative2.0, &shape1.0));
+        }
+
+        assert_eq!(deduplicator.num_instances(), shapes1.len() * 2);
+        assert_eq!(deduplicator.num_unique_shapes(), shapes1.len());
+
+        // Shapes that only differ by their border radius aren't identical.
+        #[cfg(feature = "dim3")]
+        {
+            let round = SharedShape::round_cylinder(1.0, 0.5, 0.2);
+            assert!(!Arc::ptr_eq(
+                &deduplicator.representative(&round).0,
+                &shapes1[7].0
+            ));
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::geometry::Shape;
+use crate::math::{Isometry, Point, Real};
+use parry::query::PointQueryWithLocation;
+use std::ops::{Add, Mul};
+
+/// The location of a point on the surface of a collider, expressed in terms of the source data
+/// of its shape.
+///
+/// This maps query results (e.g. ray hits) back to the mesh data the collider was created from,
+/// for placing decals, playing footstep sounds, or looking up a per-triangle surface type.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SurfaceLocation {
+    /// The point lies on a triangle of a triangle mesh.
+    TriMeshTriangle {
+        /// The index of the triangle in the index buffer of the triangle mesh.
+        triangle: u32,
+        /// The indices of the three vertices of the triangle.
+        vertices: [u32; 3],
+        /// The barycentric coordinates of the point wrt. the three vertices of the triangle.
+        barycentric_coordinates: [Real; 3],
+    },
+    /// The point lies on a segment of a polyline.
+    PolylineSegment {
+        /// The index of the segment in the index buffer of the polyline.
+        segment: u32,
+        /// The indices of the two vertices of the segment.
+        vertices: [u32; 2],
+        /// The barycentric coordinates of the point wrt. the two vertices of the segment.
+        barycentric_coordinates: [Real; 2],
+    },
+    /// The point lies on a cell of a heightfield.
+    HeightFieldCell {
+        /// The index of the cell.
+        #[cfg(feature = "dim2")]
+        cell: usize,
+        /// The row and column of the cell.
+        #[cfg(feature = "dim3")]
+        cell: (usize, usize),
+    },
+    /// The shape doesn't provide any additional information.
+    Unknown,
+}
+
+impl SurfaceLocation {
+    /// Computes the location of a point lying on the surface of a shape.
+    ///
+    /// The point is expressed in world-space, and `shape_pos` is the position of the shape.
+    pub fn new(shape: &dyn Shape, shape_pos: &Isometry<Real>, point: &Point<Real>) -> Self {
+        let local_point = shape_pos.inverse_transform_point(point);
+
+        if let Some(trimesh) = shape.as_trimesh() {
+            let (_, (triangle, location)) =
+                trimesh.project_local_point_and_get_location(&local_point, false);
+            SurfaceLocation::TriMeshTriangle {
+                triangle,
+                vertices: trimesh.indices()[triangle as usize],
+                barycentric_coordinates: location.barycentric_coordinates(),
+            }
+        } else if let Some(polyline) = shape.as_polyline() {
+            let (_, (segment, location)) =
+                polyline.project_local_point_and_get_location(&local_point, false);
+            SurfaceLocation::PolylineSegment {
+                segment,
+                vertices: polyline.indices()[segment as usize],
+                barycentric_coordinates: location.barycentric_coordinates(),
+            }
+        } else if let Some(cell) = shape
+            .as_heightfield()
+            .and_then(|heightfield| heightfield.cell_at_point(&local_point))
+        {
+            SurfaceLocation::HeightFieldCell { cell }
+        } else {
+            SurfaceLocation::Unknown
+        }
+    }
+
+    /// Interpolates per-vertex values (e.g. the UVs of a mesh) at this location, using its
+    /// barycentric coordinates.
+    ///
+    /// The `vertex_values` are indexed like the vertex buffer of the triangle mesh or polyline.
+    /// Returns `None` if this location is not on a triangle mesh or a polyline.
+    pub fn interpolate<T>(&self, vertex_values: &[T]) -> Option<T>
+    where
+        T: Copy + Mul<Real, Output = T> + Add<Output = T>,
+    {
+        match self {
+            SurfaceLocation::TriMeshTriangle {
+                vertices,
+                barycentric_coordinates: [a, b, c],
+                ..
+            } => Some(
+                vertex_values[vertices[0] as usize] * *a
+                    + vertex_values[vertices[1] as usize] * *b
+                    + vertex_values[vertices[2] as usize] * *c,
+            ),
+            SurfaceLocation::PolylineSegment {
+                vertices,
+                barycentric_coordinates: [a, b],
+                ..
+            } => Some(
+                vertex_values[vertices[0] as usize] * *a + vertex_values[vertices[1] as usize] * *b,
+            ),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SurfaceLocation;
+    use crate::geometry::{Ray, RayCast};
+    use crate::math::{Isometry, Point, Real, Vector};
+
+    #[cfg(feature = "dim3")]
+    #[test]
+    fn trimesh_surface_location() {
+        use na::Vector2;
+
+        // A unit quad on the `xz` plane, split along its diagonal.
+        let vertices = vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 1.0),
+            Point::new(0.0, 0.0, 1.0),
+        ];
+        let uvs = [
+            Vector2::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(0.0, 1.0),
+        ];
+        let trimesh = crate::geometry::TriMesh::new(vertices, vec![[0, 1, 2], [0, 2, 3]]);
+        let pos = Isometry::new(Vector::y() * 2.0, na::zero());
+
+        let ray = Ray::new(Point::new(0.25, 5.0, 0.75), -Vector::y());
+        let hit = trimesh.cast_ray(&pos, &ray, Real::MAX, true).unwrap();
+        let location = SurfaceLocation::new(&trimesh, &pos, &ray.point_at(hit));
+
+        match location {
+            SurfaceLocation::TriMeshTriangle {
+                triangle, vertices, ..
+            } => {
+                assert_eq!(triangle, 1);
+                assert_eq!(vertices, [0, 2, 3]);
+            }
+            _ => panic!("Unexpected surface location: {:?}", location),
+        }
+
+        let uv = location.interpolate(&uvs).unwrap();
+        assert!((uv - Vector2::new(0.25, 0.75)).norm() < 1.0e-5);
+    }
+
+    #[cfg(feature = "dim2")]
+    #[test]
+    fn polyline_surface_location() {
+        let vertices = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(3.0, 0.0),
+        ];
+        let texcoords = [0.0, 1.0, 2.0];
+        let polyline = crate::geometry::Polyline::new(vertices, None);
+        let pos = Isometry::identity();
+
+        let ray = Ray::new(Point::new(1.5, 5.0), -Vector::y());
+        let hit = polyline.cast_ray(&pos, &ray, Real::MAX, true).unwrap();
+        let location = SurfaceLocation::new(&polyline, &pos, &ray.point_at(hit));
+
+        match location {
+            SurfaceLocation::PolylineSegment {
+                segment, vertices, ..
+            } => {
+                assert_eq!(segment, 1);
+                assert_eq!(vertices, [1, 2]);
+            }
+            _ => panic!("Unexpected surface location: {:?}", location),
+        }
+
+        let texcoord: Real = location.interpolate(&texcoords).unwrap();
+        assert!((texcoord - 1.25).abs() < 1.0e-5);
+    }
+}
@@ -0,0 +1,262 @@
+use crate::math::{Point, Real};
+use std::collections::{HashMap, HashSet};
+
+bitflags::bitflags! {
+    #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+    /// The cleanup operations applied to a triangle mesh before building its collider.
+    ///
+    /// See `ColliderBuilder::trimesh_with_flags`.
+    pub struct TriMeshFlags: u8 {
+        /// Merge the vertices with identical coordinates.
+        const MERGE_DUPLICATE_VERTICES = 1 << 0;
+        /// Remove the triangles with a (near-)zero area, with repeated or out-of-bounds indices,
+        /// or with non-finite vertex coordinates.
+        const DELETE_DEGENERATE_TRIANGLES = 1 << 1;
+        /// Remove the triangles made of the same vertices as a previous triangle, whatever their
+        /// orientation.
+        const DELETE_DUPLICATE_TRIANGLES = 1 << 2;
+        /// Orient all the triangles consistently. In 3D, adjacent triangles are given opposite
+        /// orientations on their shared edge, and the triangles of closed meshes are oriented
+        /// toward the exterior. In 2D, all the triangles are oriented counterclockwise.
+        const FIX_ORIENTATION = 1 << 3;
+        /// All the cleanup operations.
+        const SANITIZE = Self::MERGE_DUPLICATE_VERTICES.bits
+            | Self::DELETE_DEGENERATE_TRIANGLES.bits
+            | Self::DELETE_DUPLICATE_TRIANGLES.bits
+            | Self::FIX_ORIENTATION.bits;
+    }
+}
+
+/// Applies the cleanup operations selected by `flags` to the given triangle mesh.
+///
+/// The vertices not referenced by any of the remaining triangles are removed, so the returned
+/// index buffer refers to the returned vertex buffer.
+pub fn sanitize_trimesh(
+    vertices: Vec<Point<Real>>,
+    mut indices: Vec<[u32; 3]>,
+    flags: TriMeshFlags,
+) -> (Vec<Point<Real>>, Vec<[u32; 3]>) {
+    if flags.contains(TriMeshFlags::MERGE_DUPLICATE_VERTICES) {
+        let mut representatives = HashMap::new();
+        let remap: Vec<u32> = vertices
+            .iter()
+            .enumerate()
+            .map(|(i, pt)| {
+                // NOTE: adding 0.0 turns -0.0 into 0.0, so both have the same bits.
+                let key = pt.coords.map(|e| (e + 0.0).to_bits());
+                *representatives.entry(key).or_insert(i as u32)
+            })
+            .collect();
+
+        for idx in &mut indices {
+            for i in idx.iter_mut() {
+                if let Some(representative) = remap.get(*i as usize) {
+                    *i = *representative;
+                }
+            }
+        }
+    }
+
+    if flags.contains(TriMeshFlags::DELETE_DEGENERATE_TRIANGLES) {
+        indices.retain(|idx| !is_degenerate(&vertices, idx));
+    }
+
+    if flags.contains(TriMeshFlags::DELETE_DUPLICATE_TRIANGLES) {
+        let mut seen = HashSet::new();
+        indices.retain(|idx| {
+            let mut key = *idx;
+            key.sort_unstable();
+            seen.insert(key)
+        });
+    }
+
+    if flags.contains(TriMeshFlags::FIX_ORIENTATION) {
+        fix_orientation(&vertices, &mut indices);
+    }
+
+    // Remove the unused vertices.
+    let mut new_ids = vec![u32::MAX; vertices.len()];
+    let mut new_vertices = Vec::new();
+
+    for idx in &mut indices {
+        for i in idx.iter_mut() {
+            let new_id = &mut new_ids[*i as usize];
+
+            if *new_id == u32::MAX {
+                *new_id = new_vertices.len() as u32;
+                new_vertices.push(vertices[*i as usize]);
+            }
+
+            *i = *new_id;
+        }
+    }
+
+    (new_vertices, indices)
+}
+
+fn is_degenerate(vertices: &[Point<Real>], idx: &[u32; 3]) -> bool {
+    if idx[0] == idx[1] || idx[1] == idx[2] || idx[2] == idx[0] {
+        return true;
+    }
+
+    let (a, b, c) = match (
+        vertices.get(idx[0] as usize),
+        vertices.get(idx[1] as usize),
+        vertices.get(idx[2] as usize),
+    ) {
+        (Some(a), Some(b), Some(c)) => (a, b, c),
+        _ => return true,
+    };
+
+    if [a, b, c]
+        .iter()
+        .any(|pt| pt.coords.iter().any(|e| !e.is_finite()))
+    {
+        return true;
+    }
+
+    let ab = b - a;
+    let ac = c - a;
+    let max_edge_sq = ab
+        .norm_squared()
+        .max(ac.norm_squared())
+        .max((c - b).norm_squared());
+
+    // The doubled area, relative to the longest edge.
+    #[cfg(feature = "dim2")]
+    let area2 = ab.perp(&ac).abs();
+    #[cfg(feature = "dim3")]
+    let area2 = ab.cross(&ac).norm();
+
+    area2 <= Real::EPSILON * max_edge_sq
+}
+
+#[cfg(feature = "dim2")]
+fn fix_orientation(vertices: &[Point<Real>], indices: &mut [[u32; 3]]) {
+    for idx in indices {
+        let a = vertices[idx[0] as usize];
+        let b = vertices[idx[1] as usize];
+        let c = vertices[idx[2] as usize];
+
+        if (b - a).perp(&(c - a)) < 0.0 {
+            idx.swap(1, 2);
+        }
+    }
+}
+
+#[cfg(feature = "dim3")]
+fn fix_orientation(vertices: &[Point<Real>], indices: &mut [[u32; 3]]) {
+    let mut edges: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+
+    for (tri_id, idx) in indices.iter().enumerate() {
+        for k in 0..3 {
+            let (a, b) = (idx[k], idx[(k + 1) % 3]);
+            edges.entry((a.min(b), a.max(b))).or_default().push(tri_id);
+        }
+    }
+
+    // Propagate the orientation of an arbitrary triangle of each connected component
+    // to its neighbors: two neighbors must traverse their shared edge in opposite directions.
+    let mut visited = vec![false; indices.len()];
+    let mut stack = Vec::new();
+
+    for seed in 0..indices.len() {
+        if visited[seed] {
+            continue;
+        }
+
+        visited[seed] = true;
+        stack.push(seed);
+
+        while let Some(tri_id) = stack.pop() {
+            let idx = indices[tri_id];
+
+            for k in 0..3 {
+                let (a, b) = (idx[k], idx[(k + 1) % 3]);
+
+                for neighbor in &edges[&(a.min(b), a.max(b))] {
+                    if visited[*neighbor] {
+                        continue;
+                    }
+
+                    visited[*neighbor] = true;
+                    let nb_idx = &mut indices[*neighbor];
+
+                    if (0..3).any(|l| nb_idx[l] == a && nb_idx[(l + 1) % 3] == b) {
+                        nb_idx.swap(1, 2);
+                    }
+
+                    stack.push(*neighbor);
+                }
+            }
+        }
+    }
+
+    // The triangles of closed meshes must be oriented toward the exterior, i.e., the mesh
+    // must have a positive signed volume.
+    let is_closed = edges.values().all(|triangles| triangles.len() == 2);
+
+    if is_closed {
+        let signed_volume: Real = indices
+            .iter()
+            .map(|idx| {
+                let a = vertices[idx[0] as usize].coords;
+                let b = vertices[idx[1] as usize].coords;
+                let c = vertices[idx[2] as usize].coords;
+                a.dot(&b.cross(&c))
+            })
+            .sum();
+
+        if signed_volume < 0.0 {
+            for idx in indices {
+                idx.swap(1, 2);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{sanitize_trimesh, TriMeshFlags};
+    use crate::math::{Point, Real};
+
+    #[cfg(feature = "dim2")]
+    fn point(x: Real, y: Real) -> Point<Real> {
+        Point::new(x, y)
+    }
+
+    #[cfg(feature = "dim3")]
+    fn point(x: Real, y: Real) -> Point<Real> {
+        Point::new(x, 0.0, y)
+    }
+
+    #[test]
+    fn sanitize_dirty_quad() {
+        // A quad split into two triangles with duplicated vertices along their shared edge,
+        // one of them being flipped. Followed by a degenerate and a duplicate triangle.
+        let vertices = vec![
+            point(0.0, 0.0),
+            point(1.0, 0.0),
+            point(1.0, 1.0),
+            point(0.0, 0.0),
+            point(1.0, 1.0),
+            point(0.0, 1.0),
+            point(2.0, 2.0),
+        ];
+        let indices = vec![[0, 1, 2], [3, 5, 4], [0, 1, 1], [4, 5, 3]];
+
+        let (new_vertices, new_indices) =
+            sanitize_trimesh(vertices, indices, TriMeshFlags::SANITIZE);
+
+        // The duplicate and unused vertices were removed.
+        assert_eq!(new_vertices.len(), 4);
+        assert_eq!(new_indices.len(), 2);
+
+        // Both triangles traverse their shared edge in opposite directions.
+        let directed_edges = |idx: [u32; 3]| (0..3).map(move |k| (idx[k], idx[(k + 1) % 3]));
+        let shared = directed_edges(new_indices[0])
+            .filter(|(a, b)| directed_edges(new_indices[1]).any(|e| e == (*b, *a)))
+            .count();
+        assert_eq!(shared, 1);
+    }
+}
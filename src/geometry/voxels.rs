@@ -0,0 +1,304 @@
+use crate::dynamics::MassProperties;
+use crate::geometry::{PointProjection, Ray, RayIntersection, AABB};
+use crate::math::{Isometry, Point, Real, Vector, DIM};
+use na::RealField;
+use parry::bounding_volume::BoundingSphere;
+use parry::query::{PointQuery, RayCast};
+use parry::shape::{FeatureId, Shape, ShapeType, TypedShape};
+
+/// The identifier reported by `TypedShape::Custom` for the `Voxels` shape.
+pub const VOXELS_SHAPE_ID: u32 = 0;
+
+/// A shape made of a regular grid of cubic (square in 2D) voxels, each being either filled or empty.
+///
+/// The voxel identified by the key `k` occupies the region from `k * voxel_size` to
+/// `(k + 1) * voxel_size` in the local-space of the shape. The contacts with a collider using
+/// this shape are computed by a dedicated contact generator of the narrow-phase that only
+/// considers the filled voxels overlapping the other collider. This makes modifying voxels
+/// (see `Collider::set_voxels`) cheap compared to re-computing a triangle mesh, which is useful
+/// for destructible or procedurally generated terrains.
+///
+/// Only the contacts between voxels and non-voxel shapes are computed. Intersection tests (for
+/// sensors) and continuous collision detection are not supported for voxels.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct Voxels {
+    dimensions: Vector<u32>,
+    voxel_size: Real,
+    filled: Vec<bool>,
+}
+
+impl Voxels {
+    /// Creates a grid of empty voxels with the given number of voxels along each axis.
+    pub fn new(dimensions: Vector<u32>, voxel_size: Real) -> Self {
+        assert!(voxel_size > 0.0, "The voxel size must be positive.");
+        let len = dimensions.iter().map(|d| *d as usize).product();
+
+        Self {
+            dimensions,
+            voxel_size,
+            filled: vec![false; len],
+        }
+    }
+
+    /// Creates a grid of voxels, calling `f` with the key of each voxel to determine if it is filled.
+    pub fn from_fn(
+        dimensions: Vector<u32>,
+        voxel_size: Real,
+        mut f: impl FnMut(Point<u32>) -> bool,
+    ) -> Self {
+        let mut result = Self::new(dimensions, voxel_size);
+
+        for id in 0..result.filled.len() {
+            result.filled[id] = f(result.voxel_key(id as u32));
+        }
+
+        result
+    }
+
+    /// The number of voxels along each axis of this grid.
+    pub fn dimensions(&self) -> Vector<u32> {
+        self.dimensions
+    }
+
+    /// The size of each voxel of this grid.
+    pub fn voxel_size(&self) -> Real {
+        self.voxel_size
+    }
+
+    /// Is the voxel with the given key filled?
+    ///
+    /// Returns `false` if the key is outside of this grid.
+    pub fn is_filled(&self, key: Point<u32>) -> bool {
+        self.voxel_id(key)
+            .map(|id| self.filled[id as usize])
+            .unwrap_or(false)
+    }
+
+    /// Fills or empties the voxel with the given key.
+    ///
+    /// Panics if the key is outside of this grid.
+    pub fn set_filled(&mut self, key: Point<u32>, filled: bool) {
+        let id = self
+            .voxel_id(key)
+            .expect("The voxel key is outside of the grid.");
+        self.filled[id as usize] = filled;
+    }
+
+    /// The local-space AABB of the voxel with the given key.
+    pub fn voxel_aabb(&self, key: Point<u32>) -> AABB {
+        let mins = Point::from(key.coords.map(|e| e as Real * self.voxel_size));
+        AABB::new(mins, mins + Vector::repeat(self.voxel_size))
+    }
+
+    /// The identifier of the voxel with the given key, as used by the contact manifolds
+    /// involving this shape, or `None` if the key is outside of this grid.
+    pub fn voxel_id(&self, key: Point<u32>) -> Option<u32> {
+        if (0..DIM).any(|i| key[i] >= self.dimensions[i]) {
+            return None;
+        }
+
+        Some(
+            (0..DIM)
+                .rev()
+                .fold(0, |id, i| id * self.dimensions[i] + key[i]),
+        )
+    }
+
+    /// The key of the voxel with the given identifier.
+    pub fn voxel_key(&self, id: u32) -> Point<u32> {
+        let mut key = Point::origin();
+        let mut rem = id;
+
+        for i in 0..DIM {
+            key[i] = rem % self.dimensions[i];
+            rem /= self.dimensions[i];
+        }
+
+        key
+    }
+
+    /// The identifiers and local-space AABBs of all the filled voxels.
+    pub fn filled_voxels(&self) -> impl Iterator<Item = (u32, AABB)> + '_ {
+        self.filled
+            .iter()
+            .enumerate()
+            .filter(|(_, filled)| **filled)
+            .map(move |(id, _)| (id as u32, self.voxel_aabb(self.voxel_key(id as u32))))
+    }
+
+    /// Calls `f` with the identifier and local-space AABB of each filled voxel intersecting
+    /// the given local-space AABB.
+    pub fn map_filled_voxels_in_local_aabb(&self, aabb: &AABB, mut f: impl FnMut(u32, &AABB)) {
+        let mut start = Point::origin();
+        let mut end = Point::origin();
+
+        for i in 0..DIM {
+            let lo = (aabb.mins[i] / self.voxel_size).floor().max(0.0);
+            let hi = (aabb.maxs[i] / self.voxel_size)
+                .floor()
+                .min(self.dimensions[i] as Real - 1.0);
+
+            if hi < lo {
+                return;
+            }
+
+            start[i] = lo as u32;
+            end[i] = hi as u32;
+        }
+
+        #[cfg(feature = "dim2")]
+        let k_range = 0..1;
+        #[cfg(feature = "dim3")]
+        let k_range = start.z..=end.z;
+
+        for i in start.x..=end.x {
+            for j in start.y..=end.y {
+                for _k in k_range.clone() {
+                    #[cfg(feature = "dim2")]
+                    let key = Point::new(i, j);
+                    #[cfg(feature = "dim3")]
+                    let key = Point::new(i, j, _k);
+                    let id = self.voxel_id(key).unwrap();
+
+                    if self.filled[id as usize] {
+                        f(id, &self.voxel_aabb(key));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl RayCast for Voxels {
+    fn cast_local_ray_and_get_normal(
+        &self,
+        ray: &Ray,
+        max_toi: Real,
+        solid: bool,
+    ) -> Option<RayIntersection> {
+        // TODO: traverse the grid along the ray instead of testing every filled voxel.
+        self.filled_voxels()
+            .filter_map(|(_, aabb)| aabb.cast_local_ray_and_get_normal(ray, max_toi, solid))
+            .min_by(|a, b| {
+                a.toi
+                    .partial_cmp(&b.toi)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+    }
+}
+
+impl PointQuery for Voxels {
+    fn project_local_point(&self, pt: &Point<Real>, solid: bool) -> PointProjection {
+        // NOTE: if the point is inside of a voxel and `solid` is false, the point is projected
+        //       on the boundary of that voxel, even if it is shared with another filled voxel.
+        self.filled_voxels()
+            .map(|(_, aabb)| aabb.project_local_point(pt, solid))
+            .min_by(|a, b| {
+                na::distance_squared(pt, &a.point)
+                    .partial_cmp(&na::distance_squared(pt, &b.point))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            // There is nothing to project on if all the voxels are empty.
+            .unwrap_or_else(|| PointProjection::new(false, *pt))
+    }
+
+    fn project_local_point_and_get_feature(
+        &self,
+        pt: &Point<Real>,
+    ) -> (PointProjection, FeatureId) {
+        (self.project_local_point(pt, false), FeatureId::Unknown)
+    }
+}
+
+impl Shape for Voxels {
+    fn compute_local_aabb(&self) -> AABB {
+        let extents = self.dimensions.map(|d| d as Real * self.voxel_size);
+        AABB::new(Point::origin(), Point::from(extents))
+    }
+
+    fn compute_local_bounding_sphere(&self) -> BoundingSphere {
+        self.compute_local_aabb().bounding_sphere()
+    }
+
+    fn mass_properties(&self, density: Real) -> MassProperties {
+        let half_extents = Vector::repeat(self.voxel_size / 2.0);
+
+        self.filled_voxels()
+            .map(|(_, aabb)| {
+                MassProperties::from_cuboid(density, half_extents)
+                    .transform_by(&Isometry::new(aabb.center().coords, na::zero()))
+            })
+            .fold(MassProperties::zero(), |acc, mprops| acc + mprops)
+    }
+
+    fn shape_type(&self) -> ShapeType {
+        ShapeType::Custom
+    }
+
+    fn as_typed_shape(&self) -> TypedShape {
+        TypedShape::Custom(VOXELS_SHAPE_ID)
+    }
+
+    fn ccd_thickness(&self) -> Real {
+        self.voxel_size / 2.0
+    }
+
+    fn ccd_angular_thickness(&self) -> Real {
+        Real::frac_pi_4()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Voxels;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Isometry, Point, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn voxels_terrain() {
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+
+        // A slab of 10 voxels wide and 1 voxel thick, with its top at y = 0.
+        let mut dimensions: Vector<u32> = Vector::repeat(10);
+        dimensions.y = 1;
+        let voxels = Voxels::from_fn(dimensions, 1.0, |_| true);
+        let ground = world.bodies.insert(
+            RigidBodyBuilder::new_static()
+                .position(Isometry::new(
+                    Vector::repeat(-5.0) + Vector::y() * 4.0,
+                    na::zero(),
+                ))
+                .build(),
+        );
+        let terrain = world.colliders.insert(
+            ColliderBuilder::voxels(voxels).build(),
+            ground,
+            &mut world.bodies,
+        );
+        let (rb, _) = world.insert_dynamic(Vector::y() * 2.0, ColliderBuilder::ball(0.5));
+
+        // The ball rests on the voxels.
+        world.run(120);
+        let y = world.bodies[rb].position().translation.y;
+        assert!((y - 0.5).abs() < 0.02);
+
+        // Dig a hole under the ball.
+        let mut hole = Vec::new();
+        for i in 3..7 {
+            #[cfg(feature = "dim2")]
+            hole.push((Point::new(i, 0), false));
+            #[cfg(feature = "dim3")]
+            for k in 3..7 {
+                hole.push((Point::new(i, 0, k), false));
+            }
+        }
+        world.colliders[terrain].set_voxels(&hole);
+
+        // The ball falls through the hole.
+        world.run(120);
+        assert!(world.bodies[rb].position().translation.y < -1.0);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::geometry::{ContactData, ContactManifold, ContactManifoldData, Cuboid, Shape, Voxels};
+use crate::math::{Isometry, Real, Vector};
+use parry::bounding_volume::BoundingVolume;
+use parry::query::PersistentQueryDispatcher;
+
+/// Computes the contact manifolds between voxels and another shape.
+///
+/// Each filled voxel overlapping the AABB of the other shape is given, as a cuboid, to the
+/// query dispatcher. The resulting contact manifolds are identified by the voxel identifier
+/// (see `Voxels::voxel_id`) stored as their sub-shape identifier, which is also used to match
+/// them with the manifolds computed at the previous timestep. No workspace is needed, so the
+/// voxels can be modified freely between two timesteps.
+///
+/// If `flipped` is `false`, then `pos12` is the position of the other shape relative to the
+/// voxels, and the first shape of the manifolds are the voxels. Otherwise, `pos12` is the
+/// position of the voxels relative to the other shape, and the first shape of the manifolds
+/// is the other shape.
+pub(crate) fn contact_manifolds_voxels_shape(
+    dispatcher: &dyn PersistentQueryDispatcher<ContactManifoldData, ContactData>,
+    pos12: &Isometry<Real>,
+    voxels: &Voxels,
+    shape: &dyn Shape,
+    prediction: Real,
+    manifolds: &mut Vec<ContactManifold>,
+    flipped: bool,
+) {
+    // Position of the other shape relative to the voxels.
+    let pos_voxels_shape = if flipped { pos12.inverse() } else { *pos12 };
+    let aabb = shape.compute_aabb(&pos_voxels_shape).loosened(prediction);
+    let cuboid = Cuboid::new(Vector::repeat(voxels.voxel_size() / 2.0));
+
+    let mut old_manifolds = std::mem::replace(manifolds, Vec::new());
+    let mut voxel_manifolds = Vec::new();
+
+    voxels.map_filled_voxels_in_local_aabb(&aabb, |voxel_id, voxel_aabb| {
+        // Reuse the manifolds computed at the previous step for this voxel (if any)
+        // so their contact impulses can be transferred to the new contacts.
+        let mut i = 0;
+        while i < old_manifolds.len() {
+            let manifold_voxel_id = if flipped {
+                old_manifolds[i].subshape2
+            } else {
+                old_manifolds[i].subshape1
+            };
+
+            if manifold_voxel_id == voxel_id {
+                voxel_manifolds.push(old_manifolds.swap_remove(i));
+            } else {
+                i += 1;
+            }
+        }
+
+        let voxel_pos = Isometry::new(voxel_aabb.center().coords, na::zero());
+
+        let result = if flipped {
+            dispatcher.contact_manifolds(
+                &(pos12 * voxel_pos),
+                shape,
+                &cuboid,
+                prediction,
+                &mut voxel_manifolds,
+                &mut None,
+            )
+        } else {
+            dispatcher.contact_manifolds(
+                &voxel_pos.inv_mul(pos12),
+                &cuboid,
+                shape,
+                prediction,
+                &mut voxel_manifolds,
+                &mut None,
+            )
+        };
+
+        if result.is_err() {
+            // This shape is not supported (e.g. other voxels).
+            voxel_manifolds.clear();
+            return;
+        }
+
+        for mut manifold in voxel_manifolds.drain(..) {
+            if flipped {
+                manifold.subshape2 = voxel_id;
+                manifold.subshape_pos2 = Some(voxel_pos);
+            } else {
+                manifold.subshape1 = voxel_id;
+                manifold.subshape_pos1 = Some(voxel_pos);
+            }
+
+            manifolds.push(manifold);
+        }
+    });
+}
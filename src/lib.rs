@@ -6,7 +6,13 @@
 //! Rapier has some unique features for collaborative applications:
 //! - The ability to snapshot the state of the physics engine, and restore it later.
 //! - The ability to run a perfectly deterministic simulation on different machine, as long as they
-//! are compliant with the IEEE 754-2008 floating point standard.
+//! are compliant with the IEEE 754-2008 floating point standard. This holds with the `parallel`
+//! feature too, whatever the number of threads, and the events are reported in the same order.
+//! However, the results of the `parallel` feature are **not** bit-identical to the ones of the
+//! single-threaded solver: the multithreaded solver groups the constraints of each island into
+//! batches not sharing any dynamic body, and solves them in this batch order instead of the
+//! single-threaded order. All the machines of a lockstep simulation must thus enable the same
+//! features. The physics hooks may also be called from several threads, in any order.
 
 #![warn(missing_docs)]
 
@@ -1,7 +1,9 @@
 //! Physics pipeline structures.
 
-use crate::dynamics::{JointSet, RigidBodySet};
-use crate::geometry::{BroadPhase, BroadPhasePairEvent, ColliderPair, ColliderSet, NarrowPhase};
+use crate::dynamics::{JointIslandMergePolicy, JointSet, RigidBodySet};
+use crate::geometry::{
+    BroadPhaseBackend, BroadPhasePairEvent, ColliderPair, ColliderSet, NarrowPhase,
+};
 use crate::math::Real;
 use crate::pipeline::{EventHandler, PhysicsHooks};
 
@@ -37,7 +39,7 @@ impl CollisionPipeline {
     pub fn step(
         &mut self,
         prediction_distance: Real,
-        broad_phase: &mut BroadPhase,
+        broad_phase: &mut dyn BroadPhaseBackend,
         narrow_phase: &mut NarrowPhase,
         bodies: &mut RigidBodySet,
         colliders: &mut ColliderSet,
@@ -49,7 +51,16 @@ impl CollisionPipeline {
         self.broadphase_collider_pairs.clear();
 
         self.broad_phase_events.clear();
-        broad_phase.update(prediction_distance, colliders, &mut self.broad_phase_events);
+        broad_phase.update(
+            prediction_distance,
+            bodies,
+            colliders,
+            &mut self.broad_phase_events,
+        );
+
+        for event in &self.broad_phase_events {
+            events.handle_broad_phase_pair_event(*event);
+        }
 
         narrow_phase.handle_user_changes(colliders, bodies, events);
         narrow_phase.register_pairs(colliders, bodies, &self.broad_phase_events, events);
@@ -61,6 +72,9 @@ impl CollisionPipeline {
             narrow_phase,
             self.empty_joints.joint_graph(),
             128,
+            JointIslandMergePolicy::Lazy,
+            0.0,
+            events,
         );
 
         // Update colliders positions and kinematic bodies positions.
@@ -0,0 +1,742 @@
+use crate::dynamics::{JointHandle, JointParams, JointSet, RigidBodyHandle, RigidBodySet};
+use crate::geometry::{ColliderHandle, ColliderSet, MultiSphere, NarrowPhase, Shape, Voxels, AABB};
+use crate::math::{Isometry, Point, Real, Vector, DIM};
+
+/// A color, given by its red, green, blue and alpha components in `[0, 1]`.
+pub type DebugColor = [f32; 4];
+
+bitflags::bitflags! {
+    #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+    /// Flags selecting the elements of the physics state rendered by the `DebugRenderPipeline`.
+    pub struct DebugRenderMode: u32 {
+        /// If set, the local frame of each rigid-body is rendered as one line per axis.
+        const RIGID_BODY_AXES = 1 << 0;
+        /// If set, the wireframe of the shape of each collider is rendered.
+        const COLLIDER_SHAPES = 1 << 1;
+        /// If set, the world-space AABB of each collider is rendered.
+        const COLLIDER_AABBS = 1 << 2;
+        /// If set, the solver contacts are rendered as lines along the contact normal.
+        const CONTACTS = 1 << 3;
+        /// If set, the anchors of each joint are rendered, with a line between them.
+        const JOINTS = 1 << 4;
+        /// If set, the AABB enclosing all the colliders of each active island is rendered.
+        const ISLANDS = 1 << 5;
+    }
+}
+
+impl Default for DebugRenderMode {
+    fn default() -> Self {
+        DebugRenderMode::RIGID_BODY_AXES
+            | DebugRenderMode::COLLIDER_SHAPES
+            | DebugRenderMode::JOINTS
+    }
+}
+
+/// The element of the physics state a line emitted by the `DebugRenderPipeline` belongs to.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum DebugRenderObject {
+    /// The local frame of a rigid-body.
+    RigidBody(RigidBodyHandle),
+    /// The shape of a collider.
+    Collider(ColliderHandle),
+    /// The world-space AABB of a collider.
+    ColliderAABB(ColliderHandle),
+    /// A solver contact between two colliders.
+    ContactPair(ColliderHandle, ColliderHandle),
+    /// A joint.
+    Joint(JointHandle),
+    /// An active island, identified by its index.
+    Island(usize),
+}
+
+/// The renderer the `DebugRenderPipeline` draws through.
+///
+/// Only `draw_line` needs to be implemented: every element is rendered as a set of world-space
+/// lines. A renderer able to draw shapes by itself (e.g. with cached meshes) can also override
+/// `draw_shape`.
+pub trait DebugRenderBackend {
+    /// Draws a world-space line between `a` and `b`.
+    fn draw_line(
+        &mut self,
+        object: DebugRenderObject,
+        a: Point<Real>,
+        b: Point<Real>,
+        color: DebugColor,
+    );
+
+    /// Draws a collider shape at the given world-space position.
+    ///
+    /// Returns `false` if the shape was not drawn, in which case its wireframe is drawn
+    /// with `draw_line` instead. The default implementation does nothing and returns `false`.
+    fn draw_shape(
+        &mut self,
+        _object: DebugRenderObject,
+        _shape: &dyn Shape,
+        _pos: &Isometry<Real>,
+        _color: DebugColor,
+    ) -> bool {
+        false
+    }
+}
+
+/// The colors and sizes used by the `DebugRenderPipeline`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct DebugRenderStyle {
+    /// The number of segments used for rendering a circle.
+    pub subdivisions: usize,
+    /// The length of the lines representing the axes of a rigid-body frame.
+    pub rigid_body_axes_length: Real,
+    /// The length of the lines representing the contact normals.
+    pub contact_normal_length: Real,
+    /// The half-length of the lines representing the boundary of a half-space.
+    pub halfspace_extent: Real,
+    /// The colors of the `x`, `y` (and `z`) axes of the rigid-body frames.
+    pub rigid_body_axes_colors: [DebugColor; DIM],
+    /// The color of the colliders attached to a dynamic rigid-body.
+    pub collider_dynamic_color: DebugColor,
+    /// The color of the colliders attached to a kinematic rigid-body.
+    pub collider_kinematic_color: DebugColor,
+    /// The color of the colliders attached to a static rigid-body.
+    pub collider_static_color: DebugColor,
+    /// The color of the collider AABBs.
+    pub collider_aabb_color: DebugColor,
+    /// The color of the contact normals.
+    pub contact_color: DebugColor,
+    /// The color of the lines between a rigid-body and its joint anchors.
+    pub joint_anchor_color: DebugColor,
+    /// The color of the lines between the two anchors of a joint.
+    pub joint_separation_color: DebugColor,
+    /// The color of the island AABBs.
+    pub island_color: DebugColor,
+}
+
+impl Default for DebugRenderStyle {
+    fn default() -> Self {
+        Self {
+            subdivisions: 16,
+            rigid_body_axes_length: 0.5,
+            contact_normal_length: 0.3,
+            halfspace_extent: 10.0,
+            #[cfg(feature = "dim2")]
+            rigid_body_axes_colors: [[1.0, 0.0, 0.0, 1.0], [0.0, 1.0, 0.0, 1.0]],
+            #[cfg(feature = "dim3")]
+            rigid_body_axes_colors: [
+                [1.0, 0.0, 0.0, 1.0],
+                [0.0, 1.0, 0.0, 1.0],
+                [0.0, 0.0, 1.0, 1.0],
+            ],
+            collider_dynamic_color: [0.9, 0.6, 0.2, 1.0],
+            collider_kinematic_color: [0.3, 0.6, 0.9, 1.0],
+            collider_static_color: [0.5, 0.5, 0.5, 1.0],
+            collider_aabb_color: [0.8, 0.8, 0.2, 1.0],
+            contact_color: [0.9, 0.1, 0.9, 1.0],
+            joint_anchor_color: [0.2, 0.8, 0.2, 1.0],
+            joint_separation_color: [0.9, 0.2, 0.2, 1.0],
+            island_color: [0.2, 0.9, 0.9, 1.0],
+        }
+    }
+}
+
+/// A pipeline rendering the physics state as lines, for debugging purposes.
+///
+/// The pipeline walks the rigid-bodies, colliders, joints and narrow-phase, and emits the
+/// elements selected by its `mode` through a `DebugRenderBackend`, so any renderer can
+/// visualize the simulation.
+#[derive(Copy, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct DebugRenderPipeline {
+    /// The colors and sizes of the rendered elements.
+    pub style: DebugRenderStyle,
+    /// The elements to render.
+    pub mode: DebugRenderMode,
+}
+
+impl DebugRenderPipeline {
+    /// Creates a debug-render pipeline with the given style, rendering the elements selected by `mode`.
+    pub fn new(style: DebugRenderStyle, mode: DebugRenderMode) -> Self {
+        Self { style, mode }
+    }
+
+    /// Renders all the elements selected by `self.mode`.
+    pub fn render(
+        &self,
+        backend: &mut dyn DebugRenderBackend,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        joints: &JointSet,
+        narrow_phase: &NarrowPhase,
+    ) {
+        if self.mode.contains(DebugRenderMode::RIGID_BODY_AXES) {
+            self.render_rigid_bodies(backend, bodies);
+        }
+
+        if self
+            .mode
+            .intersects(DebugRenderMode::COLLIDER_SHAPES | DebugRenderMode::COLLIDER_AABBS)
+        {
+            self.render_colliders(backend, bodies, colliders);
+        }
+
+        if self.mode.contains(DebugRenderMode::CONTACTS) {
+            self.render_contacts(backend, narrow_phase);
+        }
+
+        if self.mode.contains(DebugRenderMode::JOINTS) {
+            self.render_joints(backend, bodies, joints);
+        }
+
+        if self.mode.contains(DebugRenderMode::ISLANDS) {
+            self.render_islands(backend, bodies, colliders);
+        }
+    }
+
+    /// Renders the local frame of each rigid-body.
+    pub fn render_rigid_bodies(&self, backend: &mut dyn DebugRenderBackend, bodies: &RigidBodySet) {
+        for (handle, body) in bodies.iter() {
+            let object = DebugRenderObject::RigidBody(handle);
+            let pos = body.position();
+            let origin = Point::from(pos.translation.vector);
+
+            for i in 0..DIM {
+                let tip = pos * Point::from(Vector::ith(i, self.style.rigid_body_axes_length));
+                backend.draw_line(object, origin, tip, self.style.rigid_body_axes_colors[i]);
+            }
+        }
+    }
+
+    /// Renders the shape and/or the AABB of each collider, depending on `self.mode`.
+    pub fn render_colliders(
+        &self,
+        backend: &mut dyn DebugRenderBackend,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+    ) {
+        for (handle, collider) in colliders.iter() {
+            if self.mode.contains(DebugRenderMode::COLLIDER_SHAPES) {
+                let object = DebugRenderObject::Collider(handle);
+                let color = match bodies.get(collider.parent()) {
+                    Some(body) if body.is_dynamic() => self.style.collider_dynamic_color,
+                    Some(body) if body.is_kinematic() => self.style.collider_kinematic_color,
+                    _ => self.style.collider_static_color,
+                };
+
+                if !backend.draw_shape(object, collider.shape(), collider.position(), color) {
+                    self.render_shape(
+                        backend,
+                        object,
+                        collider.shape(),
+                        collider.position(),
+                        color,
+                    );
+                }
+            }
+
+            if self.mode.contains(DebugRenderMode::COLLIDER_AABBS) {
+                let object = DebugRenderObject::ColliderAABB(handle);
+                let mut painter = self.painter(backend, object, self.style.collider_aabb_color);
+                painter.aabb(&Isometry::identity(), &collider.compute_aabb());
+            }
+        }
+    }
+
+    /// Renders each solver contact as a line starting at the contact point, along the contact normal.
+    pub fn render_contacts(
+        &self,
+        backend: &mut dyn DebugRenderBackend,
+        narrow_phase: &NarrowPhase,
+    ) {
+        for pair in narrow_phase.contact_pairs() {
+            let object = DebugRenderObject::ContactPair(pair.pair.collider1, pair.pair.collider2);
+
+            for manifold in &pair.manifolds {
+                let normal = manifold.data.normal * self.style.contact_normal_length;
+
+                for contact in &manifold.data.solver_contacts {
+                    backend.draw_line(
+                        object,
+                        contact.point,
+                        contact.point + normal,
+                        self.style.contact_color,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Renders each joint as lines from its rigid-bodies to their anchors, and a line between
+    /// both anchors showing how much the joint is violated.
+    ///
+    /// A mouse joint is rendered as a line between its anchor and its target.
+    pub fn render_joints(
+        &self,
+        backend: &mut dyn DebugRenderBackend,
+        bodies: &RigidBodySet,
+        joints: &JointSet,
+    ) {
+        for (handle, joint) in joints.iter() {
+            let object = DebugRenderObject::Joint(handle);
+            let (rb1, rb2) = match (bodies.get(joint.body1), bodies.get(joint.body2)) {
+                (Some(rb1), Some(rb2)) => (rb1, rb2),
+                _ => continue,
+            };
+
+            let (local_anchor1, local_anchor2) = match &joint.params {
+                JointParams::BallJoint(j) => (j.local_anchor1, j.local_anchor2),
+                JointParams::FixedJoint(j) => (
+                    Point::from(j.local_anchor1.translation.vector),
+                    Point::from(j.local_anchor2.translation.vector),
+                ),
+                JointParams::PrismaticJoint(j) => (j.local_anchor1, j.local_anchor2),
+                #[cfg(feature = "dim3")]
+                JointParams::RevoluteJoint(j) => (j.local_anchor1, j.local_anchor2),
+                JointParams::MouseJoint(j) => {
+                    // Only the second body is dragged, unless it isn't dynamic.
+                    let dragged = if rb2.is_dynamic() { rb2 } else { rb1 };
+                    backend.draw_line(
+                        object,
+                        dragged.position() * j.local_anchor,
+                        j.target,
+                        self.style.joint_separation_color,
+                    );
+                    continue;
+                }
+            };
+
+            let anchor1 = rb1.position() * local_anchor1;
+            let anchor2 = rb2.position() * local_anchor2;
+            let color = self.style.joint_anchor_color;
+            backend.draw_line(
+                object,
+                rb1.position().translation.vector.into(),
+                anchor1,
+                color,
+            );
+            backend.draw_line(
+                object,
+                rb2.position().translation.vector.into(),
+                anchor2,
+                color,
+            );
+            backend.draw_line(object, anchor1, anchor2, self.style.joint_separation_color);
+        }
+    }
+
+    /// Renders the AABB enclosing all the colliders attached to the rigid-bodies of each active island.
+    ///
+    /// The islands are those computed during the last timestep.
+    pub fn render_islands(
+        &self,
+        backend: &mut dyn DebugRenderBackend,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+    ) {
+        for island_id in 0..bodies.num_islands() {
+            let mut aabb = AABB::new_invalid();
+
+            for handle in bodies.active_island(island_id) {
+                aabb.merge(&bodies[*handle].compute_aabb(colliders));
+            }
+
+            // The AABB is invalid if no rigid-body of the island has a collider.
+            if aabb.mins.x <= aabb.maxs.x {
+                let object = DebugRenderObject::Island(island_id);
+                let mut painter = self.painter(backend, object, self.style.island_color);
+                painter.aabb(&Isometry::identity(), &aabb);
+            }
+        }
+    }
+
+    /// Renders the wireframe of a shape at the given position.
+    ///
+    /// Rounded shapes are rendered without their border. Shapes unknown to this pipeline are
+    /// rendered as their local AABB.
+    pub fn render_shape(
+        &self,
+        backend: &mut dyn DebugRenderBackend,
+        object: DebugRenderObject,
+        shape: &dyn Shape,
+        pos: &Isometry<Real>,
+        color: DebugColor,
+    ) {
+        let mut painter = self.painter(backend, object, color);
+        self.paint_shape(&mut painter, shape, pos);
+    }
+
+    fn painter<'a>(
+        &self,
+        backend: &'a mut dyn DebugRenderBackend,
+        object: DebugRenderObject,
+        color: DebugColor,
+    ) -> Painter<'a> {
+        Painter {
+            backend,
+            object,
+            color,
+            subdivisions: self.style.subdivisions.max(3),
+        }
+    }
+
+    fn paint_shape(&self, painter: &mut Painter, shape: &dyn Shape, pos: &Isometry<Real>) {
+        if let Some(compound) = shape.as_compound() {
+            for (part_pos, part) in compound.shapes() {
+                self.paint_shape(painter, &**part, &(pos * part_pos));
+            }
+        } else if let Some(ball) = shape.as_ball() {
+            painter.ball(pos, &Point::origin(), &Vector::y(), ball.radius);
+        } else if let Some(cuboid) = shape.as_cuboid() {
+            let he = cuboid.half_extents;
+            painter.aabb(pos, &AABB::new((-he).into(), he.into()));
+        } else if let Some(capsule) = shape.as_capsule() {
+            painter.capsule(pos, &capsule.segment.a, &capsule.segment.b, capsule.radius);
+        } else if let Some(segment) = shape.as_segment() {
+            painter.line(pos, &segment.a, &segment.b);
+        } else if let Some(triangle) = shape.as_triangle() {
+            painter.polygon(pos, &[triangle.a, triangle.b, triangle.c]);
+        } else if let Some(trimesh) = shape.as_trimesh() {
+            painter.triangles(pos, trimesh.vertices(), trimesh.indices());
+        } else if let Some(polyline) = shape.as_polyline() {
+            for idx in polyline.indices() {
+                let vertices = polyline.vertices();
+                painter.line(pos, &vertices[idx[0] as usize], &vertices[idx[1] as usize]);
+            }
+        } else if let Some(heightfield) = shape.as_heightfield() {
+            #[cfg(feature = "dim2")]
+            for segment in heightfield.segments() {
+                painter.line(pos, &segment.a, &segment.b);
+            }
+            #[cfg(feature = "dim3")]
+            for triangle in heightfield.triangles() {
+                painter.polygon(pos, &[triangle.a, triangle.b, triangle.c]);
+            }
+        } else if let Some(halfspace) = shape.as_halfspace() {
+            let normal = *halfspace.normal;
+            let normal_tip = Point::from(normal * self.style.rigid_body_axes_length);
+            painter.line(pos, &Point::origin(), &normal_tip);
+
+            for dir in orthogonal_directions(&normal).iter() {
+                let offset = dir * self.style.halfspace_extent;
+                painter.line(pos, &Point::from(-offset), &Point::from(offset));
+            }
+        } else if let Some(multi_sphere) = shape.as_shape::<MultiSphere>() {
+            for (center, radius) in multi_sphere.spheres() {
+                painter.ball(pos, center, &Vector::y(), *radius);
+            }
+        } else if let Some(voxels) = shape.as_shape::<Voxels>() {
+            for (_, aabb) in voxels.filled_voxels() {
+                painter.aabb(pos, &aabb);
+            }
+        } else if !self.paint_dim_specific_shape(painter, shape, pos) {
+            painter.aabb(pos, &shape.compute_local_aabb());
+        }
+    }
+
+    #[cfg(feature = "dim2")]
+    fn paint_dim_specific_shape(
+        &self,
+        painter: &mut Painter,
+        shape: &dyn Shape,
+        pos: &Isometry<Real>,
+    ) -> bool {
+        if let Some(polygon) = shape.as_convex_polygon() {
+            painter.polygon(pos, polygon.points());
+        } else if let Some(s) = shape.as_round_cuboid() {
+            self.paint_shape(painter, &s.base_shape, pos);
+        } else if let Some(s) = shape.as_round_triangle() {
+            self.paint_shape(painter, &s.base_shape, pos);
+        } else if let Some(s) = shape.as_round_convex_polygon() {
+            self.paint_shape(painter, &s.base_shape, pos);
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    #[cfg(feature = "dim3")]
+    fn paint_dim_specific_shape(
+        &self,
+        painter: &mut Painter,
+        shape: &dyn Shape,
+        pos: &Isometry<Real>,
+    ) -> bool {
+        if let Some(polyhedron) = shape.as_convex_polyhedron() {
+            let (vertices, indices) = polyhedron.to_trimesh();
+            painter.triangles(pos, &vertices, &indices);
+        } else if let Some(cylinder) = shape.as_cylinder() {
+            let (half_height, radius) = (cylinder.half_height, cylinder.radius);
+            painter.frustum(pos, half_height, radius, radius);
+        } else if let Some(cone) = shape.as_cone() {
+            painter.frustum(pos, cone.half_height, cone.radius, 0.0);
+        } else if let Some(s) = shape.as_round_cuboid() {
+            self.paint_shape(painter, &s.base_shape, pos);
+        } else if let Some(s) = shape.as_round_triangle() {
+            self.paint_shape(painter, &s.base_shape, pos);
+        } else if let Some(s) = shape.as_round_convex_polyhedron() {
+            self.paint_shape(painter, &s.base_shape, pos);
+        } else if let Some(s) = shape.as_round_cylinder() {
+            self.paint_shape(painter, &s.base_shape, pos);
+        } else if let Some(s) = shape.as_round_cone() {
+            self.paint_shape(painter, &s.base_shape, pos);
+        } else {
+            return false;
+        }
+
+        true
+    }
+}
+
+// Draws the lines of one rendered object with a single color.
+//
+// All the points given to its methods are expressed in the local-space of the given position.
+struct Painter<'a> {
+    backend: &'a mut dyn DebugRenderBackend,
+    object: DebugRenderObject,
+    color: DebugColor,
+    subdivisions: usize,
+}
+
+impl<'a> Painter<'a> {
+    fn line(&mut self, pos: &Isometry<Real>, a: &Point<Real>, b: &Point<Real>) {
+        self.backend
+            .draw_line(self.object, pos * a, pos * b, self.color);
+    }
+
+    // Draws the closed polygon with the given vertices.
+    fn polygon(&mut self, pos: &Isometry<Real>, vertices: &[Point<Real>]) {
+        for (i, a) in vertices.iter().enumerate() {
+            self.line(pos, a, &vertices[(i + 1) % vertices.len()]);
+        }
+    }
+
+    fn triangles(&mut self, pos: &Isometry<Real>, vertices: &[Point<Real>], indices: &[[u32; 3]]) {
+        for idx in indices {
+            let triangle = [
+                vertices[idx[0] as usize],
+                vertices[idx[1] as usize],
+                vertices[idx[2] as usize],
+            ];
+            self.polygon(pos, &triangle);
+        }
+    }
+
+    // Draws the edges of a box.
+    fn aabb(&mut self, pos: &Isometry<Real>, aabb: &AABB) {
+        // The i-th bit of the index of a corner tells if it is at the max along the i-th axis.
+        let corner = |id: usize| {
+            Point::from(Vector::from_fn(|i, _| {
+                if id & (1 << i) != 0 {
+                    aabb.maxs[i]
+                } else {
+                    aabb.mins[i]
+                }
+            }))
+        };
+
+        for id in 0..1 << DIM {
+            for i in 0..DIM {
+                if id & (1 << i) == 0 {
+                    self.line(pos, &corner(id), &corner(id | (1 << i)));
+                }
+            }
+        }
+    }
+
+    // Draws a circle in the plane spanned by the orthonormal vectors `u` and `v`.
+    fn circle(
+        &mut self,
+        pos: &Isometry<Real>,
+        center: &Point<Real>,
+        (u, v): (&Vector<Real>, &Vector<Real>),
+        radius: Real,
+    ) {
+        let n = self.subdivisions;
+        let vertex = |i: usize| {
+            let angle = std::f64::consts::PI as Real * 2.0 * i as Real / n as Real;
+            center + (u * angle.cos() + v * angle.sin()) * radius
+        };
+
+        for i in 0..n {
+            self.line(pos, &vertex(i), &vertex(i + 1));
+        }
+    }
+
+    // Draws a ball as circles in the planes containing `axis`, and (in 3D) the plane orthogonal to it.
+    fn ball(
+        &mut self,
+        pos: &Isometry<Real>,
+        center: &Point<Real>,
+        axis: &Vector<Real>,
+        radius: Real,
+    ) {
+        let dirs = orthogonal_directions(axis);
+
+        for dir in dirs.iter() {
+            self.circle(pos, center, (axis, dir), radius);
+        }
+
+        #[cfg(feature = "dim3")]
+        self.circle(pos, center, (&dirs[0], &dirs[1]), radius);
+    }
+
+    fn capsule(&mut self, pos: &Isometry<Real>, a: &Point<Real>, b: &Point<Real>, radius: Real) {
+        let axis = (b - a)
+            .try_normalize(Real::EPSILON)
+            .unwrap_or_else(Vector::y);
+        self.ball(pos, a, &axis, radius);
+        self.ball(pos, b, &axis, radius);
+
+        for dir in orthogonal_directions(&axis).iter() {
+            for side in [*dir, -dir].iter() {
+                self.line(pos, &(a + side * radius), &(b + side * radius));
+            }
+        }
+    }
+
+    // Draws a cylinder-like shape aligned with the `y` axis, with the given bottom and top radii.
+    #[cfg(feature = "dim3")]
+    fn frustum(
+        &mut self,
+        pos: &Isometry<Real>,
+        half_height: Real,
+        bottom_radius: Real,
+        top_radius: Real,
+    ) {
+        let bottom = Point::new(0.0, -half_height, 0.0);
+        let top = Point::new(0.0, half_height, 0.0);
+        let (x, z) = (Vector::x(), Vector::z());
+        self.circle(pos, &bottom, (&x, &z), bottom_radius);
+        self.circle(pos, &top, (&x, &z), top_radius);
+
+        for dir in [x, z, -x, -z].iter() {
+            self.line(
+                pos,
+                &(bottom + dir * bottom_radius),
+                &(top + dir * top_radius),
+            );
+        }
+    }
+}
+
+// Unit vectors orthogonal to the unit vector `axis` and to each other.
+#[cfg(feature = "dim2")]
+fn orthogonal_directions(axis: &Vector<Real>) -> [Vector<Real>; 1] {
+    [Vector::new(-axis.y, axis.x)]
+}
+
+#[cfg(feature = "dim3")]
+fn orthogonal_directions(axis: &Vector<Real>) -> [Vector<Real>; 2] {
+    let other = if axis.x.abs() < 0.9 {
+        Vector::x()
+    } else {
+        Vector::y()
+    };
+    let u = axis.cross(&other).normalize();
+    [u, axis.cross(&u)]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DebugColor, DebugRenderBackend, DebugRenderMode, DebugRenderObject};
+    use super::{DebugRenderPipeline, DebugRenderStyle};
+    use crate::math::{Point, Real, DIM};
+
+    #[derive(Default)]
+    struct LineCollector {
+        lines: Vec<(DebugRenderObject, Point<Real>, Point<Real>)>,
+    }
+
+    impl DebugRenderBackend for LineCollector {
+        fn draw_line(
+            &mut self,
+            object: DebugRenderObject,
+            a: Point<Real>,
+            b: Point<Real>,
+            _color: DebugColor,
+        ) {
+            self.lines.push((object, a, b));
+        }
+    }
+
+    #[test]
+    fn debug_render_all_elements() {
+        use crate::dynamics::{BallJoint, RigidBodyBuilder};
+        use crate::geometry::ColliderBuilder;
+        use crate::math::Vector;
+        use crate::pipeline::PhysicsWorld;
+
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        #[cfg(feature = "dim2")]
+        let ground_collider = ColliderBuilder::cuboid(10.0, 0.5).build();
+        #[cfg(feature = "dim3")]
+        let ground_collider = ColliderBuilder::cuboid(10.0, 0.5, 10.0).build();
+        world
+            .colliders
+            .insert(ground_collider, ground, &mut world.bodies);
+
+        #[cfg(feature = "dim2")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.95);
+        #[cfg(feature = "dim3")]
+        let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.95, 0.0);
+        let ball = world.bodies.insert(rb.build());
+        let ball_collider = ColliderBuilder::ball(0.5).build();
+        let ball_collider = world
+            .colliders
+            .insert(ball_collider, ball, &mut world.bodies);
+
+        let joint = BallJoint::new(Point::origin(), Point::origin());
+        let joint = world.joints.insert(&mut world.bodies, ground, ball, joint);
+
+        world.step(1.0 / 60.0);
+
+        let pipeline =
+            DebugRenderPipeline::new(DebugRenderStyle::default(), DebugRenderMode::all());
+        let mut backend = LineCollector::default();
+        pipeline.render(
+            &mut backend,
+            &world.bodies,
+            &world.colliders,
+            &world.joints,
+            &world.narrow_phase,
+        );
+
+        let count = |object: DebugRenderObject| {
+            backend
+                .lines
+                .iter()
+                .filter(|(obj, ..)| *obj == object)
+                .count()
+        };
+
+        // One line per axis, and per edge of a box.
+        assert_eq!(count(DebugRenderObject::RigidBody(ball)), DIM);
+        assert_eq!(
+            count(DebugRenderObject::ColliderAABB(ball_collider)),
+            DIM * (1 << (DIM - 1))
+        );
+        assert!(count(DebugRenderObject::Collider(ball_collider)) > 0);
+        assert_eq!(count(DebugRenderObject::Joint(joint)), 3);
+        assert!(count(DebugRenderObject::Island(0)) > 0);
+
+        // The ball rests on the ground, so the contact normals are vertical.
+        let normal_length = pipeline.style.contact_normal_length;
+        assert!(backend.lines.iter().any(|(obj, a, b)| {
+            matches!(obj, DebugRenderObject::ContactPair(..))
+                && ((b.y - a.y).abs() - normal_length).abs() < 1.0e-3
+        }));
+
+        // Nothing is rendered if no element is selected.
+        let pipeline =
+            DebugRenderPipeline::new(DebugRenderStyle::default(), DebugRenderMode::empty());
+        let mut backend = LineCollector::default();
+        pipeline.render(
+            &mut backend,
+            &world.bodies,
+            &world.colliders,
+            &world.joints,
+            &world.narrow_phase,
+        );
+        assert!(backend.lines.is_empty());
+    }
+}
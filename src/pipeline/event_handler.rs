@@ -1,4 +1,7 @@
-use crate::geometry::{ContactEvent, IntersectionEvent};
+use crate::dynamics::{CcdEvent, JointLimitReached, SleepEvent};
+use crate::geometry::{
+    BroadPhasePairEvent, ContactEvent, ContactForceEvent, DetailedContactEvent, IntersectionEvent,
+};
 use crossbeam::channel::Sender;
 
 /// Trait implemented by structures responsible for handling events generated by the physics engine.
@@ -14,6 +17,40 @@ pub trait EventHandler: Send + Sync {
     /// A contact event is emitted when two collider start or stop touching, independently from the
     /// number of contact points involved.
     fn handle_contact_event(&self, event: ContactEvent);
+    /// Handle a detailed contact event.
+    ///
+    /// A detailed contact event is emitted right after each contact event involving a collider
+    /// with detailed contact events enabled (see `Collider::enable_detailed_contact_events`),
+    /// and carries the contact points of the contact pair at the time of the event. This avoids
+    /// looking up the contact pair in the narrow-phase, where it may no longer exist.
+    fn handle_detailed_contact_event(&self, _event: DetailedContactEvent) {}
+    /// Handle a contact force event.
+    ///
+    /// A contact force event is emitted after each resolution of the velocity constraints, for
+    /// each contact pair where the total contact force exceeds the contact force event threshold
+    /// of one of its colliders.
+    fn handle_contact_force_event(&self, _event: ContactForceEvent) {}
+    /// Handle a sleep event.
+    ///
+    /// A sleep event is emitted when a dynamic rigid-body falls asleep or wakes up.
+    fn handle_sleep_event(&self, _event: SleepEvent) {}
+    /// Handle a CCD event.
+    ///
+    /// A CCD event is emitted when the CCD solver clamps the motion of a rigid-body
+    /// because of a time-of-impact with another collider.
+    fn handle_ccd_event(&self, _event: CcdEvent) {}
+    /// Handle a broad-phase pair event.
+    ///
+    /// A broad-phase pair event is emitted when the broad-phase starts or stops reporting two
+    /// colliders with overlapping AABBs. These events are emitted before the narrow-phase runs,
+    /// and before any collision filtering, so they can be used as inputs for custom
+    /// narrow-phases or proximity logic.
+    fn handle_broad_phase_pair_event(&self, _event: BroadPhasePairEvent) {}
+    /// Handle a joint limit event.
+    ///
+    /// A joint limit event is emitted at the end of a timestep when a joint with
+    /// enabled limits starts being pressed against one of them.
+    fn handle_joint_limit_event(&self, _event: JointLimitReached) {}
 }
 
 impl EventHandler for () {
@@ -25,6 +62,12 @@ impl EventHandler for () {
 pub struct ChannelEventCollector {
     intersection_event_sender: Sender<IntersectionEvent>,
     contact_event_sender: Sender<ContactEvent>,
+    detailed_contact_event_sender: Option<Sender<DetailedContactEvent>>,
+    contact_force_event_sender: Option<Sender<ContactForceEvent>>,
+    sleep_event_sender: Option<Sender<SleepEvent>>,
+    ccd_event_sender: Option<Sender<CcdEvent>>,
+    broad_phase_pair_event_sender: Option<Sender<BroadPhasePairEvent>>,
+    joint_limit_event_sender: Option<Sender<JointLimitReached>>,
 }
 
 impl ChannelEventCollector {
@@ -36,8 +79,74 @@ impl ChannelEventCollector {
         Self {
             intersection_event_sender,
             contact_event_sender,
+            detailed_contact_event_sender: None,
+            contact_force_event_sender: None,
+            sleep_event_sender: None,
+            ccd_event_sender: None,
+            broad_phase_pair_event_sender: None,
+            joint_limit_event_sender: None,
         }
     }
+
+    /// Sets the crossbeam channel sender where detailed contact events will be sent.
+    ///
+    /// Detailed contact events are ignored if no sender is set.
+    pub fn with_detailed_contact_event_sender(
+        mut self,
+        detailed_contact_event_sender: Sender<DetailedContactEvent>,
+    ) -> Self {
+        self.detailed_contact_event_sender = Some(detailed_contact_event_sender);
+        self
+    }
+
+    /// Sets the crossbeam channel sender where contact force events will be sent.
+    ///
+    /// Contact force events are ignored if no sender is set.
+    pub fn with_contact_force_event_sender(
+        mut self,
+        contact_force_event_sender: Sender<ContactForceEvent>,
+    ) -> Self {
+        self.contact_force_event_sender = Some(contact_force_event_sender);
+        self
+    }
+
+    /// Sets the crossbeam channel sender where sleep events will be sent.
+    ///
+    /// Sleep events are ignored if no sender is set.
+    pub fn with_sleep_event_sender(mut self, sleep_event_sender: Sender<SleepEvent>) -> Self {
+        self.sleep_event_sender = Some(sleep_event_sender);
+        self
+    }
+
+    /// Sets the crossbeam channel sender where CCD events will be sent.
+    ///
+    /// CCD events are ignored if no sender is set.
+    pub fn with_ccd_event_sender(mut self, ccd_event_sender: Sender<CcdEvent>) -> Self {
+        self.ccd_event_sender = Some(ccd_event_sender);
+        self
+    }
+
+    /// Sets the crossbeam channel sender where broad-phase pair events will be sent.
+    ///
+    /// Broad-phase pair events are ignored if no sender is set.
+    pub fn with_broad_phase_pair_event_sender(
+        mut self,
+        broad_phase_pair_event_sender: Sender<BroadPhasePairEvent>,
+    ) -> Self {
+        self.broad_phase_pair_event_sender = Some(broad_phase_pair_event_sender);
+        self
+    }
+
+    /// Sets the crossbeam channel sender where joint limit events will be sent.
+    ///
+    /// Joint limit events are ignored if no sender is set.
+    pub fn with_joint_limit_event_sender(
+        mut self,
+        joint_limit_event_sender: Sender<JointLimitReached>,
+    ) -> Self {
+        self.joint_limit_event_sender = Some(joint_limit_event_sender);
+        self
+    }
 }
 
 impl EventHandler for ChannelEventCollector {
@@ -48,4 +157,40 @@ impl EventHandler for ChannelEventCollector {
     fn handle_contact_event(&self, event: ContactEvent) {
         let _ = self.contact_event_sender.send(event);
     }
+
+    fn handle_detailed_contact_event(&self, event: DetailedContactEvent) {
+        if let Some(sender) = &self.detailed_contact_event_sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn handle_contact_force_event(&self, event: ContactForceEvent) {
+        if let Some(sender) = &self.contact_force_event_sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn handle_sleep_event(&self, event: SleepEvent) {
+        if let Some(sender) = &self.sleep_event_sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn handle_ccd_event(&self, event: CcdEvent) {
+        if let Some(sender) = &self.ccd_event_sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn handle_broad_phase_pair_event(&self, event: BroadPhasePairEvent) {
+        if let Some(sender) = &self.broad_phase_pair_event_sender {
+            let _ = sender.send(event);
+        }
+    }
+
+    fn handle_joint_limit_event(&self, event: JointLimitReached) {
+        if let Some(sender) = &self.joint_limit_event_sender {
+            let _ = sender.send(event);
+        }
+    }
 }
@@ -0,0 +1,192 @@
+use crate::dynamics::{RigidBodyHandle, RigidBodySet};
+use crate::geometry::{BroadPhaseBackend, ColliderHandle, ColliderSet, InteractionGroups, AABB};
+use crate::math::{Point, Real, Vector};
+use parry::query::PointQuery;
+use std::cmp::Ordering;
+
+/// How the impulse of an explosion decreases with the distance to its center.
+#[derive(Copy, Clone, Debug)]
+pub enum ExplosionFalloff {
+    /// The impulse is the same everywhere within the radius of the explosion.
+    Constant,
+    /// The impulse decreases linearly, down to zero at the radius of the explosion.
+    Linear,
+    /// The impulse decreases quadratically, down to zero at the radius of the explosion.
+    Quadratic,
+    /// The impulse is scaled by the given function of the distance to the center divided by
+    /// the radius of the explosion, which is between 0 and 1.
+    Custom(fn(Real) -> Real),
+}
+
+impl ExplosionFalloff {
+    /// The factor applied to the impulse at the given distance divided by the explosion radius.
+    pub fn factor(&self, normalized_distance: Real) -> Real {
+        match self {
+            Self::Constant => 1.0,
+            Self::Linear => 1.0 - normalized_distance,
+            Self::Quadratic => (1.0 - normalized_distance) * (1.0 - normalized_distance),
+            Self::Custom(f) => f(normalized_distance),
+        }
+    }
+}
+
+/// An explosion, pushing away every dynamic rigid-body within a given radius.
+///
+/// Each rigid-body with a collider within the radius of the explosion receives an impulse
+/// pointing away from the center of the explosion, applied at the point of its colliders
+/// closest to the center. This gives a rotation to the rigid-bodies not centered on the
+/// explosion. The magnitude of the impulse is scaled by the `falloff` depending on the
+/// distance between the center and that closest point.
+#[derive(Copy, Clone, Debug)]
+pub struct Explosion {
+    /// The center of the explosion.
+    pub center: Point<Real>,
+    /// The distance beyond which the explosion has no effect.
+    pub radius: Real,
+    /// The magnitude of the impulse applied to a rigid-body touching the center.
+    pub impulse: Real,
+    /// How the impulse decreases with the distance to the center.
+    pub falloff: ExplosionFalloff,
+    /// The interaction groups tested against the collision groups of the colliders to
+    /// determine if they are affected by the explosion.
+    pub query_groups: InteractionGroups,
+}
+
+impl Explosion {
+    /// Creates an explosion affecting every collider within `radius` of `center`, with an
+    /// impulse decreasing linearly from `impulse` at its center.
+    pub fn new(center: Point<Real>, radius: Real, impulse: Real) -> Self {
+        Self {
+            center,
+            radius,
+            impulse,
+            falloff: ExplosionFalloff::Linear,
+            query_groups: InteractionGroups::all(),
+        }
+    }
+
+    /// Applies the impulses of this explosion to the affected rigid-bodies, and wakes them up.
+    ///
+    /// The affected colliders are found with the broad-phase, so colliders inserted or moved
+    /// since the last timestep are taken into account with their previous AABB, if any.
+    /// Sensors are ignored. Returns the number of rigid-bodies affected.
+    pub fn apply(
+        &self,
+        broad_phase: &dyn BroadPhaseBackend,
+        bodies: &mut RigidBodySet,
+        colliders: &ColliderSet,
+    ) -> usize {
+        let aabb = AABB::from_half_extents(self.center, Vector::repeat(self.radius));
+        let mut candidates: Vec<ColliderHandle> = vec![];
+        broad_phase.colliders_with_aabb_intersecting_aabb(&aabb, &mut |handle| {
+            candidates.push(*handle);
+            true
+        });
+
+        // The point closest to the center of each affected rigid-body.
+        let mut hits: Vec<(RigidBodyHandle, Point<Real>, Real)> = vec![];
+
+        for handle in candidates {
+            let collider = match colliders.get(handle) {
+                Some(collider) => collider,
+                None => continue,
+            };
+
+            if collider.is_sensor() || !collider.collision_groups().test(self.query_groups) {
+                continue;
+            }
+
+            match bodies.get(collider.parent()) {
+                Some(rb) if rb.is_dynamic() => {}
+                _ => continue,
+            }
+
+            let proj = collider
+                .shape()
+                .project_point(collider.position(), &self.center, true);
+            let distance = (proj.point - self.center).norm();
+
+            // NOTE: this also rejects the NaN distances, e.g., of degenerate shapes.
+            if distance <= self.radius {
+                hits.push((collider.parent(), proj.point, distance));
+            }
+        }
+
+        // Keep only the closest hit of each rigid-body, in a deterministic order.
+        hits.sort_by(|a, b| {
+            a.0.into_raw_parts()
+                .cmp(&b.0.into_raw_parts())
+                .then(a.2.partial_cmp(&b.2).unwrap_or(Ordering::Equal))
+        });
+        hits.dedup_by_key(|hit| hit.0);
+
+        for (handle, point, distance) in &hits {
+            let rb = match bodies.get_mut_internal_with_modification_tracking(*handle) {
+                Some(rb) => rb,
+                None => continue,
+            };
+
+            // Push along the direction from the center to the closest point, or to the
+            // center-of-mass if the center is inside the collider.
+            let dir = if *distance > Real::EPSILON {
+                (*point - self.center) / *distance
+            } else {
+                match (rb.world_com - self.center).try_normalize(Real::EPSILON) {
+                    Some(dir) => dir,
+                    None => continue,
+                }
+            };
+
+            let magnitude = self.impulse * self.falloff.factor(distance / self.radius);
+            rb.apply_impulse_at_point(dir * magnitude, *point, true);
+        }
+
+        hits.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Explosion, ExplosionFalloff};
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Point, Real, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn explosion_pushes_bodies_away() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        let distances: [Real; 3] = [2.0, 4.0, 10.0];
+        let handles: Vec<_> = distances
+            .iter()
+            .map(|x| {
+                #[cfg(feature = "dim2")]
+                let rb = RigidBodyBuilder::new_dynamic().translation(*x, 0.0);
+                #[cfg(feature = "dim3")]
+                let rb = RigidBodyBuilder::new_dynamic().translation(*x, 0.0, 0.0);
+                let handle = world.bodies.insert(rb.build());
+                world.colliders.insert(
+                    ColliderBuilder::ball(0.5).build(),
+                    handle,
+                    &mut world.bodies,
+                );
+                handle
+            })
+            .collect();
+
+        // Let the broad-phase know about the colliders.
+        world.step(1.0 / 60.0);
+
+        let mut explosion = Explosion::new(Point::origin(), 5.5, 10.0);
+        explosion.falloff = ExplosionFalloff::Linear;
+        let num_affected = explosion.apply(&world.broad_phase, &mut world.bodies, &world.colliders);
+        assert_eq!(num_affected, 2);
+
+        let vel = |i: usize| world.bodies[handles[i]].linvel().x;
+        let mass = world.bodies[handles[0]].mass();
+        // The closest points are at distances 1.5 and 3.5 from the center.
+        assert!((vel(0) - 10.0 * (1.0 - 1.5 / 5.5) / mass).abs() < 1.0e-4);
+        assert!((vel(1) - 10.0 * (1.0 - 3.5 / 5.5) / mass).abs() < 1.0e-4);
+        assert_eq!(vel(2), 0.0);
+    }
+}
@@ -1,15 +1,41 @@
 //! Structure for combining the various physics components to perform an actual simulation.
 
 pub use collision_pipeline::CollisionPipeline;
+pub use debug_render_pipeline::{
+    DebugColor, DebugRenderBackend, DebugRenderMode, DebugRenderObject, DebugRenderPipeline,
+    DebugRenderStyle,
+};
 pub use event_handler::{ChannelEventCollector, EventHandler};
+pub use explosion::{Explosion, ExplosionFalloff};
+pub use multi_world_stepper::{MultiWorldStepper, PhysicsWorld};
+pub use origin_shift::shift_origin;
 pub use physics_hooks::{
     ContactModificationContext, PairFilterContext, PhysicsHooks, PhysicsHooksFlags,
+    SolverContactInspectionContext,
 };
 pub use physics_pipeline::PhysicsPipeline;
+pub use physics_snapshot::PhysicsSnapshot;
+#[cfg(feature = "serde-serialize")]
+pub use physics_snapshot::{PhysicsSnapshotError, PHYSICS_SNAPSHOT_VERSION};
 pub use query_pipeline::{QueryPipeline, QueryPipelineMode};
+pub use rollback_buffer::RollbackBuffer;
+pub use state_checksum::state_checksum;
+pub use timestep_manager::TimestepManager;
+pub use world_view::PhysicsWorldView;
+
+pub(crate) use physics_hooks::update_oneway_contacts;
 
 mod collision_pipeline;
+mod debug_render_pipeline;
 mod event_handler;
+mod explosion;
+mod multi_world_stepper;
+mod origin_shift;
 mod physics_hooks;
 mod physics_pipeline;
+mod physics_snapshot;
 mod query_pipeline;
+mod rollback_buffer;
+mod state_checksum;
+mod timestep_manager;
+mod world_view;
@@ -0,0 +1,236 @@
+use crate::dynamics::{
+    CCDSolver, ForceGeneratorSet, IntegrationParameters, JointSet, RigidBodySet,
+};
+use crate::geometry::{BroadPhase, ColliderSet, NarrowPhase};
+use crate::math::{Real, Vector};
+use crate::pipeline::{
+    shift_origin, state_checksum, EventHandler, PhysicsHooks, PhysicsPipeline, PhysicsWorldView,
+};
+#[cfg(feature = "parallel")]
+use std::sync::Arc;
+
+/// An independent physics world, grouping everything needed to step its simulation.
+pub struct PhysicsWorld {
+    /// The gravity applied to the dynamic rigid-bodies of this world.
+    pub gravity: Vector<Real>,
+    /// The integration parameters used for stepping this world.
+    pub integration_parameters: IntegrationParameters,
+    /// The physics pipeline used for stepping this world.
+    pub pipeline: PhysicsPipeline,
+    /// The broad-phase of this world.
+    pub broad_phase: BroadPhase,
+    /// The narrow-phase of this world.
+    pub narrow_phase: NarrowPhase,
+    /// The rigid-bodies of this world.
+    pub bodies: RigidBodySet,
+    /// The colliders of this world.
+    pub colliders: ColliderSet,
+    /// The joints of this world.
+    pub joints: JointSet,
+    /// The CCD solver of this world.
+    pub ccd_solver: CCDSolver,
+    /// The force generators applied before each timestep of this world.
+    pub force_generators: ForceGeneratorSet,
+    /// The physics hooks called while stepping this world.
+    pub hooks: Box<dyn PhysicsHooks>,
+    /// The event handler notified of the events generated by this world.
+    pub events: Box<dyn EventHandler>,
+}
+
+impl PhysicsWorld {
+    /// Creates an empty physics world with the given gravity.
+    pub fn new(gravity: Vector<Real>) -> Self {
+        Self {
+            gravity,
+            integration_parameters: IntegrationParameters::default(),
+            pipeline: PhysicsPipeline::new(),
+            broad_phase: BroadPhase::new(),
+            narrow_phase: NarrowPhase::new(),
+            bodies: RigidBodySet::new(),
+            colliders: ColliderSet::new(),
+            joints: JointSet::new(),
+            ccd_solver: CCDSolver::new(),
+            force_generators: ForceGeneratorSet::new(),
+            hooks: Box::new(()),
+            events: Box::new(()),
+        }
+    }
+
+    /// Executes one timestep of length `dt` of this world.
+    pub fn step(&mut self, dt: Real) {
+        self.force_generators
+            .apply(dt, &mut self.bodies, &self.colliders);
+        self.pipeline.step(
+            &self.gravity,
+            dt,
+            &self.integration_parameters,
+            &mut self.broad_phase,
+            &mut self.narrow_phase,
+            &mut self.bodies,
+            &mut self.colliders,
+            &mut self.joints,
+            &mut self.ccd_solver,
+            &*self.hooks,
+            &*self.events,
+        );
+    }
+
+    /// Translates the whole world by `shift`, keeping every relative position unchanged.
+    ///
+    /// This also translates the world-space points of the force generators. See `shift_origin`
+    /// for details.
+    pub fn shift_origin(&mut self, shift: &Vector<Real>) {
+        shift_origin(
+            shift,
+            &mut self.broad_phase,
+            &mut self.bodies,
+            &mut self.colliders,
+            &mut self.joints,
+        );
+        self.force_generators.shift_origin(shift);
+    }
+
+    /// A checksum of the state of this world evolving during the simulation.
+    ///
+    /// See `state_checksum` for details.
+    pub fn checksum(&self) -> u64 {
+        state_checksum(&self.bodies, &self.joints, &self.narrow_phase)
+    }
+
+    /// A read-only view of this world.
+    pub fn view(&self) -> PhysicsWorldView {
+        PhysicsWorldView::new(
+            &self.bodies,
+            &self.colliders,
+            &self.joints,
+            &self.narrow_phase,
+        )
+    }
+}
+
+/// Steps several independent physics worlds at once.
+///
+/// With the `parallel` feature enabled, all the worlds are stepped on the same rayon
+/// thread pool. Since each world is itself stepped with rayon, idle threads steal the
+/// islands of any world still being simulated, so many small worlds (e.g. one per match
+/// hosted by a game server) make a good use of all the cores. Without the `parallel`
+/// feature, the worlds are stepped sequentially.
+#[derive(Clone, Default)]
+pub struct MultiWorldStepper {
+    #[cfg(feature = "parallel")]
+    thread_pool: Option<Arc<rayon::ThreadPool>>,
+}
+
+impl MultiWorldStepper {
+    /// Creates a stepper running on the global rayon thread pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a stepper running on the given rayon thread pool.
+    #[cfg(feature = "parallel")]
+    pub fn with_thread_pool(thread_pool: Arc<rayon::ThreadPool>) -> Self {
+        Self {
+            thread_pool: Some(thread_pool),
+        }
+    }
+
+    /// Executes one timestep of length `dt` of each of the given worlds.
+    pub fn step(&self, dt: Real, worlds: &mut [PhysicsWorld]) {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            let step_all = || worlds.par_iter_mut().for_each(|world| world.step(dt));
+
+            if let Some(thread_pool) = &self.thread_pool {
+                thread_pool.install(step_all)
+            } else {
+                step_all()
+            }
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            for world in worlds {
+                world.step(dt)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MultiWorldStepper, PhysicsWorld};
+    use crate::dynamics::{RigidBodyBuilder, RigidBodyHandle};
+    use crate::geometry::{ColliderBuilder, ColliderHandle};
+    use crate::math::{Isometry, Real, Vector};
+
+    // Helpers shared by the tests simulating a small scene.
+    impl PhysicsWorld {
+        /// Executes `num_steps` timesteps of length `1.0 / 60.0`.
+        pub(crate) fn run(&mut self, num_steps: usize) {
+            for _ in 0..num_steps {
+                self.step(1.0 / 60.0);
+            }
+        }
+
+        /// Inserts a static ground: a half-space passing through the origin, with an upward normal.
+        pub(crate) fn insert_ground(&mut self) -> ColliderHandle {
+            let ground = self.bodies.insert(RigidBodyBuilder::new_static().build());
+            self.colliders.insert(
+                ColliderBuilder::halfspace(Vector::y_axis()).build(),
+                ground,
+                &mut self.bodies,
+            )
+        }
+
+        /// Inserts a dynamic rigid-body at the given location, with a single collider.
+        pub(crate) fn insert_dynamic(
+            &mut self,
+            translation: Vector<Real>,
+            collider: ColliderBuilder,
+        ) -> (RigidBodyHandle, ColliderHandle) {
+            let body = self.bodies.insert(
+                RigidBodyBuilder::new_dynamic()
+                    .position(Isometry::new(translation, na::zero()))
+                    .build(),
+            );
+            let collider = self
+                .colliders
+                .insert(collider.build(), body, &mut self.bodies);
+            (body, collider)
+        }
+    }
+
+    #[test]
+    fn multi_world_step() {
+        let (mut worlds, handles): (Vec<_>, Vec<_>) = (0..4)
+            .map(|i| {
+                let mut world = PhysicsWorld::new(Vector::y() * -(i as Real + 1.0));
+                let handle = world.bodies.insert(RigidBodyBuilder::new_dynamic().build());
+                world.colliders.insert(
+                    ColliderBuilder::ball(0.5).build(),
+                    handle,
+                    &mut world.bodies,
+                );
+                (world, handle)
+            })
+            .unzip();
+
+        let stepper = MultiWorldStepper::new();
+        for _ in 0..10 {
+            stepper.step(1.0 / 60.0, &mut worlds);
+        }
+
+        // Each world has its own gravity, so the bodies fell by different amounts.
+        for i in 0..3 {
+            let y1 = worlds[i].bodies[handles[i]].position().translation.y;
+            let y2 = worlds[i + 1].bodies[handles[i + 1]]
+                .position()
+                .translation
+                .y;
+            assert!(y1 < 0.0 && y2 < y1);
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::dynamics::{JointParams, JointSet, RigidBodySet};
+use crate::geometry::{BroadPhaseBackend, ColliderSet};
+use crate::math::{Real, Vector};
+
+/// Translates the whole simulation by `shift`, keeping every relative position unchanged.
+///
+/// The rigid-bodies, the colliders, the world-space targets of the mouse joints, and the
+/// broad-phase data are all translated at once. The contacts don't depend on the origin, so they
+/// are kept as-is along with their warm-start impulses, and no pair event is generated: the
+/// simulation continues exactly as if nothing happened, except for the rounding of positions.
+///
+/// Simulations with huge coordinates, like space or flight simulators, lose precision far from
+/// the origin. Shifting the origin to the location of the player from time to time keeps the
+/// coordinates of the interesting part of the world small. Using the `f64` feature, enabled by
+/// the `rapier2d-f64` and `rapier3d-f64` crates, further extends the range of coordinates usable
+/// without noticeable loss of precision.
+///
+/// This must be called in-between two timesteps. Data owned by the user must be translated
+/// separately, e.g., by updating the `QueryPipeline` and moving the world-space points of gravity
+/// fields. The rigid-bodies and colliders are not marked as modified, so they are not reported by
+/// `RigidBodySet::modified_bodies`.
+pub fn shift_origin(
+    shift: &Vector<Real>,
+    broad_phase: &mut dyn BroadPhaseBackend,
+    bodies: &mut RigidBodySet,
+    colliders: &mut ColliderSet,
+    joints: &mut JointSet,
+) {
+    bodies.shift_origin(shift);
+    colliders.shift_origin(shift);
+    broad_phase.shift_origin(shift, colliders);
+
+    for (_, joint) in joints.iter_mut() {
+        if let JointParams::MouseJoint(joint) = &mut joint.params {
+            joint.target += *shift;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::{CCDSolver, IntegrationParameters, RigidBodyBuilder};
+    use crate::geometry::{BvhBroadPhase, ColliderBuilder, NarrowPhase};
+    use crate::math::{Real, Vector};
+    use crate::pipeline::{PhysicsPipeline, PhysicsWorld};
+
+    fn stacked_world() -> PhysicsWorld {
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let ground_collider = ColliderBuilder::halfspace(Vector::y_axis()).build();
+        world
+            .colliders
+            .insert(ground_collider, ground, &mut world.bodies);
+
+        for i in 0..3 {
+            #[cfg(feature = "dim2")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.5 + i as Real);
+            #[cfg(feature = "dim3")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.5 + i as Real, 0.0);
+            let handle = world.bodies.insert(rb.build());
+            #[cfg(feature = "dim2")]
+            let collider = ColliderBuilder::cuboid(0.5, 0.5).build();
+            #[cfg(feature = "dim3")]
+            let collider = ColliderBuilder::cuboid(0.5, 0.5, 0.5).build();
+            world.colliders.insert(collider, handle, &mut world.bodies);
+        }
+
+        world
+    }
+
+    #[test]
+    fn shifted_world_keeps_simulating_identically() {
+        let shift = Vector::x() * 1000.0;
+        let mut world = stacked_world();
+        let mut shifted_world = stacked_world();
+
+        for _ in 0..30 {
+            world.step(1.0 / 60.0);
+            shifted_world.step(1.0 / 60.0);
+        }
+
+        shifted_world.shift_origin(&shift);
+        let num_pairs = shifted_world.narrow_phase.contact_pairs().count();
+
+        for _ in 0..30 {
+            world.step(1.0 / 60.0);
+            shifted_world.step(1.0 / 60.0);
+            let shifted_num_pairs = shifted_world.narrow_phase.contact_pairs().count();
+            assert_eq!(shifted_num_pairs, num_pairs);
+        }
+
+        for ((_, rb), (_, shifted_rb)) in world.bodies.iter().zip(shifted_world.bodies.iter()) {
+            let delta =
+                shifted_rb.position().translation.vector - rb.position().translation.vector - shift;
+            assert!(delta.norm() < 1.0e-3);
+        }
+    }
+
+    #[test]
+    fn shift_origin_with_bvh_broad_phase() {
+        let mut world = stacked_world();
+        let mut pipeline = PhysicsPipeline::new();
+        let mut broad_phase = BvhBroadPhase::new();
+        let mut narrow_phase = NarrowPhase::new();
+        let mut ccd = CCDSolver::new();
+        let mut step = |world: &mut PhysicsWorld, broad_phase: &mut BvhBroadPhase| {
+            pipeline.step(
+                &world.gravity,
+                1.0 / 60.0,
+                &IntegrationParameters::default(),
+                broad_phase,
+                &mut narrow_phase,
+                &mut world.bodies,
+                &mut world.colliders,
+                &mut world.joints,
+                &mut ccd,
+                &(),
+                &(),
+            );
+        };
+
+        for _ in 0..30 {
+            step(&mut world, &mut broad_phase);
+        }
+
+        let heights: Vec<_> = world
+            .bodies
+            .iter()
+            .map(|(_, rb)| rb.position().translation.y)
+            .collect();
+        super::shift_origin(
+            &(Vector::x() * -1000.0),
+            &mut broad_phase,
+            &mut world.bodies,
+            &mut world.colliders,
+            &mut world.joints,
+        );
+
+        for _ in 0..30 {
+            step(&mut world, &mut broad_phase);
+        }
+
+        // The stack still rests on the ground.
+        for ((_, rb), height) in world.bodies.iter().zip(heights.iter()) {
+            assert!((rb.position().translation.x + 1000.0).abs() < 1.0e-3);
+            assert!((rb.position().translation.y - height).abs() < 1.0e-2);
+        }
+    }
+}
@@ -45,6 +45,17 @@ pub struct ContactModificationContext<'a> {
     pub user_data: &'a mut u32,
 }
 
+/// Context given to custom solver contact inspectors right before the constraints solver runs.
+pub struct SolverContactInspectionContext<'a> {
+    /// The first collider involved in the contact.
+    pub collider_handle1: ColliderHandle,
+    /// The second collider involved in the contact.
+    pub collider_handle2: ColliderHandle,
+    /// The contact manifold, including the solver contacts that will be seen by the
+    /// constraints solver, after they were modified by the contact modification hooks.
+    pub manifold: &'a ContactManifold,
+}
+
 impl<'a> ContactModificationContext<'a> {
     /// Helper function to update `self` to emulate a oneway-platform.
     ///
@@ -61,57 +72,96 @@ impl<'a> ContactModificationContext<'a> {
         allowed_local_n1: &Vector<Real>,
         allowed_angle: Real,
     ) {
-        const CONTACT_CONFIGURATION_UNKNOWN: u32 = 0;
-        const CONTACT_CURRENTLY_ALLOWED: u32 = 1;
-        const CONTACT_CURRENTLY_FORBIDDEN: u32 = 2;
+        update_oneway_contacts(
+            &self.manifold.local_n1,
+            allowed_local_n1,
+            allowed_angle,
+            self.solver_contacts,
+            self.user_data,
+        )
+    }
+
+    /// Helper function to update `self` to emulate a conveyor belt.
+    ///
+    /// The surface of the first collider involved in the contact will behave as if it
+    /// was moving with the velocity `belt_local_velocity1`, expressed in the local-space
+    /// of this collider. The component of this velocity along the contact normal is ignored.
+    ///
+    /// To make this method work properly it must be called as part of the
+    /// `PhysicsHooks::modify_solver_contacts` method at each timestep, for each
+    /// contact manifold involving a conveyor belt.
+    pub fn update_as_conveyor_belt(&mut self, belt_local_velocity1: &Vector<Real>) {
+        let belt_velocity = self.collider1.position() * belt_local_velocity1;
+        let tangent_velocity = belt_velocity - *self.normal * self.normal.dot(&belt_velocity);
 
-        let cang = ComplexField::cos(allowed_angle);
+        for contact in self.solver_contacts.iter_mut() {
+            contact.tangent_velocity = tangent_velocity;
+        }
+    }
+}
 
-        // Test the allowed normal with the local-space contact normal that
-        // points towards the exterior of context.collider1.
-        let contact_is_ok = self.manifold.local_n1.dot(&allowed_local_n1) >= cang;
+/// Removes the solver contacts incompatible with a one-way platform.
+///
+/// Here, `local_n` is the contact normal pointing toward the exterior of the platform, and
+/// `allowed_local_n` the allowed normal, both expressed in the local-space of the platform.
+/// The `state` must be initialized to zero, and persist between timesteps.
+pub(crate) fn update_oneway_contacts(
+    local_n: &Vector<Real>,
+    allowed_local_n: &Vector<Real>,
+    allowed_angle: Real,
+    solver_contacts: &mut Vec<SolverContact>,
+    state: &mut u32,
+) {
+    const CONTACT_CONFIGURATION_UNKNOWN: u32 = 0;
+    const CONTACT_CURRENTLY_ALLOWED: u32 = 1;
+    const CONTACT_CURRENTLY_FORBIDDEN: u32 = 2;
 
-        match *self.user_data {
-            CONTACT_CONFIGURATION_UNKNOWN => {
-                if contact_is_ok {
-                    // The contact is close enough to the allowed normal.
-                    *self.user_data = CONTACT_CURRENTLY_ALLOWED;
-                } else {
-                    // The contact normal isn't close enough to the allowed
-                    // normal, so remove all the contacts and mark further contacts
-                    // as forbidden.
-                    self.solver_contacts.clear();
-
-                    // NOTE: in some very rare cases `local_n1` will be
-                    // zero if the objects are exactly touching at one point.
-                    // So in this case we can't really conclude.
-                    // If the norm is non-zero, then we can tell we need to forbid
-                    // further contacts. Otherwise we have to wait for the next frame.
-                    if self.manifold.local_n1.norm_squared() > 0.1 {
-                        *self.user_data = CONTACT_CURRENTLY_FORBIDDEN;
-                    }
+    let cang = ComplexField::cos(allowed_angle);
+
+    // Test the allowed normal with the local-space contact normal that
+    // points towards the exterior of the platform.
+    let contact_is_ok = local_n.dot(allowed_local_n) >= cang;
+
+    match *state {
+        CONTACT_CONFIGURATION_UNKNOWN => {
+            if contact_is_ok {
+                // The contact is close enough to the allowed normal.
+                *state = CONTACT_CURRENTLY_ALLOWED;
+            } else {
+                // The contact normal isn't close enough to the allowed
+                // normal, so remove all the contacts and mark further contacts
+                // as forbidden.
+                solver_contacts.clear();
+
+                // NOTE: in some very rare cases `local_n` will be
+                // zero if the objects are exactly touching at one point.
+                // So in this case we can't really conclude.
+                // If the norm is non-zero, then we can tell we need to forbid
+                // further contacts. Otherwise we have to wait for the next frame.
+                if local_n.norm_squared() > 0.1 {
+                    *state = CONTACT_CURRENTLY_FORBIDDEN;
                 }
             }
-            CONTACT_CURRENTLY_FORBIDDEN => {
-                // Contacts are forbidden so we need to continue forbidding contacts
-                // until all the contacts are non-penetrating again. In that case, if
-                // the contacts are OK wrt. the contact normal, then we can mark them as allowed.
-                if contact_is_ok && self.solver_contacts.iter().all(|c| c.dist > 0.0) {
-                    *self.user_data = CONTACT_CURRENTLY_ALLOWED;
-                } else {
-                    // Discard all the contacts.
-                    self.solver_contacts.clear();
-                }
+        }
+        CONTACT_CURRENTLY_FORBIDDEN => {
+            // Contacts are forbidden so we need to continue forbidding contacts
+            // until all the contacts are non-penetrating again. In that case, if
+            // the contacts are OK wrt. the contact normal, then we can mark them as allowed.
+            if contact_is_ok && solver_contacts.iter().all(|c| c.dist > 0.0) {
+                *state = CONTACT_CURRENTLY_ALLOWED;
+            } else {
+                // Discard all the contacts.
+                solver_contacts.clear();
             }
-            CONTACT_CURRENTLY_ALLOWED => {
-                // We allow all the contacts right now. The configuration becomes
-                // uncertain again when the contact manifold no longer contains any contact.
-                if self.solver_contacts.is_empty() {
-                    *self.user_data = CONTACT_CONFIGURATION_UNKNOWN;
-                }
+        }
+        CONTACT_CURRENTLY_ALLOWED => {
+            // We allow all the contacts right now. The configuration becomes
+            // uncertain again when the contact manifold no longer contains any contact.
+            if solver_contacts.is_empty() {
+                *state = CONTACT_CONFIGURATION_UNKNOWN;
             }
-            _ => unreachable!(),
         }
+        _ => unreachable!(),
     }
 }
 
@@ -125,6 +175,8 @@ bitflags::bitflags! {
         const FILTER_INTERSECTION_PAIR = 0b0010;
         /// If set, Rapier will call `PhysicsHooks::modify_solver_contact` whenever relevant.
         const MODIFY_SOLVER_CONTACTS = 0b0100;
+        /// If set, Rapier will call `PhysicsHooks::inspect_solver_contacts` whenever relevant.
+        const INSPECT_SOLVER_CONTACTS = 0b1000;
     }
 }
 
@@ -208,6 +260,20 @@ pub trait PhysicsHooks: Send + Sync {
     ///
     /// The world-space contact normal can be modified in `context.normal`.
     fn modify_solver_contacts(&self, _context: &mut ContactModificationContext) {}
+
+    /// Reads the solver contacts right before they are handed to the constraints solver.
+    ///
+    /// Note that this method will only be called if `self.active_hooks()`
+    /// contains the `PhysicsHooksFlags::INSPECT_SOLVER_CONTACTS` flags.
+    ///
+    /// This method is called once per timestep (or CCD substep) on each contact manifold that
+    /// will actually be solved, i.e., manifolds with at least one solver contact, between
+    /// at least one awake dynamic body, and with the `SolverFlags::COMPUTE_IMPULSES` flag set.
+    /// The solver contacts given here are the final inputs of the constraints solver: they
+    /// already take into account the modifications made by `Self::modify_solver_contacts`.
+    ///
+    /// This is typically useful for logging the exact solver inputs for offline analysis.
+    fn inspect_solver_contacts(&self, _context: &SolverContactInspectionContext) {}
 }
 
 impl PhysicsHooks for () {
@@ -224,4 +290,53 @@ impl PhysicsHooks for () {
     }
 
     fn modify_solver_contacts(&self, _: &mut ContactModificationContext) {}
+
+    fn inspect_solver_contacts(&self, _: &SolverContactInspectionContext) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PairFilterContext, PhysicsHooks, PhysicsHooksFlags};
+    use crate::geometry::{ColliderBuilder, SolverFlags};
+    use crate::math::Vector;
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn contact_pair_filter_hook() {
+        // Colliders with the same non-zero team number (in `user_data`) don't collide.
+        struct TeamFilter;
+        impl PhysicsHooks for TeamFilter {
+            fn active_hooks(&self) -> PhysicsHooksFlags {
+                PhysicsHooksFlags::FILTER_CONTACT_PAIR
+            }
+
+            fn filter_contact_pair(&self, context: &PairFilterContext) -> Option<SolverFlags> {
+                let team1 = context.collider1.user_data;
+                let team2 = context.collider2.user_data;
+
+                if team1 != 0 && team1 == team2 {
+                    None
+                } else {
+                    Some(SolverFlags::COMPUTE_IMPULSES)
+                }
+            }
+        }
+
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        world.hooks = Box::new(TeamFilter);
+        let mut insert_ball = |x, team| {
+            let ball = ColliderBuilder::ball(0.5).user_data(team);
+            world.insert_dynamic(Vector::x() * x, ball).1
+        };
+
+        let shooter = insert_ball(0.0, 1);
+        let projectile = insert_ball(0.4, 1);
+        let target = insert_ball(-0.4, 2);
+        world.run(1);
+
+        assert!(!world
+            .narrow_phase
+            .has_any_active_contact(shooter, projectile));
+        assert!(world.narrow_phase.has_any_active_contact(shooter, target));
+    }
 }
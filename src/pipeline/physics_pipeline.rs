@@ -3,13 +3,17 @@
 use crate::counters::Counters;
 #[cfg(not(feature = "parallel"))]
 use crate::dynamics::IslandSolver;
-use crate::dynamics::{CCDSolver, IntegrationParameters, JointSet, RigidBodySet};
+use crate::dynamics::{
+    CCDSolver, GravityField, IntegrationParameters, JointLimitReached, JointParams, JointSet,
+    RigidBodyHandle, RigidBodySet,
+};
 #[cfg(feature = "parallel")]
 use crate::dynamics::{JointGraphEdge, ParallelIslandSolver as IslandSolver};
 use crate::geometry::{
-    BroadPhase, BroadPhasePairEvent, ColliderPair, ColliderSet, ContactManifoldIndex, NarrowPhase,
+    BroadPhaseBackend, BroadPhasePairEvent, ColliderPair, ColliderSet, ContactForceEvent,
+    ContactManifold, ContactManifoldIndex, NarrowPhase, TemporaryInteractionIndex,
 };
-use crate::math::{Real, Vector};
+use crate::math::{AngVector, Isometry, Real, Vector};
 use crate::pipeline::{EventHandler, PhysicsHooks};
 
 /// The physics pipeline, responsible for stepping the whole physics simulation.
@@ -31,6 +35,9 @@ pub struct PhysicsPipeline {
     broadphase_collider_pairs: Vec<ColliderPair>,
     broad_phase_events: Vec<BroadPhasePairEvent>,
     solvers: Vec<IslandSolver>,
+    kinematic_next_positions: Vec<(RigidBodyHandle, Isometry<Real>)>,
+    user_forces: Vec<(RigidBodyHandle, Vector<Real>, AngVector<Real>)>,
+    contact_force_event_pairs: Vec<TemporaryInteractionIndex>,
 }
 
 impl Default for PhysicsPipeline {
@@ -55,13 +62,16 @@ impl PhysicsPipeline {
             joint_constraint_indices: Vec::new(),
             broadphase_collider_pairs: Vec::new(),
             broad_phase_events: Vec::new(),
+            kinematic_next_positions: Vec::new(),
+            user_forces: Vec::new(),
+            contact_force_event_pairs: Vec::new(),
         }
     }
 
     fn detect_collisions(
         &mut self,
         integration_parameters: &IntegrationParameters,
-        broad_phase: &mut BroadPhase,
+        broad_phase: &mut dyn BroadPhaseBackend,
         narrow_phase: &mut NarrowPhase,
         bodies: &mut RigidBodySet,
         colliders: &mut ColliderSet,
@@ -70,21 +80,63 @@ impl PhysicsPipeline {
         handle_user_changes: bool,
     ) {
         self.counters.stages.collision_detection_time.resume();
+        self.find_broad_phase_pairs(
+            integration_parameters,
+            broad_phase,
+            bodies,
+            colliders,
+            events,
+        );
+        self.compute_narrow_phase_interactions(
+            integration_parameters,
+            narrow_phase,
+            bodies,
+            colliders,
+            hooks,
+            events,
+            handle_user_changes,
+        );
+        self.counters.stages.collision_detection_time.pause();
+    }
+
+    fn find_broad_phase_pairs(
+        &mut self,
+        integration_parameters: &IntegrationParameters,
+        broad_phase: &mut dyn BroadPhaseBackend,
+        bodies: &RigidBodySet,
+        colliders: &mut ColliderSet,
+        events: &dyn EventHandler,
+    ) {
         self.counters.cd.broad_phase_time.resume();
 
-        // Update broad-phase.
         self.broad_phase_events.clear();
         self.broadphase_collider_pairs.clear();
         broad_phase.update(
             integration_parameters.prediction_distance,
+            bodies,
             colliders,
             &mut self.broad_phase_events,
         );
 
+        for event in &self.broad_phase_events {
+            events.handle_broad_phase_pair_event(*event);
+        }
+
         self.counters.cd.broad_phase_time.pause();
+    }
+
+    fn compute_narrow_phase_interactions(
+        &mut self,
+        integration_parameters: &IntegrationParameters,
+        narrow_phase: &mut NarrowPhase,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        hooks: &dyn PhysicsHooks,
+        events: &dyn EventHandler,
+        handle_user_changes: bool,
+    ) {
         self.counters.cd.narrow_phase_time.resume();
 
-        // Update narrow-phase.
         if handle_user_changes {
             narrow_phase.handle_user_changes(colliders, bodies, events);
         }
@@ -98,11 +150,18 @@ impl PhysicsPipeline {
         );
         narrow_phase.compute_intersections(bodies, colliders, hooks, events);
 
+        if self.counters.enabled() {
+            let ncontact_pairs = narrow_phase
+                .contact_pairs()
+                .filter(|pair| pair.has_any_active_contact)
+                .count();
+            self.counters.set_ncontact_pairs(ncontact_pairs);
+        }
+
         // Clear colliders modification flags.
         colliders.clear_modified_colliders();
 
         self.counters.cd.narrow_phase_time.pause();
-        self.counters.stages.collision_detection_time.pause();
     }
 
     fn solve_position_constraints(
@@ -156,12 +215,14 @@ impl PhysicsPipeline {
 
     fn build_islands_and_solve_velocity_constraints(
         &mut self,
-        gravity: &Vector<Real>,
+        gravity: &dyn GravityField,
         integration_parameters: &IntegrationParameters,
         narrow_phase: &mut NarrowPhase,
         bodies: &mut RigidBodySet,
         colliders: &mut ColliderSet,
         joints: &mut JointSet,
+        hooks: &dyn PhysicsHooks,
+        events: &dyn EventHandler,
     ) {
         self.counters.stages.island_construction_time.resume();
         bodies.update_active_set_with_contacts(
@@ -169,6 +230,9 @@ impl PhysicsPipeline {
             narrow_phase,
             joints.joint_graph(),
             integration_parameters.min_island_size,
+            integration_parameters.joint_island_merge_policy,
+            integration_parameters.dt,
+            events,
         );
         self.counters.stages.island_construction_time.pause();
 
@@ -183,13 +247,52 @@ impl PhysicsPipeline {
         }
 
         let mut manifolds = Vec::new();
-        narrow_phase.select_active_contacts(bodies, &mut manifolds, &mut self.manifold_indices);
+        narrow_phase.select_active_contacts(
+            bodies,
+            colliders,
+            hooks,
+            &mut manifolds,
+            &mut self.manifold_indices,
+            &mut self.contact_force_event_pairs,
+        );
         joints.select_active_interactions(bodies, &mut self.joint_constraint_indices);
 
+        if self.counters.enabled() {
+            let num_islands = bodies.num_islands();
+            let ncontacts = manifolds
+                .iter()
+                .map(|manifold| manifold.data.solver_contacts.len())
+                .sum();
+            let njoints: usize = self.joint_constraint_indices[..num_islands]
+                .iter()
+                .map(|indices| indices.len())
+                .sum();
+            self.counters
+                .set_nactive_bodies(bodies.active_dynamic_set.len());
+            self.counters.set_nislands(num_islands);
+            self.counters.set_ncontacts(ncontacts);
+            self.counters.set_nconstraints(manifolds.len() + njoints);
+        }
+
+        if let Some(seed) = integration_parameters.solver_ordering_seed {
+            for island_id in 0..bodies.num_islands() {
+                let island_seed = seed ^ (island_id as u64).wrapping_mul(0x9e3779b97f4a7c15);
+                crate::utils::shuffle_with_seed(&mut self.manifold_indices[island_id], island_seed);
+                crate::utils::shuffle_with_seed(
+                    &mut self.joint_constraint_indices[island_id],
+                    island_seed,
+                );
+            }
+        }
+
         self.counters.stages.update_time.resume();
         bodies.foreach_active_dynamic_body_mut_internal(|_, b| {
             b.update_world_mass_properties();
-            b.add_gravity(*gravity)
+            b.add_gravity(gravity.gravity_at(&b.world_com));
+            #[cfg(feature = "dim2")]
+            b.add_angular_steering_torque(integration_parameters.dt);
+            #[cfg(feature = "dim3")]
+            b.integrate_gyroscopic_forces(integration_parameters.dt);
         });
         self.counters.stages.update_time.pause();
 
@@ -219,7 +322,6 @@ impl PhysicsPipeline {
 
         #[cfg(feature = "parallel")]
         {
-            use crate::geometry::ContactManifold;
             use rayon::prelude::*;
             use std::sync::atomic::Ordering;
 
@@ -259,6 +361,129 @@ impl PhysicsPipeline {
             });
         }
         self.counters.stages.solver_time.pause();
+
+        self.accumulate_solver_impulses(bodies, &manifolds, joints);
+    }
+
+    fn emit_contact_force_events(
+        &self,
+        integration_parameters: &IntegrationParameters,
+        narrow_phase: &NarrowPhase,
+        colliders: &ColliderSet,
+        events: &dyn EventHandler,
+    ) {
+        let inv_dt = integration_parameters.step_inv_dt();
+
+        // NOTE: these are the pairs solved during the last substep that involve at least one
+        //       collider with a finite contact force event threshold.
+        for id in &self.contact_force_event_pairs {
+            let pair = match narrow_phase.contact_graph().index_interaction(*id) {
+                Some((_, _, pair)) => pair,
+                None => continue,
+            };
+
+            let threshold = colliders[pair.pair.collider1]
+                .contact_force_event_threshold
+                .min(colliders[pair.pair.collider2].contact_force_event_threshold);
+
+            let total_impulse: Real = pair
+                .manifolds
+                .iter()
+                .flat_map(|manifold| {
+                    manifold
+                        .data
+                        .solver_contacts
+                        .iter()
+                        .map(move |c| manifold.points[c.contact_id as usize].data.impulse.abs())
+                })
+                .sum();
+            let total_force_magnitude = total_impulse * inv_dt;
+
+            if total_force_magnitude > threshold {
+                events.handle_contact_force_event(ContactForceEvent {
+                    collider1: pair.pair.collider1,
+                    collider2: pair.pair.collider2,
+                    total_force_magnitude,
+                });
+            }
+        }
+    }
+
+    fn update_joint_limits(
+        &self,
+        integration_parameters: &IntegrationParameters,
+        bodies: &RigidBodySet,
+        joints: &mut JointSet,
+        events: &dyn EventHandler,
+    ) {
+        let joint_edges = joints.joints_mut();
+        for island_joints in &self.joint_constraint_indices[..bodies.num_islands()] {
+            for joint_id in island_joints {
+                let joint = &mut joint_edges[*joint_id].weight;
+
+                if let JointParams::PrismaticJoint(prismatic) = &mut joint.params {
+                    if let (Some(rb1), Some(rb2)) =
+                        (bodies.get(joint.body1), bodies.get(joint.body2))
+                    {
+                        if let Some(limit) = prismatic.update_active_limit(
+                            rb1.position(),
+                            rb2.position(),
+                            integration_parameters.allowed_linear_error,
+                        ) {
+                            events.handle_joint_limit_event(JointLimitReached {
+                                joint: joint.handle,
+                                limit,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn accumulate_solver_impulses(
+        &self,
+        bodies: &mut RigidBodySet,
+        manifolds: &[&mut ContactManifold],
+        joints: &JointSet,
+    ) {
+        for manifold in manifolds {
+            let impulse: Real = manifold
+                .data
+                .solver_contacts
+                .iter()
+                .map(|c| manifold.points[c.contact_id as usize].data.impulse)
+                .sum();
+
+            if impulse != 0.0 {
+                let pair = manifold.data.body_pair;
+                for handle in &[pair.body1, pair.body2] {
+                    if let Some(rb) = bodies.get_mut_internal(*handle) {
+                        if rb.is_dynamic() {
+                            rb.last_solver_impulses.contact_impulse += impulse;
+                        }
+                    }
+                }
+            }
+        }
+
+        let joint_edges = &joints.joint_graph().graph.edges;
+        for island_joints in &self.joint_constraint_indices[..bodies.num_islands()] {
+            for joint_id in island_joints {
+                let joint = &joint_edges[*joint_id].weight;
+                let impulse = joint.params.impulse_magnitude();
+
+                if impulse != 0.0 {
+                    for handle in &[joint.body1, joint.body2] {
+                        if let Some(rb) = bodies.get_mut_internal(*handle) {
+                            if rb.is_dynamic() {
+                                rb.last_solver_impulses.joint_impulse += impulse;
+                            }
+                        }
+                    }
+                }
+            }
+        }
     }
 
     fn run_ccd_motion_clamping(
@@ -270,7 +495,8 @@ impl PhysicsPipeline {
         ccd_solver: &mut CCDSolver,
         events: &dyn EventHandler,
     ) {
-        self.counters.ccd.toi_computation_time.start();
+        self.counters.stages.ccd_time.resume();
+        self.counters.ccd.toi_computation_time.resume();
         // Handle CCD
         let impacts = ccd_solver.predict_impacts_at_next_positions(
             integration_parameters.dt,
@@ -281,6 +507,7 @@ impl PhysicsPipeline {
         );
         ccd_solver.clamp_motions(integration_parameters.dt, bodies, &impacts);
         self.counters.ccd.toi_computation_time.pause();
+        self.counters.stages.ccd_time.pause();
     }
 
     fn advance_to_final_positions(
@@ -290,6 +517,7 @@ impl PhysicsPipeline {
         clear_forces: bool,
     ) {
         // Set the rigid-bodies and kinematic bodies to their final position.
+        bodies.track_active_bodies_motion();
         bodies.foreach_active_body_mut_internal(|_, rb| {
             if rb.is_kinematic() {
                 rb.linvel = na::zero();
@@ -306,6 +534,63 @@ impl PhysicsPipeline {
         });
     }
 
+    // Moves the kinematic bodies by one solver substep, at the constant velocity needed to reach
+    // their next position in `remaining_substeps` substeps.
+    //
+    // Unless this is the last substep, the next position of each kinematic body is replaced by
+    // its position at the end of this substep, and saved to be restored afterwards by
+    // `restore_kinematic_next_positions`.
+    fn set_kinematic_substep_positions(
+        &mut self,
+        substep_dt: Real,
+        remaining_substeps: usize,
+        bodies: &mut RigidBodySet,
+    ) {
+        let kinematic_next_positions = &mut self.kinematic_next_positions;
+        let inv_remaining_time = crate::utils::inv(substep_dt * remaining_substeps as Real);
+        kinematic_next_positions.clear();
+
+        bodies.foreach_active_kinematic_body_mut_internal(|handle, body| {
+            body.compute_velocity_from_next_position(inv_remaining_time);
+
+            if remaining_substeps > 1 {
+                kinematic_next_positions.push((handle, body.next_position));
+                body.next_position = body.integrate_velocity(substep_dt) * body.position;
+            }
+        });
+    }
+
+    fn restore_kinematic_next_positions(&mut self, bodies: &mut RigidBodySet) {
+        for (handle, next_position) in self.kinematic_next_positions.drain(..) {
+            if let Some(body) = bodies.get_mut_internal(handle) {
+                body.next_position = next_position;
+            }
+        }
+    }
+
+    // Saves the forces and torques applied by the user to the dynamic bodies, so they can be
+    // applied again at each substep by `restore_user_forces`.
+    fn save_user_forces(&mut self, bodies: &RigidBodySet) {
+        self.user_forces.clear();
+
+        for (handle, body) in bodies.iter_active_dynamic() {
+            if body.force != na::zero() || body.torque != na::zero() {
+                self.user_forces.push((handle, body.force, body.torque));
+            }
+        }
+    }
+
+    // The solver clears the forces once it integrated them. Apply the user forces again so they
+    // act during the whole timestep, and not only during its first substep.
+    fn restore_user_forces(&mut self, bodies: &mut RigidBodySet) {
+        for (handle, force, torque) in &self.user_forces {
+            if let Some(body) = bodies.get_mut_internal(*handle) {
+                body.force = *force;
+                body.torque = *torque;
+            }
+        }
+    }
+
     fn interpolate_kinematic_velocities(
         &mut self,
         integration_parameters: &IntegrationParameters,
@@ -317,16 +602,198 @@ impl PhysicsPipeline {
         // there to determine if this kinematic body should wake-up dynamic
         // bodies it is touching.
         bodies.foreach_active_kinematic_body_mut_internal(|_, body| {
-            body.compute_velocity_from_next_position(integration_parameters.inv_dt());
+            body.compute_velocity_from_next_position(integration_parameters.step_inv_dt());
         });
     }
 
-    /// Executes one timestep of the physics simulation.
+    /// Updates the broad-phase, i.e., finds the pairs of colliders with intersecting AABBs.
+    ///
+    /// This is the first phase of a timestep, for users running the phases of the simulation
+    /// one by one instead of calling `step`, e.g., to inspect the contacts or run queries between
+    /// them. A timestep then consists of calling, in this order, `update_broad_phase`,
+    /// `update_narrow_phase`, `solve`, and `integrate`, with the same integration parameters
+    /// and timestep length. Unlike `step`, this doesn't run the CCD, doesn't reset the
+    /// `counters`, and the contacts are only recomputed at the next `update_narrow_phase`
+    /// instead of at the end of the timestep.
+    ///
+    /// This applies the modifications made by the user to the rigid-bodies and colliders since
+    /// the last timestep.
+    pub fn update_broad_phase(
+        &mut self,
+        integration_parameters: &IntegrationParameters,
+        broad_phase: &mut dyn BroadPhaseBackend,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        events: &dyn EventHandler,
+    ) {
+        colliders.handle_user_changes(bodies);
+        bodies.handle_user_changes(colliders);
+        self.find_broad_phase_pairs(
+            integration_parameters,
+            broad_phase,
+            bodies,
+            colliders,
+            events,
+        );
+    }
+
+    /// Updates the narrow-phase, i.e., computes the contacts and intersections between the
+    /// colliders at their current positions.
+    ///
+    /// This must be called after `update_broad_phase`, and before any other modification of the
+    /// colliders, since it relies on the pairs found by the broad-phase. Once it returns, the
+    /// contacts of the narrow-phase are up-to-date and the contact events have been emitted.
+    pub fn update_narrow_phase(
+        &mut self,
+        integration_parameters: &IntegrationParameters,
+        narrow_phase: &mut NarrowPhase,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        hooks: &dyn PhysicsHooks,
+        events: &dyn EventHandler,
+    ) {
+        self.compute_narrow_phase_interactions(
+            integration_parameters,
+            narrow_phase,
+            bodies,
+            colliders,
+            hooks,
+            events,
+            true,
+        );
+    }
+
+    /// Computes the island of active rigid-bodies, and solves the contact and joint constraints.
+    ///
+    /// This must be called after `update_narrow_phase`. The velocities of the dynamic
+    /// rigid-bodies are updated so they satisfy the constraints, and their next positions are
+    /// computed. Their current positions, and the positions of their colliders, are not modified
+    /// until `integrate` is called.
+    ///
+    /// Like with `step`, the timestep is split into `integration_parameters.num_substeps` solver
+    /// substeps, and the forces applied by the user act during all of them. The rigid-bodies are
+    /// moved, and the contacts recomputed, at the end of every substep but the last one, which is
+    /// completed by `integrate`.
+    pub fn solve(
+        &mut self,
+        gravity: &dyn GravityField,
+        dt: Real,
+        integration_parameters: &IntegrationParameters,
+        narrow_phase: &mut NarrowPhase,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        joints: &mut JointSet,
+        hooks: &dyn PhysicsHooks,
+        events: &dyn EventHandler,
+    ) {
+        assert!(dt >= 0.0, "The time-stepping length cannot be negative.");
+        let mut integration_parameters = *integration_parameters;
+        integration_parameters.dt = dt;
+
+        bodies.foreach_active_dynamic_body_mut_internal(|_, rb| rb.save_velocities_at_step_start());
+        self.save_user_forces(bodies);
+        self.interpolate_kinematic_velocities(&integration_parameters, bodies);
+        bodies.apply_platform_velocities(dt);
+
+        // Split the timestep into solver substeps of equal lengths, like `step` does.
+        let num_solver_substeps = integration_parameters.num_substeps.max(1);
+        let mut substep_parameters = integration_parameters;
+        substep_parameters.dt = dt / num_solver_substeps as Real;
+
+        for solver_substep in 0..num_solver_substeps {
+            let is_last_solver_substep = solver_substep + 1 == num_solver_substeps;
+            self.restore_user_forces(bodies);
+
+            if num_solver_substeps > 1 {
+                self.set_kinematic_substep_positions(
+                    substep_parameters.dt,
+                    num_solver_substeps - solver_substep,
+                    bodies,
+                );
+            }
+
+            self.build_islands_and_solve_velocity_constraints(
+                gravity,
+                &substep_parameters,
+                narrow_phase,
+                bodies,
+                colliders,
+                joints,
+                hooks,
+                events,
+            );
+
+            if is_last_solver_substep {
+                self.emit_contact_force_events(
+                    &substep_parameters,
+                    narrow_phase,
+                    colliders,
+                    events,
+                );
+            }
+
+            self.solve_position_constraints(&substep_parameters, bodies);
+
+            // The bodies are moved to the positions computed by the last substep by `integrate`.
+            if !is_last_solver_substep {
+                self.advance_to_final_positions(bodies, colliders, false);
+                self.restore_kinematic_next_positions(bodies);
+                self.broad_phase_events.clear();
+                self.compute_narrow_phase_interactions(
+                    &integration_parameters,
+                    narrow_phase,
+                    bodies,
+                    colliders,
+                    hooks,
+                    events,
+                    false,
+                );
+                bodies.modified_inactive_set.clear();
+            }
+        }
+    }
+
+    /// Moves the rigid-bodies and their colliders to the next positions computed by `solve`.
+    ///
+    /// This must be called after `solve`, and ends the timestep: the forces applied to the
+    /// rigid-bodies are cleared, and the joint limit events are emitted. The contacts of the
+    /// narrow-phase are not updated until the next call to `update_narrow_phase`.
+    pub fn integrate(
+        &mut self,
+        dt: Real,
+        integration_parameters: &IntegrationParameters,
+        bodies: &mut RigidBodySet,
+        colliders: &mut ColliderSet,
+        joints: &mut JointSet,
+        events: &dyn EventHandler,
+    ) {
+        let mut integration_parameters = *integration_parameters;
+        integration_parameters.dt = dt;
+
+        self.advance_to_final_positions(bodies, colliders, true);
+        self.user_forces.clear();
+        self.update_joint_limits(&integration_parameters, bodies, joints, events);
+
+        let inv_dt = integration_parameters.step_inv_dt();
+        bodies
+            .foreach_active_dynamic_body_mut_internal(|_, rb| rb.update_last_accelerations(inv_dt));
+        bodies.modified_inactive_set.clear();
+    }
+
+    /// Executes one timestep of length `dt` of the physics simulation.
+    ///
+    /// The timestep length may change from one call to the next. Every quantity depending
+    /// on it (like the inverse timestep used by the constraints solver) is recomputed here.
+    ///
+    /// The `gravity` is either a uniform gravity `Vector`, or any other `GravityField`
+    /// evaluated at the center-of-mass of each active dynamic rigid-body. The `broad_phase` can
+    /// be any `BroadPhaseBackend`, as long as the same one is used at every step.
     pub fn step(
         &mut self,
-        gravity: &Vector<Real>,
+        gravity: &dyn GravityField,
+        dt: Real,
         integration_parameters: &IntegrationParameters,
-        broad_phase: &mut BroadPhase,
+        broad_phase: &mut dyn BroadPhaseBackend,
         narrow_phase: &mut NarrowPhase,
         bodies: &mut RigidBodySet,
         colliders: &mut ColliderSet,
@@ -335,13 +802,17 @@ impl PhysicsPipeline {
         hooks: &dyn PhysicsHooks,
         events: &dyn EventHandler,
     ) {
+        assert!(dt >= 0.0, "The time-stepping length cannot be negative.");
+        let mut integration_parameters = *integration_parameters;
+        integration_parameters.dt = dt;
+
         self.counters.reset();
         self.counters.step_started();
         colliders.handle_user_changes(bodies);
         bodies.handle_user_changes(colliders);
 
         self.detect_collisions(
-            integration_parameters,
+            &integration_parameters,
             broad_phase,
             narrow_phase,
             bodies,
@@ -351,8 +822,11 @@ impl PhysicsPipeline {
             true,
         );
 
+        let inv_dt = integration_parameters.step_inv_dt();
         let mut remaining_time = integration_parameters.dt;
-        let mut integration_parameters = *integration_parameters;
+
+        bodies.foreach_active_dynamic_body_mut_internal(|_, rb| rb.save_velocities_at_step_start());
+        self.save_user_forces(bodies);
 
         let (ccd_is_enabled, mut remaining_substeps) =
             if integration_parameters.max_ccd_substeps == 0 {
@@ -361,6 +835,19 @@ impl PhysicsPipeline {
                 (true, integration_parameters.max_ccd_substeps)
             };
 
+        // Bullets need more substeps so they can keep moving after their first impact.
+        // These extra substeps are only spent on the impacts involving a bullet, once the
+        // regular substeps are exhausted.
+        let mut remaining_bullet_substeps = 0;
+        if ccd_is_enabled && integration_parameters.max_bullet_ccd_substeps > remaining_substeps {
+            let _ = ccd_solver.update_ccd_active_flags(bodies, integration_parameters.dt, true);
+
+            if ccd_solver.has_active_bullets(bodies) {
+                remaining_bullet_substeps =
+                    integration_parameters.max_bullet_ccd_substeps - remaining_substeps;
+            }
+        }
+
         while remaining_substeps > 0 {
             // If there are more than one CCD substep, we need to split
             // the timestep into multiple intervals. First, estimate the
@@ -372,27 +859,43 @@ impl PhysicsPipeline {
             //
             // If there is only one or zero CCD substep, there is no need
             // to split the timetsep interval. So we can just skip this part.
-            if ccd_is_enabled && remaining_substeps > 1 {
+            let total_substeps = remaining_substeps + remaining_bullet_substeps;
+
+            if ccd_is_enabled && total_substeps > 1 {
                 // NOTE: Take forces into account when updating the bodies CCD activation flags
                 //       these forces have not been integrated to the body's velocity yet.
+                self.counters.stages.ccd_time.resume();
                 let ccd_active = ccd_solver.update_ccd_active_flags(bodies, remaining_time, true);
-                let first_impact = if ccd_active {
-                    ccd_solver.find_first_impact(remaining_time, bodies, colliders, narrow_phase)
-                } else {
+                let bullets_only = remaining_substeps == 1;
+                let first_impact = if !ccd_active {
                     None
+                } else if bullets_only {
+                    ccd_solver.find_first_bullet_impact(
+                        remaining_time,
+                        bodies,
+                        colliders,
+                        narrow_phase,
+                    )
+                } else {
+                    ccd_solver.find_first_impact(remaining_time, bodies, colliders, narrow_phase)
                 };
+                self.counters.stages.ccd_time.pause();
 
                 if let Some(toi) = first_impact {
-                    let original_interval = remaining_time / (remaining_substeps as Real);
+                    let original_interval = remaining_time / (total_substeps as Real);
 
                     if toi < original_interval {
                         integration_parameters.dt = original_interval;
                     } else {
                         integration_parameters.dt =
-                            toi + (remaining_time - toi) / (remaining_substeps as Real);
+                            toi + (remaining_time - toi) / (total_substeps as Real);
                     }
 
-                    remaining_substeps -= 1;
+                    if bullets_only {
+                        remaining_bullet_substeps -= 1;
+                    } else {
+                        remaining_substeps -= 1;
+                    }
                 } else {
                     // No impact, don't do any other substep after this one.
                     integration_parameters.dt = remaining_time;
@@ -415,57 +918,117 @@ impl PhysicsPipeline {
             self.counters.ccd.num_substeps += 1;
 
             self.interpolate_kinematic_velocities(&integration_parameters, bodies);
-            self.build_islands_and_solve_velocity_constraints(
-                gravity,
-                &integration_parameters,
-                narrow_phase,
-                bodies,
-                colliders,
-                joints,
-            );
+            bodies.apply_platform_velocities(integration_parameters.dt);
+
+            // Split this time interval into solver substeps of equal lengths.
+            let num_solver_substeps = integration_parameters.num_substeps.max(1);
+            let mut substep_parameters = integration_parameters;
+            substep_parameters.dt = integration_parameters.dt / num_solver_substeps as Real;
 
-            // If CCD is enabled, execute the CCD motion clamping.
-            if ccd_is_enabled {
-                // NOTE: don't the forces into account when updating the CCD active flags because
-                //       they have already been integrated into the velocities by the solver.
-                let ccd_active =
-                    ccd_solver.update_ccd_active_flags(bodies, integration_parameters.dt, false);
-                if ccd_active {
-                    self.run_ccd_motion_clamping(
+            for solver_substep in 0..num_solver_substeps {
+                let is_last_solver_substep = solver_substep + 1 == num_solver_substeps;
+                self.restore_user_forces(bodies);
+
+                if num_solver_substeps > 1 {
+                    self.set_kinematic_substep_positions(
+                        substep_parameters.dt,
+                        num_solver_substeps - solver_substep,
+                        bodies,
+                    );
+                }
+
+                self.build_islands_and_solve_velocity_constraints(
+                    gravity,
+                    &substep_parameters,
+                    narrow_phase,
+                    bodies,
+                    colliders,
+                    joints,
+                    hooks,
+                    events,
+                );
+
+                // NOTE: the contact force events are emitted only once per timestep, with the
+                //       forces computed during its last substep.
+                if remaining_substeps == 0 && is_last_solver_substep {
+                    self.emit_contact_force_events(
+                        &substep_parameters,
+                        narrow_phase,
+                        colliders,
+                        events,
+                    );
+                }
+
+                // If CCD is enabled, execute the CCD motion clamping.
+                if ccd_is_enabled {
+                    // NOTE: don't the forces into account when updating the CCD active flags because
+                    //       they have already been integrated into the velocities by the solver.
+                    let ccd_active =
+                        ccd_solver.update_ccd_active_flags(bodies, substep_parameters.dt, false);
+                    if ccd_active {
+                        self.run_ccd_motion_clamping(
+                            &substep_parameters,
+                            bodies,
+                            colliders,
+                            narrow_phase,
+                            ccd_solver,
+                            events,
+                        );
+                    }
+                }
+
+                // NOTE: we need to run the position solver **after** the
+                //       CCD motion clamping because otherwise the clamping
+                //       would undo the depenetration done by the position
+                //       solver.
+                //       This happens because our CCD use the real rigid-body
+                //       velocities instead of just interpolating between
+                //       isometries.
+                self.solve_position_constraints(&substep_parameters, bodies);
+
+                let clear_forces = remaining_substeps == 0 && is_last_solver_substep;
+                self.advance_to_final_positions(bodies, colliders, clear_forces);
+
+                if is_last_solver_substep {
+                    self.detect_collisions(
                         &integration_parameters,
+                        broad_phase,
+                        narrow_phase,
                         bodies,
                         colliders,
+                        hooks,
+                        events,
+                        false,
+                    );
+                } else {
+                    self.restore_kinematic_next_positions(bodies);
+
+                    // NOTE: the broad-phase is only updated at the end of the interval, so the
+                    //       contacts of the intermediate substeps are only computed for the pairs
+                    //       of colliders that were already close to each other.
+                    self.counters.stages.collision_detection_time.resume();
+                    self.broad_phase_events.clear();
+                    self.compute_narrow_phase_interactions(
+                        &integration_parameters,
                         narrow_phase,
-                        ccd_solver,
+                        bodies,
+                        colliders,
+                        hooks,
                         events,
+                        false,
                     );
+                    self.counters.stages.collision_detection_time.pause();
                 }
+
+                bodies.modified_inactive_set.clear();
             }
+        }
 
-            // NOTE: we need to run the position solver **after** the
-            //       CCD motion clamping because otherwise the clamping
-            //       would undo the depenetration done by the position
-            //       solver.
-            //       This happens because our CCD use the real rigid-body
-            //       velocities instead of just interpolating between
-            //       isometries.
-            self.solve_position_constraints(&integration_parameters, bodies);
-
-            let clear_forces = remaining_substeps == 0;
-            self.advance_to_final_positions(bodies, colliders, clear_forces);
-            self.detect_collisions(
-                &integration_parameters,
-                broad_phase,
-                narrow_phase,
-                bodies,
-                colliders,
-                hooks,
-                events,
-                false,
-            );
+        self.user_forces.clear();
+        self.update_joint_limits(&integration_parameters, bodies, joints, events);
 
-            bodies.modified_inactive_set.clear();
-        }
+        bodies
+            .foreach_active_dynamic_body_mut_internal(|_, rb| rb.update_last_accelerations(inv_dt));
 
         self.counters.step_completed();
     }
@@ -478,7 +1041,7 @@ mod test {
     };
     use crate::geometry::{BroadPhase, ColliderBuilder, ColliderSet, NarrowPhase};
     use crate::math::Vector;
-    use crate::pipeline::PhysicsPipeline;
+    use crate::pipeline::{PhysicsPipeline, PhysicsWorld};
 
     #[test]
     fn kinematic_and_static_contact_crash() {
@@ -501,6 +1064,7 @@ mod test {
 
         pipeline.step(
             &Vector::zeros(),
+            1.0 / 60.0,
             &IntegrationParameters::default(),
             &mut bf,
             &mut nf,
@@ -513,6 +1077,34 @@ mod test {
         );
     }
 
+    #[test]
+    fn contact_force_events() {
+        use crate::pipeline::ChannelEventCollector;
+
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        world.insert_ground();
+        let ball = ColliderBuilder::ball(0.5).contact_force_event_threshold(100.0);
+        world.insert_dynamic(Vector::y() * 3.0, ball);
+
+        let (intersection_send, _intersection_recv) = crossbeam::channel::unbounded();
+        let (contact_send, _contact_recv) = crossbeam::channel::unbounded();
+        let (force_send, force_recv) = crossbeam::channel::unbounded();
+        world.events = Box::new(
+            ChannelEventCollector::new(intersection_send, contact_send)
+                .with_contact_force_event_sender(force_send),
+        );
+
+        // The impact on the ground is a hard hit.
+        world.run(60);
+        let impacts: Vec<_> = force_recv.try_iter().collect();
+        assert!(!impacts.is_empty());
+        assert!(impacts.iter().all(|e| e.total_force_magnitude > 100.0));
+
+        // Resting on the ground isn't.
+        world.run(60);
+        assert!(force_recv.try_recv().is_err());
+    }
+
     #[test]
     fn rigid_body_removal_before_step() {
         let mut colliders = ColliderSet::new();
@@ -545,6 +1137,7 @@ mod test {
 
         pipeline.step(
             &Vector::zeros(),
+            1.0 / 60.0,
             &IntegrationParameters::default(),
             &mut bf,
             &mut nf,
@@ -589,6 +1182,61 @@ mod test {
         assert_eq!(h3a, h3b);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn snapshot_restore_preserves_sleeping_and_islands() {
+        use crate::math::Real;
+        use serde::{de::DeserializeOwned, Serialize};
+
+        fn roundtrip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+            bincode::deserialize(&bincode::serialize(value).unwrap()).unwrap()
+        }
+
+        let gravity = Vector::y() * -9.81;
+        let mut world = PhysicsWorld::new(gravity);
+        world.insert_ground();
+        let handles: Vec<_> = (0..4)
+            .map(|i| {
+                let translation = Vector::x() * (i as Real * 3.0) + Vector::y() * 0.5;
+                world
+                    .insert_dynamic(translation, ColliderBuilder::ball(0.5))
+                    .0
+            })
+            .collect();
+
+        // Step until every body falls asleep.
+        world.run(500);
+        assert!(handles.iter().all(|h| world.bodies[*h].is_sleeping()));
+
+        let mut world2 = PhysicsWorld::new(gravity);
+        world2.bodies = roundtrip(&world.bodies);
+        world2.colliders = roundtrip(&world.colliders);
+        world2.joints = roundtrip(&world.joints);
+        world2.broad_phase = roundtrip(&world.broad_phase);
+        world2.narrow_phase = roundtrip(&world.narrow_phase);
+        world2.ccd_solver = roundtrip(&world.ccd_solver);
+
+        assert_eq!(world.bodies.active_islands, world2.bodies.active_islands);
+        assert_eq!(
+            world.bodies.active_dynamic_set,
+            world2.bodies.active_dynamic_set
+        );
+
+        for _ in 0..10 {
+            world.run(1);
+            world2.run(1);
+
+            // Restoring the snapshot must not wake-up the bodies.
+            assert!(world2.bodies.active_dynamic_set.is_empty());
+            assert_eq!(world.bodies.active_islands, world2.bodies.active_islands);
+
+            for h in &handles {
+                assert!(world2.bodies[*h].is_sleeping());
+                assert_eq!(world.bodies[*h].position(), world2.bodies[*h].position());
+            }
+        }
+    }
+
     #[test]
     fn collider_removal_before_step() {
         let mut pipeline = PhysicsPipeline::new();
@@ -613,6 +1261,7 @@ mod test {
         for _ in 0..10 {
             pipeline.step(
                 &gravity,
+                1.0 / 60.0,
                 &integration_parameters,
                 &mut broad_phase,
                 &mut narrow_phase,
@@ -625,4 +1274,322 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn manual_phases() {
+        use crate::math::Real;
+
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        let ground_co = world.insert_ground();
+        let (_, ball_co) = world.insert_dynamic(Vector::y() * 2.0, ColliderBuilder::ball(0.5));
+        let mut first_contact_frame = None;
+        let dt = 1.0 / 60.0;
+
+        for frame in 0..120 {
+            world.pipeline.update_broad_phase(
+                &world.integration_parameters,
+                &mut world.broad_phase,
+                &mut world.bodies,
+                &mut world.colliders,
+                &(),
+            );
+            world.pipeline.update_narrow_phase(
+                &world.integration_parameters,
+                &mut world.narrow_phase,
+                &mut world.bodies,
+                &mut world.colliders,
+                &(),
+                &(),
+            );
+
+            // Inspect the contacts before they are solved.
+            let in_contact = world
+                .narrow_phase
+                .contact_pair(ground_co, ball_co)
+                .map(|pair| pair.has_any_active_contact)
+                .unwrap_or(false);
+            if in_contact && first_contact_frame.is_none() {
+                first_contact_frame = Some(frame);
+            }
+
+            world.pipeline.solve(
+                &world.gravity,
+                dt,
+                &world.integration_parameters,
+                &mut world.narrow_phase,
+                &mut world.bodies,
+                &mut world.colliders,
+                &mut world.joints,
+                &(),
+                &(),
+            );
+            world.pipeline.integrate(
+                dt,
+                &world.integration_parameters,
+                &mut world.bodies,
+                &mut world.colliders,
+                &mut world.joints,
+                &(),
+            );
+        }
+
+        // The ball fell for about half a second before touching the ground, and rests on it.
+        let first_contact_time = first_contact_frame.unwrap() as Real * dt;
+        assert!(first_contact_time > 0.4 && first_contact_time < 0.7);
+        assert!((world.colliders[ball_co].position().translation.y - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn manual_phases_apply_user_forces_during_every_substep() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        world.integration_parameters.num_substeps = 4;
+        let (handle, _) = world.insert_dynamic(Vector::zeros(), ColliderBuilder::ball(0.5));
+        let mass = world.bodies[handle].mass();
+        let dt = 0.1;
+
+        for _ in 0..10 {
+            world.bodies[handle].apply_force(Vector::x() * mass, true);
+            world.pipeline.update_broad_phase(
+                &world.integration_parameters,
+                &mut world.broad_phase,
+                &mut world.bodies,
+                &mut world.colliders,
+                &(),
+            );
+            world.pipeline.update_narrow_phase(
+                &world.integration_parameters,
+                &mut world.narrow_phase,
+                &mut world.bodies,
+                &mut world.colliders,
+                &(),
+                &(),
+            );
+            world.pipeline.solve(
+                &world.gravity,
+                dt,
+                &world.integration_parameters,
+                &mut world.narrow_phase,
+                &mut world.bodies,
+                &mut world.colliders,
+                &mut world.joints,
+                &(),
+                &(),
+            );
+            world.pipeline.integrate(
+                dt,
+                &world.integration_parameters,
+                &mut world.bodies,
+                &mut world.colliders,
+                &mut world.joints,
+                &(),
+            );
+        }
+
+        // Same as with `step`: the force acted during the four substeps of each timestep.
+        assert!((world.bodies[handle].linvel() - Vector::x()).norm() < 1.0e-4);
+        assert_eq!(world.bodies[handle].force, Vector::zeros());
+    }
+
+    #[test]
+    fn solver_substeps() {
+        use crate::math::{Isometry, Real};
+
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        world.integration_parameters.num_substeps = 4;
+        world.insert_ground();
+
+        // A ball resting on the ground, and a ball in free fall.
+        let (resting, _) = world.insert_dynamic(Vector::y() * 2.0, ColliderBuilder::ball(0.5));
+        let (falling, _) = world.insert_dynamic(
+            Vector::x() * 5.0 + Vector::y() * 100.0,
+            ColliderBuilder::ball(0.5),
+        );
+
+        let platform = world.bodies.insert(
+            RigidBodyBuilder::new_kinematic()
+                .position(Isometry::new(Vector::x() * -5.0, na::zero()))
+                .build(),
+        );
+        world.colliders.insert(
+            ColliderBuilder::ball(0.5).build(),
+            platform,
+            &mut world.bodies,
+        );
+
+        for i in 0..60 {
+            let target = Isometry::new(
+                Vector::x() * -5.0 + Vector::y() * (i + 1) as Real,
+                na::zero(),
+            );
+            world.bodies[platform].set_next_kinematic_position(target);
+            world.run(1);
+
+            // The kinematic body reaches its target at the end of the step.
+            let translation = world.bodies[platform].position().translation.vector;
+            assert!((translation - target.translation.vector).norm() < 1.0e-4);
+        }
+
+        // The gravity was applied once per step, split among the substeps.
+        assert!((world.bodies[falling].linvel().y + 9.81).abs() < 1.0e-3);
+        assert!((world.bodies[resting].position().translation.y - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn solver_substeps_apply_user_forces() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        world.integration_parameters.num_substeps = 4;
+        let (handle, _) = world.insert_dynamic(Vector::zeros(), ColliderBuilder::ball(0.5));
+        let mass = world.bodies[handle].mass();
+
+        for _ in 0..10 {
+            world.bodies[handle].apply_force(Vector::x() * mass, true);
+            world.step(0.1);
+        }
+
+        // The force acted during the whole timestep, not only its first substep.
+        assert!((world.bodies[handle].linvel() - Vector::x()).norm() < 1.0e-4);
+        assert_eq!(world.bodies[handle].force, Vector::zeros());
+    }
+
+    #[test]
+    fn solver_substeps_emit_one_contact_force_event_per_step() {
+        use crate::pipeline::ChannelEventCollector;
+
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        world.integration_parameters.num_substeps = 4;
+        world.insert_ground();
+        let ball = ColliderBuilder::ball(0.5).contact_force_event_threshold(0.0);
+        world.insert_dynamic(Vector::y() * 0.5, ball);
+
+        let (intersection_send, _intersection_recv) = crossbeam::channel::unbounded();
+        let (contact_send, _contact_recv) = crossbeam::channel::unbounded();
+        let (force_send, force_recv) = crossbeam::channel::unbounded();
+        world.events = Box::new(
+            ChannelEventCollector::new(intersection_send, contact_send)
+                .with_contact_force_event_sender(force_send),
+        );
+        world.run(10);
+
+        // Let the ball settle, then check we get exactly one event per step.
+        let _ = force_recv.try_iter().count();
+        for _ in 0..5 {
+            world.run(1);
+            assert_eq!(force_recv.try_iter().count(), 1);
+        }
+    }
+
+    #[test]
+    fn step_counters() {
+        use crate::math::Real;
+
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        world.insert_ground();
+
+        // Two balls resting on the ground, far from each other.
+        for i in 0..2 {
+            let translation = Vector::x() * (i as Real * 10.0) + Vector::y() * 0.5;
+            world.insert_dynamic(translation, ColliderBuilder::ball(0.5));
+        }
+
+        world.pipeline.counters.enable();
+        world.run(10);
+
+        let counters = &world.pipeline.counters;
+        assert_eq!(counters.solver.nactive_bodies, 2);
+        // NOTE: both balls are in the same island because of the minimum island size.
+        assert_eq!(counters.solver.nislands, 1);
+        assert_eq!(counters.solver.nconstraints, 2);
+        assert_eq!(counters.cd.ncontact_pairs, 2);
+        assert!(counters.solver.ncontacts >= 2);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_solver_is_independent_of_the_thread_count() {
+        use crate::math::{Isometry, Real};
+
+        let simulate = |num_threads: usize| -> Vec<Isometry<Real>> {
+            let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+            world.insert_ground();
+
+            // A pyramid, with many constraints solved concurrently.
+            for i in 0..20 {
+                for j in i..20 {
+                    let x = j as Real - i as Real * 0.5 - 10.0;
+                    let y = i as Real + 0.5;
+                    #[cfg(feature = "dim2")]
+                    let co = ColliderBuilder::cuboid(0.5, 0.5);
+                    #[cfg(feature = "dim3")]
+                    let co = ColliderBuilder::cuboid(0.5, 0.5, 0.5);
+                    world.insert_dynamic(Vector::x() * x + Vector::y() * y, co);
+                }
+            }
+
+            let thread_pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .unwrap();
+            thread_pool.install(|| world.run(60));
+
+            world.bodies.iter().map(|(_, rb)| *rb.position()).collect()
+        };
+
+        // Bit-identical results, whatever the number of threads. They aren't compared to the
+        // single-threaded solver, which solves the constraints in a different order.
+        assert_eq!(simulate(1), simulate(4));
+    }
+
+    #[test]
+    fn bullet_substeps_are_only_spent_on_bullet_impacts() {
+        use crate::math::{Isometry, Real};
+
+        // Counts the CCD substeps of the first step of a ball launched toward an obstacle,
+        // while a bullet moves fast in empty space, or the other way round.
+        let count_substeps = |bullet_hits_obstacle: bool| {
+            let mut world = PhysicsWorld::new(Vector::zeros());
+            world.integration_parameters.max_ccd_substeps = 1;
+            world.integration_parameters.max_bullet_ccd_substeps = 4;
+            world.pipeline.counters.enable();
+
+            let obstacle = world.bodies.insert(
+                RigidBodyBuilder::new_static()
+                    .position(Isometry::new(Vector::x() * 5.0, na::zero()))
+                    .build(),
+            );
+            world.colliders.insert(
+                ColliderBuilder::ball(1.0).build(),
+                obstacle,
+                &mut world.bodies,
+            );
+
+            for (i, is_bullet) in [false, true].iter().enumerate() {
+                let hits_obstacle = *is_bullet == bullet_hits_obstacle;
+                let y = if hits_obstacle {
+                    0.0
+                } else {
+                    100.0 + i as Real
+                };
+                let mut rb = RigidBodyBuilder::new_dynamic()
+                    .position(Isometry::new(Vector::y() * y, na::zero()))
+                    .ccd_enabled(true)
+                    .bullet(*is_bullet)
+                    .build();
+                rb.set_linvel(Vector::x() * 1000.0, true);
+                let handle = world.bodies.insert(rb);
+                world.colliders.insert(
+                    ColliderBuilder::ball(0.1).build(),
+                    handle,
+                    &mut world.bodies,
+                );
+            }
+
+            world.step(1.0 / 60.0);
+            world.pipeline.counters.ccd.num_substeps
+        };
+
+        // The impact of a regular body doesn't get the extra substeps of the bullet.
+        assert_eq!(count_substeps(false), 1);
+        // The impact of the bullet does.
+        assert!(count_substeps(true) > 1);
+    }
 }
@@ -0,0 +1,290 @@
+use crate::dynamics::{
+    CCDSolver, ForceGeneratorSet, IntegrationParameters, JointSet, RigidBodySet,
+};
+use crate::geometry::{BroadPhase, ColliderSet, NarrowPhase};
+#[cfg(feature = "serde-serialize")]
+use crate::math::DIM;
+use crate::math::{Real, Vector};
+use crate::pipeline::{state_checksum, PhysicsPipeline, PhysicsWorld};
+#[cfg(feature = "serde-serialize")]
+use std::fmt;
+
+/// The magic bytes at the beginning of every serialized physics snapshot.
+#[cfg(feature = "serde-serialize")]
+const PHYSICS_SNAPSHOT_MAGIC: &[u8; 4] = b"RPSN";
+/// The version of the serialized physics snapshot format.
+///
+/// This must be incremented whenever the serialized representation of any of the structures
+/// contained by a snapshot changes.
+#[cfg(feature = "serde-serialize")]
+pub const PHYSICS_SNAPSHOT_VERSION: u32 = 1;
+/// The length of the header preceding the serialized snapshot data.
+#[cfg(feature = "serde-serialize")]
+const HEADER_LEN: usize = 10;
+
+#[cfg(feature = "serde-serialize")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Error returned when a physics snapshot could not be serialized or deserialized.
+pub enum PhysicsSnapshotError {
+    /// The data does not start with a valid physics snapshot header.
+    InvalidHeader,
+    /// The data was serialized with an unsupported version of the snapshot format.
+    UnsupportedVersion(u32),
+    /// The data was serialized for a different dimension or floating-point precision.
+    IncompatibleBuild,
+    /// The snapshot could not be serialized or deserialized.
+    Serialization(String),
+}
+
+#[cfg(feature = "serde-serialize")]
+impl fmt::Display for PhysicsSnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "the data is not a physics snapshot"),
+            Self::UnsupportedVersion(v) => {
+                write!(f, "unsupported physics snapshot format version {}", v)
+            }
+            Self::IncompatibleBuild => write!(
+                f,
+                "the snapshot was taken for another dimension or floating-point precision"
+            ),
+            Self::Serialization(e) => write!(f, "physics snapshot serialization failed: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "serde-serialize")]
+impl std::error::Error for PhysicsSnapshotError {}
+
+/// The complete state of a physics world, which can be saved and restored as a whole.
+///
+/// The rigid-body, collider and joint sets, the broad-phase, narrow-phase and CCD solver
+/// reference each other (e.g. through the handles stored in the contact pairs, or the
+/// removal events they subscribed to), so they must be saved and restored together. This
+/// bundles all of them, as well as the gravity and integration parameters. Restoring a
+/// snapshot preserves the handles, the islands, the contact manifolds and their warm-start
+/// impulses, so the restored simulation continues exactly like the original one would have.
+///
+/// The physics hooks, event handler, force generators, and physics pipeline of a world are
+/// not part of its snapshot. A `QueryPipeline` must be updated after a snapshot is restored.
+///
+/// Snapshots only support the Sweep-and-Prune `BroadPhase`, which is the broad-phase of every
+/// `PhysicsWorld`. The state of a simulation stepped with another `BroadPhaseBackend`, e.g. a
+/// `BvhBroadPhase`, can't be captured by a snapshot: its broad-phase must be saved separately.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
+pub struct PhysicsSnapshot {
+    /// The gravity applied to the dynamic rigid-bodies.
+    pub gravity: Vector<Real>,
+    /// The integration parameters.
+    pub integration_parameters: IntegrationParameters,
+    /// The broad-phase, which is always a Sweep-and-Prune.
+    pub broad_phase: BroadPhase,
+    /// The narrow-phase, including the contact manifolds and their impulses.
+    pub narrow_phase: NarrowPhase,
+    /// The rigid-bodies, including the active islands.
+    pub bodies: RigidBodySet,
+    /// The colliders.
+    pub colliders: ColliderSet,
+    /// The joints.
+    pub joints: JointSet,
+    /// The CCD solver.
+    pub ccd_solver: CCDSolver,
+}
+
+impl PhysicsSnapshot {
+    /// Captures the current state of the given world.
+    pub fn capture(world: &PhysicsWorld) -> Self {
+        Self {
+            gravity: world.gravity,
+            integration_parameters: world.integration_parameters,
+            broad_phase: world.broad_phase.clone(),
+            narrow_phase: world.narrow_phase.clone(),
+            bodies: world.bodies.clone(),
+            colliders: world.colliders.clone(),
+            joints: world.joints.clone(),
+            ccd_solver: world.ccd_solver.clone(),
+        }
+    }
+
+    /// Replaces the state of the given world by this snapshot.
+    ///
+    /// The physics hooks, event handler, and force generators of the world are kept.
+    pub fn restore(self, world: &mut PhysicsWorld) {
+        world.gravity = self.gravity;
+        world.integration_parameters = self.integration_parameters;
+        world.broad_phase = self.broad_phase;
+        world.narrow_phase = self.narrow_phase;
+        world.bodies = self.bodies;
+        world.colliders = self.colliders;
+        world.joints = self.joints;
+        world.ccd_solver = self.ccd_solver;
+    }
+
+    /// Creates a new world with the state of this snapshot, without any physics hooks, event
+    /// handler, nor force generators.
+    pub fn into_world(self) -> PhysicsWorld {
+        PhysicsWorld {
+            gravity: self.gravity,
+            integration_parameters: self.integration_parameters,
+            pipeline: PhysicsPipeline::new(),
+            broad_phase: self.broad_phase,
+            narrow_phase: self.narrow_phase,
+            bodies: self.bodies,
+            colliders: self.colliders,
+            joints: self.joints,
+            ccd_solver: self.ccd_solver,
+            force_generators: ForceGeneratorSet::new(),
+            hooks: Box::new(()),
+            events: Box::new(()),
+        }
+    }
+
+    /// A checksum of the state of the world captured by this snapshot evolving during the simulation.
+    ///
+    /// See `state_checksum` for details.
+    pub fn checksum(&self) -> u64 {
+        state_checksum(&self.bodies, &self.joints, &self.narrow_phase)
+    }
+
+    /// Serializes this snapshot to a binary representation.
+    ///
+    /// The floating-point values are stored exactly, and the serialized data start with a
+    /// versioned header so that `Self::deserialize` rejects data serialized with another
+    /// version of the format, or with another dimension or floating-point precision.
+    #[cfg(feature = "serde-serialize")]
+    pub fn serialize(&self) -> Result<Vec<u8>, PhysicsSnapshotError> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN);
+        bytes.extend_from_slice(PHYSICS_SNAPSHOT_MAGIC);
+        bytes.extend_from_slice(&PHYSICS_SNAPSHOT_VERSION.to_le_bytes());
+        bytes.push(DIM as u8);
+        bytes.push(std::mem::size_of::<Real>() as u8);
+
+        bincode::serialize_into(&mut bytes, self)
+            .map_err(|e| PhysicsSnapshotError::Serialization(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    /// Loads a snapshot from the binary representation output by `Self::serialize`.
+    #[cfg(feature = "serde-serialize")]
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, PhysicsSnapshotError> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != PHYSICS_SNAPSHOT_MAGIC {
+            return Err(PhysicsSnapshotError::InvalidHeader);
+        }
+
+        let mut version = [0; 4];
+        version.copy_from_slice(&bytes[4..8]);
+        let version = u32::from_le_bytes(version);
+
+        if version != PHYSICS_SNAPSHOT_VERSION {
+            return Err(PhysicsSnapshotError::UnsupportedVersion(version));
+        }
+
+        if bytes[8] as usize != DIM || bytes[9] as usize != std::mem::size_of::<Real>() {
+            return Err(PhysicsSnapshotError::IncompatibleBuild);
+        }
+
+        bincode::deserialize(&bytes[HEADER_LEN..])
+            .map_err(|e| PhysicsSnapshotError::Serialization(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PhysicsSnapshot;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Real, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    fn build_world() -> PhysicsWorld {
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let ground_collider = ColliderBuilder::halfspace(Vector::y_axis()).build();
+        world
+            .colliders
+            .insert(ground_collider, ground, &mut world.bodies);
+
+        // A stack of boxes, so that the contact impulses are warm-started between timesteps.
+        for i in 0..4 {
+            #[cfg(feature = "dim2")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.5 + i as Real * 1.01);
+            #[cfg(feature = "dim3")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.5 + i as Real * 1.01, 0.0);
+            let handle = world.bodies.insert(rb.build());
+            #[cfg(feature = "dim2")]
+            let collider = ColliderBuilder::cuboid(0.5, 0.5).build();
+            #[cfg(feature = "dim3")]
+            let collider = ColliderBuilder::cuboid(0.5, 0.5, 0.5).build();
+            world.colliders.insert(collider, handle, &mut world.bodies);
+        }
+
+        world
+    }
+
+    #[test]
+    fn restored_snapshot_continues_identically() {
+        let mut world = build_world();
+
+        for _ in 0..20 {
+            world.step(1.0 / 60.0);
+        }
+
+        let mut world2 = PhysicsSnapshot::capture(&world).into_world();
+
+        for _ in 0..20 {
+            world.step(1.0 / 60.0);
+            world2.step(1.0 / 60.0);
+        }
+
+        for (handle, body) in world.bodies.iter() {
+            assert_eq!(body.position(), world2.bodies[handle].position());
+            assert_eq!(body.linvel(), world2.bodies[handle].linvel());
+        }
+    }
+
+    #[cfg(feature = "serde-serialize")]
+    #[test]
+    fn serialized_snapshot_roundtrip() {
+        use super::PhysicsSnapshotError;
+
+        let mut world = build_world();
+
+        for _ in 0..20 {
+            world.step(1.0 / 60.0);
+        }
+
+        let bytes = PhysicsSnapshot::capture(&world).serialize().unwrap();
+        let mut world2 = PhysicsWorld::new(Vector::zeros());
+        PhysicsSnapshot::deserialize(&bytes)
+            .unwrap()
+            .restore(&mut world2);
+
+        // The contacts and their warm-start impulses are restored.
+        let impulses = |world: &PhysicsWorld| {
+            world
+                .narrow_phase
+                .contact_pairs()
+                .flat_map(|pair| pair.manifolds.iter())
+                .flat_map(|manifold| manifold.points.iter())
+                .map(|contact| contact.data.impulse)
+                .collect::<Vec<_>>()
+        };
+        assert!(impulses(&world).iter().any(|impulse| *impulse != 0.0));
+        assert_eq!(impulses(&world), impulses(&world2));
+
+        for _ in 0..20 {
+            world.step(1.0 / 60.0);
+            world2.step(1.0 / 60.0);
+        }
+
+        for (handle, body) in world.bodies.iter() {
+            assert_eq!(body.position(), world2.bodies[handle].position());
+        }
+
+        assert_eq!(
+            PhysicsSnapshot::deserialize(&bytes[..4]).err(),
+            Some(PhysicsSnapshotError::InvalidHeader)
+        );
+    }
+}
@@ -1,6 +1,10 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::data::Coarena;
 use crate::dynamics::RigidBodySet;
 use crate::geometry::{
-    Collider, ColliderHandle, ColliderSet, InteractionGroups, PointProjection, Ray,
+    ClosestPoints, Collider, ColliderHandle, ColliderSet, InteractionGroups, PointProjection, Ray,
     RayIntersection, SimdQuadTree, AABB,
 };
 use crate::math::{Isometry, Point, Real, Vector};
@@ -19,6 +23,11 @@ use parry::shape::{FeatureId, Shape, TypedSimdCompositeShape};
 use std::sync::Arc;
 
 /// A pipeline for performing queries on all the colliders of a scene.
+///
+/// Besides the interaction groups, most queries accept an optional `filter` closure called with
+/// the handle and a reference of each candidate collider. This is useful for filters that can't
+/// be expressed with interaction groups, e.g., ignoring all the colliders attached to the
+/// rigid-body performing the query with `|_, co| co.parent() != body_handle`.
 #[cfg_attr(feature = "serde-serialize", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct QueryPipeline {
@@ -30,6 +39,10 @@ pub struct QueryPipeline {
     quadtree: SimdQuadTree<ColliderHandle>,
     tree_built: bool,
     dilation_factor: Real,
+    // The collider positions used by the queries if the last update used
+    // the `QueryPipelineMode::NextPosition` mode.
+    next_positions: Coarena<Option<Isometry<Real>>>,
+    use_next_positions: bool,
 }
 
 struct QueryPipelineAsCompositeShape<'a> {
@@ -53,6 +66,13 @@ pub enum QueryPipelineMode {
         /// The time used to integrate the rigid-body's velocity and acceleration.
         dt: Real,
     },
+    /// The `RigidBody::next_position * Collider::position_wrt_parent` is taken into account for
+    /// the colliders positions, both for their bounding boxes and for the exact shape tests of
+    /// the queries.
+    ///
+    /// This lets character controllers and cameras query the scene as it will be once the
+    /// kinematic rigid-bodies reach the next position they were given, before calling `step`.
+    NextPosition,
 }
 
 impl<'a> TypedSimdCompositeShape for QueryPipelineAsCompositeShape<'a> {
@@ -68,7 +88,8 @@ impl<'a> TypedSimdCompositeShape for QueryPipelineAsCompositeShape<'a> {
             if collider.collision_groups.test(self.query_groups)
                 && self.filter.map(|f| f(shape_id, collider)).unwrap_or(true)
             {
-                f(Some(collider.position()), collider.shape())
+                let position = self.query_pipeline.collider_position(shape_id, collider);
+                f(Some(position), collider.shape())
             }
         }
     }
@@ -125,6 +146,8 @@ impl QueryPipeline {
             quadtree: SimdQuadTree::new(),
             tree_built: false,
             dilation_factor: 0.01,
+            next_positions: Coarena::new(),
+            use_next_positions: false,
         }
     }
 
@@ -145,6 +168,9 @@ impl QueryPipeline {
         colliders: &ColliderSet,
         mode: QueryPipelineMode,
     ) {
+        self.use_next_positions = matches!(mode, QueryPipelineMode::NextPosition);
+        let next_positions = &mut self.next_positions;
+
         if !self.tree_built {
             match mode {
                 QueryPipelineMode::CurrentPosition => {
@@ -153,18 +179,24 @@ impl QueryPipeline {
                 }
                 QueryPipelineMode::SweepTestWithNextPosition => {
                     let data = colliders.iter().map(|(h, c)| {
-                        let next_position =
-                            bodies[c.parent()].next_position * c.position_wrt_parent();
-                        (h, c.compute_swept_aabb(&next_position))
+                        let next_position = bodies[c.parent()].next_position;
+                        (h, c.compute_swept_aabb_with_parent_position(&next_position))
                     });
                     self.quadtree.clear_and_rebuild(data, self.dilation_factor);
                 }
                 QueryPipelineMode::SweepTestWithPredictedPosition { dt } => {
                     let data = colliders.iter().map(|(h, c)| {
-                        let next_position = bodies[c.parent()]
-                            .predict_position_using_velocity_and_forces(dt)
-                            * c.position_wrt_parent();
-                        (h, c.compute_swept_aabb(&next_position))
+                        let next_position =
+                            bodies[c.parent()].predict_position_using_velocity_and_forces(dt);
+                        (h, c.compute_swept_aabb_with_parent_position(&next_position))
+                    });
+                    self.quadtree.clear_and_rebuild(data, self.dilation_factor);
+                }
+                QueryPipelineMode::NextPosition => {
+                    let data = colliders.iter().map(|(h, c)| {
+                        let position = bodies[c.parent()].next_position * c.position_wrt_parent();
+                        next_positions.insert(h.0, Some(position));
+                        (h, c.shape().compute_aabb(&position))
                     });
                     self.quadtree.clear_and_rebuild(data, self.dilation_factor);
                 }
@@ -195,9 +227,8 @@ impl QueryPipeline {
                 self.quadtree.update(
                     |handle| {
                         let co = &colliders[*handle];
-                        let next_position =
-                            bodies[co.parent()].next_position * co.position_wrt_parent();
-                        co.compute_swept_aabb(&next_position)
+                        let next_position = bodies[co.parent()].next_position;
+                        co.compute_swept_aabb_with_parent_position(&next_position)
                     },
                     self.dilation_factor,
                 );
@@ -206,15 +237,53 @@ impl QueryPipeline {
                 self.quadtree.update(
                     |handle| {
                         let co = &colliders[*handle];
-                        let next_position = bodies[co.parent()]
-                            .predict_position_using_velocity_and_forces(dt)
-                            * co.position_wrt_parent();
-                        co.compute_swept_aabb(&next_position)
+                        let next_position =
+                            bodies[co.parent()].predict_position_using_velocity_and_forces(dt);
+                        co.compute_swept_aabb_with_parent_position(&next_position)
                     },
                     self.dilation_factor,
                 );
             }
+            QueryPipelineMode::NextPosition => {
+                for (_, body) in bodies
+                    .iter_active_dynamic()
+                    .chain(bodies.iter_active_kinematic())
+                {
+                    for handle in &body.colliders {
+                        let position =
+                            body.next_position * colliders[*handle].position_wrt_parent();
+                        next_positions.insert(handle.0, Some(position));
+                    }
+                }
+
+                self.quadtree.update(
+                    |handle| {
+                        let co = &colliders[*handle];
+                        let position = next_positions
+                            .get(handle.0)
+                            .and_then(|position| position.as_ref())
+                            .unwrap_or_else(|| co.position());
+                        co.shape().compute_aabb(position)
+                    },
+                    self.dilation_factor,
+                );
+            }
+        }
+    }
+
+    // The position of the given collider, as taken into account by the queries.
+    fn collider_position<'a>(
+        &'a self,
+        handle: ColliderHandle,
+        collider: &'a Collider,
+    ) -> &'a Isometry<Real> {
+        if self.use_next_positions {
+            if let Some(Some(position)) = self.next_positions.get(handle.0) {
+                return position;
+            }
         }
+
+        collider.position()
     }
 
     /// Find the closest intersection between a ray and a set of collider.
@@ -250,6 +319,9 @@ impl QueryPipeline {
 
     /// Find the closest intersection between a ray and a set of collider.
     ///
+    /// The triangle of a triangle mesh (or the segment of a polyline, or the cell of a
+    /// heightfield) hit by the ray can be retrieved with `Collider::surface_location`.
+    ///
     /// # Parameters
     /// - `position`: the position of this shape.
     /// - `ray`: the ray to cast.
@@ -283,6 +355,47 @@ impl QueryPipeline {
         self.quadtree.traverse_best_first(&mut visitor).map(|h| h.1)
     }
 
+    /// Find the closest intersection between each of the given rays and a set of colliders.
+    ///
+    /// This is equivalent to calling `Self::cast_ray_and_get_normal` for each ray, but with a
+    /// lower overhead for large batches of rays (e.g. for the vision cones of many AIs, or for
+    /// simulating a lidar). With the `parallel` feature enabled, the rays are cast in parallel.
+    ///
+    /// The returned vector contains the result of each ray, in the same order as `rays`. See
+    /// `Self::cast_ray_and_get_normal` for the description of the other parameters.
+    pub fn cast_rays(
+        &self,
+        colliders: &ColliderSet,
+        rays: &[Ray],
+        max_toi: Real,
+        solid: bool,
+        query_groups: InteractionGroups,
+        filter: Option<&(dyn Fn(ColliderHandle, &Collider) -> bool + Sync)>,
+    ) -> Vec<Option<(ColliderHandle, RayIntersection)>> {
+        let cast = |ray: &Ray| {
+            let pipeline_shape = self.as_composite_shape(
+                colliders,
+                query_groups,
+                filter.map(|f| f as &dyn Fn(ColliderHandle, &Collider) -> bool),
+            );
+            let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
+                &pipeline_shape,
+                ray,
+                max_toi,
+                solid,
+            );
+
+            self.quadtree.traverse_best_first(&mut visitor).map(|h| h.1)
+        };
+
+        #[cfg(feature = "parallel")]
+        let rays = rays.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let rays = rays.iter();
+
+        rays.map(cast).collect()
+    }
+
     /// Find the all intersections between a ray and a set of collider and passes them to a callback.
     ///
     /// # Parameters
@@ -316,9 +429,11 @@ impl QueryPipeline {
                 if coll.collision_groups.test(query_groups)
                     && filter.map(|f| f(*handle, coll)).unwrap_or(true)
                 {
-                    if let Some(hit) =
-                        coll.shape()
-                            .cast_ray_and_get_normal(coll.position(), ray, max_toi, solid)
+                    let position = self.collider_position(*handle, coll);
+
+                    if let Some(hit) = coll
+                        .shape()
+                        .cast_ray_and_get_normal(position, ray, max_toi, solid)
                     {
                         return callback(*handle, coll, hit);
                     }
@@ -332,6 +447,44 @@ impl QueryPipeline {
         self.quadtree.traverse_depth_first(&mut visitor);
     }
 
+    /// Finds a collider that would prevent the capsule of the given collider from being resized.
+    ///
+    /// Returns the handle of a collider intersecting the capsule of the collider `collider_handle`
+    /// once resized with the given half-height and radius, if any. The capsule is resized the
+    /// same way as `Collider::resized_capsule`, i.e., its bottom doesn't move.
+    ///
+    /// Colliders attached to the same rigid-body as `collider_handle`, and colliders already
+    /// intersecting its current capsule (e.g. the ground the character rests on) are ignored.
+    /// This is typically used for checking if a crouching character can stand up without
+    /// hitting a ceiling, before calling `Collider::set_capsule_dimensions`.
+    ///
+    /// Panics if the collider does not exist or if its shape is not a capsule.
+    pub fn capsule_resize_obstacle(
+        &self,
+        colliders: &ColliderSet,
+        collider_handle: ColliderHandle,
+        half_height: Real,
+        radius: Real,
+        query_groups: InteractionGroups,
+    ) -> Option<ColliderHandle> {
+        let collider = &colliders[collider_handle];
+        let capsule = collider
+            .resized_capsule(half_height, radius)
+            .expect("The collider shape is not a capsule.");
+        let parent = collider.parent();
+        let position = self.collider_position(collider_handle, collider);
+        let filter = |other_handle: ColliderHandle, other: &Collider| {
+            let pos12 = position.inv_mul(self.collider_position(other_handle, other));
+            other.parent() != parent
+                && !self
+                    .query_dispatcher
+                    .intersection_test(&pos12, collider.shape(), other.shape())
+                    .unwrap_or(false)
+        };
+
+        self.intersection_with_shape(colliders, position, &capsule, query_groups, Some(&filter))
+    }
+
     /// Gets the handle of up to one collider intersecting the given shape.
     ///
     /// # Parameters
@@ -420,7 +573,9 @@ impl QueryPipeline {
             if let Some(coll) = colliders.get(*handle) {
                 if coll.collision_groups.test(query_groups)
                     && filter.map(|f| f(*handle, coll)).unwrap_or(true)
-                    && coll.shape().contains_point(coll.position(), point)
+                    && coll
+                        .shape()
+                        .contains_point(self.collider_position(*handle, coll), point)
                 {
                     return callback(*handle, coll);
                 }
@@ -434,6 +589,34 @@ impl QueryPipeline {
         self.quadtree.traverse_depth_first(&mut visitor);
     }
 
+    /// Gets the handles of all the colliders containing the given point, e.g., for picking
+    /// the colliders under the mouse cursor.
+    ///
+    /// See `intersections_with_point` for a version that doesn't allocate.
+    ///
+    /// # Parameters
+    /// * `colliders` - The set of colliders taking part in this pipeline.
+    /// * `point` - The point used for the containment test.
+    /// * `query_groups` - the interaction groups which will be tested against the collider's `contact_group`
+    ///                   to determine if it should be taken into account by this query.
+    /// * `filter` - a more fine-grained filter. A collider is taken into account by this query if
+    ///             its `contact_group` is compatible with the `query_groups`, and if this `filter`
+    ///             is either `None` or returns `true`.
+    pub fn colliders_containing_point(
+        &self,
+        colliders: &ColliderSet,
+        point: &Point<Real>,
+        query_groups: InteractionGroups,
+        filter: Option<&dyn Fn(ColliderHandle, &Collider) -> bool>,
+    ) -> Vec<ColliderHandle> {
+        let mut result = Vec::new();
+        self.intersections_with_point(colliders, point, query_groups, filter, |handle, _| {
+            result.push(handle);
+            true
+        });
+        result
+    }
+
     /// Find the projection of a point on the closest collider.
     ///
     /// The results include the ID of the feature hit by the point.
@@ -561,12 +744,68 @@ impl QueryPipeline {
         self.quadtree.traverse_best_first(&mut visitor).map(|h| h.1)
     }
 
+    /// Sweeps a shape from a start position to an end position, rotating it around a fixed
+    /// point, and retrieve the first collider it hits.
+    ///
+    /// Unlike `cast_shape`, this takes the rotation of the shape into account all along its
+    /// motion, which is needed for accurately sweeping spinning or swinging shapes (e.g. melee
+    /// weapons or doors rotating around their hinge). The shape moves with a constant linear
+    /// velocity of its `local_center`, and a constant angular velocity around it. The time of
+    /// impact of the result is a fraction of the motion, between `0.0` and `1.0`.
+    ///
+    /// # Parameters
+    /// * `colliders` - The set of colliders taking part in this pipeline.
+    /// * `shape_start` - The position of the shape at the beginning of its motion.
+    /// * `shape_end` - The position of the shape at the end of its motion.
+    /// * `local_center` - The center of rotation, expressed in the local-space of the shape.
+    /// * `shape` - The shape to cast.
+    /// * `stop_at_penetration` - If the casted shape starts in a penetration state with any
+    ///    collider, two results are possible. If `stop_at_penetration` is `true` then, the
+    ///    result will have a `toi` equal to `0.0`. Otherwise, see `nonlinear_cast_shape`.
+    /// * `query_groups` - the interaction groups which will be tested against the collider's `contact_group`
+    ///                   to determine if it should be taken into account by this query.
+    /// * `filter` - a more fine-grained filter. A collider is taken into account by this query if
+    ///             its `contact_group` is compatible with the `query_groups`, and if this `filter`
+    ///             is either `None` or returns `true`.
+    pub fn sweep_shape(
+        &self,
+        colliders: &ColliderSet,
+        shape_start: &Isometry<Real>,
+        shape_end: &Isometry<Real>,
+        local_center: &Point<Real>,
+        shape: &dyn Shape,
+        stop_at_penetration: bool,
+        query_groups: InteractionGroups,
+        filter: Option<&dyn Fn(ColliderHandle, &Collider) -> bool>,
+    ) -> Option<(ColliderHandle, TOI)> {
+        let linvel = shape_end * local_center - shape_start * local_center;
+        let rotation = shape_end.rotation * shape_start.rotation.inverse();
+        #[cfg(feature = "dim2")]
+        let angvel = rotation.angle();
+        #[cfg(feature = "dim3")]
+        let angvel = rotation.scaled_axis();
+
+        let shape_motion = NonlinearRigidMotion::new(*shape_start, *local_center, linvel, angvel);
+        self.nonlinear_cast_shape(
+            colliders,
+            &shape_motion,
+            shape,
+            0.0,
+            1.0,
+            stop_at_penetration,
+            query_groups,
+            filter,
+        )
+    }
+
     /// Retrieve all the colliders intersecting the given shape.
     ///
+    /// This is useful for area-of-effect queries, without having to insert a temporary
+    /// sensor collider and wait for the next step for its intersection events.
+    ///
     /// # Parameters
     /// * `colliders` - The set of colliders taking part in this pipeline.
-    /// * `shapePos` - The position of the shape to test.
-    /// * `shapeRot` - The orientation of the shape to test.
+    /// * `shape_pos` - The position of the shape to test.
     /// * `shape` - The shape to test.
     /// * `query_groups` - the interaction groups which will be tested against the collider's `contact_group`
     ///                   to determine if it should be taken into account by this query.
@@ -591,7 +830,7 @@ impl QueryPipeline {
                 if coll.collision_groups.test(query_groups)
                     && filter.map(|f| f(*handle, coll)).unwrap_or(true)
                 {
-                    let pos12 = inv_shape_pos * coll.position();
+                    let pos12 = inv_shape_pos * self.collider_position(*handle, coll);
 
                     if dispatcher.intersection_test(&pos12, shape, coll.shape()) == Ok(true) {
                         return callback(*handle, coll);
@@ -607,4 +846,461 @@ impl QueryPipeline {
 
         self.quadtree.traverse_depth_first(&mut visitor);
     }
+
+    /// Computes the closest points between two colliders.
+    ///
+    /// The points are expressed in world-space. Returns `None` if one of the colliders does not
+    /// exist, or if the closest points between their shapes are not supported by the query
+    /// dispatcher of this pipeline.
+    ///
+    /// # Parameters
+    /// * `colliders` - The set of colliders taking part in this pipeline.
+    /// * `handle1` - The handle of the first collider.
+    /// * `handle2` - The handle of the second collider.
+    /// * `max_dist` - The maximum distance between the colliders. If they are further apart,
+    ///   the result is `ClosestPoints::Disjoint`.
+    pub fn closest_points(
+        &self,
+        colliders: &ColliderSet,
+        handle1: ColliderHandle,
+        handle2: ColliderHandle,
+        max_dist: Real,
+    ) -> Option<ClosestPoints> {
+        let co1 = colliders.get(handle1)?;
+        let co2 = colliders.get(handle2)?;
+        let pos1 = self.collider_position(handle1, co1);
+        let pos2 = self.collider_position(handle2, co2);
+        let pos12 = pos1.inv_mul(pos2);
+
+        self.query_dispatcher
+            .closest_points(&pos12, co1.shape(), co2.shape(), max_dist)
+            .ok()
+            .map(|points| points.transform_by(pos1, pos2))
+    }
+
+    /// Computes the distance between a collider and a shape.
+    ///
+    /// The distance is zero if they are intersecting. Returns `None` if the collider does not
+    /// exist, or if the distance between its shape and `shape` is not supported by the query
+    /// dispatcher of this pipeline. Use `closest_points` for the corresponding witness points.
+    ///
+    /// # Parameters
+    /// * `colliders` - The set of colliders taking part in this pipeline.
+    /// * `handle` - The handle of the collider.
+    /// * `shape_pos` - The position of the shape.
+    /// * `shape` - The shape.
+    pub fn distance(
+        &self,
+        colliders: &ColliderSet,
+        handle: ColliderHandle,
+        shape_pos: &Isometry<Real>,
+        shape: &dyn Shape,
+    ) -> Option<Real> {
+        let co = colliders.get(handle)?;
+        let pos12 = self.collider_position(handle, co).inv_mul(shape_pos);
+
+        self.query_dispatcher
+            .distance(&pos12, co.shape(), shape)
+            .ok()
+    }
+
+    /// Computes the closest points between a collider and a shape.
+    ///
+    /// The points are expressed in world-space, the first one being on the collider. Returns
+    /// `None` if the collider does not exist, or if the closest points between its shape and
+    /// `shape` are not supported by the query dispatcher of this pipeline.
+    ///
+    /// # Parameters
+    /// * `colliders` - The set of colliders taking part in this pipeline.
+    /// * `handle` - The handle of the collider.
+    /// * `shape_pos` - The position of the shape.
+    /// * `shape` - The shape.
+    /// * `max_dist` - The maximum distance between the collider and the shape. If they are
+    ///   further apart, the result is `ClosestPoints::Disjoint`.
+    pub fn closest_points_with_shape(
+        &self,
+        colliders: &ColliderSet,
+        handle: ColliderHandle,
+        shape_pos: &Isometry<Real>,
+        shape: &dyn Shape,
+        max_dist: Real,
+    ) -> Option<ClosestPoints> {
+        let co = colliders.get(handle)?;
+        let position = self.collider_position(handle, co);
+        let pos12 = position.inv_mul(shape_pos);
+
+        self.query_dispatcher
+            .closest_points(&pos12, co.shape(), shape, max_dist)
+            .ok()
+            .map(|points| points.transform_by(position, shape_pos))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{QueryPipeline, QueryPipelineMode};
+    use crate::dynamics::{RigidBodyBuilder, RigidBodySet};
+    use crate::geometry::{Ball, Collider, ColliderBuilder, ColliderHandle, ColliderSet};
+    use crate::geometry::{ClosestPoints, Cuboid, InteractionGroups, Ray};
+    use crate::math::{Isometry, Point, Real, Vector};
+
+    #[test]
+    fn closure_filters() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        // A character made of two colliders at the origin, and a wall further along `x`.
+        let character = bodies.insert(RigidBodyBuilder::new_dynamic().build());
+        colliders.insert(ColliderBuilder::ball(0.5).build(), character, &mut bodies);
+        colliders.insert(
+            ColliderBuilder::ball(0.25)
+                .position_wrt_parent(Isometry::new(Vector::x() * 0.5, na::zero()))
+                .build(),
+            character,
+            &mut bodies,
+        );
+
+        let wall = bodies.insert(
+            RigidBodyBuilder::new_static()
+                .position(Isometry::new(Vector::x() * 5.0, na::zero()))
+                .build(),
+        );
+        let wall_co = colliders.insert(ColliderBuilder::ball(0.5).build(), wall, &mut bodies);
+
+        let mut pipeline = QueryPipeline::new();
+        pipeline.update(&bodies, &colliders);
+
+        // Ignore all the colliders of the character.
+        let filter = |_: ColliderHandle, co: &Collider| co.parent() != character;
+        let groups = InteractionGroups::all();
+
+        let ray = Ray::new(Point::origin(), Vector::x());
+        let (hit, toi) = pipeline
+            .cast_ray(&colliders, &ray, Real::MAX, true, groups, Some(&filter))
+            .unwrap();
+        assert_eq!(hit, wall_co);
+        assert!((toi - 4.5).abs() < 1.0e-5);
+        assert_ne!(
+            pipeline
+                .cast_ray(&colliders, &ray, Real::MAX, true, groups, None)
+                .unwrap()
+                .0,
+            wall_co
+        );
+
+        let (hit, _) = pipeline
+            .cast_shape(
+                &colliders,
+                &Isometry::identity(),
+                &Vector::x(),
+                &Ball::new(0.1),
+                Real::MAX,
+                groups,
+                Some(&filter),
+            )
+            .unwrap();
+        assert_eq!(hit, wall_co);
+
+        let (hit, _) = pipeline
+            .project_point(&colliders, &Point::origin(), true, groups, Some(&filter))
+            .unwrap();
+        assert_eq!(hit, wall_co);
+    }
+
+    #[test]
+    fn batch_ray_casts() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+        for i in 0..4 {
+            let co = ColliderBuilder::ball(0.5)
+                .position_wrt_parent(Isometry::new(Vector::x() * (i as Real * 2.0), na::zero()))
+                .build();
+            colliders.insert(co, ground, &mut bodies);
+        }
+
+        let mut pipeline = QueryPipeline::new();
+        pipeline.update(&bodies, &colliders);
+
+        // A fan of downward rays, some of them missing all the balls.
+        let rays: Vec<_> = (0..16)
+            .map(|i| {
+                let origin = Point::from(Vector::x() * (i as Real * 0.5) + Vector::y() * 2.0);
+                Ray::new(origin, -Vector::y())
+            })
+            .collect();
+        let groups = InteractionGroups::all();
+        let filter = |_: ColliderHandle, co: &Collider| co.shape().as_ball().is_some();
+        let hits = pipeline.cast_rays(&colliders, &rays, 10.0, true, groups, Some(&filter));
+
+        assert_eq!(hits.len(), rays.len());
+        assert!(hits.iter().any(|hit| hit.is_none()));
+
+        for (ray, hit) in rays.iter().zip(hits.iter()) {
+            let expected =
+                pipeline.cast_ray_and_get_normal(&colliders, ray, 10.0, true, groups, None);
+            assert_eq!(
+                hit.as_ref().map(|(handle, inter)| (*handle, inter.toi)),
+                expected.map(|(handle, inter)| (handle, inter.toi))
+            );
+        }
+    }
+
+    // The position of a blade of length 2 centered at `(1, 0)` after rotating by `angle`
+    // around the origin.
+    #[cfg(feature = "dim2")]
+    fn blade_position(angle: Real) -> Isometry<Real> {
+        Isometry::new(Vector::new(angle.cos(), angle.sin()), angle)
+    }
+
+    #[cfg(feature = "dim3")]
+    fn blade_position(angle: Real) -> Isometry<Real> {
+        Isometry::new(
+            Vector::new(angle.cos(), angle.sin(), 0.0),
+            Vector::z() * angle,
+        )
+    }
+
+    #[test]
+    fn rotating_shape_sweep() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        // An obstacle lying on the path of the blade tip, but not on the straight line between
+        // its start and end positions.
+        let obstacle = bodies.insert(
+            RigidBodyBuilder::new_static()
+                .position(Isometry::new(Vector::y(), na::zero()))
+                .build(),
+        );
+        let obstacle_co =
+            colliders.insert(ColliderBuilder::ball(0.2).build(), obstacle, &mut bodies);
+
+        let mut pipeline = QueryPipeline::new();
+        pipeline.update(&bodies, &colliders);
+
+        let blade = Cuboid::new(Vector::repeat(0.05) + Vector::x() * 0.95);
+        let start = blade_position(0.0);
+        let end = blade_position(0.9 * std::f64::consts::PI as Real);
+        let hinge = Point::from(-Vector::x());
+        let groups = InteractionGroups::all();
+
+        let (hit, toi) = pipeline
+            .sweep_shape(&colliders, &start, &end, &hinge, &blade, true, groups, None)
+            .unwrap();
+        assert_eq!(hit, obstacle_co);
+        assert!(toi.toi > 0.3 && toi.toi < 0.6);
+
+        // A linear cast misses the obstacle.
+        let linear_hit = pipeline.cast_shape(
+            &colliders,
+            &start,
+            &(end.translation.vector - start.translation.vector),
+            &blade,
+            1.0,
+            groups,
+            None,
+        );
+        assert!(linear_hit.is_none());
+    }
+
+    #[test]
+    fn closest_points_and_distance() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+        let co1 = colliders.insert(ColliderBuilder::ball(0.5).build(), ground, &mut bodies);
+        let co2 = colliders.insert(
+            ColliderBuilder::ball(1.0)
+                .position_wrt_parent(Isometry::new(Vector::x() * 4.0, na::zero()))
+                .build(),
+            ground,
+            &mut bodies,
+        );
+
+        let pipeline = QueryPipeline::new();
+
+        match pipeline.closest_points(&colliders, co1, co2, 10.0) {
+            Some(ClosestPoints::WithinMargin(p1, p2)) => {
+                assert!((p1 - Point::from(Vector::x() * 0.5)).norm() < 1.0e-5);
+                assert!((p2 - Point::from(Vector::x() * 3.0)).norm() < 1.0e-5);
+            }
+            result => panic!("Unexpected closest points: {:?}", result),
+        }
+        assert_eq!(
+            pipeline.closest_points(&colliders, co1, co2, 1.0),
+            Some(ClosestPoints::Disjoint)
+        );
+
+        let shape_pos = Isometry::new(-Vector::x() * 2.0, na::zero());
+        let dist = pipeline
+            .distance(&colliders, co1, &shape_pos, &Ball::new(0.5))
+            .unwrap();
+        assert!((dist - 1.0).abs() < 1.0e-5);
+
+        match pipeline.closest_points_with_shape(&colliders, co1, &shape_pos, &Ball::new(0.5), 10.0)
+        {
+            Some(ClosestPoints::WithinMargin(p1, p2)) => {
+                assert!((p1 - Point::from(-Vector::x() * 0.5)).norm() < 1.0e-5);
+                assert!((p2 - Point::from(-Vector::x() * 1.5)).norm() < 1.0e-5);
+            }
+            result => panic!("Unexpected closest points: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn point_and_shape_intersections() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+        let handles: Vec<_> = (0..3)
+            .map(|i| {
+                let co = ColliderBuilder::ball(1.0)
+                    .position_wrt_parent(Isometry::new(Vector::x() * (i as Real * 1.5), na::zero()))
+                    .build();
+                colliders.insert(co, ground, &mut bodies)
+            })
+            .collect();
+
+        let mut pipeline = QueryPipeline::new();
+        pipeline.update(&bodies, &colliders);
+        let groups = InteractionGroups::all();
+
+        let mut picked = pipeline.colliders_containing_point(
+            &colliders,
+            &Point::from(Vector::x() * 0.75),
+            groups,
+            None,
+        );
+        picked.sort_by_key(|handle| handle.into_raw_parts());
+        assert_eq!(picked, vec![handles[0], handles[1]]);
+        assert!(pipeline
+            .colliders_containing_point(&colliders, &Point::from(Vector::y() * 2.0), groups, None)
+            .is_empty());
+
+        // An explosion at the end of the row only reaches the last two balls.
+        let mut hit = Vec::new();
+        pipeline.intersections_with_shape(
+            &colliders,
+            &Isometry::new(Vector::x() * 4.0, na::zero()),
+            &Ball::new(1.75),
+            groups,
+            None,
+            |handle, _| {
+                hit.push(handle);
+                true
+            },
+        );
+        hit.sort_by_key(|handle| handle.into_raw_parts());
+        assert_eq!(hit, vec![handles[1], handles[2]]);
+    }
+
+    #[test]
+    fn queries_with_next_positions() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        // A platform that will move up by 1 at the next step.
+        let platform = bodies.insert(RigidBodyBuilder::new_kinematic().build());
+        let platform_co =
+            colliders.insert(ColliderBuilder::ball(0.5).build(), platform, &mut bodies);
+        bodies
+            .get_mut(platform)
+            .unwrap()
+            .set_next_kinematic_position(Isometry::new(Vector::y(), na::zero()));
+
+        let ray = Ray::new(Point::from(Vector::y() * 5.0), -Vector::y());
+        let groups = InteractionGroups::all();
+        let mut pipeline = QueryPipeline::new();
+
+        pipeline.update(&bodies, &colliders);
+        let (_, toi) = pipeline
+            .cast_ray(&colliders, &ray, Real::MAX, true, groups, None)
+            .unwrap();
+        assert!((toi - 4.5).abs() < 1.0e-5);
+
+        pipeline.update_with_mode(&bodies, &colliders, QueryPipelineMode::NextPosition);
+        let (hit, toi) = pipeline
+            .cast_ray(&colliders, &ray, Real::MAX, true, groups, None)
+            .unwrap();
+        assert_eq!(hit, platform_co);
+        assert!((toi - 3.5).abs() < 1.0e-5);
+        assert_eq!(
+            pipeline.colliders_containing_point(
+                &colliders,
+                &Point::from(Vector::y()),
+                groups,
+                None
+            ),
+            vec![platform_co]
+        );
+    }
+
+    #[test]
+    fn capsule_resize_obstacle() {
+        let mut bodies = RigidBodySet::new();
+        let mut colliders = ColliderSet::new();
+
+        let ground = bodies.insert(RigidBodyBuilder::new_static().build());
+        colliders.insert(
+            ColliderBuilder::halfspace(Vector::y_axis()).build(),
+            ground,
+            &mut bodies,
+        );
+
+        // A crouching character, slightly penetrating the ground.
+        let character = bodies.insert(
+            RigidBodyBuilder::new_dynamic()
+                .position(Isometry::new(Vector::y() * 0.74, na::zero()))
+                .build(),
+        );
+        let capsule = colliders.insert(
+            ColliderBuilder::capsule_y(0.25, 0.5).build(),
+            character,
+            &mut bodies,
+        );
+
+        let mut pipeline = QueryPipeline::new();
+        pipeline.update(&bodies, &colliders);
+        let groups = InteractionGroups::all();
+
+        // The ground doesn't prevent the character from standing up.
+        assert_eq!(
+            pipeline.capsule_resize_obstacle(&colliders, capsule, 1.0, 0.5, groups),
+            None
+        );
+
+        // A ceiling does.
+        let ceiling = bodies.insert(
+            RigidBodyBuilder::new_static()
+                .position(Isometry::new(Vector::y() * 2.5, na::zero()))
+                .build(),
+        );
+        let ceiling_co = colliders.insert(
+            ColliderBuilder::halfspace(-Vector::y_axis()).build(),
+            ceiling,
+            &mut bodies,
+        );
+        pipeline.update(&bodies, &colliders);
+        assert_eq!(
+            pipeline.capsule_resize_obstacle(&colliders, capsule, 1.0, 0.5, groups),
+            Some(ceiling_co)
+        );
+
+        // Resizing keeps the bottom of the capsule in place.
+        let bottom = colliders[capsule].compute_aabb().mins.y;
+        assert!(colliders[capsule]
+            .set_capsule_dimensions(1.0, 0.4)
+            .is_some());
+        assert!((colliders[capsule].compute_aabb().mins.y - bottom).abs() < 1.0e-5);
+        assert!((colliders[capsule].compute_aabb().maxs.y - bottom - 2.8).abs() < 1.0e-5);
+
+        // Only capsules can be resized.
+        assert!(colliders[ceiling_co]
+            .set_capsule_dimensions(1.0, 0.4)
+            .is_none());
+    }
 }
@@ -0,0 +1,265 @@
+use crate::dynamics::{ActivationStatus, RigidBodyHandle, RigidBodySet};
+use crate::geometry::{ColliderHandle, ContactData, ContactManifoldData, NarrowPhase};
+use crate::math::{AngVector, Isometry, Real, Vector};
+use crate::pipeline::PhysicsWorld;
+use parry::shape::PackedFeatureId;
+
+#[derive(Copy, Clone, Debug)]
+struct SavedBody {
+    handle: RigidBodyHandle,
+    position: Isometry<Real>,
+    linvel: Vector<Real>,
+    angvel: AngVector<Real>,
+    activation: ActivationStatus,
+}
+
+type PairKey = ((usize, u64), (usize, u64));
+
+#[derive(Copy, Clone, Debug)]
+struct SavedContactPair {
+    key: PairKey,
+    // The range of the manifolds of this pair in `RollbackBuffer::manifolds`.
+    manifolds: (usize, usize),
+}
+
+#[derive(Copy, Clone, Debug)]
+struct SavedManifold {
+    subshape1: u32,
+    subshape2: u32,
+    warmstart_multiplier: Real,
+    // The range of the contacts of this manifold in `RollbackBuffer::contacts`.
+    contacts: (usize, usize),
+}
+
+#[derive(Copy, Clone, Debug)]
+struct SavedContact {
+    fid1: PackedFeatureId,
+    fid2: PackedFeatureId,
+    data: ContactData,
+}
+
+fn pair_key(collider1: ColliderHandle, collider2: ColliderHandle) -> PairKey {
+    (collider1.into_raw_parts(), collider2.into_raw_parts())
+}
+
+/// A buffer for quickly saving and restoring the state of a simulation evolving over time.
+///
+/// Unlike a `PhysicsSnapshot`, this only contains the state of the non-static rigid-bodies
+/// (positions, velocities, and activation status) and the warm-start data of the contacts
+/// (impulses). The buffer is reused by every capture, so capturing the state of a world
+/// doesn't allocate once the buffer grew large enough. This makes it suitable for rollback
+/// networking, where the state of the simulation is captured at every timestep, and restored
+/// whenever a late input is received.
+///
+/// The contacts are not created nor removed by `RollbackBuffer::restore`: the warm-start data
+/// of the contacts existing when the buffer is restored are replaced by those of the contacts
+/// with the same colliders and features when it was captured, or reset if there was none.
+/// The restored simulation is identical to the original one only if the set of contact pairs
+/// didn't change in-between, which is usually the case for the few timesteps of a rollback.
+/// Colliders, joints, and rigid-bodies must not be added or removed in-between either. Use a
+/// `PhysicsSnapshot` for restoring the complete state of a world.
+#[derive(Clone, Debug, Default)]
+pub struct RollbackBuffer {
+    bodies: Vec<SavedBody>,
+    contact_pairs: Vec<SavedContactPair>,
+    manifolds: Vec<SavedManifold>,
+    contacts: Vec<SavedContact>,
+}
+
+impl RollbackBuffer {
+    /// Creates an empty rollback buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty rollback buffer with enough capacity for the given number of
+    /// rigid-bodies and contact points.
+    pub fn with_capacity(num_bodies: usize, num_contacts: usize) -> Self {
+        Self {
+            bodies: Vec::with_capacity(num_bodies),
+            contact_pairs: Vec::with_capacity(num_contacts),
+            manifolds: Vec::with_capacity(num_contacts),
+            contacts: Vec::with_capacity(num_contacts),
+        }
+    }
+
+    /// Replaces the content of this buffer by the current state of the given rigid-bodies
+    /// and contacts.
+    pub fn capture(&mut self, bodies: &RigidBodySet, narrow_phase: &NarrowPhase) {
+        self.bodies.clear();
+        self.contact_pairs.clear();
+        self.manifolds.clear();
+        self.contacts.clear();
+
+        for (handle, rb) in bodies.iter() {
+            if !rb.is_static() {
+                self.bodies.push(SavedBody {
+                    handle,
+                    position: rb.position,
+                    linvel: rb.linvel,
+                    angvel: rb.angvel,
+                    activation: rb.activation,
+                });
+            }
+        }
+
+        for pair in narrow_phase.contact_pairs() {
+            let first_manifold = self.manifolds.len();
+
+            for manifold in &pair.manifolds {
+                let first_contact = self.contacts.len();
+                self.contacts
+                    .extend(manifold.points.iter().map(|contact| SavedContact {
+                        fid1: contact.fid1,
+                        fid2: contact.fid2,
+                        data: contact.data,
+                    }));
+                self.manifolds.push(SavedManifold {
+                    subshape1: manifold.subshape1,
+                    subshape2: manifold.subshape2,
+                    warmstart_multiplier: manifold.data.warmstart_multiplier,
+                    contacts: (first_contact, self.contacts.len()),
+                });
+            }
+
+            self.contact_pairs.push(SavedContactPair {
+                key: pair_key(pair.pair.collider1, pair.pair.collider2),
+                manifolds: (first_manifold, self.manifolds.len()),
+            });
+        }
+
+        // Sorted for finding the saved pairs with a binary search when restoring.
+        self.contact_pairs.sort_unstable_by_key(|pair| pair.key);
+    }
+
+    /// Sets the state of the rigid-bodies and contacts to the state captured by this buffer.
+    ///
+    /// Rigid-bodies that no longer exist are ignored.
+    pub fn restore(&self, bodies: &mut RigidBodySet, narrow_phase: &mut NarrowPhase) {
+        for saved in &self.bodies {
+            if let Some(rb) = bodies.get_mut(saved.handle) {
+                // Let the rigid-body set update its active sets.
+                if saved.activation.sleeping {
+                    rb.sleep();
+                } else {
+                    rb.wake_up(true);
+                }
+
+                rb.set_position(saved.position, false);
+                rb.set_linvel(saved.linvel, false);
+                rb.set_angvel(saved.angvel, false);
+                rb.activation = saved.activation;
+            }
+        }
+
+        for pair in narrow_phase.contact_pairs_mut() {
+            let key = pair_key(pair.pair.collider1, pair.pair.collider2);
+            let saved_manifolds = match self
+                .contact_pairs
+                .binary_search_by_key(&key, |saved| saved.key)
+            {
+                Ok(i) => {
+                    let (start, end) = self.contact_pairs[i].manifolds;
+                    &self.manifolds[start..end]
+                }
+                Err(_) => &[],
+            };
+
+            for manifold in &mut pair.manifolds {
+                let saved_manifold = saved_manifolds.iter().find(|saved| {
+                    saved.subshape1 == manifold.subshape1 && saved.subshape2 == manifold.subshape2
+                });
+                let saved_contacts = match saved_manifold {
+                    Some(saved) => {
+                        manifold.data.warmstart_multiplier = saved.warmstart_multiplier;
+                        &self.contacts[saved.contacts.0..saved.contacts.1]
+                    }
+                    None => {
+                        manifold.data.warmstart_multiplier =
+                            ContactManifoldData::min_warmstart_multiplier();
+                        &[]
+                    }
+                };
+
+                for contact in &mut manifold.points {
+                    contact.data = saved_contacts
+                        .iter()
+                        .find(|saved| saved.fid1 == contact.fid1 && saved.fid2 == contact.fid2)
+                        .map(|saved| saved.data)
+                        .unwrap_or_default();
+                }
+            }
+        }
+    }
+
+    /// Replaces the content of this buffer by the current state of the given world.
+    pub fn capture_world(&mut self, world: &PhysicsWorld) {
+        self.capture(&world.bodies, &world.narrow_phase)
+    }
+
+    /// Sets the state of the given world to the state captured by this buffer.
+    pub fn restore_world(&self, world: &mut PhysicsWorld) {
+        self.restore(&mut world.bodies, &mut world.narrow_phase)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RollbackBuffer;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Real, Vector};
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn rollback_and_resimulate() {
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let ground_collider = ColliderBuilder::halfspace(Vector::y_axis()).build();
+        world
+            .colliders
+            .insert(ground_collider, ground, &mut world.bodies);
+
+        for i in 0..3 {
+            #[cfg(feature = "dim2")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.5 + i as Real);
+            #[cfg(feature = "dim3")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(0.0, 0.5 + i as Real, 0.0);
+            let handle = world.bodies.insert(rb.build());
+            #[cfg(feature = "dim2")]
+            let collider = ColliderBuilder::cuboid(0.5, 0.5).build();
+            #[cfg(feature = "dim3")]
+            let collider = ColliderBuilder::cuboid(0.5, 0.5, 0.5).build();
+            world.colliders.insert(collider, handle, &mut world.bodies);
+        }
+
+        for _ in 0..10 {
+            world.step(1.0 / 60.0);
+        }
+
+        let mut buffer = RollbackBuffer::new();
+        buffer.capture_world(&world);
+        let captured_positions: Vec<_> =
+            world.bodies.iter().map(|(_, rb)| *rb.position()).collect();
+
+        for _ in 0..5 {
+            world.step(1.0 / 60.0);
+        }
+
+        let checksum = world.checksum();
+
+        buffer.restore_world(&mut world);
+
+        for ((_, rb), position) in world.bodies.iter().zip(captured_positions.iter()) {
+            assert_eq!(rb.position(), position);
+        }
+
+        // Re-simulating from the restored state, with the restored contact impulses, gives
+        // exactly the same positions, velocities and impulses.
+        for _ in 0..5 {
+            world.step(1.0 / 60.0);
+        }
+
+        assert_eq!(world.checksum(), checksum);
+    }
+}
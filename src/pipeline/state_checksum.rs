@@ -0,0 +1,191 @@
+use crate::dynamics::{JointParams, JointSet, RigidBodySet};
+use crate::geometry::NarrowPhase;
+use crate::math::{Isometry, Real};
+
+/// Computes a checksum of the state evolving during the simulation.
+///
+/// The checksum covers the position, velocities and sleeping state of every rigid-body, the
+/// impulses of every joint, and the impulses of every contact. All these elements are visited
+/// in the order of their handles, and their floating-point values are hashed bit-wise, so two
+/// worlds in the same state have the same checksum whatever the history of their internal
+/// data structures, and on any platform.
+///
+/// This is meant for detecting desyncs between the simulations run by the peers of a lockstep
+/// multiplayer game: comparing the checksums of their worlds every few timesteps is much
+/// cheaper than comparing their full states.
+pub fn state_checksum(bodies: &RigidBodySet, joints: &JointSet, narrow_phase: &NarrowPhase) -> u64 {
+    let mut hasher = StateHasher::new();
+
+    // NOTE: the rigid-bodies are already iterated in the order of their handles.
+    for (handle, body) in bodies.iter() {
+        hasher.write_handle(handle.into_raw_parts());
+        hasher.write_isometry(body.position());
+        body.linvel().write_to(&mut hasher);
+        body.angvel().write_to(&mut hasher);
+        hasher.write_u64(body.is_sleeping() as u64);
+    }
+
+    let mut sorted_joints: Vec<_> = joints.iter().collect();
+    sorted_joints.sort_by_key(|(handle, _)| handle.into_raw_parts());
+
+    for (handle, joint) in sorted_joints {
+        hasher.write_handle(handle.into_raw_parts());
+
+        match &joint.params {
+            JointParams::BallJoint(j) => {
+                j.impulse.write_to(&mut hasher);
+                j.motor_impulse.write_to(&mut hasher);
+            }
+            JointParams::FixedJoint(j) => j.impulse.write_to(&mut hasher),
+            JointParams::PrismaticJoint(j) => {
+                j.impulse.write_to(&mut hasher);
+                j.limits_impulse.write_to(&mut hasher);
+                j.motor_impulse.write_to(&mut hasher);
+            }
+            #[cfg(feature = "dim3")]
+            JointParams::RevoluteJoint(j) => {
+                j.impulse.write_to(&mut hasher);
+                j.motor_impulse.write_to(&mut hasher);
+            }
+            JointParams::MouseJoint(j) => j.impulse.write_to(&mut hasher),
+        }
+    }
+
+    let mut sorted_pairs: Vec<_> = narrow_phase.contact_pairs().collect();
+    sorted_pairs.sort_by_key(|pair| {
+        (
+            pair.pair.collider1.into_raw_parts(),
+            pair.pair.collider2.into_raw_parts(),
+        )
+    });
+
+    for pair in sorted_pairs {
+        hasher.write_handle(pair.pair.collider1.into_raw_parts());
+        hasher.write_handle(pair.pair.collider2.into_raw_parts());
+        hasher.write_u64(pair.manifolds.len() as u64);
+
+        for manifold in &pair.manifolds {
+            hasher.write_u64(manifold.points.len() as u64);
+
+            for contact in &manifold.points {
+                contact.data.impulse.write_to(&mut hasher);
+                contact.data.tangent_impulse.write_to(&mut hasher);
+            }
+        }
+    }
+
+    hasher.finish()
+}
+
+// A 64-bit FNV-1a hasher.
+//
+// Unlike the hashers of the standard library, its output is guaranteed to be the same on every
+// platform and with every version of Rust.
+struct StateHasher {
+    hash: u64,
+}
+
+impl StateHasher {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        Self {
+            hash: Self::OFFSET_BASIS,
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        for byte in value.to_le_bytes().iter() {
+            self.hash ^= *byte as u64;
+            self.hash = self.hash.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_handle(&mut self, (index, generation): (usize, u64)) {
+        self.write_u64(index as u64);
+        self.write_u64(generation);
+    }
+
+    fn write_isometry(&mut self, pos: &Isometry<Real>) {
+        pos.translation.vector.write_to(self);
+        #[cfg(feature = "dim2")]
+        {
+            pos.rotation.re.write_to(self);
+            pos.rotation.im.write_to(self);
+        }
+        #[cfg(feature = "dim3")]
+        pos.rotation.coords.write_to(self);
+    }
+
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+// The values whose floating-point components can be fed to a `StateHasher`.
+trait HashComponents {
+    fn write_to(&self, hasher: &mut StateHasher);
+}
+
+impl HashComponents for Real {
+    fn write_to(&self, hasher: &mut StateHasher) {
+        // NOTE: `to_bits` returns an `u32` or an `u64` depending on the precision of `Real`.
+        hasher.write_u64(u64::from(self.to_bits()));
+    }
+}
+
+impl<R, C, S> HashComponents for na::Matrix<Real, R, C, S>
+where
+    R: na::Dim,
+    C: na::Dim,
+    S: na::storage::Storage<Real, R, C>,
+{
+    fn write_to(&self, hasher: &mut StateHasher) {
+        for component in self.iter() {
+            component.write_to(hasher);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::{Real, Vector};
+    use crate::pipeline::{PhysicsSnapshot, PhysicsWorld};
+
+    #[test]
+    fn checksum_tracks_dynamic_state() {
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        let ground = world.bodies.insert(RigidBodyBuilder::new_static().build());
+        let ground_collider = ColliderBuilder::halfspace(Vector::y_axis()).build();
+        world
+            .colliders
+            .insert(ground_collider, ground, &mut world.bodies);
+
+        for i in 0..3 {
+            #[cfg(feature = "dim2")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(i as Real * 2.0, 0.5);
+            #[cfg(feature = "dim3")]
+            let rb = RigidBodyBuilder::new_dynamic().translation(i as Real * 2.0, 0.5, 0.0);
+            let handle = world.bodies.insert(rb.build());
+            world.colliders.insert(
+                ColliderBuilder::ball(0.5).build(),
+                handle,
+                &mut world.bodies,
+            );
+        }
+
+        world.step(1.0 / 60.0);
+        let mut world2 = PhysicsSnapshot::capture(&world).into_world();
+        assert_eq!(world.checksum(), world2.checksum());
+
+        // The checksums differ as soon as one world diverges.
+        world.step(1.0 / 60.0);
+        assert_ne!(world.checksum(), world2.checksum());
+
+        world2.step(1.0 / 60.0);
+        assert_eq!(world.checksum(), world2.checksum());
+    }
+}
@@ -0,0 +1,201 @@
+use crate::dynamics::{RigidBodyHandle, RigidBodySet};
+use crate::math::{Isometry, Real};
+use crate::pipeline::PhysicsWorld;
+use std::collections::HashMap;
+
+/// Runs a simulation with a fixed timestep length, from variable frame times.
+///
+/// Each call to `update` accumulates the real time elapsed since the previous frame, and runs
+/// as many fixed timesteps as fit in the accumulated time. The remaining time is reflected by
+/// `alpha`, the fraction of a timestep the rendering lags behind the simulation. Rendering the
+/// rigid-bodies at `interpolated_position`, i.e., between their positions before and after the
+/// last timestep, gives a smooth motion whatever the frame rate.
+///
+/// ```ignore
+/// let mut timestep_manager = TimestepManager::new(1.0 / 60.0);
+/// loop {
+///     timestep_manager.update_world(frame_time, &mut world);
+///     for (handle, _) in world.bodies.iter() {
+///         let pose = timestep_manager.interpolated_position(&world.bodies, handle);
+///         // Render the body at `pose`.
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TimestepManager {
+    /// The length of each timestep.
+    pub dt: Real,
+    /// The maximum number of timesteps run by a single call to `update` (default: `5`).
+    ///
+    /// If a frame took longer than `max_steps_per_update * dt`, the simulation runs at most this
+    /// many timesteps and the remaining time is dropped, so that a slow simulation doesn't
+    /// make the next frames even slower.
+    pub max_steps_per_update: usize,
+    accumulator: Real,
+    previous_positions: HashMap<RigidBodyHandle, Isometry<Real>>,
+}
+
+impl TimestepManager {
+    /// Creates a timestep manager running timesteps of length `dt`.
+    pub fn new(dt: Real) -> Self {
+        assert!(dt > 0.0, "The timestep length must be positive.");
+
+        Self {
+            dt,
+            max_steps_per_update: 5,
+            accumulator: 0.0,
+            previous_positions: HashMap::new(),
+        }
+    }
+
+    /// Accumulates `frame_time` and calls `step` once for each timestep to run.
+    ///
+    /// The `step` closure is given the rigid-bodies and the timestep length, and must step the
+    /// simulation, e.g., by calling `PhysicsPipeline::step`. Before each timestep, the positions
+    /// of the active rigid-bodies are saved for computing their interpolated positions.
+    /// Returns the number of timesteps that were run.
+    pub fn update(
+        &mut self,
+        frame_time: Real,
+        bodies: &mut RigidBodySet,
+        step: impl FnMut(&mut RigidBodySet, Real),
+    ) -> usize {
+        self.run_timesteps(frame_time, bodies, |bodies| bodies, step)
+    }
+
+    /// Accumulates `frame_time` and steps the given world once for each timestep to run.
+    ///
+    /// Returns the number of timesteps that were run.
+    pub fn update_world(&mut self, frame_time: Real, world: &mut PhysicsWorld) -> usize {
+        self.run_timesteps(
+            frame_time,
+            world,
+            |world| &world.bodies,
+            |world, dt| world.step(dt),
+        )
+    }
+
+    fn run_timesteps<W>(
+        &mut self,
+        frame_time: Real,
+        world: &mut W,
+        bodies: impl Fn(&W) -> &RigidBodySet,
+        mut step: impl FnMut(&mut W, Real),
+    ) -> usize {
+        self.accumulator += frame_time.max(0.0);
+        let mut num_steps = 0;
+
+        while self.accumulator >= self.dt && num_steps < self.max_steps_per_update {
+            self.save_previous_positions(bodies(world));
+            step(world, self.dt);
+            self.accumulator -= self.dt;
+            num_steps += 1;
+        }
+
+        if num_steps == self.max_steps_per_update {
+            // Drop the time we were not able to simulate.
+            self.accumulator = self.accumulator.min(self.dt);
+        }
+
+        num_steps
+    }
+
+    /// The fraction of a timestep elapsed since the last timestep, in `[0, 1]`.
+    pub fn alpha(&self) -> Real {
+        (self.accumulator / self.dt).min(1.0)
+    }
+
+    /// The position of a rigid-body before the last timestep.
+    ///
+    /// Returns `None` if the rigid-body was not active during the last timestep, in which case
+    /// it did not move.
+    pub fn previous_position(&self, handle: RigidBodyHandle) -> Option<&Isometry<Real>> {
+        self.previous_positions.get(&handle)
+    }
+
+    /// The position of a rigid-body interpolated between its positions before and after the
+    /// last timestep, with the interpolation factor `self.alpha()`.
+    ///
+    /// Returns `None` if the rigid-body does not exist.
+    pub fn interpolated_position(
+        &self,
+        bodies: &RigidBodySet,
+        handle: RigidBodyHandle,
+    ) -> Option<Isometry<Real>> {
+        let current = bodies.get(handle)?.position();
+
+        match self.previous_positions.get(&handle) {
+            Some(previous) => Some(interpolate_isometry(previous, current, self.alpha())),
+            None => Some(*current),
+        }
+    }
+
+    fn save_previous_positions(&mut self, bodies: &RigidBodySet) {
+        self.previous_positions.clear();
+        self.previous_positions.extend(
+            bodies
+                .iter_active_dynamic()
+                .chain(bodies.iter_active_kinematic())
+                .map(|(handle, body)| (handle, *body.position())),
+        );
+    }
+}
+
+#[cfg(feature = "dim2")]
+fn interpolate_isometry(start: &Isometry<Real>, end: &Isometry<Real>, t: Real) -> Isometry<Real> {
+    let translation = start.translation.vector.lerp(&end.translation.vector, t);
+    let angle = start.rotation.angle() + (end.rotation / start.rotation).angle() * t;
+    Isometry::new(translation, angle)
+}
+
+#[cfg(feature = "dim3")]
+fn interpolate_isometry(start: &Isometry<Real>, end: &Isometry<Real>, t: Real) -> Isometry<Real> {
+    let translation = start.translation.vector.lerp(&end.translation.vector, t);
+    let rotation = start
+        .rotation
+        .try_slerp(&end.rotation, t, Real::EPSILON)
+        .unwrap_or(end.rotation);
+    Isometry::from_parts(translation.into(), rotation)
+}
+
+#[cfg(test)]
+mod test {
+    use super::TimestepManager;
+    use crate::dynamics::RigidBodyBuilder;
+    use crate::math::Vector;
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn fixed_timesteps_and_interpolation() {
+        let mut world = PhysicsWorld::new(Vector::zeros());
+        #[cfg(feature = "dim2")]
+        let rb = RigidBodyBuilder::new_dynamic().linvel(4.0, 0.0).build();
+        #[cfg(feature = "dim3")]
+        let rb = RigidBodyBuilder::new_dynamic()
+            .linvel(4.0, 0.0, 0.0)
+            .build();
+        let body = world.bodies.insert(rb);
+
+        // NOTE: all these durations are exactly representable as floats.
+        let mut timestep_manager = TimestepManager::new(0.25);
+
+        // Frames of 1.5 timesteps.
+        assert_eq!(timestep_manager.update_world(0.375, &mut world), 1);
+        assert!((timestep_manager.alpha() - 0.5).abs() < 1.0e-6);
+        assert_eq!(timestep_manager.update_world(0.375, &mut world), 2);
+        assert!(timestep_manager.alpha() < 1.0e-6);
+        assert_eq!(timestep_manager.update_world(0.125, &mut world), 0);
+
+        // The body moved by one unit per timestep, and is rendered halfway through the third one.
+        let position = timestep_manager
+            .interpolated_position(&world.bodies, body)
+            .unwrap();
+        assert!((world.bodies[body].position().translation.x - 3.0).abs() < 1.0e-3);
+        assert!((position.translation.x - 2.5).abs() < 1.0e-3);
+
+        // A long frame doesn't run more than `max_steps_per_update` timesteps.
+        let num_steps = timestep_manager.update_world(100.0, &mut world);
+        assert_eq!(num_steps, timestep_manager.max_steps_per_update);
+        assert!(timestep_manager.alpha() <= 1.0);
+    }
+}
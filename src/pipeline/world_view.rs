@@ -0,0 +1,124 @@
+use crate::dynamics::{Joint, JointHandle, JointSet, RigidBody, RigidBodyHandle, RigidBodySet};
+use crate::geometry::{Collider, ColliderHandle, ColliderSet, ContactPair, NarrowPhase};
+
+/// A read-only view of the physics world that can be shared between threads.
+///
+/// A `PhysicsWorldView` borrows the rigid-bodies, colliders, joints, and narrow-phase immutably.
+/// It implements `Copy`, `Send`, and `Sync` so it can be handed to any number of jobs (AI,
+/// rendering, etc.) reading the world in parallel between two timesteps. Since stepping the
+/// simulation requires mutable references to these structures, the borrow checker guarantees
+/// that no step can start while a view is still alive, so the jobs never observe a world
+/// being modified.
+#[derive(Copy, Clone)]
+pub struct PhysicsWorldView<'a> {
+    bodies: &'a RigidBodySet,
+    colliders: &'a ColliderSet,
+    joints: &'a JointSet,
+    narrow_phase: &'a NarrowPhase,
+}
+
+impl<'a> PhysicsWorldView<'a> {
+    /// Creates a read-only view of the given physics world.
+    pub fn new(
+        bodies: &'a RigidBodySet,
+        colliders: &'a ColliderSet,
+        joints: &'a JointSet,
+        narrow_phase: &'a NarrowPhase,
+    ) -> Self {
+        Self {
+            bodies,
+            colliders,
+            joints,
+            narrow_phase,
+        }
+    }
+
+    /// The set of rigid-bodies of this world.
+    pub fn bodies(&self) -> &'a RigidBodySet {
+        self.bodies
+    }
+
+    /// The set of colliders of this world.
+    pub fn colliders(&self) -> &'a ColliderSet {
+        self.colliders
+    }
+
+    /// The set of joints of this world.
+    pub fn joints(&self) -> &'a JointSet {
+        self.joints
+    }
+
+    /// The narrow-phase of this world, containing the contacts and intersections between colliders.
+    pub fn narrow_phase(&self) -> &'a NarrowPhase {
+        self.narrow_phase
+    }
+
+    /// The rigid-body with the given handle, if it exists.
+    pub fn rigid_body(&self, handle: RigidBodyHandle) -> Option<&'a RigidBody> {
+        self.bodies.get(handle)
+    }
+
+    /// The collider with the given handle, if it exists.
+    pub fn collider(&self, handle: ColliderHandle) -> Option<&'a Collider> {
+        self.colliders.get(handle)
+    }
+
+    /// The joint with the given handle, if it exists.
+    pub fn joint(&self, handle: JointHandle) -> Option<&'a Joint> {
+        self.joints.get(handle)
+    }
+
+    /// The contact pair between the two given colliders, if they have an AABB overlap.
+    pub fn contact_pair(
+        &self,
+        collider1: ColliderHandle,
+        collider2: ColliderHandle,
+    ) -> Option<&'a ContactPair> {
+        self.narrow_phase.contact_pair(collider1, collider2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PhysicsWorldView;
+    use crate::geometry::ColliderBuilder;
+    use crate::math::Vector;
+    use crate::pipeline::PhysicsWorld;
+
+    #[test]
+    fn world_view_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PhysicsWorldView>();
+    }
+
+    #[test]
+    fn world_view_is_read_from_several_threads() {
+        let mut world = PhysicsWorld::new(Vector::y() * -9.81);
+        let ground = world.insert_ground();
+        let (rb, co) = world.insert_dynamic(Vector::y() * 0.5, ColliderBuilder::ball(0.5));
+        world.run(10);
+
+        let position = *world.bodies[rb].position();
+        let view = world.view();
+        crossbeam::scope(|s| {
+            let jobs: Vec<_> = (0..4)
+                .map(|_| {
+                    s.spawn(move |_| {
+                        let in_contact = view
+                            .contact_pair(ground, co)
+                            .map(|pair| pair.has_any_active_contact)
+                            .unwrap_or(false);
+                        (*view.rigid_body(rb).unwrap().position(), in_contact)
+                    })
+                })
+                .collect();
+
+            // Every job observes the state of the world at the end of the last step.
+            for job in jobs {
+                assert_eq!(job.join().unwrap(), (position, true));
+            }
+        })
+        .unwrap();
+        assert_eq!(*view.collider(co).unwrap().position(), position);
+    }
+}
@@ -20,6 +20,19 @@ pub(crate) fn inv(val: Real) -> Real {
     }
 }
 
+/// The symmetric matrix with the components of `diag` on its diagonal.
+#[cfg(feature = "dim3")]
+pub(crate) fn sdp_diagonal<N: SimdRealField>(diag: &Vector3<N>) -> SdpMatrix3<N> {
+    SdpMatrix3 {
+        m11: diag.x,
+        m12: N::zero(),
+        m13: N::zero(),
+        m22: diag.y,
+        m23: N::zero(),
+        m33: diag.z,
+    }
+}
+
 /// Trait to copy the sign of each component of one scalar/vector/matrix to another.
 pub trait WSign<Rhs>: Sized {
     // See SIMD implementations of copy_sign there: https://stackoverflow.com/a/57872652
@@ -670,6 +683,26 @@ impl Drop for FlushToZeroDenormalsAreZeroFlags {
     }
 }
 
+/// Shuffles the given slice with a Fisher-Yates shuffle, using a PRNG initialized by `seed`.
+///
+/// The result only depends on the `seed` and the length of the slice.
+pub(crate) fn shuffle_with_seed<T>(elts: &mut [T], seed: u64) {
+    // splitmix64, see http://prng.di.unimi.it/splitmix64.c
+    let mut state = seed;
+    let mut next_random = || {
+        state = state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    };
+
+    for i in (1..elts.len()).rev() {
+        let j = (next_random() % (i as u64 + 1)) as usize;
+        elts.swap(i, j);
+    }
+}
+
 pub(crate) fn select_other<T: PartialEq>(pair: (T, T), elt: T) -> T {
     if pair.0 == elt {
         pair.1
@@ -150,6 +150,9 @@ impl Box2dWorld {
 
                     self.world.create_joint(&def);
                 }
+                JointParams::MouseJoint(_) => {
+                    eprintln!("Joint type currently unsupported by the Box2D backend: MouseJoint.")
+                }
             }
         }
     }
@@ -211,10 +214,10 @@ impl Box2dWorld {
         }
     }
 
-    pub fn step(&mut self, counters: &mut Counters, params: &IntegrationParameters) {
+    pub fn step(&mut self, counters: &mut Counters, dt: f32, params: &IntegrationParameters) {
         counters.step_started();
         self.world.step(
-            params.dt,
+            dt,
             params.max_velocity_iterations as i32,
             params.max_position_iterations as i32,
         );
@@ -6,13 +6,13 @@ use kiss3d::window::Window;
 
 use na::Point3;
 
-use crate::math::{Isometry, Point};
+use crate::math::{Isometry, Point, Vector};
 use crate::objects::ball::Ball;
 use crate::objects::box_node::Box as BoxNode;
 use crate::objects::heightfield::HeightField;
 use crate::objects::node::{GraphicsNode, Node};
 use rapier::dynamics::{RigidBodyHandle, RigidBodySet};
-use rapier::geometry::{ColliderHandle, ColliderSet, Shape};
+use rapier::geometry::{ColliderHandle, ColliderSet, MultiSphere, Shape, Voxels};
 //use crate::objects::capsule::Capsule;
 use crate::objects::convex::Convex;
 //#[cfg(feature = "dim3")]
@@ -295,6 +295,31 @@ impl GraphicsManager {
             }
         }
 
+        if let Some(voxels) = shape.as_shape::<Voxels>() {
+            let half_extents = Vector::repeat(voxels.voxel_size() / 2.0);
+
+            for (_, aabb) in voxels.filled_voxels() {
+                let voxel_pos = delta * Isometry::new(aabb.center().coords, na::zero());
+                out.push(Node::Box(BoxNode::new(
+                    handle,
+                    voxel_pos,
+                    half_extents,
+                    color,
+                    window,
+                )))
+            }
+        }
+
+        if let Some(multi_sphere) = shape.as_shape::<MultiSphere>() {
+            // NOTE: only the balls are rendered, not their convex hull.
+            for (center, radius) in multi_sphere.spheres() {
+                let ball_pos = delta * Isometry::new(center.coords, na::zero());
+                out.push(Node::Ball(Ball::new(
+                    handle, ball_pos, *radius, color, window,
+                )))
+            }
+        }
+
         if let Some(ball) = shape.as_ball() {
             out.push(Node::Ball(Ball::new(
                 handle,
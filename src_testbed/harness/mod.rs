@@ -174,6 +174,7 @@ impl Harness {
             self.state.thread_pool.install(|| {
                 physics.pipeline.step(
                     &physics.gravity,
+                    physics.dt,
                     &physics.integration_parameters,
                     &mut physics.broad_phase,
                     &mut physics.narrow_phase,
@@ -190,6 +191,7 @@ impl Harness {
         #[cfg(not(feature = "parallel"))]
         self.physics.pipeline.step(
             &self.physics.gravity,
+            self.physics.dt,
             &self.physics.integration_parameters,
             &mut self.physics.broad_phase,
             &mut self.physics.narrow_phase,
@@ -237,7 +239,7 @@ impl Harness {
 
         self.events.poll_all();
 
-        self.state.time += self.physics.integration_parameters.dt;
+        self.state.time += self.physics.dt;
         self.state.timestep_id += 1;
     }
 
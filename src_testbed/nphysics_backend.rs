@@ -121,6 +121,11 @@ impl NPhysicsWorld {
                     }
 
                     nphysics_joints.insert(c);
+                }
+                JointParams::MouseJoint(_) => {
+                    eprintln!(
+                        "Joint type currently unsupported by the nphysics backend: MouseJoint."
+                    )
                 } // JointParams::GenericJoint(_) => {
                   //     eprintln!(
                   //         "Joint type currently unsupported by the nphysics backend: GenericJoint."
@@ -140,16 +145,14 @@ impl NPhysicsWorld {
         }
     }
 
-    pub fn step(&mut self, counters: &mut Counters, params: &IntegrationParameters) {
+    pub fn step(&mut self, counters: &mut Counters, dt: f32, params: &IntegrationParameters) {
         self.mechanical_world
             .integration_parameters
             .max_position_iterations = params.max_position_iterations;
         self.mechanical_world
             .integration_parameters
             .max_velocity_iterations = params.max_velocity_iterations;
-        self.mechanical_world
-            .integration_parameters
-            .set_dt(params.dt);
+        self.mechanical_world.integration_parameters.set_dt(dt);
         self.mechanical_world.integration_parameters.warmstart_coeff = params.warmstart_coeff;
 
         counters.step_started();
@@ -77,6 +77,7 @@ pub struct PhysicsState {
     pub pipeline: PhysicsPipeline,
     pub query_pipeline: QueryPipeline,
     pub integration_parameters: IntegrationParameters,
+    pub dt: f32,
     pub gravity: Vector<f32>,
     pub hooks: Box<dyn PhysicsHooks>,
 }
@@ -93,6 +94,7 @@ impl PhysicsState {
             pipeline: PhysicsPipeline::new(),
             query_pipeline: QueryPipeline::new(),
             integration_parameters: IntegrationParameters::default(),
+            dt: 1.0 / 60.0,
             gravity: Vector::y() * -9.81,
             hooks: Box::new(()),
         }
@@ -464,6 +464,11 @@ impl PhysxWorld {
                             actor2,
                             &frame2 as *const _,
                         );
+                    }
+                    JointParams::MouseJoint(_) => {
+                        eprintln!(
+                            "Joint type currently unsupported by the PhysX backend: MouseJoint."
+                        )
                     } // JointParams::GenericJoint(_) => {
                       //     eprintln!(
                       //         "Joint type currently unsupported by the PhysX backend: GenericJoint."
@@ -474,7 +479,7 @@ impl PhysxWorld {
         }
     }
 
-    pub fn step(&mut self, counters: &mut Counters, params: &IntegrationParameters) {
+    pub fn step(&mut self, counters: &mut Counters, dt: f32, params: &IntegrationParameters) {
         let mut scratch = unsafe { ScratchBuffer::new(4) };
 
         counters.step_started();
@@ -482,7 +487,7 @@ impl PhysxWorld {
             .as_mut()
             .unwrap()
             .step(
-                params.dt,
+                dt,
                 None::<&mut physx_sys::PxBaseTask>,
                 Some(&mut scratch),
                 true,
@@ -21,10 +21,11 @@ use na::{self, Point2, Point3, Vector3};
 use rapier::dynamics::{
     ActivationStatus, IntegrationParameters, JointSet, RigidBodyHandle, RigidBodySet,
 };
-use rapier::geometry::{ColliderHandle, ColliderSet, NarrowPhase};
+use rapier::geometry::{ColliderHandle, ColliderSet, NarrowPhase, AABB};
 #[cfg(feature = "dim3")]
 use rapier::geometry::{InteractionGroups, Ray};
 use rapier::math::Vector;
+use rapier::parry::bounding_volume::BoundingVolume;
 use rapier::pipeline::PhysicsHooks;
 
 #[cfg(all(feature = "dim2", feature = "other-backends"))]
@@ -85,6 +86,7 @@ bitflags! {
         const STATISTICS = 1 << 9;
         const PROFILE = 1 << 10;
         const DEBUG = 1 << 11;
+        const CONTACT_SKINS = 1 << 12;
     }
 }
 
@@ -474,6 +476,7 @@ impl Testbed {
                                 if self.state.selected_backend == BOX2D_BACKEND {
                                     self.box2d.as_mut().unwrap().step(
                                         &mut self.harness.physics.pipeline.counters,
+                                        self.harness.physics.dt,
                                         &self.harness.physics.integration_parameters,
                                     );
                                     self.box2d.as_mut().unwrap().sync(
@@ -491,6 +494,7 @@ impl Testbed {
                                     //                        println!("Step");
                                     self.physx.as_mut().unwrap().step(
                                         &mut self.harness.physics.pipeline.counters,
+                                        self.harness.physics.dt,
                                         &self.harness.physics.integration_parameters,
                                     );
                                     self.physx.as_mut().unwrap().sync(
@@ -505,6 +509,7 @@ impl Testbed {
                                 if self.state.selected_backend == NPHYSICS_BACKEND {
                                     self.nphysics.as_mut().unwrap().step(
                                         &mut self.harness.physics.pipeline.counters,
+                                        self.harness.physics.dt,
                                         &self.harness.physics.integration_parameters,
                                     );
                                     self.nphysics.as_mut().unwrap().sync(
@@ -1045,6 +1050,7 @@ impl State for Testbed {
             ui.update(
                 window,
                 &mut self.harness.physics.integration_parameters,
+                &mut self.harness.physics.dt,
                 &mut self.state,
                 &mut self.harness.state,
             );
@@ -1095,6 +1101,7 @@ impl State for Testbed {
 
                 if self.state.selected_example != prev_example {
                     self.harness.physics.integration_parameters = IntegrationParameters::default();
+                    self.harness.physics.dt = 1.0 / 60.0;
 
                     if cfg!(feature = "dim3")
                         && (self.state.selected_backend == PHYSX_BACKEND_PATCH_FRICTION
@@ -1290,6 +1297,7 @@ impl State for Testbed {
                     if self.state.selected_backend == BOX2D_BACKEND {
                         self.box2d.as_mut().unwrap().step(
                             &mut self.harness.physics.pipeline.counters,
+                            self.harness.physics.dt,
                             &self.harness.physics.integration_parameters,
                         );
                         self.box2d.as_mut().unwrap().sync(
@@ -1307,6 +1315,7 @@ impl State for Testbed {
                         //                        println!("Step");
                         self.physx.as_mut().unwrap().step(
                             &mut self.harness.physics.pipeline.counters,
+                            self.harness.physics.dt,
                             &self.harness.physics.integration_parameters,
                         );
                         self.physx.as_mut().unwrap().sync(
@@ -1321,6 +1330,7 @@ impl State for Testbed {
                     if self.state.selected_backend == NPHYSICS_BACKEND {
                         self.nphysics.as_mut().unwrap().step(
                             &mut self.harness.physics.pipeline.counters,
+                            self.harness.physics.dt,
                             &self.harness.physics.integration_parameters,
                         );
                         self.nphysics.as_mut().unwrap().sync(
@@ -1350,6 +1360,17 @@ impl State for Testbed {
             draw_contacts(window, &physics.narrow_phase, &physics.colliders);
         }
 
+        if self.state.flags.contains(TestbedStateFlags::CONTACT_SKINS) {
+            draw_contact_skins(
+                window,
+                &physics.bodies,
+                &physics.colliders,
+                physics.dt,
+                &physics.integration_parameters,
+                self.state.highlighted_body,
+            );
+        }
+
         if self.state.running == RunMode::Step {
             self.state.running = RunMode::Stop;
         }
@@ -1498,3 +1519,91 @@ fn draw_contacts(window: &mut Window, nf: &NarrowPhase, colliders: &ColliderSet)
         }
     }
 }
+
+/// Draws the contact prediction envelopes and the CCD swept volumes of the colliders
+/// attached to the selected body, or to every CCD-enabled body if none is selected.
+///
+/// The contact envelope (in green) is the AABB of the collider enlarged by the
+/// prediction distance and its contact skin: any other collider entering it will generate speculative
+/// contacts. The swept volume (in orange) is the AABB covering the motion of the
+/// collider during the next timestep, assuming the body keeps its current velocity.
+fn draw_contact_skins(
+    window: &mut Window,
+    bodies: &RigidBodySet,
+    colliders: &ColliderSet,
+    dt: f32,
+    params: &IntegrationParameters,
+    selected: Option<RigidBodyHandle>,
+) {
+    let skin_color = Point3::new(0.2, 0.8, 0.2);
+    let swept_color = Point3::new(1.0, 0.5, 0.0);
+
+    for (handle, body) in bodies.iter() {
+        let is_selected = match selected {
+            Some(selected) => selected == handle,
+            None => body.is_ccd_enabled(),
+        };
+
+        if !is_selected {
+            continue;
+        }
+
+        let next_body_pos = body.predict_position_using_velocity_and_forces(dt);
+
+        for collider_handle in body.colliders() {
+            let collider = &colliders[*collider_handle];
+            let skin = collider
+                .compute_aabb()
+                .loosened(params.prediction_distance + collider.contact_skin());
+            draw_aabb(window, &skin, &skin_color);
+
+            if body.is_ccd_enabled() {
+                let next_pos = next_body_pos * collider.position_wrt_parent();
+                let swept = collider.compute_swept_aabb(&next_pos);
+                draw_aabb(window, &swept, &swept_color);
+            }
+        }
+    }
+}
+
+fn draw_aabb(window: &mut Window, aabb: &AABB, color: &Point3<f32>) {
+    use crate::engine::GraphicsWindow;
+    use rapier::math::Point;
+
+    let (mins, maxs) = (aabb.mins, aabb.maxs);
+
+    #[cfg(feature = "dim2")]
+    {
+        let corners = [
+            Point::new(mins.x, mins.y),
+            Point::new(maxs.x, mins.y),
+            Point::new(maxs.x, maxs.y),
+            Point::new(mins.x, maxs.y),
+        ];
+
+        for i in 0..4 {
+            window.draw_graphics_line(&corners[i], &corners[(i + 1) % 4], color);
+        }
+    }
+
+    #[cfg(feature = "dim3")]
+    {
+        let corners = [
+            Point::new(mins.x, mins.y, mins.z),
+            Point::new(maxs.x, mins.y, mins.z),
+            Point::new(maxs.x, maxs.y, mins.z),
+            Point::new(mins.x, maxs.y, mins.z),
+            Point::new(mins.x, mins.y, maxs.z),
+            Point::new(maxs.x, mins.y, maxs.z),
+            Point::new(maxs.x, maxs.y, maxs.z),
+            Point::new(mins.x, maxs.y, maxs.z),
+        ];
+
+        for i in 0..4 {
+            // Bottom face, top face, and vertical edges.
+            window.draw_graphics_line(&corners[i], &corners[(i + 1) % 4], color);
+            window.draw_graphics_line(&corners[i + 4], &corners[(i + 1) % 4 + 4], color);
+            window.draw_graphics_line(&corners[i], &corners[i + 4], color);
+        }
+    }
+}
@@ -49,6 +49,7 @@ widget_ids! {
         toggle_joints,
         toggle_aabbs,
         toggle_contact_points,
+        toggle_contact_skins,
         toggle_contact_normals,
         toggle_center_of_masses,
         toggle_statistics,
@@ -98,6 +99,7 @@ impl TestbedUi {
         &mut self,
         window: &mut Window,
         integration_parameters: &mut IntegrationParameters,
+        dt: &mut f32,
         state: &mut TestbedState,
         _run_state: &mut RunState,
     ) {
@@ -260,7 +262,11 @@ impl TestbedUi {
         let curr_max_ccd_substeps = integration_parameters.max_ccd_substeps;
         let curr_min_island_size = integration_parameters.min_island_size;
         let curr_warmstart_coeff = integration_parameters.warmstart_coeff;
-        let curr_frequency = integration_parameters.inv_dt().round() as usize;
+        let curr_frequency = if *dt == 0.0 {
+            0
+        } else {
+            (1.0 / *dt).round() as usize
+        };
 
         conrod::widget::Text::new("Vel. Iters.:")
             .down_from(self.ids.separator1, VSPACE)
@@ -377,7 +383,8 @@ impl TestbedUi {
             .w_h(ELEMENT_W, ELEMENT_H)
             .set(self.ids.slider_frequency, &mut ui)
         {
-            integration_parameters.set_inv_dt(val.round());
+            let val = val.round();
+            *dt = if val == 0.0 { 0.0 } else { 1.0 / val };
         }
 
         let toggle_list = [
@@ -397,6 +404,11 @@ impl TestbedUi {
                 self.ids.toggle_contact_points,
                 TestbedStateFlags::CONTACT_POINTS,
             ),
+            (
+                "Contact skins",
+                self.ids.toggle_contact_skins,
+                TestbedStateFlags::CONTACT_SKINS,
+            ),
             //            ("ContactManifold Normals", self.ids.toggle_contact_normals, TestbedStateFlags::CONTACT_NORMALS),
             (
                 "Wireframe",